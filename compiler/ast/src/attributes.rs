@@ -0,0 +1,49 @@
+//! AST attribute related definitions.
+
+use compiler_utils::{Position, hash::HashedString};
+use diagnostics::{
+    DiagnosticSpanOrigin,
+    diagnostic::{Diagnostic, Level, Span, SpanKind, SpanPosition},
+};
+
+/// An attribute written before a declaration or a statement such as `@allow(unused_variable)`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ASTAttribute {
+    pub name: HashedString,
+
+    /// The keywords or string literals given within the parentheses
+    pub args: Vec<String>,
+
+    pub start: Position,
+    pub end: Position,
+}
+
+impl DiagnosticSpanOrigin for ASTAttribute {
+    fn make_span(&self, kind: SpanKind, msg: Option<String>) -> Span {
+        Span {
+            start: SpanPosition::from_pos2(self.start.clone(), self.end.clone()),
+            label: msg,
+            kind,
+            replacement: None,
+        }
+    }
+
+    fn get_pos(&self) -> SpanPosition {
+        SpanPosition::from_pos2(self.start.clone(), self.end.clone())
+    }
+
+    fn make_simple_diagnostic(
+        &self,
+        code: usize,
+        level: Level,
+        message: String,
+        primary_span_msg: Option<String>,
+        spans: Vec<Span>,
+        notes: Vec<String>,
+        help: Vec<String>,
+    ) -> Diagnostic {
+        let primary = self.make_span(SpanKind::Primary, primary_span_msg);
+
+        Diagnostic::new_base(level, code, message, primary, spans, notes, help)
+    }
+}
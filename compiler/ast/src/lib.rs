@@ -2,6 +2,7 @@
 //! The AST module of Quickfall. Contains all of the AST code required for the Quickfall language.
 //!
 
+pub mod attributes;
 pub mod ctx;
 pub mod operators;
 pub mod ranges;
@@ -4,7 +4,7 @@
 
 use std::{collections::HashMap, fmt::Display};
 
-use compiler_typing::TypeParameterContainer;
+use compiler_typing::{TypeParameterBounds, TypeParameterContainer};
 use compiler_utils::{
     Position,
     hash::{HashedString, SelfHash},
@@ -15,7 +15,7 @@ use diagnostics::{
     diagnostic::{Diagnostic, Span, SpanKind, SpanPosition},
 };
 
-use crate::{ranges::ASTRange, types::ASTType};
+use crate::{attributes::ASTAttribute, ranges::ASTRange, types::ASTType};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct FunctionDeclarationArgument {
@@ -32,12 +32,34 @@ impl FunctionDeclarationArgument {
     }
 }
 
+/// A variable captured by a closure.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClosureCapture {
+    pub name: HashedString,
+
+    /// Whether the variable is captured by reference instead of being copied into the closure
+    pub by_ref: bool,
+}
+
+/// The type layout intrinsics, evaluated at compile time.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LayoutIntrinsic {
+    /// The size of the type in bytes, padding included
+    SizeOf,
+
+    /// The alignment of the type in bytes
+    AlignOf,
+
+    /// The offset of a field within the type in bytes
+    OffsetOf,
+}
+
 /// The main AST node type in the AST parsing system.
 #[derive(Debug, PartialEq, Clone)]
 pub enum ASTTreeNodeKind {
     IntegerLit {
         val: i128,
-        hash: u64,
+        int_type: HashedString,
     },
     StringLit(String),
 
@@ -60,6 +82,27 @@ pub enum ASTTreeNodeKind {
         unsafe_unwrap: bool,
     },
 
+    /// An acknowledged access to a union field, written `unsafe_unwrap<value.field>`
+    UnsafeFieldAccess {
+        access: Box<ASTTreeNode>,
+    },
+
+    CastValue {
+        original: Box<ASTTreeNode>,
+        target_type: ASTType,
+    },
+
+    /// A value or statement lowered without runtime safety checks. Written `unchecked(arr[i])`
+    UncheckedValue {
+        val: Box<ASTTreeNode>,
+    },
+
+    LayoutIntrinsic {
+        intrinsic: LayoutIntrinsic,
+        target: ASTType,
+        field: Option<HashedString>,
+    },
+
     OperatorBasedConditionMember {
         lval: Box<ASTTreeNode>,
         rval: Box<ASTTreeNode>,
@@ -90,6 +133,9 @@ pub enum ASTTreeNodeKind {
     StructInitializer {
         map: HashMap<SelfHash, Box<ASTTreeNode>>,
     },
+    TupleLiteral {
+        values: Vec<Box<ASTTreeNode>>,
+    },
     ArrayVariableInitializerValueSameValue {
         size: usize,
         v: Box<ASTTreeNode>,
@@ -107,27 +153,58 @@ pub enum ASTTreeNodeKind {
         index: Box<ASTTreeNode>,
         val: Box<ASTTreeNode>,
     },
+    /// A slice over a part of an array, slice or pointer of arrays. Written `arr[1..4]`
+    SubSlice {
+        val: Box<ASTTreeNode>,
+        range: ASTRange,
+    },
 
     EnumDeclaration {
         name: HashedString,
+        repr: Option<ASTType>,
         entries: Vec<Box<ASTTreeNode>>,
         functions: Vec<Box<ASTTreeNode>>,
         type_params: TypeParameterContainer,
+        type_bounds: TypeParameterBounds,
     },
     EnumEntryDeclaration {
         name: HashedString,
         fields: Vec<Box<ASTTreeNode>>,
+        discriminant: Option<i128>,
     },
 
     StructLayoutDeclaration {
         name: HashedString,
         layout: bool,
+
+        /// Do the fields share the same storage
+        union: bool,
         members: Vec<Box<ASTTreeNode>>,
         type_params: TypeParameterContainer,
+        type_bounds: TypeParameterBounds,
     },
     StructFieldMember {
         name: HashedString,
         member_type: ASTType,
+
+        /// The amount of bits used by the field if it is a bitfield
+        bit_width: Option<usize>,
+    },
+
+    TraitDeclaration {
+        name: HashedString,
+        functions: Vec<Box<ASTTreeNode>>,
+    },
+    TraitFunctionDeclaration {
+        func_name: HashedString,
+        args: Vec<FunctionDeclarationArgument>,
+        return_type: Option<ASTType>,
+        requires_this: bool,
+    },
+    TraitImplementation {
+        trait_name: HashedString,
+        target: ASTType,
+        functions: Vec<Box<ASTTreeNode>>,
     },
 
     VarDeclaration {
@@ -135,6 +212,12 @@ pub enum ASTTreeNodeKind {
         var_type: ASTType,
         value: Option<Box<ASTTreeNode>>,
     },
+
+    /// Declares a variable for every element of the tuple value, written `var (a, b) = val`
+    TupleDestructuring {
+        names: Vec<HashedString>,
+        value: Box<ASTTreeNode>,
+    },
     VarValueChange {
         var: Box<ASTTreeNode>,
         value: Box<ASTTreeNode>,
@@ -196,6 +279,10 @@ pub enum ASTTreeNodeKind {
         body: Vec<Box<ASTTreeNode>>,
         return_type: Option<ASTType>,
         requires_this: bool,
+        /// Whether runtime safety checks are disabled for the function. Written `unchecked func`
+        unchecked: bool,
+        /// Whether every arithmetic operation of the function uses fast semantics. Written `fast func`
+        fast: bool,
     },
 
     ExternFunctionDeclaration {
@@ -204,6 +291,13 @@ pub enum ASTTreeNodeKind {
         return_type: Option<ASTType>,
     },
 
+    ClosureExpression {
+        captures: Vec<ClosureCapture>,
+        args: Vec<FunctionDeclarationArgument>,
+        body: Vec<Box<ASTTreeNode>>,
+        return_type: Option<ASTType>,
+    },
+
     StructLRVariable {
         l: Box<ASTTreeNode>,
         r: Box<ASTTreeNode>,
@@ -237,6 +331,8 @@ impl ASTTreeNodeKind {
                 | ASTTreeNodeKind::StaticVariableDeclaration { .. }
                 | ASTTreeNodeKind::ExternFunctionDeclaration { .. }
                 | ASTTreeNodeKind::StructLayoutDeclaration { .. }
+                | ASTTreeNodeKind::TraitDeclaration { .. }
+                | ASTTreeNodeKind::TraitImplementation { .. }
         );
     }
 
@@ -248,6 +344,8 @@ impl ASTTreeNodeKind {
                 body: _,
                 return_type: _,
                 requires_this: _,
+                unchecked: _,
+                fast: _,
             } => {
                 return Some(HashedString::new(func_name.val.to_string()));
             }
@@ -269,8 +367,10 @@ impl ASTTreeNodeKind {
             ASTTreeNodeKind::StructLayoutDeclaration {
                 name,
                 layout: _,
+                union: _,
                 members: _,
                 type_params: _,
+                type_bounds: _,
             } => {
                 return Some(HashedString::new(name.val.to_string()));
             }
@@ -285,11 +385,25 @@ impl ASTTreeNodeKind {
 
             ASTTreeNodeKind::EnumDeclaration {
                 name,
+                repr: _,
                 entries: _,
                 functions: _,
                 type_params: _,
+                type_bounds: _,
             } => return Some(name.clone()),
 
+            ASTTreeNodeKind::TraitDeclaration { name, functions: _ } => return Some(name.clone()),
+
+            ASTTreeNodeKind::TraitImplementation {
+                trait_name,
+                target,
+                functions: _,
+            } => Some(HashedString::new(format!(
+                "{}${}",
+                trait_name.val,
+                target.get_generic_name()
+            ))),
+
             _ => return None,
         }
     }
@@ -301,11 +415,19 @@ pub struct ASTTreeNode {
     pub kind: ASTTreeNodeKind,
     pub start: Position,
     pub end: Position,
+
+    /// The attributes written before the node, eg: `@allow(unused_variable)`
+    pub attributes: Vec<ASTAttribute>,
 }
 
 impl ASTTreeNode {
     pub fn new(kind: ASTTreeNodeKind, start: Position, end: Position) -> Self {
-        return ASTTreeNode { kind, start, end };
+        return ASTTreeNode {
+            kind,
+            start,
+            end,
+            attributes: vec![],
+        };
     }
 }
 
@@ -319,6 +441,7 @@ impl DiagnosticSpanOrigin for ASTTreeNode {
             start: SpanPosition::from_pos2(self.start.clone(), self.end.clone()),
             label: msg,
             kind,
+            replacement: None,
         }
     }
 
@@ -355,6 +478,10 @@ impl Display for ASTTreeNodeKind {
         let s = match self {
             Self::UseStatement { .. } => "use statement",
             Self::UnwrapCondition { .. } | Self::UnwrapValue { .. } => "unwrap",
+            Self::UnsafeFieldAccess { .. } => "unsafe field access",
+            Self::CastValue { .. } => "cast",
+            Self::UncheckedValue { .. } => "unchecked value",
+            Self::LayoutIntrinsic { .. } => "layout intrinsic",
             Self::IntegerLit { .. } => "integer literal",
             Self::StringLit(_) => "string literal",
             Self::ThisStructParam => "this reference",
@@ -366,10 +493,13 @@ impl Display for ASTTreeNodeKind {
             Self::Dereference(_) => "dereference",
             Self::ReferenceGrab(_) => "reference",
             Self::StructInitializer { .. } => "struct value initializer",
+            Self::TupleLiteral { .. } => "tuple literal",
             Self::ArrayVariableInitializerValue { .. }
             | Self::ArrayVariableInitializerValueSameValue { .. } => "array value initializer",
             Self::ArrayIndexAccess { .. } | Self::ArrayIndexModifiy { .. } => "index access",
+            Self::SubSlice { .. } => "sub-slice",
             Self::VarDeclaration { .. } => "variable declaration",
+            Self::TupleDestructuring { .. } => "tuple destructuring",
             Self::VarValueChange { .. } => "variable assignment",
             Self::VarIncrement { .. } => "variable incrementation",
             Self::IfStatement { .. } => "if statement",
@@ -382,12 +512,16 @@ impl Display for ASTTreeNodeKind {
             Self::FunctionCall { .. } => "function call",
             Self::FunctionDeclaration { .. } => "function declaration",
             Self::ExternFunctionDeclaration { .. } => "extern function declaration",
+            Self::ClosureExpression { .. } => "closure",
             Self::StructLRFunction { .. } => "struct LRU function usage",
             Self::StructLRVariable { .. } => "struct LRU variable usage",
-            Self::StructLayoutDeclaration { .. } => "struct / layout declaration",
+            Self::StructLayoutDeclaration { .. } => "struct / layout / union declaration",
             Self::StructFieldMember { .. } => "struct field",
             Self::EnumDeclaration { .. } => "enum declaration",
             Self::EnumEntryDeclaration { .. } => "enum entry declaration",
+            Self::TraitDeclaration { .. } => "trait declaration",
+            Self::TraitFunctionDeclaration { .. } => "trait function declaration",
+            Self::TraitImplementation { .. } => "trait implementation",
         };
 
         write!(f, "{}", s)?;
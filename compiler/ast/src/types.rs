@@ -32,6 +32,24 @@ pub enum ASTType {
     /// 0: The size of the array
     /// 1: Inner type
     Array(usize, Box<ASTType>),
+
+    /// A function pointer type node, written `func(s32, s32) -> s32`
+    /// 0: The argument types
+    /// 1: The return type
+    Function(Vec<Box<ASTType>>, Option<Box<ASTType>>),
+
+    /// A closure type node, written `func[](s32, s32) -> s32`
+    /// 0: The argument types
+    /// 1: The return type
+    Closure(Vec<Box<ASTType>>, Option<Box<ASTType>>),
+
+    /// A tuple type node, written `(s32, bool)`
+    /// 0: The element types
+    Tuple(Vec<Box<ASTType>>),
+
+    /// A slice type node, written `s32[]`
+    /// 0: The element type
+    Slice(Box<ASTType>),
 }
 
 impl ASTType {
@@ -41,6 +59,9 @@ impl ASTType {
             Self::Pointer(_, inner) => inner.get_generic_name(),
             Self::Reference(inner) => inner.get_generic_name(),
             Self::Array(_, inner) => inner.get_generic_name(),
+            Self::Slice(inner) => inner.get_generic_name(),
+            Self::Function(_, _) | Self::Closure(_, _) => "func".to_string(),
+            Self::Tuple(_) => "tuple".to_string(),
         }
     }
 }
@@ -2,18 +2,47 @@ use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
 use diagnostics::DiagnosticResult;
 use lexer::token::{LexerToken, LexerTokenType};
 
-use crate::value::parse_ast_value;
+use crate::{ranges::parse_value_range, value::parse_ast_value};
 
+/// Parses an index access or a sub-slice if the brackets contain a range.
+/// # Syntax
+/// `arr[2]`, `arr[1..4]`, `arr[..4]`
 pub fn parse_array_access(
     tokens: &Vec<LexerToken>,
     ind: &mut usize,
     original: Box<ASTTreeNode>,
 ) -> DiagnosticResult<Box<ASTTreeNode>> {
     let start = tokens[*ind].pos.clone();
+    let start_ind = *ind;
 
     *ind += 1;
 
-    let index = parse_ast_value(tokens, ind)?;
+    let mut is_range = tokens[*ind].tok_type == LexerTokenType::Dot;
+
+    let index = if is_range {
+        None
+    } else {
+        let index = parse_ast_value(tokens, ind)?;
+
+        is_range = tokens[*ind].tok_type == LexerTokenType::Dot;
+
+        Some(index)
+    };
+
+    if is_range {
+        *ind = start_ind;
+
+        let range = parse_value_range(tokens, ind)?;
+
+        return Ok(Box::new(ASTTreeNode::new(
+            ASTTreeNodeKind::SubSlice {
+                val: original,
+                range,
+            },
+            start,
+            tokens[*ind - 1].get_end_pos(),
+        )));
+    }
 
     tokens[*ind].expects(LexerTokenType::ArrayClose)?;
 
@@ -22,7 +51,7 @@ pub fn parse_array_access(
     return Ok(Box::new(ASTTreeNode::new(
         ASTTreeNodeKind::ArrayIndexAccess {
             val: original,
-            index,
+            index: index.unwrap(),
         },
         start,
         tokens[*ind].get_end_pos(),
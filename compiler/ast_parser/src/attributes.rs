@@ -0,0 +1,71 @@
+//! Parser module for attributes such as `@allow(unused_variable)`
+
+use ast::attributes::ASTAttribute;
+use compiler_utils::hash::HashedString;
+use diagnostics::{DiagnosticResult, builders::make_unexpected_simple_error};
+use lexer::token::{LexerToken, LexerTokenType};
+
+/// Parses every attribute written at the current position. Attribute arguments can either be keywords or string literals
+pub fn parse_attributes(
+    tokens: &[LexerToken],
+    ind: &mut usize,
+) -> DiagnosticResult<Vec<ASTAttribute>> {
+    let mut attributes = vec![];
+
+    while tokens[*ind].tok_type == LexerTokenType::At {
+        let start = tokens[*ind].pos.clone();
+
+        *ind += 1;
+
+        let name = tokens[*ind].expects_keyword()?;
+        let mut end = tokens[*ind].get_end_pos();
+
+        *ind += 1;
+
+        let mut args = vec![];
+
+        if tokens[*ind].tok_type == LexerTokenType::ParenOpen {
+            *ind += 1;
+
+            while tokens[*ind].tok_type != LexerTokenType::ParenClose {
+                let arg = match &tokens[*ind].tok_type {
+                    LexerTokenType::Keyword(val, _) => val.clone(),
+                    LexerTokenType::StringLit(val) => val.clone(),
+
+                    _ => {
+                        return Err(make_unexpected_simple_error(
+                            &tokens[*ind],
+                            &tokens[*ind].tok_type,
+                        )
+                        .into());
+                    }
+                };
+
+                args.push(arg);
+
+                *ind += 1;
+
+                if tokens[*ind].tok_type == LexerTokenType::ParenClose {
+                    break;
+                }
+
+                tokens[*ind].expects(LexerTokenType::Comma)?;
+
+                *ind += 1;
+            }
+
+            end = tokens[*ind].get_end_pos();
+
+            *ind += 1;
+        }
+
+        attributes.push(ASTAttribute {
+            name: HashedString::new(name.0),
+            args,
+            start,
+            end,
+        });
+    }
+
+    return Ok(attributes);
+}
@@ -0,0 +1,38 @@
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
+use diagnostics::DiagnosticResult;
+use lexer::token::{LexerToken, LexerTokenType};
+
+use crate::{types::parse_type, value::parse_ast_value};
+
+/// Parses an explicit cast such as `cast<value, type>`
+pub fn parse_cast_value(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+) -> DiagnosticResult<Box<ASTTreeNode>> {
+    let start = tokens[*ind].pos.clone();
+
+    tokens[*ind].expects(LexerTokenType::Cast)?;
+    *ind += 1;
+
+    tokens[*ind].expects(LexerTokenType::AngelBracketOpen)?;
+    *ind += 1;
+
+    let original = parse_ast_value(tokens, ind)?;
+
+    tokens[*ind].expects(LexerTokenType::Comma)?;
+    *ind += 1;
+
+    let target_type = parse_type(tokens, ind)?;
+
+    tokens[*ind].expects(LexerTokenType::AngelBracketClose)?;
+
+    *ind += 1;
+    return Ok(Box::new(ASTTreeNode::new(
+        ASTTreeNodeKind::CastValue {
+            original,
+            target_type,
+        },
+        start,
+        tokens[*ind].get_end_pos(),
+    )));
+}
@@ -0,0 +1,93 @@
+//! Closure expression parsing
+
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind, ClosureCapture};
+use compiler_utils::hash::HashedString;
+use diagnostics::DiagnosticResult;
+use lexer::token::{LexerToken, LexerTokenType};
+
+use crate::functions::{
+    arguments::parse_function_arguments, parse_function_return_type, parse_node_body,
+};
+
+/// Parses the capture list of a closure. Variables prefixed by `&` are captured by reference.
+/// # Syntax
+/// `[a, &b]`
+pub fn parse_closure_captures(
+    tokens: &[LexerToken],
+    ind: &mut usize,
+) -> DiagnosticResult<Vec<ClosureCapture>> {
+    let mut captures = vec![];
+
+    if tokens[*ind].tok_type != LexerTokenType::ArrayOpen {
+        return Ok(captures);
+    }
+
+    *ind += 1;
+
+    while tokens[*ind].tok_type != LexerTokenType::ArrayClose {
+        let by_ref = tokens[*ind].tok_type == LexerTokenType::Ampersand;
+
+        if by_ref {
+            *ind += 1;
+        }
+
+        let name = tokens[*ind].expects_keyword()?;
+
+        captures.push(ClosureCapture {
+            name: HashedString::new(name.0),
+            by_ref,
+        });
+
+        *ind += 1;
+
+        if tokens[*ind].tok_type == LexerTokenType::ArrayClose {
+            break;
+        }
+
+        tokens[*ind].expects(LexerTokenType::Comma)?;
+        *ind += 1;
+    }
+
+    *ind += 1;
+
+    return Ok(captures);
+}
+
+/// Parses an anonymous function expression.
+/// # Syntax
+/// `func[a, &b](s32 c) -> s32 { ... }`, the capture list can be omitted
+pub fn parse_closure_expression(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+) -> DiagnosticResult<Box<ASTTreeNode>> {
+    let start = tokens[*ind].pos.clone();
+
+    *ind += 1;
+
+    let captures = parse_closure_captures(tokens, ind)?;
+
+    tokens[*ind].expects(LexerTokenType::ParenOpen)?;
+
+    let args = parse_function_arguments(tokens, ind, None)?;
+
+    *ind += 1;
+
+    let return_type = parse_function_return_type(tokens, ind)?;
+
+    tokens[*ind].expects(LexerTokenType::BracketOpen)?;
+
+    let body = parse_node_body(tokens, ind)?;
+
+    let end = tokens[*ind - 1].get_end_pos();
+
+    return Ok(Box::new(ASTTreeNode::new(
+        ASTTreeNodeKind::ClosureExpression {
+            captures,
+            args: args.0,
+            body,
+            return_type,
+        },
+        start,
+        end,
+    )));
+}
@@ -14,6 +14,7 @@ use crate::{
 };
 
 pub mod arguments;
+pub mod closures;
 pub mod returns;
 pub mod shadow;
 
@@ -42,6 +43,23 @@ pub fn parse_function_declaraction(
 ) -> DiagnosticResult<Box<ASTTreeNode>> {
     let start = tokens[*ind].pos.clone();
 
+    let mut unchecked = false;
+    let mut fast = false;
+
+    // Function modifiers can be written in any order before func
+    loop {
+        match tokens[*ind].tok_type {
+            LexerTokenType::Unchecked if !unchecked => unchecked = true,
+            LexerTokenType::Fast if !fast => fast = true,
+
+            _ => break,
+        }
+
+        *ind += 1;
+    }
+
+    tokens[*ind].expects(LexerTokenType::Function)?;
+
     *ind += 1;
     let function_name = tokens[*ind].expects_keyword()?;
 
@@ -67,6 +85,8 @@ pub fn parse_function_declaraction(
             body,
             return_type: ret_type,
             requires_this: args.1,
+            unchecked,
+            fast,
         },
         start,
         end,
@@ -0,0 +1,54 @@
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind, LayoutIntrinsic};
+use compiler_utils::hash::HashedString;
+use diagnostics::DiagnosticResult;
+use lexer::token::{LexerToken, LexerTokenType};
+
+use crate::types::parse_type;
+
+/// Parses a type layout intrinsic such as `sizeof(T)`, `alignof(T)` or `offsetof(T, field)`
+pub fn parse_layout_intrinsic(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+) -> DiagnosticResult<Box<ASTTreeNode>> {
+    let start = tokens[*ind].pos.clone();
+
+    let intrinsic = match tokens[*ind].tok_type {
+        LexerTokenType::SizeOf => LayoutIntrinsic::SizeOf,
+        LexerTokenType::AlignOf => LayoutIntrinsic::AlignOf,
+        _ => LayoutIntrinsic::OffsetOf,
+    };
+
+    *ind += 1;
+
+    tokens[*ind].expects(LexerTokenType::ParenOpen)?;
+    *ind += 1;
+
+    let target = parse_type(tokens, ind)?;
+
+    let mut field = None;
+
+    if intrinsic == LayoutIntrinsic::OffsetOf {
+        tokens[*ind].expects(LexerTokenType::Comma)?;
+        *ind += 1;
+
+        let kwd = tokens[*ind].expects_keyword()?;
+
+        field = Some(HashedString::new(kwd.0));
+        *ind += 1;
+    }
+
+    tokens[*ind].expects(LexerTokenType::ParenClose)?;
+
+    let end = tokens[*ind].get_end_pos();
+    *ind += 1;
+
+    return Ok(Box::new(ASTTreeNode::new(
+        ASTTreeNodeKind::LayoutIntrinsic {
+            intrinsic,
+            target,
+            field,
+        },
+        start,
+        end,
+    )));
+}
@@ -9,22 +9,40 @@ use lexer::token::{LexerToken, LexerTokenType};
 use crate::parser::parse_ast_node;
 
 pub mod arrays;
+pub mod attributes;
+pub mod casts;
 pub mod comp;
 pub mod control;
 pub mod functions;
+pub mod intrinsics;
 pub mod literals;
 pub mod math;
 pub mod parser;
 pub mod pointers;
 pub mod ranges;
 pub mod structs;
+pub mod traits;
+pub mod tuples;
 pub mod types;
+pub mod unchecked;
 pub mod unwraps;
 pub mod use_statements;
 pub mod value;
 pub mod variables;
 
 pub fn parse_ast_ctx(tokens: &Vec<LexerToken>) -> DiagnosticResult<ParserCtx> {
+    // Comments carry no meaning for the tree and may appear in between any two tokens
+    let tokens: &Vec<LexerToken> = &tokens
+        .iter()
+        .filter(|t| {
+            !matches!(
+                t.tok_type,
+                LexerTokenType::Comment(_) | LexerTokenType::GlobalComment(_)
+            )
+        })
+        .cloned()
+        .collect();
+
     let mut ind = 0;
 
     let mut ctx = ParserCtx::new();
@@ -15,7 +15,7 @@ pub fn parse_integer_literal(
     return Ok(Box::new(ASTTreeNode::new(
         ASTTreeNodeKind::IntegerLit {
             val: val.0,
-            hash: val.1,
+            int_type: val.1,
         },
         tokens[*ind].pos.clone(),
         tokens[*ind].get_end_pos(),
@@ -10,6 +10,7 @@ use diagnostics::{
 use lexer::token::{LexerToken, LexerTokenType};
 
 use crate::{
+    attributes::parse_attributes,
     control::{
         for_loop::parse_for_loop, if_else::parse_if_statement, while_block::parse_while_block,
     },
@@ -19,8 +20,12 @@ use crate::{
     },
     pointers::parse_deref_modify,
     structs::{enums::parse_enum_declaration, parse_type_declaration},
+    traits::{parse_trait_declaration, parse_trait_implementation},
+    tuples::{is_tuple_destructuring, parse_tuple_destructuring},
+    unchecked::parse_unchecked_statement,
+    unwraps::parse_unwrap_value,
     use_statements::parse_use_statement,
-    value::parse_ast_value_post_l,
+    value::{parse_ast_value_dotacess, parse_ast_value_post_l},
     variables::{decl::parse_variable_declaration, static_decl::parse_static_variable_declaration},
 };
 
@@ -31,12 +36,22 @@ use crate::{
 /// - Function declarations
 /// - Struct declarations
 /// - Layout declarations
+/// - Union declarations
 pub fn parse_ast_node(
     tokens: &Vec<LexerToken>,
     ind: &mut usize,
 ) -> DiagnosticResult<Box<ASTTreeNode>> {
     match &tokens[*ind].tok_type {
-        LexerTokenType::Function => {
+        LexerTokenType::At => {
+            let attributes = parse_attributes(tokens, ind)?;
+
+            let mut node = parse_ast_node(tokens, ind)?;
+            node.attributes = attributes;
+
+            return Ok(node);
+        }
+
+        LexerTokenType::Function | LexerTokenType::Unchecked | LexerTokenType::Fast => {
             return parse_function_declaraction(tokens, ind, None);
         }
 
@@ -44,17 +59,15 @@ pub fn parse_ast_node(
             return parse_extern_function_definition(tokens, ind);
         }
 
-        LexerTokenType::Struct => {
-            return parse_type_declaration(tokens, ind, false);
-        }
+        LexerTokenType::Struct => parse_type_declaration(tokens, ind, false, false),
 
         LexerTokenType::Static => {
             return parse_static_variable_declaration(tokens, ind);
         }
 
-        LexerTokenType::Layout => {
-            return parse_type_declaration(tokens, ind, true);
-        }
+        LexerTokenType::Layout => parse_type_declaration(tokens, ind, true, false),
+
+        LexerTokenType::Union => parse_type_declaration(tokens, ind, true, true),
 
         LexerTokenType::Enum => {
             return parse_enum_declaration(tokens, ind);
@@ -64,6 +77,10 @@ pub fn parse_ast_node(
             return parse_use_statement(tokens, ind);
         }
 
+        LexerTokenType::Trait => parse_trait_declaration(tokens, ind),
+
+        LexerTokenType::Impl => parse_trait_implementation(tokens, ind),
+
         _ => {
             return Err(make_unexpected_simple_error(&tokens[*ind], &tokens[*ind].tok_type).into());
         }
@@ -76,7 +93,20 @@ pub fn parse_ast_node_in_body(
     ind: &mut usize,
 ) -> DiagnosticResult<Box<ASTTreeNode>> {
     match &tokens[*ind].tok_type {
+        LexerTokenType::At => {
+            let attributes = parse_attributes(tokens, ind)?;
+
+            let mut node = parse_ast_node_in_body(tokens, ind)?;
+            node.attributes = attributes;
+
+            return Ok(node);
+        }
+
         LexerTokenType::Var => {
+            if is_tuple_destructuring(tokens, *ind) {
+                return parse_tuple_destructuring(tokens, ind);
+            }
+
             return parse_variable_declaration(tokens, ind, true);
         }
 
@@ -94,10 +124,18 @@ pub fn parse_ast_node_in_body(
 
         LexerTokenType::Asterisk => return parse_deref_modify(tokens, ind),
 
+        LexerTokenType::UnwrapUnsafe => {
+            let access = parse_unwrap_value(tokens, ind);
+
+            return parse_ast_value_post_l(tokens, ind, access, true);
+        }
+
         LexerTokenType::Return => {
             return parse_function_return_statement(tokens, ind);
         }
 
+        LexerTokenType::Unchecked => parse_unchecked_statement(tokens, ind),
+
         LexerTokenType::Keyword(str, _) => {
             if tokens[*ind + 1].tok_type == LexerTokenType::ParenOpen {
                 let call = parse_function_call(tokens, ind);
@@ -113,7 +151,27 @@ pub fn parse_ast_node_in_body(
 
             *ind += 1;
 
-            let new = parse_ast_value_post_l(tokens, ind, n, true)?;
+            let chain = parse_ast_value_dotacess(tokens, ind, n);
+            let new = parse_ast_value_post_l(tokens, ind, chain, true)?;
+
+            if new.kind.is_var_access() {
+                return Err(make_unused_variable(&*new, &"access".to_string()).into());
+            }
+
+            return Ok(new);
+        }
+
+        LexerTokenType::This => {
+            let n = Ok(make_node!(
+                ASTTreeNodeKind::VariableReference(HashedString::new("this".to_string())),
+                &tokens[*ind],
+                &tokens[*ind]
+            ));
+
+            *ind += 1;
+
+            let chain = parse_ast_value_dotacess(tokens, ind, n);
+            let new = parse_ast_value_post_l(tokens, ind, chain, true)?;
 
             if new.kind.is_var_access() {
                 return Err(make_unused_variable(&*new, &"access".to_string()).into());
@@ -2,7 +2,7 @@ use ast::{
     ranges::ASTRange,
     tree::{ASTTreeNode, ASTTreeNodeKind},
 };
-use compiler_utils::hash;
+use compiler_utils::hash::HashedString;
 use diagnostics::DiagnosticResult;
 use lexer::token::{LexerToken, LexerTokenType};
 
@@ -19,7 +19,7 @@ pub fn parse_value_range(tokens: &Vec<LexerToken>, ind: &mut usize) -> Diagnosti
         min = Box::new(ASTTreeNode::new(
             ASTTreeNodeKind::IntegerLit {
                 val: 0,
-                hash: hash!("s64"),
+                int_type: HashedString::new("s64".to_string()),
             },
             tokens[*ind].pos.clone(),
             tokens[*ind].get_end_pos(),
@@ -1,14 +1,12 @@
-use ast::{
-    tree::{ASTTreeNode, ASTTreeNodeKind},
-    types::ASTType,
-};
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
 use compiler_utils::hash::HashedString;
 use diagnostics::DiagnosticResult;
 use lexer::token::{LexerToken, LexerTokenType};
 
 use crate::{
-    functions::parse_function_declaraction, structs::members::parse_types_field_member,
-    types::parse_type_parameters_declaration,
+    functions::parse_function_declaraction,
+    structs::members::parse_types_field_member,
+    types::{make_type_declaration_self_type, parse_type, parse_type_parameters_declaration},
 };
 
 pub fn parse_enum_entry(
@@ -22,25 +20,50 @@ pub fn parse_enum_entry(
 
     let mut fields = vec![];
 
-    tokens[*ind].expects(LexerTokenType::ParenOpen)?;
-    *ind += 1;
+    // Fieldless entries can omit their parenthesis
+    if tokens[*ind].tok_type == LexerTokenType::ParenOpen {
+        *ind += 1;
 
-    loop {
-        fields.push(parse_types_field_member(tokens, ind)?);
+        while tokens[*ind].tok_type != LexerTokenType::ParenClose {
+            fields.push(parse_types_field_member(tokens, ind)?);
 
-        if tokens[*ind].tok_type == LexerTokenType::ParenClose {
-            break;
+            if tokens[*ind].tok_type == LexerTokenType::ParenClose {
+                break;
+            }
+
+            tokens[*ind].expects(LexerTokenType::Comma)?;
+            *ind += 1;
         }
 
-        tokens[*ind].expects(LexerTokenType::Comma)?;
+        *ind += 1;
     }
 
-    *ind += 1;
+    let mut discriminant = None;
+
+    if tokens[*ind].tok_type == LexerTokenType::EqualSign {
+        *ind += 1;
+
+        let negative = tokens[*ind].tok_type == LexerTokenType::Minus;
+
+        if negative {
+            *ind += 1;
+        }
+
+        let val = tokens[*ind].expects_int_lit()?.0;
+        *ind += 1;
+
+        if negative {
+            discriminant = Some(-val);
+        } else {
+            discriminant = Some(val);
+        }
+    }
 
     return Ok(Box::new(ASTTreeNode::new(
         ASTTreeNodeKind::EnumEntryDeclaration {
             name: HashedString::new(name.0),
             fields,
+            discriminant,
         },
         start,
         tokens[*ind].get_end_pos(),
@@ -58,7 +81,15 @@ pub fn parse_enum_declaration(
     let name = tokens[*ind].expects_keyword()?;
     *ind += 1;
 
-    let t = parse_type_parameters_declaration(tokens, ind)?;
+    let (t, bounds) = parse_type_parameters_declaration(tokens, ind)?;
+
+    let mut repr = None;
+
+    if tokens[*ind].tok_type == LexerTokenType::Collon {
+        *ind += 1;
+
+        repr = Some(parse_type(tokens, ind)?);
+    }
 
     tokens[*ind].expects(LexerTokenType::BracketOpen)?;
     *ind += 1;
@@ -66,7 +97,7 @@ pub fn parse_enum_declaration(
     let mut entries = vec![];
     let mut functions = vec![];
 
-    let temp_type = ASTType::Generic(name.0.clone(), vec![], vec![], None);
+    let temp_type = make_type_declaration_self_type(name.0.clone(), &t);
 
     loop {
         if tokens[*ind].is_keyword() {
@@ -90,9 +121,11 @@ pub fn parse_enum_declaration(
     return Ok(Box::new(ASTTreeNode::new(
         ASTTreeNodeKind::EnumDeclaration {
             name: HashedString::new(name.0),
+            repr,
             entries,
             functions,
             type_params: t,
+            type_bounds: bounds,
         },
         start,
         tokens[*ind].get_end_pos(),
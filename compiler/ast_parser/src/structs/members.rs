@@ -1,6 +1,6 @@
 use compiler_utils::hash::HashedString;
 use diagnostics::DiagnosticResult;
-use lexer::token::LexerToken;
+use lexer::token::{LexerToken, LexerTokenType};
 
 use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
 
@@ -16,14 +16,27 @@ pub fn parse_types_field_member(
 
     let field_name = tokens[*ind].expects_keyword()?;
 
-    let end = tokens[*ind].get_end_pos().clone();
+    let mut end = tokens[*ind].get_end_pos().clone();
 
     *ind += 1;
 
+    let mut bit_width = None;
+
+    // Bitfield: u8 flags : 3
+    if tokens[*ind].tok_type == LexerTokenType::Collon {
+        *ind += 1;
+
+        bit_width = Some(tokens[*ind].expects_int_lit()?.0 as usize);
+        end = tokens[*ind].get_end_pos().clone();
+
+        *ind += 1;
+    }
+
     return Ok(Box::new(ASTTreeNode::new(
         ASTTreeNodeKind::StructFieldMember {
             name: HashedString::new(field_name.0),
             member_type,
+            bit_width,
         },
         start,
         end,
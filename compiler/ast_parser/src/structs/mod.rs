@@ -2,14 +2,13 @@ use compiler_utils::hash::HashedString;
 use diagnostics::DiagnosticResult;
 use lexer::token::{LexerToken, LexerTokenType};
 
-use ast::{
-    tree::{ASTTreeNode, ASTTreeNodeKind},
-    types::ASTType,
-};
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
 
 use crate::{
-    functions::parse_function_declaraction, structs::members::parse_types_field_member,
-    types::parse_type_parameters_declaration,
+    attributes::parse_attributes,
+    functions::parse_function_declaraction,
+    structs::members::parse_types_field_member,
+    types::{make_type_declaration_self_type, parse_type_parameters_declaration},
 };
 
 pub mod enums;
@@ -20,6 +19,7 @@ pub fn parse_type_declaration(
     tokens: &Vec<LexerToken>,
     ind: &mut usize,
     layout: bool,
+    union: bool,
 ) -> DiagnosticResult<Box<ASTTreeNode>> {
     let start = tokens[*ind].pos.clone();
 
@@ -29,7 +29,7 @@ pub fn parse_type_declaration(
 
     *ind += 1;
 
-    let type_params = parse_type_parameters_declaration(tokens, ind)?;
+    let (type_params, type_bounds) = parse_type_parameters_declaration(tokens, ind)?;
 
     tokens[*ind].expects(LexerTokenType::BracketOpen)?;
 
@@ -37,10 +37,18 @@ pub fn parse_type_declaration(
 
     let mut members: Vec<Box<ASTTreeNode>> = Vec::new();
 
-    let temp_type = ASTType::Generic(type_name.0.clone(), vec![], vec![], None);
+    let temp_type = make_type_declaration_self_type(type_name.0.clone(), &type_params);
 
     while tokens[*ind].tok_type != LexerTokenType::BracketClose {
-        if tokens[*ind].tok_type == LexerTokenType::Function {
+        let attributes = parse_attributes(tokens, ind)?;
+
+        // Fields of function pointer and closure types also start with func
+        if tokens[*ind].tok_type == LexerTokenType::Unchecked
+            || tokens[*ind].tok_type == LexerTokenType::Fast
+            || (tokens[*ind].tok_type == LexerTokenType::Function
+                && tokens[*ind + 1].tok_type != LexerTokenType::ParenOpen
+                && tokens[*ind + 1].tok_type != LexerTokenType::ArrayOpen)
+        {
             members.push(parse_function_declaraction(
                 tokens,
                 ind,
@@ -49,6 +57,8 @@ pub fn parse_type_declaration(
         } else {
             members.push(parse_types_field_member(tokens, ind)?);
         }
+
+        members.last_mut().unwrap().attributes = attributes;
     }
 
     let end = tokens[*ind].get_end_pos().clone();
@@ -59,8 +69,10 @@ pub fn parse_type_declaration(
         ASTTreeNodeKind::StructLayoutDeclaration {
             name: HashedString::new(type_name.0),
             layout,
+            union,
             members,
             type_params,
+            type_bounds,
         },
         start,
         end,
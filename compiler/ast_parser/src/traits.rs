@@ -0,0 +1,169 @@
+//! Parser module for traits and trait implementations
+
+use ast::{
+    tree::{ASTTreeNode, ASTTreeNodeKind},
+    types::ASTType,
+};
+use compiler_utils::hash::HashedString;
+use diagnostics::DiagnosticResult;
+use lexer::token::{LexerToken, LexerTokenType};
+
+use crate::{
+    functions::{
+        arguments::parse_function_arguments, parse_function_declaraction,
+        parse_function_return_type,
+    },
+    types::parse_type,
+};
+
+/// Parses a trait declaration.
+/// # Syntax
+/// ```text
+/// trait hashable {
+///     func hash(this) -> u64;
+/// }
+/// ```
+pub fn parse_trait_declaration(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+) -> DiagnosticResult<Box<ASTTreeNode>> {
+    let start = tokens[*ind].pos.clone();
+
+    *ind += 1;
+
+    let trait_name = tokens[*ind].expects_keyword()?;
+
+    *ind += 1;
+
+    tokens[*ind].expects(LexerTokenType::BracketOpen)?;
+
+    *ind += 1;
+
+    let mut functions = vec![];
+
+    // Only used to allow the `this` argument, trait functions never store it
+    let temp_type = ASTType::Generic(trait_name.0.clone(), vec![], vec![], None);
+
+    while tokens[*ind].tok_type != LexerTokenType::BracketClose {
+        if tokens[*ind].tok_type == LexerTokenType::SemiCollon {
+            *ind += 1;
+            continue;
+        }
+
+        tokens[*ind].expects(LexerTokenType::Function)?;
+
+        functions.push(parse_trait_function_declaration(
+            tokens,
+            ind,
+            temp_type.clone(),
+        )?);
+    }
+
+    let end = tokens[*ind].get_end_pos().clone();
+
+    *ind += 1;
+
+    return Ok(Box::new(ASTTreeNode::new(
+        ASTTreeNodeKind::TraitDeclaration {
+            name: HashedString::new(trait_name.0),
+            functions,
+        },
+        start,
+        end,
+    )));
+}
+
+/// Parses a function signature contained within a trait declaration.
+pub fn parse_trait_function_declaration(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+    temp_type: ASTType,
+) -> DiagnosticResult<Box<ASTTreeNode>> {
+    let start = tokens[*ind].pos.clone();
+
+    *ind += 1;
+    let function_name = tokens[*ind].expects_keyword()?;
+
+    *ind += 1;
+    tokens[*ind].expects(LexerTokenType::ParenOpen)?;
+
+    let mut args = parse_function_arguments(tokens, ind, Some(temp_type))?;
+
+    *ind += 1;
+
+    let ret_type = parse_function_return_type(tokens, ind)?;
+    let end = tokens[*ind - 1].get_end_pos();
+
+    if args.1 {
+        args.0.remove(0);
+    }
+
+    return Ok(Box::new(ASTTreeNode::new(
+        ASTTreeNodeKind::TraitFunctionDeclaration {
+            func_name: HashedString::new(function_name.0),
+            args: args.0,
+            return_type: ret_type,
+            requires_this: args.1,
+        },
+        start,
+        end,
+    )));
+}
+
+/// Parses a trait implementation.
+/// # Syntax
+/// ```text
+/// impl hashable for point {
+///     func hash(this) -> u64 {
+///         ret this.x;
+///     }
+/// }
+/// ```
+pub fn parse_trait_implementation(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+) -> DiagnosticResult<Box<ASTTreeNode>> {
+    let start = tokens[*ind].pos.clone();
+
+    *ind += 1;
+
+    let trait_name = tokens[*ind].expects_keyword()?;
+
+    *ind += 1;
+
+    tokens[*ind].expects(LexerTokenType::For)?;
+
+    *ind += 1;
+
+    let target = parse_type(tokens, ind)?;
+
+    tokens[*ind].expects(LexerTokenType::BracketOpen)?;
+
+    *ind += 1;
+
+    let mut functions = vec![];
+
+    while tokens[*ind].tok_type != LexerTokenType::BracketClose {
+        tokens[*ind].expects(LexerTokenType::Function)?;
+
+        functions.push(parse_function_declaraction(
+            tokens,
+            ind,
+            Some(target.clone()),
+        )?);
+    }
+
+    let end = tokens[*ind].get_end_pos().clone();
+
+    *ind += 1;
+
+    return Ok(Box::new(ASTTreeNode::new(
+        ASTTreeNodeKind::TraitImplementation {
+            trait_name: HashedString::new(trait_name.0),
+            target,
+            functions,
+        },
+        start,
+        end,
+    )));
+}
@@ -0,0 +1,116 @@
+//! Tuple related parsing
+
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
+use compiler_utils::hash::HashedString;
+use diagnostics::DiagnosticResult;
+use lexer::token::{LexerToken, LexerTokenType};
+
+use crate::value::parse_ast_value;
+
+/// Parses a tuple literal. A single parenthesized value is only a grouping of said value.
+/// # Syntax
+/// `(5, a + 2)`
+pub fn parse_tuple_literal(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+) -> DiagnosticResult<Box<ASTTreeNode>> {
+    let start = tokens[*ind].pos.clone();
+
+    tokens[*ind].expects(LexerTokenType::ParenOpen)?;
+    *ind += 1;
+
+    let mut values = vec![];
+
+    loop {
+        values.push(parse_ast_value(tokens, ind)?);
+
+        if tokens[*ind].tok_type == LexerTokenType::ParenClose {
+            break;
+        }
+
+        tokens[*ind].expects(LexerTokenType::Comma)?;
+        *ind += 1;
+    }
+
+    let end = tokens[*ind].get_end_pos();
+
+    *ind += 1;
+
+    if values.len() == 1 {
+        return Ok(values.remove(0));
+    }
+
+    return Ok(Box::new(ASTTreeNode::new(
+        ASTTreeNodeKind::TupleLiteral { values },
+        start,
+        end,
+    )));
+}
+
+/// Checks if the variable declaration starting at the given index is a tuple destructuring rather than a declaration of a tuple typed variable
+pub fn is_tuple_destructuring(tokens: &[LexerToken], ind: usize) -> bool {
+    let mut ind = ind + 1;
+
+    if tokens[ind].tok_type != LexerTokenType::ParenOpen {
+        return false;
+    }
+
+    ind += 1;
+
+    while tokens[ind].is_keyword() {
+        ind += 1;
+
+        if tokens[ind].tok_type != LexerTokenType::Comma {
+            break;
+        }
+
+        ind += 1;
+    }
+
+    return tokens[ind].tok_type == LexerTokenType::ParenClose
+        && tokens[ind + 1].tok_type == LexerTokenType::EqualSign;
+}
+
+/// Parses a tuple destructuring.
+/// # Syntax
+/// `var (a, b) = val`
+pub fn parse_tuple_destructuring(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+) -> DiagnosticResult<Box<ASTTreeNode>> {
+    let start = tokens[*ind].pos.clone();
+
+    tokens[*ind].expects(LexerTokenType::Var)?;
+    *ind += 1;
+
+    tokens[*ind].expects(LexerTokenType::ParenOpen)?;
+    *ind += 1;
+
+    let mut names = vec![];
+
+    loop {
+        names.push(HashedString::new(tokens[*ind].expects_keyword()?.0));
+        *ind += 1;
+
+        if tokens[*ind].tok_type == LexerTokenType::ParenClose {
+            break;
+        }
+
+        tokens[*ind].expects(LexerTokenType::Comma)?;
+        *ind += 1;
+    }
+
+    *ind += 1;
+
+    tokens[*ind].expects(LexerTokenType::EqualSign)?;
+    *ind += 1;
+
+    let value = parse_ast_value(tokens, ind)?;
+    let end = value.end.clone();
+
+    return Ok(Box::new(ASTTreeNode::new(
+        ASTTreeNodeKind::TupleDestructuring { names, value },
+        start,
+        end,
+    )));
+}
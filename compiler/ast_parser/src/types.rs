@@ -1,7 +1,10 @@
 //! Parsing for type related features
 
 use ast::types::ASTType;
-use compiler_typing::TypeParameterContainer;
+use compiler_typing::{
+    TypeParameterBounds, TypeParameterContainer,
+    bounds::traits::{Trait, TraitBound, TraitBoundMember},
+};
 use compiler_utils::hash::HashedString;
 use diagnostics::{
     DiagnosticResult,
@@ -9,12 +12,18 @@ use diagnostics::{
 };
 use lexer::token::{LexerToken, LexerTokenType};
 
+use crate::functions::parse_function_return_type;
+
 #[derive(Clone, Debug)]
 pub enum ParsingASTTypeMember {
     Generic(String, Vec<Box<ASTType>>, Vec<usize>, Option<String>),
+    Function(Vec<Box<ASTType>>, Option<Box<ASTType>>),
+    Closure(Vec<Box<ASTType>>, Option<Box<ASTType>>),
+    Tuple(Vec<Box<ASTType>>),
     Pointer(bool),
     Reference,
     Array(usize),
+    Slice,
 }
 
 /// Parses the type size specifiers
@@ -102,6 +111,79 @@ pub fn parse_type_generic(
     ));
 }
 
+/// Parses a function pointer or closure type.
+/// # Syntax
+/// `func(s32, s32) -> s32` or `func[](s32, s32) -> s32` for closures
+pub fn parse_type_function(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+) -> DiagnosticResult<ParsingASTTypeMember> {
+    *ind += 1;
+
+    let closure = tokens[*ind].tok_type == LexerTokenType::ArrayOpen;
+
+    if closure {
+        *ind += 1;
+
+        tokens[*ind].expects(LexerTokenType::ArrayClose)?;
+        *ind += 1;
+    }
+
+    tokens[*ind].expects(LexerTokenType::ParenOpen)?;
+    *ind += 1;
+
+    let mut args = vec![];
+
+    while tokens[*ind].tok_type != LexerTokenType::ParenClose {
+        args.push(Box::new(parse_type(tokens, ind)?));
+
+        if tokens[*ind].tok_type == LexerTokenType::ParenClose {
+            break;
+        }
+
+        tokens[*ind].expects(LexerTokenType::Comma)?;
+        *ind += 1;
+    }
+
+    *ind += 1;
+
+    let ret = parse_function_return_type(tokens, ind)?.map(Box::new);
+
+    if closure {
+        return Ok(ParsingASTTypeMember::Closure(args, ret));
+    }
+
+    return Ok(ParsingASTTypeMember::Function(args, ret));
+}
+
+/// Parses a tuple type.
+/// # Syntax
+/// `(s32, bool)`
+pub fn parse_type_tuple(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+) -> DiagnosticResult<ParsingASTTypeMember> {
+    tokens[*ind].expects(LexerTokenType::ParenOpen)?;
+    *ind += 1;
+
+    let mut elements = vec![];
+
+    loop {
+        elements.push(Box::new(parse_type(tokens, ind)?));
+
+        if tokens[*ind].tok_type == LexerTokenType::ParenClose {
+            break;
+        }
+
+        tokens[*ind].expects(LexerTokenType::Comma)?;
+        *ind += 1;
+    }
+
+    *ind += 1;
+
+    return Ok(ParsingASTTypeMember::Tuple(elements));
+}
+
 pub fn parse_type_member(
     tokens: &Vec<LexerToken>,
     ind: &mut usize,
@@ -116,16 +198,20 @@ pub fn parse_type_member(
             return Ok(Some(parse_type_generic(tokens, ind)?));
         }
 
-        LexerTokenType::ParenOpen => {
-            *ind += 1;
+        LexerTokenType::Function => {
+            if took_generic {
+                return Ok(None);
+            }
 
-            let res = parse_type_member(tokens, ind, took_generic)?;
-            *ind += 1;
+            return Ok(Some(parse_type_function(tokens, ind)?));
+        }
 
-            tokens[*ind].expects(LexerTokenType::ParenClose)?;
-            *ind += 1;
+        LexerTokenType::ParenOpen => {
+            if took_generic {
+                return Ok(None);
+            }
 
-            return Ok(res);
+            return Ok(Some(parse_type_tuple(tokens, ind)?));
         }
 
         LexerTokenType::Ampersand => {
@@ -153,6 +239,12 @@ pub fn parse_type_member(
         LexerTokenType::ArrayOpen => {
             *ind += 1;
 
+            if tokens[*ind].tok_type == LexerTokenType::ArrayClose {
+                *ind += 1;
+
+                return Ok(Some(ParsingASTTypeMember::Slice));
+            }
+
             let size = tokens[*ind].expects_int_lit()?;
 
             *ind += 1;
@@ -182,7 +274,11 @@ pub fn parse_type(tokens: &Vec<LexerToken>, ind: &mut usize) -> DiagnosticResult
         let parsed_member = parse_type_member(tokens, ind, took_generic)?;
 
         if let Some(value) = parsed_member {
-            if let ParsingASTTypeMember::Generic(_, _, _, _) = &value {
+            if let ParsingASTTypeMember::Generic(_, _, _, _)
+            | ParsingASTTypeMember::Function(_, _)
+            | ParsingASTTypeMember::Closure(_, _)
+            | ParsingASTTypeMember::Tuple(_) = &value
+            {
                 took_generic = true;
             }
 
@@ -199,9 +295,13 @@ pub fn parse_type(tokens: &Vec<LexerToken>, ind: &mut usize) -> DiagnosticResult
             ParsingASTTypeMember::Generic(t, types, sizes, specifier) => {
                 ASTType::Generic(t, types, sizes, specifier)
             }
+            ParsingASTTypeMember::Function(args, ret) => ASTType::Function(args, ret),
+            ParsingASTTypeMember::Closure(args, ret) => ASTType::Closure(args, ret),
+            ParsingASTTypeMember::Tuple(elements) => ASTType::Tuple(elements),
             ParsingASTTypeMember::Pointer(array) => ASTType::Pointer(array, child.unwrap()),
             ParsingASTTypeMember::Reference => ASTType::Reference(child.unwrap()),
             ParsingASTTypeMember::Array(size) => ASTType::Array(size, child.unwrap()),
+            ParsingASTTypeMember::Slice => ASTType::Slice(child.unwrap()),
         };
 
         child = Some(Box::new(converted_member));
@@ -217,15 +317,62 @@ pub fn parse_type(tokens: &Vec<LexerToken>, ind: &mut usize) -> DiagnosticResult
     };
 }
 
+/// Parses the trait bound of a type parameter declaration.
+/// # Syntax
+/// `A: !numeric ~!cpusupported` where `!trait` requires the trait and `~!trait` excludes it
+pub fn parse_type_parameter_bound(
+    tokens: &[LexerToken],
+    ind: &mut usize,
+) -> DiagnosticResult<TraitBound> {
+    let mut bound = TraitBound::new();
+
+    if tokens[*ind].tok_type != LexerTokenType::Collon {
+        return Ok(bound);
+    }
+
+    *ind += 1;
+
+    while tokens[*ind].tok_type == LexerTokenType::ExclamationMark
+        || tokens[*ind].tok_type == LexerTokenType::Tidle
+    {
+        let exclude = tokens[*ind].tok_type == LexerTokenType::Tidle;
+
+        if exclude {
+            *ind += 1;
+            tokens[*ind].expects(LexerTokenType::ExclamationMark)?;
+        }
+
+        *ind += 1;
+
+        let name = tokens[*ind].expects_keyword()?;
+
+        let t = match Trait::from_hash(name.1) {
+            Some(v) => v,
+            None => Trait::Custom(HashedString::new(name.0)),
+        };
+
+        if exclude {
+            bound.members.push(TraitBoundMember::Exclude(t));
+        } else {
+            bound.members.push(TraitBoundMember::Select(t));
+        }
+
+        *ind += 1;
+    }
+
+    return Ok(bound);
+}
+
 pub fn parse_type_parameters_declaration(
     tokens: &Vec<LexerToken>,
     ind: &mut usize,
-) -> DiagnosticResult<TypeParameterContainer> {
+) -> DiagnosticResult<(TypeParameterContainer, TypeParameterBounds)> {
     if tokens[*ind].tok_type != LexerTokenType::AngelBracketOpen {
-        return Ok(TypeParameterContainer::new());
+        return Ok((TypeParameterContainer::new(), vec![]));
     }
 
     let mut container = TypeParameterContainer::new();
+    let mut bounds = vec![];
 
     *ind += 1;
 
@@ -236,6 +383,8 @@ pub fn parse_type_parameters_declaration(
 
         *ind += 1;
 
+        bounds.push(parse_type_parameter_bound(tokens, ind)?);
+
         if tokens[*ind].tok_type == LexerTokenType::AngelBracketClose {
             break;
         }
@@ -246,5 +395,22 @@ pub fn parse_type_parameters_declaration(
 
     *ind += 1;
 
-    return Ok(container);
+    return Ok((container, bounds));
+}
+
+/// Makes the type of `this` for a type declaration. Every type parameter is given as is to be resolved later on
+pub fn make_type_declaration_self_type(
+    name: String,
+    type_params: &TypeParameterContainer,
+) -> ASTType {
+    let mut params: Vec<(&HashedString, &usize)> = type_params.iter().collect();
+
+    params.sort_by_key(|param| *param.1);
+
+    let params = params
+        .into_iter()
+        .map(|param| Box::new(ASTType::Generic(param.0.val.clone(), vec![], vec![], None)))
+        .collect();
+
+    return ASTType::Generic(name, params, vec![], None);
 }
@@ -0,0 +1,55 @@
+//! Parser module for the `unchecked` safety opt-out
+
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
+use diagnostics::DiagnosticResult;
+use lexer::token::{LexerToken, LexerTokenType};
+
+use crate::{parser::parse_ast_node_in_body, value::parse_ast_value};
+
+/// Parses a value without runtime safety checks such as `unchecked(arr[i])`
+pub fn parse_unchecked_value(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+) -> DiagnosticResult<Box<ASTTreeNode>> {
+    return parse_unchecked(tokens, ind, false);
+}
+
+/// Parses a statement without runtime safety checks such as `unchecked(arr[i] = 5)`
+pub fn parse_unchecked_statement(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+) -> DiagnosticResult<Box<ASTTreeNode>> {
+    return parse_unchecked(tokens, ind, true);
+}
+
+fn parse_unchecked(
+    tokens: &Vec<LexerToken>,
+    ind: &mut usize,
+    statement: bool,
+) -> DiagnosticResult<Box<ASTTreeNode>> {
+    let start = tokens[*ind].pos.clone();
+
+    tokens[*ind].expects(LexerTokenType::Unchecked)?;
+    *ind += 1;
+
+    tokens[*ind].expects(LexerTokenType::ParenOpen)?;
+    *ind += 1;
+
+    let val = if statement {
+        parse_ast_node_in_body(tokens, ind)?
+    } else {
+        parse_ast_value(tokens, ind)?
+    };
+
+    tokens[*ind].expects(LexerTokenType::ParenClose)?;
+
+    let end = tokens[*ind].get_end_pos();
+
+    *ind += 1;
+
+    return Ok(Box::new(ASTTreeNode::new(
+        ASTTreeNodeKind::UncheckedValue { val },
+        start,
+        end,
+    )));
+}
@@ -1,9 +1,12 @@
 use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
 use compiler_utils::hash::HashedString;
-use diagnostics::DiagnosticResult;
+use diagnostics::{DiagnosticResult, builders::make_expected_simple_error};
 use lexer::token::{LexerToken, LexerTokenType};
 
-use crate::{types::parse_type, value::parse_ast_value};
+use crate::{
+    types::parse_type,
+    value::{parse_ast_value, parse_ast_value_full, parse_ast_value_post_l},
+};
 
 pub fn parse_unwrap_condition(
     tokens: &Vec<LexerToken>,
@@ -64,7 +67,31 @@ pub fn parse_unwrap_value(
     tokens[*ind].expects(LexerTokenType::AngelBracketOpen)?;
     *ind += 1;
 
-    let original = parse_ast_value(tokens, ind)?;
+    // The closing bracket would otherwise be parsed as a comparison
+    let original = parse_ast_value_full(tokens, ind, false)?;
+
+    if unsafe_unwrap && tokens[*ind].tok_type == LexerTokenType::AngelBracketClose {
+        if let ASTTreeNodeKind::StructLRVariable { .. } = original.kind {
+            let end = tokens[*ind].get_end_pos();
+
+            *ind += 1;
+
+            return Ok(Box::new(ASTTreeNode::new(
+                ASTTreeNodeKind::UnsafeFieldAccess { access: original },
+                start,
+                end,
+            )));
+        }
+
+        return Err(make_expected_simple_error(
+            &tokens[*ind],
+            &"field access".to_string(),
+            &original.kind,
+        )
+        .into());
+    }
+
+    let original = parse_ast_value_post_l(tokens, ind, Ok(original), false)?;
 
     tokens[*ind].expects(LexerTokenType::Comma)?;
     *ind += 1;
@@ -14,9 +14,13 @@ use crate::literals::{parse_integer_literal, parse_string_literal};
 use crate::math::parse_math_operation;
 use crate::{
     arrays::parse_array_access,
+    casts::parse_cast_value,
     comp::parse_ast_compare,
-    functions::parse_function_call,
+    functions::{closures::parse_closure_expression, parse_function_call},
+    intrinsics::parse_layout_intrinsic,
     structs::val::parse_struct_initialize,
+    tuples::parse_tuple_literal,
+    unchecked::parse_unchecked_value,
     unwraps::{parse_unwrap_condition, parse_unwrap_value},
 };
 
@@ -91,8 +95,31 @@ pub fn parse_ast_value_dotacess_chain_member(
             )));
         }
 
+        // Tuple elements are accessed by their index
+        LexerTokenType::IntLit(v, _) => {
+            let start = original.clone()?.start.clone();
+            let end = tokens[*ind].get_end_pos();
+
+            let r_member = Box::new(ASTTreeNode::new(
+                ASTTreeNodeKind::VariableReference(HashedString::new(v.to_string())),
+                start.clone(),
+                end.clone(),
+            ));
+
+            *ind += 1;
+
+            Ok(Box::new(ASTTreeNode::new(
+                ASTTreeNodeKind::StructLRVariable {
+                    l: original?,
+                    r: r_member,
+                },
+                start,
+                end,
+            )))
+        }
+
         _ => return original,
-    };
+    }
 }
 
 /// Parses the post side of an AST node that can and WILL be intrepreted as a value.
@@ -219,7 +246,8 @@ pub fn parse_ast_condition_if_statement_value(
 /// - Math operation results (both with or without value changing)
 /// - Boolean negation result
 /// - Boolean compare result
-
+/// - Closures
+/// - Tuple literals
 pub fn parse_ast_value(
     tokens: &Vec<LexerToken>,
     ind: &mut usize,
@@ -288,10 +316,23 @@ pub fn parse_ast_value_full(
             return parse_struct_initialize(tokens, ind);
         }
 
+        LexerTokenType::ParenOpen => {
+            let tuple = parse_tuple_literal(tokens, ind);
+
+            if allow_lparsing {
+                return parse_ast_value_post_l(tokens, ind, tuple, false);
+            } else {
+                return tuple;
+            }
+        }
+
         LexerTokenType::Keyword(str, _) => {
             if tokens[*ind + 1].tok_type == LexerTokenType::ParenOpen {
                 let call = parse_function_call(tokens, ind);
 
+                // Allows accessing the elements of the returned value
+                let call = parse_ast_value_dotacess(tokens, ind, call);
+
                 if allow_lparsing {
                     return parse_ast_value_post_l(tokens, ind, call, false);
                 } else {
@@ -316,7 +357,65 @@ pub fn parse_ast_value_full(
             }
         }
 
-        LexerTokenType::Unwrap | LexerTokenType::UnwrapUnsafe => parse_unwrap_value(tokens, ind),
+        LexerTokenType::This => {
+            let n = Ok(make_node!(
+                ASTTreeNodeKind::VariableReference(HashedString::new("this".to_string())),
+                &tokens[*ind],
+                &tokens[*ind]
+            ));
+
+            *ind += 1;
+
+            let chain = parse_ast_value_dotacess(tokens, ind, n);
+
+            if allow_lparsing {
+                return parse_ast_value_post_l(tokens, ind, chain, false);
+            } else {
+                return chain;
+            }
+        }
+
+        LexerTokenType::Unwrap | LexerTokenType::UnwrapUnsafe => {
+            let unwrap = parse_unwrap_value(tokens, ind);
+
+            if allow_lparsing {
+                return parse_ast_value_post_l(tokens, ind, unwrap, false);
+            } else {
+                return unwrap;
+            }
+        }
+
+        LexerTokenType::Unchecked => {
+            let unchecked = parse_unchecked_value(tokens, ind);
+
+            if allow_lparsing {
+                return parse_ast_value_post_l(tokens, ind, unchecked, false);
+            } else {
+                return unchecked;
+            }
+        }
+
+        LexerTokenType::Cast => {
+            let cast = parse_cast_value(tokens, ind);
+
+            if allow_lparsing {
+                return parse_ast_value_post_l(tokens, ind, cast, false);
+            } else {
+                return cast;
+            }
+        }
+
+        LexerTokenType::Function => return parse_closure_expression(tokens, ind),
+
+        LexerTokenType::SizeOf | LexerTokenType::AlignOf | LexerTokenType::OffsetOf => {
+            let intrinsic = parse_layout_intrinsic(tokens, ind);
+
+            if allow_lparsing {
+                return parse_ast_value_post_l(tokens, ind, intrinsic, false);
+            } else {
+                return intrinsic;
+            }
+        }
 
         _ => {
             return Err(make_unexpected_simple_error(&tokens[*ind], &tokens[*ind].tok_type).into());
@@ -338,6 +437,12 @@ pub fn parse_ast_array_init(
         *ind += 1;
 
         let count = tokens[*ind].expects_int_lit()?;
+        *ind += 1;
+
+        tokens[*ind].expects(LexerTokenType::ArrayClose)?;
+
+        let end = tokens[*ind].get_end_pos();
+        *ind += 1;
 
         return Ok(Box::new(ASTTreeNode::new(
             ASTTreeNodeKind::ArrayVariableInitializerValueSameValue {
@@ -345,14 +450,14 @@ pub fn parse_ast_array_init(
                 v: val,
             },
             start,
-            tokens[*ind].get_end_pos(),
+            end,
         )));
     }
 
     let mut vals = vec![];
 
     loop {
-        let val = parse_ast_value(tokens, ind)?;
+        vals.push(parse_ast_value(tokens, ind)?);
 
         if tokens[*ind].tok_type == LexerTokenType::ArrayClose {
             break;
@@ -360,14 +465,15 @@ pub fn parse_ast_array_init(
 
         tokens[*ind].expects(LexerTokenType::Comma)?;
         *ind += 1;
-
-        vals.push(val);
     }
 
+    let end = tokens[*ind].get_end_pos();
+    *ind += 1;
+
     return Ok(Box::new(ASTTreeNode::new(
         ASTTreeNodeKind::ArrayVariableInitializerValue { vals },
         start,
-        tokens[*ind].get_end_pos(),
+        end,
     )));
 }
 
@@ -5,6 +5,8 @@ use astoir_mir::ctx::MIRContext;
 use astoir_mir_lowering::lower_hir;
 use diagnostics::DiagnosticResult;
 
+pub use astoir_mir_lowering::safety::SafetyLevel;
+
 pub enum IRLevel {
     HIR,
     MIR,
@@ -15,8 +17,8 @@ pub fn run_astoir_hir(ctx: ParserCtx) -> DiagnosticResult<HIRContext> {
     return lower_ast(ctx);
 }
 
-pub fn run_astoir_mir(ctx: ParserCtx) -> DiagnosticResult<MIRContext> {
+pub fn run_astoir_mir(ctx: ParserCtx, safety: SafetyLevel) -> DiagnosticResult<MIRContext> {
     let hir = run_astoir_hir(ctx)?;
 
-    return lower_hir(hir);
+    return lower_hir(hir, safety);
 }
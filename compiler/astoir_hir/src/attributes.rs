@@ -0,0 +1,58 @@
+//! The attributes of declarations that are reported whenever the declaration gets used
+
+use compiler_utils::Position;
+use diagnostics::diagnostic::SpanPosition;
+
+/// The kind of declaration the attributes are written on
+#[derive(Debug, Clone, PartialEq)]
+pub enum HIRDeclarationKind {
+    Function,
+    Type,
+    Field,
+}
+
+/// The attributes of a declaration, see `HIRGlobalScopeStorage::attributes`
+#[derive(Debug, Clone)]
+pub struct HIRDeclarationAttributes {
+    pub name: String,
+    pub kind: HIRDeclarationKind,
+
+    /// Whether the declaration is deprecated, along with the optional message given
+    pub deprecated: Option<Option<String>>,
+    pub experimental: bool,
+    pub is_unsafe: bool,
+
+    pub start: Position,
+    pub end: Position,
+}
+
+impl HIRDeclarationAttributes {
+    pub fn is_empty(&self) -> bool {
+        return self.deprecated.is_none() && !self.experimental && !self.is_unsafe;
+    }
+
+    /// Gets the position of the declaration. Declarations spanning multiple lines only point at their first line
+    pub fn get_pos(&self) -> SpanPosition {
+        if self.start.line == self.end.line {
+            return SpanPosition::from_pos2(self.start.clone(), self.end.clone());
+        }
+
+        let end_col = match self.start.get_line_content() {
+            Ok(line) => line.trim_end().len() + 1,
+            Err(_) => self.start.col,
+        };
+
+        return SpanPosition::from_pos(self.start.clone(), end_col);
+    }
+
+    /// Whether the given position is within the declaration. Declarations never report their own uses
+    pub fn contains(&self, pos: &SpanPosition) -> bool {
+        if pos.file_path != self.start.file_path {
+            return false;
+        }
+
+        let pos = (pos.line, pos.col);
+
+        return (self.start.line, self.start.col) <= pos && pos <= (self.end.line, self.end.col);
+    }
+}
@@ -2,15 +2,17 @@
 
 use std::collections::{HashMap, HashSet};
 
+use ast::tree::ASTTreeNode;
 use compiler_global_scope::key::EntryKey;
 use compiler_typing::tree::Type;
-use compiler_utils::hash::SelfHash;
+use compiler_utils::hash::{HashedString, SelfHash};
 use diagnostics::{
     DiagnosticResult, DiagnosticSpanOrigin,
     builders::{make_cannot_find_var, make_doesnt_exist_in_era},
+    diagnostic::SpanPosition,
 };
 
-use crate::{nodes::HIRNode, scope::HIRGlobalScopeStorage};
+use crate::{nodes::HIRNode, scope::HIRGlobalScopeStorage, structs::HIRClosureCapture};
 
 pub type HIRFunction = (Option<Type>, Vec<(u64, Type)>, String);
 pub type HIRFunctionImpl = Box<HIRNode>;
@@ -42,6 +44,26 @@ pub struct HIRBranchedContext {
 
     pub current_branch: usize,
     pub current_element_index: usize,
+
+    /// The variables captured by the closures assigned to a variable. Indexed by variable index
+    pub closure_captures: HashMap<usize, Vec<HIRClosureCapture>>,
+
+    /// Whether runtime safety checks are disabled for the whole function
+    pub unchecked: bool,
+
+    /// Whether every arithmetic operation of the function uses fast semantics
+    pub fast: bool,
+
+    /// Where the named variables were declared. Used to report unused variables. Indexed by variable index
+    pub variable_origins: HashMap<usize, HIRVariableOrigin>,
+}
+
+/// The declaration of a named variable or argument
+#[derive(Debug, Clone)]
+pub struct HIRVariableOrigin {
+    pub name: String,
+    pub pos: SpanPosition,
+    pub argument: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -67,6 +89,10 @@ impl HIRBranchedContext {
             return_type,
             current_branch: 0,
             current_element_index: 0,
+            closure_captures: HashMap::new(),
+            unchecked: false,
+            fast: false,
+            variable_origins: HashMap::new(),
         }
     }
 
@@ -162,6 +188,18 @@ impl HIRBranchedContext {
         return Ok(ind);
     }
 
+    /// Gives the variable with the given index a readable name and declaration position
+    pub fn name_variable(&mut self, ind: usize, name: String, pos: SpanPosition, argument: bool) {
+        self.variable_origins.insert(
+            ind,
+            HIRVariableOrigin {
+                name,
+                pos,
+                argument,
+            },
+        );
+    }
+
     pub fn introduce_variable_assign(&mut self, ind: usize) -> bool {
         let var = &mut self.variables[ind];
 
@@ -277,22 +315,22 @@ impl HIRBranchedContext {
         return self.ending_eras[&self.variables[ind].introduced_in_era];
     }
 
-    /// Obtains the variable index from the hash if it's available, otherwise returns an error explaining why it failed
+    /// Obtains the variable index from the name if it's available, otherwise returns an error explaining why it failed
     pub fn obtain<K: DiagnosticSpanOrigin>(
         &mut self,
-        hash: u64,
+        name: &HashedString,
         origin: &K,
     ) -> DiagnosticResult<usize> {
-        let identity = SelfHash { hash };
+        let identity = SelfHash { hash: name.hash };
 
         match self.hash_to_ind.get(&identity) {
-            None => return Err(make_cannot_find_var(origin, &hash).into()),
+            None => return Err(make_cannot_find_var(origin, &name.val).into()),
             Some(ind) => {
                 let ind = *ind;
 
                 if !self.is_alive(ind) {
                     if self.is_dropped_before(ind) {
-                        return Err(make_doesnt_exist_in_era(origin, &hash).into());
+                        return Err(make_doesnt_exist_in_era(origin, &name.val).into());
                     }
 
                     println!(
@@ -301,7 +339,7 @@ impl HIRBranchedContext {
                         self.ending_eras[&self.variables[ind].introduced_in_era]
                     );
 
-                    panic!("Dropped unalived variable {} -> hash {}", ind, hash);
+                    panic!("Dropped unalived variable {} -> hash {}", ind, name.hash);
                 }
 
                 self.variables[ind].usage_count += 1;
@@ -340,6 +378,24 @@ pub struct HIRBranchedVariable {
 #[derive(Debug)]
 pub struct HIRContext {
     pub global_scope: HIRGlobalScopeStorage,
+
+    /// The concrete types of the type parameters currently in use. Indexed by the hash of the type parameter name
+    pub type_params: HashMap<u64, Type>,
+
+    /// The function declarations of types with type parameters. These are lowered once per instantiation. Indexed by the type's global index
+    pub generic_functions: HashMap<usize, Vec<Box<ASTTreeNode>>>,
+
+    /// The lowered functions of every instantiation of a type with type parameters. Indexed by the hash of the concrete type
+    pub generic_instances: HashMap<u64, Vec<usize>>,
+
+    /// The concrete types of every instantiation of a type with type parameters. Indexed by the type's global index
+    pub generic_instantiations: HashMap<usize, Vec<Type>>,
+
+    /// The global entries referenced by the lowered code. Used to report unused declarations
+    pub used_entries: HashSet<usize>,
+
+    /// The uses already reported because of the attributes of their declaration. Functions of types with type parameters are lowered once per instantiation
+    pub reported_uses: HashSet<(String, usize, usize, String)>,
 }
 
 #[derive(PartialEq)]
@@ -352,6 +408,12 @@ impl HIRContext {
     pub fn new() -> Self {
         return HIRContext {
             global_scope: HIRGlobalScopeStorage::new(),
+            type_params: HashMap::new(),
+            generic_functions: HashMap::new(),
+            generic_instances: HashMap::new(),
+            generic_instantiations: HashMap::new(),
+            used_entries: HashSet::new(),
+            reported_uses: HashSet::new(),
         };
     }
 }
@@ -359,11 +421,14 @@ impl HIRContext {
 pub fn get_variable<K: DiagnosticSpanOrigin>(
     context: &HIRContext,
     curr_ctx: &mut HIRBranchedContext,
-    hash: u64,
+    name: &HashedString,
     origin: &K,
 ) -> DiagnosticResult<(VariableKind, Type, usize)> {
-    if curr_ctx.hash_to_ind.contains_key(&SelfHash { hash }) {
-        let ind = curr_ctx.obtain(hash, origin)?;
+    if curr_ctx
+        .hash_to_ind
+        .contains_key(&SelfHash { hash: name.hash })
+    {
+        let ind = curr_ctx.obtain(name, origin)?;
 
         return Ok((
             VariableKind::LOCAL,
@@ -372,7 +437,10 @@ pub fn get_variable<K: DiagnosticSpanOrigin>(
         ));
     }
 
-    let name = EntryKey { name_hash: hash };
+    let name = EntryKey {
+        name_hash: name.hash,
+        name: name.val.clone(),
+    };
 
     let ind = context.global_scope.scope.value_to_ind
         [&context.global_scope.get_base(name.clone(), origin)?];
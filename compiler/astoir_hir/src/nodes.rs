@@ -9,10 +9,10 @@ use compiler_typing::{
 use compiler_utils::{
     Position,
     hash::SelfHash,
-    operators::{ComparingOperator, MathOperator},
+    operators::{ComparingOperator, MathOperator, MathOperatorType, OverflowBehavior},
 };
 use diagnostics::{
-    DiagnosticSpanOrigin,
+    DiagnosticResult, DiagnosticSpanOrigin,
     builders::{make_diff_type, make_diff_type_val, make_expected_simple_error_originless},
     diagnostic::{Diagnostic, Span, SpanKind, SpanPosition},
     unsure_panic,
@@ -21,7 +21,7 @@ use diagnostics::{
 use crate::{
     ctx::{HIRBranchedContext, HIRContext},
     resolve::resolve_to_type,
-    structs::{HIRIfBranch, HIRRange, StructLRUStep},
+    structs::{HIRClosureCapture, HIRIfBranch, HIRRange, StructLRUStep},
 };
 
 #[derive(Debug, Clone)]
@@ -74,6 +74,7 @@ impl DiagnosticSpanOrigin for HIRNode {
             start: SpanPosition::from_pos2(self.start.clone(), self.end.clone()),
             label: msg,
             kind,
+            replacement: None,
         }
     }
 }
@@ -87,6 +88,11 @@ pub enum HIRNodeKind {
         new_type: Type,
     },
 
+    /// A value or statement lowered without runtime safety checks
+    UncheckedValue {
+        val: Box<HIRNode>,
+    },
+
     VarDeclaration {
         variable: usize,
         var_type: Type,
@@ -108,6 +114,13 @@ pub enum HIRNodeKind {
         right: Box<HIRNode>,
         operation: MathOperator,
     },
+    /// An integer operation with an explicit overflow behavior such as `wrapping_add(a, b)`
+    OverflowOperation {
+        left: Box<HIRNode>,
+        right: Box<HIRNode>,
+        operator: MathOperatorType,
+        behavior: OverflowBehavior,
+    },
 
     UnwrapCondition {
         original: Box<HIRNode>,
@@ -143,7 +156,13 @@ pub enum HIRNodeKind {
 
     StructLRU {
         steps: Vec<StructLRUStep>,
-        last: Type,
+        last: Option<Type>,
+    },
+
+    /// Writes into the field reached by the `StructLRU` chain
+    StructLRUModify {
+        lru: Box<HIRNode>,
+        val: Box<HIRNode>,
     },
 
     EnumParentCast {
@@ -188,6 +207,23 @@ pub enum HIRNodeKind {
         new_val: Box<HIRNode>,
     },
 
+    /// Creates a slice over the elements of an array, slice or pointer of arrays within the given range. Covers every element if no range is given
+    SubSlice {
+        val: Box<HIRNode>,
+        range: Option<HIRRange>,
+        from_slice: bool,
+        t: Type,
+    },
+    SliceIndexAccess {
+        slice: Box<HIRNode>,
+        index: Box<HIRNode>,
+    },
+    SliceIndexModify {
+        slice: Box<HIRNode>,
+        index: Box<HIRNode>,
+        new_val: Box<HIRNode>,
+    },
+
     /// Before transmutation
     StructInitializer {
         fields: HashMap<SelfHash, Box<HIRNode>>,
@@ -197,6 +233,24 @@ pub enum HIRNodeKind {
         fields: Vec<Box<HIRNode>>,
     },
 
+    /// Unions are initialized using a single field
+    UnionInitializerTyped {
+        t: Type,
+        field: usize,
+        val: Box<HIRNode>,
+    },
+
+    TupleInitializer {
+        t: Type,
+        values: Vec<Box<HIRNode>>,
+    },
+
+    /// Declares a variable for every element of the tuple value. The variables are `VarDeclaration` nodes without any value
+    TupleDestructuring {
+        value: Box<HIRNode>,
+        variables: Vec<Box<HIRNode>>,
+    },
+
     FunctionDeclaration {
         func_name: usize,
         arguments: Vec<(u64, Type)>,
@@ -216,6 +270,22 @@ pub enum HIRNodeKind {
         func_name: usize,
         arguments: Vec<Box<HIRNode>>,
     },
+    IndirectCall {
+        callee: Box<HIRNode>,
+        arguments: Vec<Box<HIRNode>>,
+    },
+
+    /// Creates a closure calling the given generated function with an environment containing the captured variables
+    ClosureExpression {
+        function: usize,
+        captures: Vec<HIRClosureCapture>,
+        closure_type: Type,
+    },
+    /// Unpacks the environment given as argument to the function generated for a closure. Always the first node of said function
+    ClosureEnvironment {
+        env: usize,
+        captures: Vec<HIRClosureCapture>,
+    },
 
     WhileBlock {
         condition: Box<HIRNode>,
@@ -271,8 +341,9 @@ impl HIRNode {
     }
 
     pub fn is_ending_point(&self) -> bool {
-        match self.kind {
+        match &self.kind {
             HIRNodeKind::ReturnStatement { .. } => true,
+            HIRNodeKind::UncheckedValue { val } => val.is_ending_point(),
             _ => false,
         }
     }
@@ -313,7 +384,7 @@ impl HIRNode {
         t: Type,
         origin: &K,
         var_origin: Option<&K>,
-    ) -> Result<HIRNode, ()> {
+    ) -> DiagnosticResult<HIRNode> {
         if self.is_intederminately_typed() {
             return Ok(resolve_to_type(
                 Box::new(self.clone()),
@@ -325,6 +396,29 @@ impl HIRNode {
             .use_as(context, curr_ctx, t, origin, var_origin)?);
         }
 
+        // Tuple literals are used as the given tuple type element by element
+        if let (HIRNodeKind::TupleInitializer { t: _, values }, Type::Tuple(elements)) =
+            (&self.kind, &t)
+            && values.len() == elements.len()
+        {
+            let mut new_vals = vec![];
+
+            for (val, element) in values.iter().zip(elements) {
+                new_vals.push(Box::new(val.use_as(
+                    context,
+                    curr_ctx,
+                    *element.clone(),
+                    origin,
+                    var_origin,
+                )?));
+            }
+
+            return Ok(self.with(HIRNodeKind::TupleInitializer {
+                t: t.clone(),
+                values: new_vals,
+            }));
+        }
+
         let self_type = match self.get_node_type(context, curr_ctx) {
             Some(v) => v,
             _ => panic!("Tried using a typeless node in use_as: {:#?}", self),
@@ -334,6 +428,18 @@ impl HIRNode {
             return Ok(self.clone());
         }
 
+        // Fixed arrays are used as slices over all of their elements
+        if let (Type::Array(_, inner), Type::Slice(inner2)) = (&self_type, &t)
+            && inner == inner2
+        {
+            return Ok(self.with(HIRNodeKind::SubSlice {
+                val: Box::new(self.clone()),
+                range: None,
+                from_slice: false,
+                t: t.clone(),
+            }));
+        }
+
         if self_type.can_transmute(&t, &context.global_scope.scope) {
             match &self.kind {
                 HIRNodeKind::IntegerLiteral { value, int_type: _ } => {
@@ -463,6 +569,10 @@ impl HIRNode {
                 return Some(Type::Generic(RawType::Boolean, vec![], vec![]));
             }
 
+            HIRNodeKind::CastValue { new_type, .. } => return Some(new_type.clone()),
+
+            HIRNodeKind::UncheckedValue { val } => return val.get_node_type(context, curr_ctx),
+
             HIRNodeKind::UnwrapValue {
                 original: _,
                 new_type,
@@ -496,7 +606,7 @@ impl HIRNode {
                 ));
             }
 
-            HIRNodeKind::StructLRU { steps: _, last } => return Some(last.clone()),
+            HIRNodeKind::StructLRU { steps: _, last } => return last.clone(),
 
             HIRNodeKind::MathOperation {
                 left,
@@ -504,11 +614,32 @@ impl HIRNode {
                 operation: _,
             } => return left.get_node_type(context, curr_ctx),
 
+            HIRNodeKind::OverflowOperation { left, behavior, .. } => {
+                let t = left.get_node_type(context, curr_ctx)?;
+
+                if *behavior == OverflowBehavior::Checked {
+                    return Some(Type::Tuple(vec![
+                        Box::new(t),
+                        Box::new(Type::Generic(RawType::Boolean, vec![], vec![])),
+                    ]));
+                }
+
+                return Some(t);
+            }
+
             HIRNodeKind::BooleanOperator { .. } | HIRNodeKind::BooleanCondition { .. } => {
                 return Some(Type::Generic(RawType::Boolean, vec![], vec![]));
             }
 
             HIRNodeKind::StructInitializerTyped { t, fields: _ } => Some(t.clone()),
+            HIRNodeKind::UnionInitializerTyped { t, .. } => Some(t.clone()),
+            HIRNodeKind::TupleInitializer { t, .. } => Some(t.clone()),
+            HIRNodeKind::SubSlice { t, .. } => Some(t.clone()),
+            HIRNodeKind::SliceIndexAccess { slice, index: _ } => {
+                let t = slice.get_node_type(context, curr_ctx).unwrap();
+
+                return Some(*t.get_inner_type());
+            }
 
             HIRNodeKind::FunctionCall {
                 func_name,
@@ -540,6 +671,20 @@ impl HIRNode {
                 return context.global_scope.descriptors[*ind].clone().0;
             }
 
+            HIRNodeKind::FunctionReference { index } => {
+                return context.global_scope.get_function_pointer_type(*index);
+            }
+
+            HIRNodeKind::IndirectCall {
+                callee,
+                arguments: _,
+            } => match callee.get_node_type(context, curr_ctx) {
+                Some(Type::Function(_, ret)) | Some(Type::Closure(_, ret)) => ret.map(|t| *t),
+                _ => None,
+            },
+
+            HIRNodeKind::ClosureExpression { closure_type, .. } => Some(closure_type.clone()),
+
             _ => return None,
         }
     }
@@ -1,10 +1,12 @@
 //! Used to resolve incomplete HIR nodes like initializers
 
-use compiler_typing::tree::Type;
+use std::collections::HashMap;
+
+use compiler_typing::{raw::RawType, tree::Type};
 use compiler_utils::hash::SelfHash;
 use diagnostics::{
     DiagnosticResult, DiagnosticSpanOrigin,
-    builders::{make_req_type_kind, make_struct_init_missing_field},
+    builders::{make_req_type_kind, make_struct_init_missing_field, make_union_init_field_count},
 };
 
 use crate::{
@@ -22,6 +24,7 @@ pub fn resolve_to_type<K: DiagnosticSpanOrigin>(
 ) -> DiagnosticResult<Box<HIRNode>> {
     match node.kind {
         HIRNodeKind::StructInitializer { fields } => {
+            let (node_start, node_end) = (node.start.clone(), node.end.clone());
             let generic = destination.as_generic_safe(origin)?;
             let mut new_fields = vec![];
 
@@ -29,6 +32,15 @@ pub fn resolve_to_type<K: DiagnosticSpanOrigin>(
                 return Err(make_req_type_kind(origin, &"field-having".to_string()).into());
             }
 
+            if let RawType::Struct(_, container) = &generic
+                && container.is_union
+            {
+                let node =
+                    resolve_union_initializer(fields, destination, context, curr_ctx, origin)?;
+
+                return Ok(Box::new(HIRNode::new(node, &node_start, &node_end)));
+            }
+
             for field in destination.get_fields(&context.global_scope.scope) {
                 let identity = SelfHash { hash: field };
 
@@ -61,3 +73,29 @@ pub fn resolve_to_type<K: DiagnosticSpanOrigin>(
         _ => panic!("Invalid node"),
     }
 }
+
+/// Resolves the initializer of a union. Only one field of the union can be initialized
+fn resolve_union_initializer<K: DiagnosticSpanOrigin>(
+    fields: HashMap<SelfHash, Box<HIRNode>>,
+    destination: Type,
+    context: &HIRContext,
+    curr_ctx: &HIRBranchedContext,
+    origin: &K,
+) -> DiagnosticResult<HIRNodeKind> {
+    if fields.len() != 1 {
+        return Err(make_union_init_field_count(origin, &fields.len()).into());
+    }
+
+    let (identity, val) = fields.into_iter().next().unwrap();
+
+    let (field, field_type) = destination.get_field(&context.global_scope.scope, identity.hash)?;
+    let field_type = field_type.resolve(&destination);
+
+    let val = Box::new(val.use_as(context, curr_ctx, field_type, origin, None)?);
+
+    return Ok(HIRNodeKind::UnionInitializerTyped {
+        t: destination,
+        field,
+        val,
+    });
+}
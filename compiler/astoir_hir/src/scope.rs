@@ -1,5 +1,7 @@
 //! HIR version of the global scope in order to store descriptors and implementations
 
+use std::collections::HashMap;
+
 use compiler_global_scope::key::EntryKey;
 use compiler_typing::{TypedGlobalScope, TypedGlobalScopeEntry, raw::RawType, tree::Type};
 use diagnostics::{
@@ -8,6 +10,7 @@ use diagnostics::{
 };
 
 use crate::{
+    attributes::HIRDeclarationAttributes,
     ctx::{HIRBranchedContext, HIRFunction, HIRFunctionImpl},
     nodes::HIRNode,
 };
@@ -20,6 +23,15 @@ pub struct HIRGlobalScopeStorage {
     pub descriptors: Vec<HIRFunction>,
     pub implementations: Vec<HIRFunctionImpl>,
     pub contexts: Vec<HIRBranchedContext>,
+
+    /// The attributes of the declarations reported on use. Indexed by the global entry
+    pub attributes: HashMap<usize, HIRDeclarationAttributes>,
+
+    /// The attributes of the fields and functions of types. Indexed by the type's global entry and the member name hash
+    pub member_attributes: HashMap<(usize, u64), HIRDeclarationAttributes>,
+
+    /// The names of the fields of types, only used by diagnostics. Indexed by the type's global entry and the field name hash
+    pub member_names: HashMap<(usize, u64), String>,
 }
 
 impl HIRGlobalScopeStorage {
@@ -29,6 +41,9 @@ impl HIRGlobalScopeStorage {
             descriptors: vec![],
             implementations: vec![],
             contexts: vec![],
+            attributes: HashMap::new(),
+            member_attributes: HashMap::new(),
+            member_names: HashMap::new(),
         }
     }
 
@@ -38,7 +53,7 @@ impl HIRGlobalScopeStorage {
         origin: &K,
     ) -> MaybeDiagnostic {
         if self.scope.entry_to_ind.contains_key(&name) {
-            return Err(make_already_in_scope(origin, &name.name_hash).into());
+            return Err(make_already_in_scope(origin, &name.name).into());
         }
 
         Ok(())
@@ -117,26 +132,68 @@ impl HIRGlobalScopeStorage {
         descriptor: HIRFunction,
         implementation: Box<HIRNode>,
         brctx: HIRBranchedContext,
-        struct_type: RawType,
+        struct_type: usize,
         origin: &K,
     ) -> DiagnosticResult<usize> {
         self.descriptors.push(descriptor);
         self.implementations.push(implementation);
         self.contexts.push(brctx);
 
-        let ind = self.scope.value_to_ind[&TypedGlobalScopeEntry::Type(struct_type)];
-
         self.scope.append(
             name,
             TypedGlobalScopeEntry::StructFunction {
                 descriptor_ind: self.scope.descriptor_counter,
                 impl_ind: self.scope.impl_counter,
-                struct_type: ind,
+                struct_type,
             },
             origin,
         )
     }
 
+    /// Gives an implementation to an already appended impl-less function, turning it into a function. The entry keeps its index
+    pub fn implement_function(
+        &mut self,
+        ind: usize,
+        implementation: Box<HIRNode>,
+        brctx: HIRBranchedContext,
+    ) {
+        let descriptor_ind = match self.scope.entries[ind].entry_type {
+            TypedGlobalScopeEntry::ImplLessFunction(descriptor_ind) => descriptor_ind,
+            _ => panic!("Entry is not an impl-less function"),
+        };
+
+        self.implementations.push(implementation);
+        self.contexts.push(brctx);
+
+        let entry = TypedGlobalScopeEntry::Function {
+            descriptor_ind,
+            impl_ind: self.scope.impl_counter,
+        };
+
+        self.scope.impl_counter += 1;
+        self.scope.ctx_counter += 1;
+
+        self.scope
+            .value_to_ind
+            .remove(&self.scope.entries[ind].entry_type);
+        self.scope.value_to_ind.insert(entry.clone(), ind);
+
+        self.scope.entries[ind].entry_type = entry;
+    }
+
+    /// Sets the implementation of an already appended struct function. Allows struct functions to be appended before their bodies are lowered
+    pub fn implement_struct_function(
+        &mut self,
+        ind: usize,
+        implementation: Box<HIRNode>,
+        brctx: HIRBranchedContext,
+    ) {
+        let impl_ind = self.scope.entries[ind].as_struct_function_unsafe().1;
+
+        self.implementations[impl_ind] = implementation;
+        self.contexts[impl_ind] = brctx;
+    }
+
     pub fn append_type<K: DiagnosticSpanOrigin>(
         &mut self,
         name: EntryKey,
@@ -174,7 +231,7 @@ impl HIRGlobalScopeStorage {
             return Ok(self.scope.entry_to_ind[&name]);
         }
 
-        return Err(make_cannot_find(origin, &name.name_hash).into());
+        Err(make_cannot_find(origin, &name.name).into())
     }
 
     pub fn get_type<K: DiagnosticSpanOrigin>(
@@ -249,6 +306,46 @@ impl HIRGlobalScopeStorage {
         ));
     }
 
+    /// Gets the descriptor index of the free function stored at the given entry index. Returns `None` if the entry is
+    /// not a free function. This never raises a diagnostic.
+    pub fn get_function_descriptor_ind(&self, ind: usize) -> Option<usize> {
+        return match &self.scope.entries[ind].entry_type {
+            TypedGlobalScopeEntry::Function {
+                descriptor_ind,
+                impl_ind: _,
+            } => Some(*descriptor_ind),
+            TypedGlobalScopeEntry::ImplLessFunction(descriptor_ind) => Some(*descriptor_ind),
+            TypedGlobalScopeEntry::HalfImplFunction {
+                descriptor_ind,
+                branch_ctx: _,
+            } => Some(*descriptor_ind),
+
+            _ => None,
+        };
+    }
+
+    /// Gets the entry index of the free function with the given name, if any. This never raises a diagnostic.
+    pub fn find_function(&self, name: &EntryKey) -> Option<usize> {
+        let ind = *self.scope.entry_to_ind.get(name)?;
+
+        self.get_function_descriptor_ind(ind)?;
+
+        return Some(ind);
+    }
+
+    /// Gets the function pointer type of the free function stored at the given entry index.
+    pub fn get_function_pointer_type(&self, ind: usize) -> Option<Type> {
+        let descriptor = &self.descriptors[self.get_function_descriptor_ind(ind)?];
+
+        let args = descriptor
+            .1
+            .iter()
+            .map(|arg| Box::new(arg.1.clone()))
+            .collect();
+
+        return Some(Type::Function(args, descriptor.0.clone().map(Box::new)));
+    }
+
     pub fn get_exact_struct_function<K: DiagnosticSpanOrigin>(
         &self,
         name: EntryKey,
@@ -1,5 +1,8 @@
 //! AstoIR HIR structures related to HIR nodes
 
+use compiler_typing::tree::Type;
+use compiler_utils::hash::HashedString;
+
 use crate::nodes::HIRNode;
 
 #[derive(Debug, Clone)]
@@ -34,6 +37,21 @@ pub struct HIRRange {
     pub max: Box<HIRNode>,
 }
 
+/// A variable captured by a closure.
+#[derive(Debug, Clone)]
+pub struct HIRClosureCapture {
+    pub name: HashedString,
+
+    /// The index of the captured variable within the function creating the closure
+    pub outer: usize,
+
+    /// The index of the captured variable within the closure body
+    pub inner: usize,
+
+    pub variable_type: Type,
+    pub by_ref: bool,
+}
+
 #[derive(Debug)]
 pub struct HIRStructContainer {
     pub function_impls: Vec<Box<HIRNode>>,
@@ -3,10 +3,12 @@ use astoir_hir::{
     ctx::{HIRBranchedContext, HIRContext},
     nodes::{HIRNode, HIRNodeKind},
 };
-use compiler_typing::{raw::RawType, tree::Type};
+use compiler_typing::{tree::Type, utils::get_index_type};
 use diagnostics::{DiagnosticResult, builders::make_index_usage};
 
-use crate::{values::lower_ast_value, var::lower_ast_variable_reference};
+use crate::{
+    closures::check_closure_escape, values::lower_ast_value, var::lower_ast_variable_reference,
+};
 
 pub fn lower_ast_array_index_access(
     context: &mut HIRContext,
@@ -15,25 +17,32 @@ pub fn lower_ast_array_index_access(
 ) -> DiagnosticResult<Box<HIRNode>> {
     if let ASTTreeNodeKind::ArrayIndexAccess { val, index } = node.clone().kind {
         let array = lower_ast_value(context, curr_ctx, val)?;
+        let array_type = array.get_node_type(context, curr_ctx).unwrap();
 
-        if !array
-            .get_node_type(context, curr_ctx)
-            .unwrap()
-            .can_use_index_access()
-        {
-            return Err(
-                make_index_usage(&*node, &array.get_node_type(context, curr_ctx).unwrap()).into(),
-            );
+        if !array_type.can_use_index_access() {
+            return Err(make_index_usage(&*node, &array_type).into());
         }
 
         let index = Box::new(lower_ast_value(context, curr_ctx, index)?.use_as(
             context,
             curr_ctx,
-            Type::GenericLowered(RawType::Integer(32, false)),
+            Type::Generic(get_index_type(), vec![], vec![]),
             &*node,
             None,
         )?);
 
+        // Slices carry their element count and thus are accessed differently
+        if array_type.is_slice() {
+            return Ok(Box::new(HIRNode::new(
+                HIRNodeKind::SliceIndexAccess {
+                    slice: array,
+                    index,
+                },
+                &node.start,
+                &node.end,
+            )));
+        }
+
         return Ok(Box::new(HIRNode::new(
             HIRNodeKind::ArrayIndexAccess { val: array, index },
             &node.start,
@@ -55,7 +64,7 @@ pub fn lower_ast_array_modify(
         let index = Box::new(lower_ast_value(context, curr_ctx, index)?.use_as(
             context,
             curr_ctx,
-            Type::GenericLowered(RawType::Integer(32, false)),
+            Type::Generic(get_index_type(), vec![], vec![]),
             &*node,
             None,
         )?);
@@ -73,6 +82,20 @@ pub fn lower_ast_array_modify(
             )?,
         );
 
+        check_closure_escape(curr_ctx, &new_val, &*node)?;
+
+        if array.get_node_type(context, curr_ctx).unwrap().is_slice() {
+            return Ok(Box::new(HIRNode::new(
+                HIRNodeKind::SliceIndexModify {
+                    slice: array,
+                    index,
+                    new_val,
+                },
+                &node.start,
+                &node.end,
+            )));
+        }
+
         return Ok(Box::new(HIRNode::new(
             HIRNodeKind::ArrayIndexModify {
                 array,
@@ -0,0 +1,262 @@
+//! Lowering of attributes.
+//!
+//! Attributes are applied before the node they are written on gets lowered:
+//! - `@allow(<warning>, ...)` silences the given warnings within the node, see `diagnostics::lints`
+//!
+//! The following attributes are stored within the global scope and reported at every use of the declaration instead:
+//! - `@deprecated` or `@deprecated("<message>")` on functions, types and fields
+//! - `@experimental` on functions, types and fields
+//! - `@unsafe` on functions
+
+use ast::{
+    attributes::ASTAttribute,
+    tree::{ASTTreeNode, ASTTreeNodeKind},
+};
+use astoir_hir::{
+    attributes::{HIRDeclarationAttributes, HIRDeclarationKind},
+    ctx::HIRContext,
+};
+use compiler_typing::{raw::RawType, tree::Type};
+use diagnostics::{
+    DiagnosticSpanOrigin, MaybeDiagnostic,
+    builders::{
+        make_deprecated_use, make_experimental_field, make_experimental_function,
+        make_experimental_type, make_invalid_attribute, make_unknown_attribute,
+        make_unknown_warning, make_unsafe_function,
+    },
+    lints::allow_warning_in,
+    warnings::get_warning_codes,
+};
+
+const ALLOW_ATTRIBUTE: &str = "allow";
+const DEPRECATED_ATTRIBUTE: &str = "deprecated";
+const EXPERIMENTAL_ATTRIBUTE: &str = "experimental";
+const UNSAFE_ATTRIBUTE: &str = "unsafe";
+
+/// Gets the kind of declaration the given node is, if any
+fn get_declaration_kind(node: &ASTTreeNode) -> Option<HIRDeclarationKind> {
+    return match &node.kind {
+        ASTTreeNodeKind::FunctionDeclaration { .. }
+        | ASTTreeNodeKind::ExternFunctionDeclaration { .. } => Some(HIRDeclarationKind::Function),
+
+        ASTTreeNodeKind::StructLayoutDeclaration { .. }
+        | ASTTreeNodeKind::EnumDeclaration { .. } => Some(HIRDeclarationKind::Type),
+
+        ASTTreeNodeKind::StructFieldMember { .. } => Some(HIRDeclarationKind::Field),
+
+        _ => None,
+    };
+}
+
+/// Checks that the given declaration attribute can be written on the node
+fn check_declaration_attribute(node: &ASTTreeNode, attribute: &ASTAttribute) -> MaybeDiagnostic {
+    let name = &attribute.name.val;
+
+    let kind = match get_declaration_kind(node) {
+        Some(v) => v,
+        None => {
+            return Err(make_invalid_attribute(
+                attribute,
+                name,
+                &"can only be used on functions, types and fields",
+            )
+            .into());
+        }
+    };
+
+    if name == UNSAFE_ATTRIBUTE && kind != HIRDeclarationKind::Function {
+        return Err(
+            make_invalid_attribute(attribute, name, &"can only be used on functions").into(),
+        );
+    }
+
+    // Only the deprecation message can be given
+    let (max_args, reason) = match name.as_str() {
+        DEPRECATED_ATTRIBUTE => (1, "takes at most one message"),
+        _ => (0, "doesn't take any arguments"),
+    };
+
+    if attribute.args.len() > max_args {
+        return Err(make_invalid_attribute(attribute, name, &reason).into());
+    }
+
+    return Ok(());
+}
+
+/// Applies the attributes of the given node as well as the ones of the members of type declarations
+pub fn lower_ast_attributes(node: &ASTTreeNode) -> MaybeDiagnostic {
+    for attribute in &node.attributes {
+        match attribute.name.val.as_str() {
+            ALLOW_ATTRIBUTE => {
+                for name in &attribute.args {
+                    let codes = get_warning_codes(name);
+
+                    if codes.is_empty() {
+                        return Err(make_unknown_warning(attribute, name).into());
+                    }
+
+                    for code in codes {
+                        allow_warning_in(code, node.start.clone(), node.end.clone());
+                    }
+                }
+            }
+
+            DEPRECATED_ATTRIBUTE | EXPERIMENTAL_ATTRIBUTE | UNSAFE_ATTRIBUTE => {
+                check_declaration_attribute(node, attribute)?;
+            }
+
+            _ => return Err(make_unknown_attribute(attribute, &attribute.name.val).into()),
+        }
+    }
+
+    match &node.kind {
+        ASTTreeNodeKind::StructLayoutDeclaration { members, .. } => {
+            for member in members {
+                lower_ast_attributes(member)?;
+            }
+        }
+
+        ASTTreeNodeKind::EnumDeclaration { functions, .. } => {
+            for function in functions {
+                lower_ast_attributes(function)?;
+            }
+        }
+
+        _ => {}
+    }
+
+    return Ok(());
+}
+
+/// Gathers the attributes of the given declaration that are reported on use. Returns `None` if there are none
+fn get_declaration_attributes(node: &ASTTreeNode, name: &str) -> Option<HIRDeclarationAttributes> {
+    let mut attributes = HIRDeclarationAttributes {
+        name: name.to_string(),
+        kind: get_declaration_kind(node)?,
+        deprecated: None,
+        experimental: false,
+        is_unsafe: false,
+        start: node.start.clone(),
+        end: node.end.clone(),
+    };
+
+    for attribute in &node.attributes {
+        match attribute.name.val.as_str() {
+            DEPRECATED_ATTRIBUTE => attributes.deprecated = Some(attribute.args.first().cloned()),
+            EXPERIMENTAL_ATTRIBUTE => attributes.experimental = true,
+            UNSAFE_ATTRIBUTE => attributes.is_unsafe = true,
+
+            _ => {}
+        }
+    }
+
+    if attributes.is_empty() {
+        return None;
+    }
+
+    return Some(attributes);
+}
+
+/// Stores the attributes of the declaration of the given global entry
+pub fn register_declaration_attributes(
+    context: &mut HIRContext,
+    ind: usize,
+    node: &ASTTreeNode,
+    name: &str,
+) {
+    if let Some(attributes) = get_declaration_attributes(node, name) {
+        context.global_scope.attributes.insert(ind, attributes);
+    }
+}
+
+/// Stores the attributes of the declaration of a field or function of the given type
+pub fn register_member_attributes(
+    context: &mut HIRContext,
+    type_ind: usize,
+    node: &ASTTreeNode,
+    hash: u64,
+    name: &str,
+) {
+    if let Some(attributes) = get_declaration_attributes(node, name) {
+        context
+            .global_scope
+            .member_attributes
+            .insert((type_ind, hash), attributes);
+    }
+}
+
+/// Reports the attributes of the used declaration
+fn report_declaration_use<K: DiagnosticSpanOrigin>(
+    context: &mut HIRContext,
+    attributes: HIRDeclarationAttributes,
+    origin: &K,
+) {
+    let pos = origin.get_pos();
+
+    let key = (
+        pos.file_path.clone(),
+        pos.line,
+        pos.col,
+        attributes.name.clone(),
+    );
+
+    if attributes.contains(&pos) || !context.reported_uses.insert(key) {
+        return;
+    }
+
+    let name = &attributes.name;
+
+    if let Some(message) = &attributes.deprecated {
+        make_deprecated_use(origin, name, message, attributes.get_pos());
+    }
+
+    if attributes.experimental {
+        match attributes.kind {
+            HIRDeclarationKind::Function => {
+                make_experimental_function(origin, name, attributes.get_pos())
+            }
+            HIRDeclarationKind::Type => make_experimental_type(origin, name, attributes.get_pos()),
+            HIRDeclarationKind::Field => {
+                make_experimental_field(origin, name, attributes.get_pos())
+            }
+        };
+    }
+
+    if attributes.is_unsafe {
+        make_unsafe_function(origin, name, attributes.get_pos());
+    }
+}
+
+/// Marks the given global entry as used, reporting the attributes of its declaration
+pub fn use_global_entry<K: DiagnosticSpanOrigin>(context: &mut HIRContext, ind: usize, origin: &K) {
+    context.used_entries.insert(ind);
+
+    if let Some(attributes) = context.global_scope.attributes.get(&ind).cloned() {
+        report_declaration_use(context, attributes, origin);
+    }
+}
+
+/// Reports the attributes of the declaration of the used field or function of the given type
+pub fn use_type_member<K: DiagnosticSpanOrigin>(
+    context: &mut HIRContext,
+    t: &Type,
+    hash: u64,
+    origin: &K,
+) {
+    let type_ind = match t.get_generic() {
+        RawType::Struct(_, container) => container.self_ref,
+        RawType::Enum(container) => container.self_ref,
+        RawType::EnumEntry(container) => container.parent,
+
+        _ => return,
+    };
+
+    if let Some(attributes) = context
+        .global_scope
+        .member_attributes
+        .get(&(type_ind, hash))
+        .cloned()
+    {
+        report_declaration_use(context, attributes, origin);
+    }
+}
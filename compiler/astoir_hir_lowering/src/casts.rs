@@ -0,0 +1,98 @@
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
+use astoir_hir::{
+    ctx::{HIRBranchedContext, HIRContext},
+    nodes::{HIRNode, HIRNodeKind},
+};
+use compiler_typing::{raw::RawType, tree::Type};
+use diagnostics::{
+    DiagnosticResult,
+    builders::{make_invalid_cast, make_req_type_kind},
+};
+
+use crate::{types::lower_ast_type, values::lower_ast_value};
+
+fn is_enum_tag(t: &Type) -> bool {
+    t.is_generic_direct() && (t.get_generic().is_enum_parent() || t.get_generic().is_enum_child())
+}
+
+fn is_integer(t: &Type) -> bool {
+    t.is_generic_direct() && t.get_generic().is_integer()
+}
+
+/// Lowers an explicit cast. On top of the automatic conversions, explicit casts allow converting enums from and into their integer tag.
+pub fn lower_ast_cast_value(
+    context: &mut HIRContext,
+    curr_ctx: &mut HIRBranchedContext,
+    node: Box<ASTTreeNode>,
+) -> DiagnosticResult<Box<HIRNode>> {
+    if let ASTTreeNodeKind::CastValue {
+        original,
+        target_type,
+    } = node.kind.clone()
+    {
+        let original = lower_ast_value(context, curr_ctx, original)?;
+        let target_type = lower_ast_type(context, target_type, &*node)?;
+
+        let original_type = match original.get_node_type(context, curr_ctx) {
+            Some(v) => v,
+            None => {
+                return Ok(Box::new(original.use_as(
+                    context,
+                    curr_ctx,
+                    target_type,
+                    &*node,
+                    None,
+                )?));
+            }
+        };
+
+        if original_type.can_transmute(&target_type, &context.global_scope.scope) {
+            return Ok(Box::new(original.use_as(
+                context,
+                curr_ctx,
+                target_type,
+                &*node,
+                None,
+            )?));
+        }
+
+        let valid = match (&original_type, &target_type) {
+            (from, to) if is_enum_tag(from) && is_integer(to) => true,
+            (from, to) if is_integer(from) && is_enum_tag(to) => match to.get_generic() {
+                RawType::Enum(container) => {
+                    if !container.is_fieldless() {
+                        return Err(
+                            make_req_type_kind(&*node, &"fieldless enum".to_string()).into()
+                        );
+                    }
+
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+
+        if !valid {
+            return Err(make_invalid_cast(
+                &*node,
+                &original_type.faulty_lowering_generic(&context.global_scope.scope),
+                &target_type.faulty_lowering_generic(&context.global_scope.scope),
+            )
+            .into());
+        }
+
+        return Ok(Box::new(HIRNode::new(
+            HIRNodeKind::CastValue {
+                intentional: true,
+                value: original,
+                old_type: original_type,
+                new_type: target_type,
+            },
+            &node.start,
+            &node.end,
+        )));
+    }
+
+    panic!("Invalid node!")
+}
@@ -0,0 +1,295 @@
+//! Lowering of closures.
+//!
+//! A closure is lowered into a generated function taking the closure environment as its first argument. The environment
+//! contains a copy of every variable captured by value and a pointer to every variable captured by reference. Captured
+//! variables are then accessed through the environment within the closure body, see `HIRNodeKind::ClosureEnvironment`.
+//!
+//! The environment lives on the stack of the function creating the closure. A closure capturing variables thus cannot be
+//! returned, even within an aggregate, nor stored through a field, an index or a pointer. A closure capturing variables by
+//! reference cannot be called once the era of said variables has ended.
+
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
+use astoir_hir::{
+    ctx::{HIRBranchedContext, HIRContext, VariableKind, get_variable},
+    nodes::{HIRNode, HIRNodeKind},
+    structs::{HIRClosureCapture, StructLRUStep},
+};
+use compiler_global_scope::key::EntryKey;
+use compiler_typing::{raw::RawType, tree::Type};
+use compiler_utils::hash::HashedString;
+use diagnostics::{
+    DiagnosticResult, DiagnosticSpanOrigin, MaybeDiagnostic,
+    builders::{
+        make_already_in_scope, make_capture_not_local, make_closure_escapes,
+        make_closure_outlives_capture, make_ending_point_missing, make_variable_uninit,
+    },
+};
+
+use crate::{func::name_function_arguments, lower_ast_body, types::lower_ast_type};
+
+/// The name of the environment argument of closure functions. Cannot collide with any identifier
+const CLOSURE_ENV_NAME: &str = "$env";
+
+/// Gets the type of the environment argument of closure functions. The environment is accessed through an untyped pointer
+pub fn get_closure_env_type() -> Type {
+    return Type::Pointer(
+        false,
+        Box::new(Type::Generic(RawType::Integer(8, false), vec![], vec![])),
+    );
+}
+
+pub fn lower_ast_closure(
+    context: &mut HIRContext,
+    curr_ctx: &mut HIRBranchedContext,
+    node: Box<ASTTreeNode>,
+) -> DiagnosticResult<Box<HIRNode>> {
+    if let ASTTreeNodeKind::ClosureExpression {
+        captures,
+        args,
+        body,
+        return_type,
+    } = node.kind.clone()
+    {
+        let ret_type = match return_type {
+            Some(t) => Some(lower_ast_type(context, t, &*node)?),
+            None => None,
+        };
+
+        let env = HashedString::new(CLOSURE_ENV_NAME.to_string());
+
+        let mut arguments = vec![(env.hash, get_closure_env_type())];
+        let mut names = vec![env.val];
+
+        for arg in args.clone() {
+            let t = lower_ast_type(context, arg.argument_type, &*node)?;
+
+            arguments.push((arg.name.hash, t));
+            names.push(arg.name.val);
+        }
+
+        let mut closure_ctx = HIRBranchedContext::new(ret_type.clone());
+        closure_ctx.unchecked = curr_ctx.unchecked;
+        closure_ctx.fast = curr_ctx.fast;
+
+        let branch = closure_ctx.start_branch();
+
+        for (arg, name) in arguments.iter().zip(names) {
+            context.global_scope.enforce_not_here(
+                EntryKey {
+                    name_hash: arg.0,
+                    name: name.clone(),
+                },
+                &*node,
+            )?;
+
+            match closure_ctx.introduce_variable(arg.0, arg.1.clone(), true) {
+                Ok(_) => {}
+                Err(_) => return Err(make_already_in_scope(&*node, &name).into()),
+            }
+        }
+
+        name_function_arguments(&mut closure_ctx, &args, &*node);
+
+        let mut hir_captures = vec![];
+
+        for capture in captures {
+            let var = get_variable(context, curr_ctx, &capture.name, &*node)?;
+
+            if var.0 != VariableKind::LOCAL {
+                return Err(make_capture_not_local(&*node, &capture.name.val).into());
+            }
+
+            if !curr_ctx.has_variable_value(var.2) {
+                return Err(make_variable_uninit(&*node, &capture.name.val).into());
+            }
+
+            // Variables captured by reference must live in memory
+            if capture.by_ref {
+                curr_ctx.introduce_variable_refer(var.2);
+            }
+
+            let inner = match closure_ctx.introduce_variable(capture.name.hash, var.1.clone(), true)
+            {
+                Ok(v) => v,
+                Err(_) => return Err(make_already_in_scope(&*node, &capture.name.val).into()),
+            };
+
+            // Captured variables are accessed through the environment
+            closure_ctx.introduce_variable_refer(inner);
+
+            hir_captures.push(HIRClosureCapture {
+                name: capture.name,
+                outer: var.2,
+                inner,
+                variable_type: var.1,
+                by_ref: capture.by_ref,
+            });
+        }
+
+        let name = format!("closure.{}", context.global_scope.scope.entries.len());
+        let key = EntryKey {
+            name_hash: HashedString::new(name.clone()).hash,
+            name: name.clone(),
+        };
+
+        let ind = context.global_scope.append_implless_function(
+            key,
+            (ret_type.clone(), arguments.clone(), name),
+            &*node,
+        )?;
+
+        let mut hir_body = vec![Box::new(HIRNode::new(
+            HIRNodeKind::ClosureEnvironment {
+                env: 0,
+                captures: hir_captures.clone(),
+            },
+            &node.start,
+            &node.end,
+        ))];
+
+        hir_body.append(&mut lower_ast_body(context, &mut closure_ctx, body, false)?);
+
+        closure_ctx.end_branch(branch);
+
+        if !closure_ctx.meets_ending_point() {
+            return Err(make_ending_point_missing(&*hir_body[hir_body.len() - 1]).into());
+        }
+
+        let implementation = Box::new(HIRNode::new(
+            HIRNodeKind::FunctionDeclaration {
+                func_name: ind,
+                arguments: arguments.clone(),
+                return_type: ret_type.clone(),
+                body: hir_body,
+                ctx: closure_ctx.clone(),
+                requires_this: false,
+            },
+            &node.start,
+            &node.end,
+        ));
+
+        context
+            .global_scope
+            .implement_function(ind, implementation, closure_ctx);
+
+        let closure_type = Type::Closure(
+            arguments[1..]
+                .iter()
+                .map(|arg| Box::new(arg.1.clone()))
+                .collect(),
+            ret_type.map(Box::new),
+        );
+
+        return Ok(Box::new(HIRNode::new(
+            HIRNodeKind::ClosureExpression {
+                function: ind,
+                captures: hir_captures,
+                closure_type,
+            },
+            &node.start,
+            &node.end,
+        )));
+    }
+
+    panic!("Invalid node passed!");
+}
+
+/// Gets the variables captured by the closures the given value evaluates to or contains.
+pub fn get_closure_captures(
+    curr_ctx: &HIRBranchedContext,
+    val: &HIRNode,
+) -> Vec<HIRClosureCapture> {
+    // Closures within aggregates leave the function along with them
+    let inner: Vec<&HIRNode> = match &val.kind {
+        HIRNodeKind::ClosureExpression { captures, .. } => return captures.clone(),
+        HIRNodeKind::VariableReference {
+            index,
+            is_static: false,
+        } => return get_variable_captures(curr_ctx, *index),
+
+        // Fields are assumed to hold any of the closures stored within their root variable
+        HIRNodeKind::StructLRU { steps, .. } => match steps.first() {
+            Some(StructLRUStep::VariableStep { variable }) => {
+                return get_variable_captures(curr_ctx, *variable);
+            }
+            _ => vec![],
+        },
+
+        HIRNodeKind::StructInitializer { fields } => fields.values().map(|v| &**v).collect(),
+        HIRNodeKind::StructInitializerTyped { fields, .. } => fields.iter().map(|v| &**v).collect(),
+        HIRNodeKind::TupleInitializer { values, .. } => values.iter().map(|v| &**v).collect(),
+        HIRNodeKind::ArrayVariableInitializerValue { vals } => vals.iter().map(|v| &**v).collect(),
+
+        HIRNodeKind::UnionInitializerTyped { val, .. }
+        | HIRNodeKind::ArrayVariableInitializerValueSameValue { val, .. }
+        | HIRNodeKind::ArrayIndexAccess { val, .. }
+        | HIRNodeKind::UncheckedValue { val }
+        | HIRNodeKind::CastValue { value: val, .. } => vec![&**val],
+
+        _ => vec![],
+    };
+
+    return inner
+        .into_iter()
+        .flat_map(|v| get_closure_captures(curr_ctx, v))
+        .collect();
+}
+
+fn get_variable_captures(curr_ctx: &HIRBranchedContext, var: usize) -> Vec<HIRClosureCapture> {
+    return curr_ctx
+        .closure_captures
+        .get(&var)
+        .cloned()
+        .unwrap_or_default();
+}
+
+/// Keeps track of the variables captured by the closure assigned to the given variable.
+pub fn track_closure_assign(curr_ctx: &mut HIRBranchedContext, var: usize, val: &HIRNode) {
+    let captures = get_closure_captures(curr_ctx, val);
+
+    if captures.is_empty() {
+        return;
+    }
+
+    curr_ctx
+        .closure_captures
+        .entry(var)
+        .or_default()
+        .extend(captures);
+}
+
+/// Ensures that every variable captured by reference by the closure is still alive.
+pub fn check_closure_captures_alive<K: DiagnosticSpanOrigin>(
+    curr_ctx: &HIRBranchedContext,
+    closure: &HIRNode,
+    origin: &K,
+) -> MaybeDiagnostic {
+    for capture in get_closure_captures(curr_ctx, closure) {
+        if !capture.by_ref {
+            continue;
+        }
+
+        let era = curr_ctx.variables[capture.outer].introduced_in_era;
+
+        if !curr_ctx.is_era_alive(era) {
+            return Err(make_closure_outlives_capture(origin, &capture.name.val).into());
+        }
+    }
+
+    return Ok(());
+}
+
+/// Ensures that a value leaving the function is not and does not contain a closure capturing variables since its
+/// environment would not outlive the function. Values leave the function when returned, stored in a static variable, or
+/// stored through a field, an index or a pointer which might all point outside of the function.
+pub fn check_closure_escape<K: DiagnosticSpanOrigin>(
+    curr_ctx: &HIRBranchedContext,
+    val: &HIRNode,
+    origin: &K,
+) -> MaybeDiagnostic {
+    if let Some(capture) = get_closure_captures(curr_ctx, val).first() {
+        return Err(make_closure_escapes(origin, &capture.name.val).into());
+    }
+
+    return Ok(());
+}
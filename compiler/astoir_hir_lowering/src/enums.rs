@@ -5,22 +5,76 @@ use astoir_hir::{
 };
 use compiler_global_scope::{entry::GlobalStorageEntryType, key::EntryKey};
 use compiler_typing::{enums::RawEnumTypeContainer, raw::RawType};
-use diagnostics::{DiagnosticResult, MaybeDiagnostic, builders::make_already_in_scope};
+use diagnostics::{
+    DiagnosticResult, DiagnosticSpanOrigin, MaybeDiagnostic,
+    builders::{make_bitfield_outside_layout, make_discriminant_overflow, make_req_type_kind},
+    move_current_diagnostic_pos,
+};
+
+use crate::{
+    attributes::{register_declaration_attributes, register_member_attributes},
+    structs::{
+        check_type_cycle, lower_ast_struct_function_bodies, lower_ast_struct_function_signature,
+    },
+    traits::check_type_bounds,
+    types::{lower_ast_type, lower_ast_type_struct},
+};
+
+/// Checks if the discriminant can be stored within the given integer type
+fn fits_in_integer(discriminant: i128, t: &RawType) -> bool {
+    let (bits, signed) = match t {
+        RawType::Integer(bits, signed) => (*bits as u32, *signed),
+        _ => return true,
+    };
+
+    if signed {
+        if bits >= 128 {
+            return true;
+        }
 
-use crate::types::lower_ast_type_struct;
+        let bound = 1i128 << (bits - 1);
+
+        return discriminant >= -bound && discriminant < bound;
+    }
+
+    if bits >= 127 {
+        return discriminant >= 0;
+    }
+
+    return discriminant >= 0 && discriminant < (1i128 << bits);
+}
 
 pub fn lower_ast_enum_entry(
     context: &mut HIRContext,
     node: Box<ASTTreeNode>,
     container: &mut RawEnumTypeContainer,
 ) -> MaybeDiagnostic {
-    if let ASTTreeNodeKind::EnumEntryDeclaration { name, fields } = node.kind.clone() {
+    if let ASTTreeNodeKind::EnumEntryDeclaration {
+        name,
+        fields,
+        discriminant,
+    } = node.kind.clone()
+    {
         let mut hir_fields = vec![];
 
         for f in fields {
-            if let ASTTreeNodeKind::StructFieldMember { name, member_type } = f.kind {
+            if let ASTTreeNodeKind::StructFieldMember {
+                name,
+                member_type,
+                bit_width,
+            } = f.kind
+            {
+                if bit_width.is_some() {
+                    return Err(make_bitfield_outside_layout(&*node, &name.val).into());
+                }
+
                 let t = lower_ast_type_struct(context, member_type, container, &*node)?;
 
+                context
+                    .global_scope
+                    .member_names
+                    .insert((container.self_ref, name.hash), name.val.clone());
+
                 hir_fields.push((name.hash, t));
                 continue;
             }
@@ -28,7 +82,35 @@ pub fn lower_ast_enum_entry(
             panic!("Invalid field node type!");
         }
 
-        container.append_entry(name, hir_fields);
+        let discriminant = match discriminant.or(container.next_discriminant) {
+            Some(v) => v,
+            None => {
+                // The previous entry used the biggest discriminant
+                let t = match &container.repr {
+                    Some(repr) => repr.to_string(),
+                    None => "s128".to_string(),
+                };
+
+                return Err(make_discriminant_overflow(
+                    &*node,
+                    &(i128::MAX as u128 + 1),
+                    &name.val,
+                    &t,
+                )
+                .into());
+            }
+        };
+
+        move_current_diagnostic_pos(node.get_pos());
+
+        container.append_entry(name.clone(), hir_fields, Some(discriminant))?;
+
+        let hint = container.get_hint_type();
+
+        if !fits_in_integer(discriminant, &hint) {
+            return Err(make_discriminant_overflow(&*node, &discriminant, &name.val, &hint).into());
+        }
+
         return Ok(());
     }
 
@@ -41,27 +123,72 @@ pub fn lower_ast_enum(
 ) -> DiagnosticResult<Box<HIRNode>> {
     if let ASTTreeNodeKind::EnumDeclaration {
         name,
+        repr,
         entries,
-        functions: _,
+        functions,
         type_params,
+        type_bounds,
     } = node.kind.clone()
     {
-        let mut container =
-            RawEnumTypeContainer::new(context.global_scope.scope.entries.len(), type_params);
+        check_type_bounds(context, &type_bounds, &node)?;
 
-        for entry in entries {
-            lower_ast_enum_entry(context, entry, &mut container)?;
+        let mut container = RawEnumTypeContainer::new(
+            name.clone(),
+            context.global_scope.scope.entries.len(),
+            type_params,
+            type_bounds,
+        );
+
+        if let Some(repr) = repr {
+            let repr = lower_ast_type(context, repr, &*node)?;
+
+            if !repr.is_generic_direct() || !repr.get_generic().is_integer() {
+                return Err(make_req_type_kind(&*node, &"integer".to_string()).into());
+            }
+
+            container.repr = Some(Box::new(repr.get_generic()));
         }
 
-        let ind = match context.global_scope.append(
+        // The enum is registered before its entries so that they can refer to it
+        let ind = context.global_scope.append(
             EntryKey {
                 name_hash: name.hash,
+                name: name.val.clone(),
             },
             GlobalStorageEntryType::Type(RawType::Enum(container.clone())),
             &*node,
-        ) {
-            Ok(v) => v,
-            Err(_) => return Err(make_already_in_scope(&*node, &name.val).into()),
+        )?;
+
+        register_declaration_attributes(context, ind, &node, &name.val);
+
+        for entry in entries {
+            lower_ast_enum_entry(context, entry.clone(), &mut container)?;
+
+            context.global_scope.scope.entries[ind].entry_type =
+                GlobalStorageEntryType::Type(RawType::Enum(container.clone()));
+        }
+
+        for function in &functions {
+            if let ASTTreeNodeKind::FunctionDeclaration { func_name, .. } = &function.kind {
+                register_member_attributes(context, ind, function, func_name.hash, &func_name.val);
+            }
+
+            let (hash, signature) =
+                lower_ast_struct_function_signature(context, function, &container)?;
+
+            container.functions.append(hash, signature);
+
+            context.global_scope.scope.entries[ind].entry_type =
+                GlobalStorageEntryType::Type(RawType::Enum(container.clone()));
+        }
+
+        check_type_cycle(context, ind, &*node)?;
+
+        lower_ast_struct_function_bodies(context, &functions, ind)?;
+
+        let container = match context.global_scope.scope.entries[ind].as_type_unsafe() {
+            RawType::Enum(container) => container,
+            _ => panic!("Invalid enum type"),
         };
 
         return Ok(Box::new(HIRNode::new(
@@ -1,16 +1,134 @@
-use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind, FunctionDeclarationArgument};
 use astoir_hir::{
     ctx::{HIRBranchedContext, HIRContext},
     nodes::{HIRNode, HIRNodeKind},
 };
 use compiler_global_scope::key::EntryKey;
-use compiler_typing::TypedGlobalScopeEntry;
+use compiler_typing::{TypedGlobalScopeEntry, layout::TypeLayout, raw::RawType, tree::Type};
+use compiler_utils::hash::{HashedString, SelfHash};
 use diagnostics::{
-    DiagnosticResult,
-    builders::{make_already_in_scope, make_ending_point_missing},
+    DiagnosticResult, DiagnosticSpanOrigin,
+    builders::{
+        make_already_in_scope, make_call_argument_count, make_ending_point_missing,
+        make_req_type_kind,
+    },
 };
 
-use crate::{lower_ast_body, types::lower_ast_type, values::lower_ast_value};
+use crate::{
+    attributes::{register_declaration_attributes, use_global_entry},
+    closures::check_closure_captures_alive,
+    lower_ast_body,
+    overflow::{get_overflow_builtin, lower_ast_overflow_operation},
+    types::lower_ast_type,
+    values::lower_ast_value,
+    var::lower_ast_variable_reference,
+};
+
+/// Gets the global entry index of the free function with the given name. Variables shadow functions.
+pub fn get_function_by_name(
+    context: &HIRContext,
+    curr_ctx: &HIRBranchedContext,
+    name: &HashedString,
+) -> Option<usize> {
+    if curr_ctx
+        .hash_to_ind
+        .contains_key(&SelfHash { hash: name.hash })
+    {
+        return None;
+    }
+
+    return context.global_scope.find_function(&EntryKey {
+        name_hash: name.hash,
+        name: name.val.clone(),
+    });
+}
+
+/// Names the arguments of the function being lowered, see `HIRBranchedContext::name_variable`
+pub(crate) fn name_function_arguments<K: DiagnosticSpanOrigin>(
+    curr_ctx: &mut HIRBranchedContext,
+    args: &[FunctionDeclarationArgument],
+    origin: &K,
+) {
+    for arg in args {
+        if let Some(ind) = curr_ctx
+            .hash_to_ind
+            .get(&SelfHash {
+                hash: arg.name.hash,
+            })
+            .copied()
+        {
+            curr_ctx.name_variable(ind, arg.name.val.clone(), origin.get_pos(), true);
+        }
+    }
+}
+
+/// Checks whether the called name refers to a variable instead of a function.
+fn is_variable_call(
+    context: &HIRContext,
+    curr_ctx: &HIRBranchedContext,
+    name: &HashedString,
+) -> bool {
+    if curr_ctx
+        .hash_to_ind
+        .contains_key(&SelfHash { hash: name.hash })
+    {
+        return true;
+    }
+
+    return match context.global_scope.scope.entry_to_ind.get(&EntryKey {
+        name_hash: name.hash,
+        name: name.val.clone(),
+    }) {
+        Some(ind) => matches!(
+            context.global_scope.scope.entries[*ind].entry_type,
+            TypedGlobalScopeEntry::StaticVariable(_)
+        ),
+        None => false,
+    };
+}
+
+/// Lowers a call through a function pointer value.
+pub fn lower_ast_indirect_call(
+    context: &mut HIRContext,
+    curr_ctx: &mut HIRBranchedContext,
+    node: Box<ASTTreeNode>,
+    callee: Box<HIRNode>,
+    args: Vec<Box<ASTTreeNode>>,
+) -> DiagnosticResult<Box<HIRNode>> {
+    let callee_type = callee.get_node_type(context, curr_ctx).unwrap();
+
+    let arg_types = match callee_type.get_callable_arguments() {
+        Some(args) => args.clone(),
+        None => return Err(make_req_type_kind(&*node, &"callable".to_string()).into()),
+    };
+
+    check_closure_captures_alive(curr_ctx, &callee, &*node)?;
+
+    if arg_types.len() != args.len() {
+        return Err(
+            make_call_argument_count(&*node, &callee_type, &arg_types.len(), &args.len()).into(),
+        );
+    }
+
+    let mut hir_args = vec![];
+
+    for (ast, t) in args.into_iter().zip(arg_types) {
+        let hir = lower_ast_value(context, curr_ctx, ast)?;
+
+        let val = hir.use_as(context, curr_ctx, *t, &*node, None)?;
+
+        hir_args.push(Box::new(val));
+    }
+
+    return Ok(Box::new(HIRNode::new(
+        HIRNodeKind::IndirectCall {
+            callee,
+            arguments: hir_args,
+        },
+        &node.start,
+        &node.end,
+    )));
+}
 
 pub fn lower_ast_function_call(
     context: &mut HIRContext,
@@ -18,8 +136,28 @@ pub fn lower_ast_function_call(
     node: Box<ASTTreeNode>,
 ) -> DiagnosticResult<Box<HIRNode>> {
     if let ASTTreeNodeKind::FunctionCall { func, args } = node.kind.clone() {
+        if is_variable_call(context, curr_ctx, &func) {
+            let callee = Box::new(ASTTreeNode::new(
+                ASTTreeNodeKind::VariableReference(func.clone()),
+                node.start.clone(),
+                node.end.clone(),
+            ));
+
+            let callee = lower_ast_variable_reference(context, curr_ctx, callee, true)?;
+
+            return lower_ast_indirect_call(context, curr_ctx, node, callee, args);
+        }
+
+        // User functions shadow the arithmetic builtins
+        if get_function_by_name(context, curr_ctx, &func).is_none()
+            && let Some((behavior, operator)) = get_overflow_builtin(&func.val)
+        {
+            return lower_ast_overflow_operation(context, curr_ctx, node, behavior, operator);
+        }
+
         let name = EntryKey {
             name_hash: func.hash,
+            name: func.val.clone(),
         };
 
         let func = context
@@ -28,6 +166,8 @@ pub fn lower_ast_function_call(
 
         let func_ind = context.global_scope.get_ind(name, &*node)?;
 
+        use_global_entry(context, func_ind, &*node);
+
         let mut hir_args = vec![];
         let mut ind = 0;
 
@@ -64,6 +204,8 @@ pub fn lower_ast_function_declaration(
         body,
         return_type,
         requires_this,
+        unchecked,
+        fast,
     } = node.clone().kind
     {
         let ret_type;
@@ -79,7 +221,7 @@ pub fn lower_ast_function_declaration(
         let mut arguments = vec![];
         let mut types = vec![];
 
-        for arg in args {
+        for arg in args.clone() {
             types.push(arg.argument_type.clone());
             let t = lower_ast_type(context, arg.argument_type, &*node)?;
 
@@ -87,33 +229,41 @@ pub fn lower_ast_function_declaration(
         }
 
         let mut curr_ctx = HIRBranchedContext::new(ret_type.clone());
+        curr_ctx.unchecked = unchecked;
+        curr_ctx.fast = fast;
 
         let branch = curr_ctx.start_branch();
 
-        for arg in &arguments {
-            context
-                .global_scope
-                .enforce_not_here(EntryKey { name_hash: arg.0 }, &*node)?;
+        for (arg, declared) in arguments.iter().zip(&args) {
+            context.global_scope.enforce_not_here(
+                EntryKey {
+                    name_hash: arg.0,
+                    name: declared.name.val.clone(),
+                },
+                &*node,
+            )?;
 
             match curr_ctx.introduce_variable(arg.0, arg.1.clone(), true) {
                 Ok(_) => {}
-                Err(_) => return Err(make_already_in_scope(&*node, &arg.0).into()),
+                Err(_) => return Err(make_already_in_scope(&*node, &declared.name.val).into()),
             }
         }
 
+        name_function_arguments(&mut curr_ctx, &args, &*node);
+
         let key = EntryKey {
             name_hash: func_name.hash,
+            name: func_name.val.clone(),
         };
 
-        let entry =
-            TypedGlobalScopeEntry::ImplLessFunction(context.global_scope.scope.descriptor_counter);
-
         let ind = context.global_scope.append_implless_function(
-            key.clone(),
+            key,
             (ret_type.clone(), arguments.clone(), func_name.val.clone()),
             &*node,
         )?;
 
+        register_declaration_attributes(context, ind, &node, &func_name.val);
+
         let body = lower_ast_body(context, &mut curr_ctx, body, false)?;
 
         curr_ctx.end_branch(branch);
@@ -135,24 +285,10 @@ pub fn lower_ast_function_declaration(
             &node.end,
         ));
 
-        // Remove old impless version
-        context.global_scope.scope.entries.pop();
-        context.global_scope.scope.entry_to_ind.remove(&key);
-        context.global_scope.scope.value_to_ind.remove(&entry);
-        context.global_scope.descriptors.pop();
-        //context.global_scope.contexts.pop();
-        context.global_scope.scope.descriptor_counter -= 1;
-        //context.global_scope.scope.ctx_counter -= 1;
-
-        // Append the new verison as a impl-containing function
-
-        context.global_scope.append_func(
-            key,
-            (ret_type.clone(), arguments.clone(), func_name.val.clone()),
-            implementation.clone(),
-            curr_ctx.clone(),
-            &*node,
-        )?;
+        // The impl-less version allowed for the function to be referenced within its own body
+        context
+            .global_scope
+            .implement_function(ind, implementation.clone(), curr_ctx);
 
         return Ok(implementation);
     }
@@ -160,6 +296,19 @@ pub fn lower_ast_function_declaration(
     panic!("Invalid node passed!");
 }
 
+/// Integers of odd widths cross extern boundaries as their storage width, see `compiler_typing::layout`
+fn get_extern_abi_type(t: Type) -> Type {
+    if let Type::Generic(RawType::Integer(size, signed), _, _) = &t {
+        let storage = TypeLayout::scalar(*size).size;
+
+        if storage != *size {
+            return Type::Generic(RawType::Integer(storage, *signed), vec![], vec![]);
+        }
+    }
+
+    return t;
+}
+
 pub fn lower_ast_extern_function_declaration(
     context: &mut HIRContext,
     node: Box<ASTTreeNode>,
@@ -175,7 +324,7 @@ pub fn lower_ast_extern_function_declaration(
         if return_type.is_some() {
             let lower = lower_ast_type(context, return_type.unwrap(), &*node)?;
 
-            ret_type = Some(lower)
+            ret_type = Some(get_extern_abi_type(lower))
         } else {
             ret_type = None;
         }
@@ -187,17 +336,20 @@ pub fn lower_ast_extern_function_declaration(
             types.push(arg.argument_type.clone());
             let t = lower_ast_type(context, arg.argument_type, &*node)?;
 
-            arguments.push((arg.name.hash, t));
+            arguments.push((arg.name.hash, get_extern_abi_type(t)));
         }
 
         let ind = context.global_scope.append_implless_function(
             EntryKey {
                 name_hash: func_name.hash,
+                name: func_name.val.clone(),
             },
             (ret_type.clone(), arguments.clone(), func_name.val.clone()),
             &*node,
         )?;
 
+        register_declaration_attributes(context, ind, &node, &func_name.val);
+
         return Ok(Box::new(HIRNode::new(
             HIRNodeKind::ExternFunctionDeclaration {
                 func_name: ind,
@@ -0,0 +1,74 @@
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind, LayoutIntrinsic};
+use astoir_hir::{
+    ctx::HIRContext,
+    nodes::{HIRNode, HIRNodeKind},
+};
+use compiler_global_scope::key::EntryKey;
+use compiler_typing::{StructuredType, raw::RawType, tree::Type};
+use diagnostics::{
+    DiagnosticResult,
+    builders::{make_cannot_find_type_field, make_req_type_kind},
+};
+use prelude::types::UNSIGNED_INTEGER_64;
+
+use crate::types::lower_ast_type;
+
+/// Lowers a type layout intrinsic into an `u64` integer literal. Layouts are computed in bits but exposed in bytes.
+pub fn lower_ast_layout_intrinsic(
+    context: &mut HIRContext,
+    node: Box<ASTTreeNode>,
+) -> DiagnosticResult<Box<HIRNode>> {
+    if let ASTTreeNodeKind::LayoutIntrinsic {
+        intrinsic,
+        target,
+        field,
+    } = node.kind.clone()
+    {
+        let t = lower_ast_type(context, target, &*node)?;
+        let storage = &context.global_scope.scope;
+
+        let bits = match intrinsic {
+            LayoutIntrinsic::SizeOf => t.get_layout(storage).size,
+            LayoutIntrinsic::AlignOf => t.get_layout(storage).alignment,
+            LayoutIntrinsic::OffsetOf => {
+                let field = field.unwrap();
+
+                let (layout, container) = match t.get_generic() {
+                    RawType::Struct(layout, container) if t.is_generic_direct() => {
+                        (layout, container)
+                    }
+                    _ => {
+                        return Err(make_req_type_kind(&*node, &"struct".to_string()).into());
+                    }
+                };
+
+                if !container.get_fields(storage).contains(&field.hash) {
+                    return Err(make_cannot_find_type_field(&field.val, &t).into());
+                }
+
+                let ind = container.get_field_hash(field.hash, storage)?;
+
+                container.get_fields_layout(&t, !layout, storage).1[ind]
+            }
+        };
+
+        let int_type = context.global_scope.get_type(
+            EntryKey {
+                name_hash: UNSIGNED_INTEGER_64,
+                name: "u64".to_string(),
+            },
+            &*node,
+        )?;
+
+        return Ok(Box::new(HIRNode::new(
+            HIRNodeKind::IntegerLiteral {
+                value: (bits / 8) as i128,
+                int_type: Type::Generic(int_type, vec![], vec![]),
+            },
+            &node.start,
+            &node.end,
+        )));
+    }
+
+    panic!("Invalid node type")
+}
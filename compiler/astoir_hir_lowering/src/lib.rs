@@ -9,12 +9,15 @@ use astoir_hir::{
 use diagnostics::{
     DiagnosticResult, DiagnosticSpanOrigin,
     builders::{make_ret_type_kind, make_unreachable_code},
+    lints::clear_allowed_regions,
     move_current_diagnostic_pos,
 };
 use prelude::apply_prelude;
 
 use crate::{
     arrays::lower_ast_array_modify,
+    attributes::lower_ast_attributes,
+    closures::check_closure_escape,
     control::{
         lower_ast_for_block, lower_ast_for_ranged_block, lower_ast_if_statement,
         lower_ast_while_block,
@@ -26,21 +29,35 @@ use crate::{
     },
     math::lower_ast_math_operation,
     structs::lower_ast_struct_declaration,
+    traits::{lower_ast_trait_declaration, lower_ast_trait_implementation},
+    tuples::lower_ast_tuple_destructuring,
+    unchecked::lower_ast_unchecked_statement,
+    usage::{check_unused_declarations, check_unused_variables},
     uses::handle_ast_use_statement,
-    values::{lower_ast_pointer_modify, lower_ast_value},
+    values::{lower_ast_lru, lower_ast_pointer_modify, lower_ast_value},
     var::{lower_ast_variable_assign, lower_ast_variable_declaration},
 };
 
 pub mod arrays;
+pub mod attributes;
 pub mod bools;
+pub mod casts;
+pub mod closures;
 pub mod control;
 pub mod enums;
 pub mod func;
+pub mod intrinsics;
 pub mod literals;
 pub mod math;
+pub mod overflow;
+pub mod slices;
 pub mod structs;
+pub mod traits;
+pub mod tuples;
 pub mod types;
+pub mod unchecked;
 pub mod unwraps;
+pub mod usage;
 pub mod uses;
 pub mod values;
 pub mod var;
@@ -51,10 +68,15 @@ pub fn lower_ast_body_node(
     node: Box<ASTTreeNode>,
 ) -> DiagnosticResult<Box<HIRNode>> {
     move_current_diagnostic_pos(node.get_pos());
+    lower_ast_attributes(&node)?;
+
     match node.kind.clone() {
         ASTTreeNodeKind::VarDeclaration { .. } => {
             return lower_ast_variable_declaration(context, curr_ctx, node, false, None);
         }
+        ASTTreeNodeKind::TupleDestructuring { .. } => {
+            return lower_ast_tuple_destructuring(context, curr_ctx, node);
+        }
         ASTTreeNodeKind::FunctionCall { .. } => {
             return lower_ast_function_call(context, curr_ctx, node);
         }
@@ -91,6 +113,8 @@ pub fn lower_ast_body_node(
                     None,
                 )?);
 
+                check_closure_escape(curr_ctx, &k, &*node)?;
+
                 v = Some(k)
             }
 
@@ -116,6 +140,14 @@ pub fn lower_ast_body_node(
             return lower_ast_pointer_modify(context, curr_ctx, node);
         }
 
+        ASTTreeNodeKind::UncheckedValue { .. } => {
+            return lower_ast_unchecked_statement(context, curr_ctx, node);
+        }
+
+        ASTTreeNodeKind::StructLRFunction { .. } => {
+            return lower_ast_lru(context, curr_ctx, node);
+        }
+
         _ => panic!("Invalid node type"),
     }
 }
@@ -186,23 +218,47 @@ pub fn lower_ast_toplevel(
             return Ok(true);
         }
 
+        ASTTreeNodeKind::TraitDeclaration { .. } => {
+            lower_ast_trait_declaration(context, node)?;
+
+            return Ok(true);
+        }
+
+        ASTTreeNodeKind::TraitImplementation { .. } => {
+            lower_ast_trait_implementation(context, node)?;
+
+            return Ok(true);
+        }
+
         _ => panic!("Invalid node type"),
     }
 }
 
 pub fn lower_ast(ctx: ParserCtx) -> DiagnosticResult<HIRContext> {
     let mut hir_ctx = HIRContext::new();
+
+    // The regions of a previous lowering would otherwise still apply, such as when the LSP checks an edited file
+    clear_allowed_regions();
+
     apply_prelude(&mut hir_ctx, &*ctx.map[&ctx.iter_order[0]])?;
 
-    for u in ctx.uses {
+    // Attributes can affect diagnostics emitted before their node is lowered
+    for node in ctx.uses.iter().chain(ctx.iter_order.iter().map(|s| &ctx.map[s])) {
+        lower_ast_attributes(node)?;
+    }
+
+    for u in ctx.uses.clone() {
         handle_ast_use_statement(&mut hir_ctx, u)?;
     }
 
-    for s in ctx.iter_order {
-        let k = ctx.map[&s].clone();
+    for s in &ctx.iter_order {
+        let k = ctx.map[s].clone();
 
         lower_ast_toplevel(&mut hir_ctx, k)?;
     }
 
+    check_unused_variables(&hir_ctx);
+    check_unused_declarations(&hir_ctx, &ctx);
+
     return Ok(hir_ctx);
 }
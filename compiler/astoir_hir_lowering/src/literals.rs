@@ -5,21 +5,21 @@ use astoir_hir::{
 };
 use compiler_global_scope::key::EntryKey;
 use compiler_typing::tree::Type;
-use diagnostics::{DiagnosticResult, builders::make_cannot_find_type};
+use diagnostics::DiagnosticResult;
 
 pub fn lower_ast_literal(
     context: &HIRContext,
     node: Box<ASTTreeNode>,
 ) -> DiagnosticResult<Box<HIRNode>> {
     match node.kind {
-        ASTTreeNodeKind::IntegerLit { val, hash } => {
-            let lit_type = match context
-                .global_scope
-                .get_type(EntryKey { name_hash: hash }, &*node)
-            {
-                Ok(v) => v,
-                Err(_) => return Err(make_cannot_find_type(&*node, &hash).into()),
-            };
+        ASTTreeNodeKind::IntegerLit { val, ref int_type } => {
+            let lit_type = context.global_scope.get_type(
+                EntryKey {
+                    name_hash: int_type.hash,
+                    name: int_type.val.clone(),
+                },
+                &*node,
+            )?;
 
             return Ok(Box::new(HIRNode::new(
                 HIRNodeKind::IntegerLiteral {
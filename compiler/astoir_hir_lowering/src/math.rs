@@ -3,12 +3,81 @@ use astoir_hir::{
     ctx::{HIRBranchedContext, HIRContext},
     nodes::{HIRNode, HIRNodeKind},
 };
+use compiler_typing::{
+    raw::{MAX_INTEGER_SIZE, RawType},
+    tree::Type,
+};
 use diagnostics::{
-    DiagnosticResult, DiagnosticSpanOrigin, diagnostic::Level, errors::MATH_OPERATION_ASSIGNS,
+    DiagnosticResult, DiagnosticSpanOrigin, builders::make_lossy_promotion, diagnostic::Level,
+    errors::MATH_OPERATION_ASSIGNS,
 };
 
 use crate::values::lower_ast_value;
 
+fn is_integer_literal(node: &HIRNode) -> bool {
+    return matches!(node.kind, HIRNodeKind::IntegerLiteral { .. });
+}
+
+/// Gets the type both operands of an integer operation are promoted to, if any.
+///
+/// Operands are promoted to the smallest integer able to represent every value of both types: the widest of both if
+/// they share their signedness, otherwise a signed integer at least one bit wider than the unsigned operand. Integer
+/// literals take the type of the other operand instead.
+///
+/// Promoted integers are never wider than `MAX_INTEGER_SIZE`: an unsigned operand of that width is promoted to the
+/// widest signed integer instead, which cannot represent its biggest values.
+fn get_promoted_type<K: DiagnosticSpanOrigin>(
+    left: &HIRNode,
+    left_type: &Type,
+    right: &HIRNode,
+    right_type: &Type,
+    origin: &K,
+) -> Option<Type> {
+    if !left_type.is_generic_direct() || !right_type.is_generic_direct() {
+        return None;
+    }
+
+    let (left_size, left_signed, right_size, right_signed) =
+        match (left_type.get_generic(), right_type.get_generic()) {
+            (RawType::Integer(a, b), RawType::Integer(c, d)) => (a, b, c, d),
+            _ => return None,
+        };
+
+    if is_integer_literal(right) {
+        return None;
+    }
+
+    if is_integer_literal(left) {
+        return Some(right_type.clone());
+    }
+
+    let (size, signed) = match (left_signed, right_signed) {
+        (true, false) => (left_size.max(right_size + 1), true),
+        (false, true) => (right_size.max(left_size + 1), true),
+
+        _ => (left_size.max(right_size), left_signed),
+    };
+
+    if size > MAX_INTEGER_SIZE {
+        let promoted = RawType::Integer(MAX_INTEGER_SIZE, true);
+
+        let unsigned = match left_signed {
+            true => right_type,
+            false => left_type,
+        };
+
+        make_lossy_promotion(origin, unsigned, &promoted);
+
+        return Some(Type::Generic(promoted, vec![], vec![]));
+    }
+
+    Some(Type::Generic(
+        RawType::Integer(size, signed),
+        vec![],
+        vec![],
+    ))
+}
+
 pub fn lower_ast_math_operation(
     context: &mut HIRContext,
     curr_ctx: &mut HIRBranchedContext,
@@ -36,14 +105,33 @@ pub fn lower_ast_math_operation(
         }
 
         let left = lower_ast_value(context, curr_ctx, lval)?;
+        let left_type = left.get_node_type(context, curr_ctx).unwrap();
+
+        let right = lower_ast_value(context, curr_ctx, rval)?;
+        let right_type = right.get_node_type(context, curr_ctx);
+
+        let promoted = match right_type {
+            Some(right_type) if !operator.assigns => {
+                get_promoted_type(&left, &left_type, &right, &right_type, &*node)
+            }
+
+            _ => None,
+        };
+
+        // Assigning operations keep the type of the variable
+        let target_type = promoted.unwrap_or(left_type.clone());
+
+        let left = match target_type == left_type {
+            true => left,
+            false => Box::new(left.use_as(context, curr_ctx, target_type.clone(), &*node, None)?),
+        };
+
+        let right = Box::new(right.use_as(context, curr_ctx, target_type, &*node, None)?);
+
+        let mut operator = operator;
 
-        let right = Box::new(lower_ast_value(context, curr_ctx, rval)?.use_as(
-            context,
-            curr_ctx,
-            left.get_node_type(context, curr_ctx).unwrap(),
-            &*node,
-            None,
-        )?);
+        // Every operation of a fast function is fast
+        operator.fast |= curr_ctx.fast;
 
         return Ok(Box::new(HIRNode::new(
             HIRNodeKind::MathOperation {
@@ -0,0 +1,74 @@
+//! Lowering of the arithmetic builtins with an explicit overflow behavior such as `wrapping_add(a, b)`.
+
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
+use astoir_hir::{
+    ctx::{HIRBranchedContext, HIRContext},
+    nodes::{HIRNode, HIRNodeKind},
+};
+use compiler_utils::operators::{MathOperatorType, OverflowBehavior};
+use diagnostics::{
+    DiagnosticResult,
+    builders::{make_call_argument_count, make_req_type_kind},
+};
+
+use crate::values::lower_ast_value;
+
+/// Gets the overflow behavior and the operator of the given builtin name. Builtins are named `<behavior>_<operator>`
+pub fn get_overflow_builtin(name: &str) -> Option<(OverflowBehavior, MathOperatorType)> {
+    let (behavior, operator) = name.split_once('_')?;
+
+    let behavior = match behavior {
+        "wrapping" => OverflowBehavior::Wrapping,
+        "saturating" => OverflowBehavior::Saturating,
+        "checked" => OverflowBehavior::Checked,
+        _ => return None,
+    };
+
+    let operator = match operator {
+        "add" => MathOperatorType::Add,
+        "sub" => MathOperatorType::Subtract,
+        "mul" => MathOperatorType::Multiply,
+        _ => return None,
+    };
+
+    return Some((behavior, operator));
+}
+
+pub fn lower_ast_overflow_operation(
+    context: &mut HIRContext,
+    curr_ctx: &mut HIRBranchedContext,
+    node: Box<ASTTreeNode>,
+    behavior: OverflowBehavior,
+    operator: MathOperatorType,
+) -> DiagnosticResult<Box<HIRNode>> {
+    if let ASTTreeNodeKind::FunctionCall { func, args } = node.kind.clone() {
+        if args.len() != 2 {
+            return Err(make_call_argument_count(&*node, &func.val, &2, &args.len()).into());
+        }
+
+        let mut args = args.into_iter();
+
+        let left = lower_ast_value(context, curr_ctx, args.next().unwrap())?;
+        let t = left.get_node_type(context, curr_ctx).unwrap();
+
+        if !t.is_generic_direct() || !t.get_generic().is_integer() {
+            return Err(make_req_type_kind(&*node, &"integer".to_string()).into());
+        }
+
+        let right = lower_ast_value(context, curr_ctx, args.next().unwrap())?;
+        let right = right.use_as(context, curr_ctx, t, &*node, None)?;
+
+        return Ok(Box::new(HIRNode::new(
+            HIRNodeKind::OverflowOperation {
+                left,
+                right: Box::new(right),
+                operator,
+                behavior,
+            },
+            &node.start,
+            &node.end,
+        )));
+    }
+
+    panic!("Invalid node passed!");
+}
@@ -0,0 +1,71 @@
+//! Lowering of slices.
+//!
+//! Slices are created from arrays, other slices or pointers of arrays by sub-slicing them with a range (`arr[1..4]`).
+//! Fixed arrays are also implicitly used as slices over all of their elements. The element count of a slice is accessed
+//! through `.len`.
+
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
+use astoir_hir::{
+    ctx::{HIRBranchedContext, HIRContext},
+    nodes::{HIRNode, HIRNodeKind},
+};
+use compiler_typing::{tree::Type, utils::get_index_type};
+use compiler_utils::hash::HashedString;
+use diagnostics::{
+    DiagnosticResult, DiagnosticSpanOrigin,
+    builders::{make_index_usage, make_struct_missing_field},
+};
+
+use crate::values::{lower_ast_range, lower_ast_value};
+
+/// Gets the index and type of the slice member with the given name. Only `len` is exposed
+pub fn get_slice_element<K: DiagnosticSpanOrigin>(
+    t: &Type,
+    name: &HashedString,
+    origin: &K,
+) -> DiagnosticResult<(usize, Type)> {
+    if name.val == "len" {
+        return Ok((1, Type::Generic(get_index_type(), vec![], vec![])));
+    }
+
+    return Err(make_struct_missing_field(origin, t, &name.val).into());
+}
+
+pub fn lower_ast_sub_slice(
+    context: &mut HIRContext,
+    curr_ctx: &mut HIRBranchedContext,
+    node: Box<ASTTreeNode>,
+) -> DiagnosticResult<Box<HIRNode>> {
+    if let ASTTreeNodeKind::SubSlice { val, range } = node.kind.clone() {
+        let val = lower_ast_value(context, curr_ctx, val)?;
+        let val_type = val.get_node_type(context, curr_ctx).unwrap();
+
+        let inner = match &val_type {
+            Type::Array(_, inner) | Type::Slice(inner) | Type::Pointer(true, inner) => {
+                inner.clone()
+            }
+            _ => return Err(make_index_usage(&*node, &val_type).into()),
+        };
+
+        let range = lower_ast_range(
+            context,
+            curr_ctx,
+            range,
+            Type::Generic(get_index_type(), vec![], vec![]),
+            &*node,
+        )?;
+
+        return Ok(Box::new(HIRNode::new(
+            HIRNodeKind::SubSlice {
+                val,
+                range: Some(range),
+                from_slice: val_type.is_slice(),
+                t: Type::Slice(inner),
+            },
+            &node.start,
+            &node.end,
+        )));
+    }
+
+    panic!("Invalid node passed!");
+}
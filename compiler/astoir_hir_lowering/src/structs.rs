@@ -9,20 +9,56 @@ use astoir_hir::{
     nodes::{HIRNode, HIRNodeKind},
 };
 use compiler_global_scope::{entry::GlobalStorageEntryType, key::EntryKey};
-use compiler_typing::{raw::RawType, structs::RawStructTypeContainer};
+use compiler_typing::{
+    TypeParamType, TypeParameterContainer, TypedFunction, bitfields::MAX_STORAGE_UNIT_SIZE,
+    cycles::find_type_cycle, raw::RawType, references::TypeReference,
+    structs::RawStructTypeContainer, tree::Type,
+};
 use compiler_utils::{hash::HashedString, utils::indexed::IndexStorage};
-use diagnostics::{DiagnosticResult, builders::make_already_in_scope};
+use diagnostics::{
+    DiagnosticResult, DiagnosticSpanOrigin, MaybeDiagnostic,
+    builders::{
+        make_already_in_scope, make_bitfield_not_integer, make_bitfield_outside_layout,
+        make_bitfield_width, make_ending_point_missing, make_infinite_size_type,
+    },
+    move_current_diagnostic_pos,
+};
 
-use crate::{lower_ast_body, types::lower_ast_type_struct, values::lower_ast_value};
+use crate::{
+    attributes::{register_declaration_attributes, register_member_attributes},
+    func::name_function_arguments,
+    lower_ast_body,
+    traits::check_type_bounds,
+    types::lower_ast_type_struct,
+    values::lower_ast_value,
+};
 
 fn lower_ast_struct_member(
     context: &mut HIRContext,
     node: Box<ASTTreeNode>,
     container: &mut RawStructTypeContainer,
+    layout: bool,
 ) -> DiagnosticResult<bool> {
-    if let ASTTreeNodeKind::StructFieldMember { name, member_type } = node.kind.clone() {
+    if let ASTTreeNodeKind::StructFieldMember {
+        name,
+        member_type,
+        bit_width,
+    } = node.kind.clone()
+    {
         let t = lower_ast_type_struct(context, member_type, container, &*node)?;
 
+        if let Some(width) = bit_width {
+            if !layout || container.is_union {
+                return Err(make_bitfield_outside_layout(&*node, &name.val).into());
+            }
+
+            check_bitfield_width(&t, &name.val, width, &*node)?;
+
+            container
+                .bitfields
+                .insert(container.fields.vals.len(), width);
+        }
+
         container.fields.append(name.hash, t);
         return Ok(true);
     }
@@ -30,50 +66,371 @@ fn lower_ast_struct_member(
     panic!("Invalid node type")
 }
 
-fn lower_ast_struct_function_decl(
+/// Checks that the bitfield is an integer able to hold the given amount of bits
+fn check_bitfield_width<K: DiagnosticSpanOrigin>(
+    t: &TypeReference,
+    name: &String,
+    width: usize,
+    origin: &K,
+) -> MaybeDiagnostic {
+    let t = match t {
+        TypeReference::Resolved(t) => t,
+        TypeReference::Unresolved(_) => {
+            return Err(make_bitfield_not_integer(origin, name, &"type parameter").into());
+        }
+    };
+
+    let size = match t {
+        Type::Generic(RawType::Integer(size, _), _, _) => *size,
+        _ => return Err(make_bitfield_not_integer(origin, name, t).into()),
+    };
+
+    let max = size.min(MAX_STORAGE_UNIT_SIZE);
+
+    if width == 0 || width > max {
+        return Err(make_bitfield_width(origin, name, &width, t, &max).into());
+    }
+
+    return Ok(());
+}
+
+/// Lowers the signature of a struct or enum function, giving back its name hash. Type parameters are kept unresolved
+pub(crate) fn lower_ast_struct_function_signature<T: TypeParamType>(
     context: &mut HIRContext,
-    node: Box<ASTTreeNode>,
-    container: &mut RawStructTypeContainer,
-    ty: RawType,
-) -> DiagnosticResult<(Box<HIRNode>, usize)> {
+    node: &ASTTreeNode,
+    container: &T,
+) -> DiagnosticResult<(u64, TypedFunction)> {
     if let ASTTreeNodeKind::FunctionDeclaration {
         func_name,
         args,
-        body,
         return_type,
-        requires_this,
+        ..
     } = node.kind.clone()
     {
         let mut arguments = vec![];
 
         for arg in args {
-            let lowered = lower_ast_type_struct(context, arg.argument_type, container, &*node)?;
+            let lowered = lower_ast_type_struct(context, arg.argument_type, container, node)?;
 
             arguments.push((arg.name.hash, lowered));
         }
 
-        let ret_type;
+        let ret_type = match return_type {
+            Some(t) => Some(lower_ast_type_struct(context, t, container, node)?),
+            None => None,
+        };
+
+        return Ok((func_name.hash, (arguments, ret_type)));
+    }
+
+    panic!("Invalid node type")
+}
+
+/// Gets the global index and the type parameters of a struct or enum, the only types able to declare functions
+fn get_function_owner(raw: &RawType) -> Option<(usize, &TypeParameterContainer)> {
+    match raw {
+        RawType::Struct(_, container) => Some((container.self_ref, &container.type_params)),
+        RawType::Enum(container) => Some((container.self_ref, &container.type_params)),
+        _ => None,
+    }
+}
+
+/// Lowers the bodies of the given struct or enum functions. The functions of types with type parameters are instead lowered once per instantiation, see `lower_ast_struct_instantiation`
+pub(crate) fn lower_ast_struct_function_bodies(
+    context: &mut HIRContext,
+    functions: &[Box<ASTTreeNode>],
+    struct_ind: usize,
+) -> MaybeDiagnostic {
+    let raw = context.global_scope.scope.entries[struct_ind].as_type_unsafe();
+
+    let (_, type_params) = get_function_owner(&raw).expect("Invalid struct type");
+
+    if !type_params.is_empty() {
+        context
+            .generic_functions
+            .entry(struct_ind)
+            .or_default()
+            .extend(functions.iter().cloned());
+
+        // Functions implemented after the type was instantiated are lowered for the existing instantiations as well
+        let instantiations = context
+            .generic_instantiations
+            .get(&struct_ind)
+            .cloned()
+            .unwrap_or_default();
+
+        for t in instantiations {
+            lower_ast_struct_instance_functions(context, functions, &t)?;
+        }
+
+        return Ok(());
+    }
+
+    return lower_ast_struct_functions(context, functions, &Type::Generic(raw, vec![], vec![]));
+}
+
+/// Lowers the functions of a struct or enum with type parameters for the given concrete type. Every instantiation obtains its own functions
+pub(crate) fn lower_ast_struct_instantiation<K: DiagnosticSpanOrigin>(
+    context: &mut HIRContext,
+    t: &Type,
+    origin: &K,
+) -> MaybeDiagnostic {
+    let raw = t.get_generic();
+
+    let (self_ref, type_params) = match get_function_owner(&raw) {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    if type_params.is_empty() || context.generic_instances.contains_key(&get_type_hash(t)) {
+        return Ok(());
+    }
+
+    let functions = match context.generic_functions.get(&self_ref) {
+        Some(v) => v.clone(),
+        None => return Ok(()),
+    };
+
+    move_current_diagnostic_pos(origin.get_pos());
+
+    lower_ast_struct_instance_functions(context, &functions, t)
+}
+
+/// Lowers the given functions of a struct or enum with type parameters for the concrete type `t`
+fn lower_ast_struct_instance_functions(
+    context: &mut HIRContext,
+    functions: &[Box<ASTTreeNode>],
+    t: &Type,
+) -> MaybeDiagnostic {
+    let raw = t.get_generic();
+    let (_, type_params) = get_function_owner(&raw).expect("Invalid struct type");
+
+    let previous = context.type_params.clone();
+    let params = t.get_generic_info().0;
+
+    for (param, ind) in type_params {
+        context
+            .type_params
+            .insert(param.hash, *params[*ind].clone());
+    }
+
+    let res = lower_ast_struct_functions(context, functions, t);
+
+    context.type_params = previous;
+
+    return res;
+}
+
+/// Gets the global index of the given function of a struct or enum type
+pub(crate) fn get_struct_function_ind<K: DiagnosticSpanOrigin>(
+    context: &mut HIRContext,
+    t: &Type,
+    func: usize,
+    origin: &K,
+) -> DiagnosticResult<usize> {
+    let raw = t.get_generic();
+    let (self_ref, type_params) = get_function_owner(&raw).expect("Invalid struct type");
+
+    if type_params.is_empty() {
+        // The container contained in the type might be outdated
+        return match context.global_scope.scope.entries[self_ref].as_type_unsafe() {
+            RawType::Struct(_, container) => Ok(container.function_ids[func]),
+            RawType::Enum(container) => Ok(container.function_ids[func]),
+            _ => panic!("Invalid struct type"),
+        };
+    }
+
+    lower_ast_struct_instantiation(context, t, origin)?;
+
+    return Ok(context.generic_instances[&get_type_hash(t)][func]);
+}
+
+fn get_type_hash(t: &Type) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+
+    return hasher.finish();
+}
+
+/// Lowers the given functions for the concrete struct or enum type `t`. Every function is appended before any body is lowered in order to allow them to reference each other
+fn lower_ast_struct_functions(
+    context: &mut HIRContext,
+    functions: &[Box<ASTTreeNode>],
+    t: &Type,
+) -> MaybeDiagnostic {
+    let (self_ref, type_params) = match get_function_owner(&t.get_generic()) {
+        Some((self_ref, type_params)) => (self_ref, type_params.clone()),
+        None => panic!("Invalid struct type"),
+    };
+
+    // Functions implemented after `t` was obtained are missing from its container
+    let t = &match t {
+        Type::Generic(_, params, sizes) => Type::Generic(
+            context.global_scope.scope.entries[self_ref].as_type_unsafe(),
+            params.clone(),
+            sizes.clone(),
+        ),
+        _ => t.clone(),
+    };
+
+    let type_hash = get_type_hash(t);
+    let mut inds = vec![];
+
+    for node in functions {
+        if let ASTTreeNodeKind::FunctionDeclaration {
+            func_name,
+            requires_this,
+            ..
+        } = node.kind.clone()
+        {
+            let signature = t
+                .get_function(&context.global_scope.scope, func_name.hash)?
+                .1;
+
+            let mut arguments = vec![];
+
+            for (ind, arg) in signature.0.into_iter().enumerate() {
+                // `this` always refers to the concrete type
+                if ind == 0 && requires_this {
+                    arguments.push((arg.0, t.clone()));
+                    continue;
+                }
+
+                arguments.push((arg.0, arg.1.resolve(t)));
+            }
+
+            let ret_type = signature.1.map(|ret| ret.resolve(t));
+
+            let fnname = format!("{}$${}", type_hash, func_name.hash);
+
+            // The body is lowered later on, see `HIRGlobalScopeStorage::implement_struct_function`
+            let implementation = Box::new(HIRNode::new(
+                HIRNodeKind::StructFunctionDeclaration {
+                    func_name: context.global_scope.scope.entries.len(),
+                    arguments: vec![],
+                    return_type: None,
+                    body: vec![],
+                    ctx: HIRBranchedContext::new(ret_type.clone()),
+                    requires_this,
+                },
+                &node.start,
+                &node.end,
+            ));
+
+            let ind = context.global_scope.append_struct_function(
+                EntryKey {
+                    name_hash: HashedString::new(fnname.clone()).hash,
+                    name: fnname.clone(),
+                },
+                (ret_type.clone(), arguments, fnname),
+                implementation,
+                HIRBranchedContext::new(ret_type),
+                self_ref,
+                &**node,
+            )?;
+
+            inds.push(ind);
+        }
+    }
+
+    if type_params.is_empty() {
+        match &mut context.global_scope.scope.entries[self_ref].entry_type {
+            GlobalStorageEntryType::Type(RawType::Struct(_, container)) => {
+                container.function_ids.extend(inds.iter())
+            }
+            GlobalStorageEntryType::Type(RawType::Enum(container)) => {
+                container.function_ids.extend(inds.iter())
+            }
+            _ => panic!("Invalid struct type"),
+        }
+    } else {
+        if !context.generic_instances.contains_key(&type_hash) {
+            context
+                .generic_instantiations
+                .entry(self_ref)
+                .or_default()
+                .push(t.clone());
+        }
+
+        context
+            .generic_instances
+            .entry(type_hash)
+            .or_default()
+            .extend(inds.iter());
+    }
+
+    for (node, ind) in functions.iter().zip(inds) {
+        lower_ast_struct_function_body(context, node, ind)?;
+    }
+
+    return Ok(());
+}
 
-        if return_type.is_some() {
-            let lowered = lower_ast_type_struct(context, return_type.unwrap(), container, &*node)?;
+fn lower_ast_struct_function_body(
+    context: &mut HIRContext,
+    node: &ASTTreeNode,
+    ind: usize,
+) -> MaybeDiagnostic {
+    if let ASTTreeNodeKind::FunctionDeclaration {
+        args,
+        body,
+        requires_this,
+        unchecked,
+        fast,
+        ..
+    } = node.kind.clone()
+    {
+        let descriptor_ind = context.global_scope.scope.entries[ind]
+            .as_struct_function_unsafe()
+            .0;
+
+        let (ret_type, arguments, _) = context.global_scope.descriptors[descriptor_ind].clone();
+
+        let mut curr_ctx = HIRBranchedContext::new(ret_type.clone());
+        curr_ctx.unchecked = unchecked;
+        curr_ctx.fast = fast;
+
+        let branch = curr_ctx.start_branch();
+
+        for arg in &arguments {
+            // The implicit `this` argument has no declaration of its own
+            let name = match args.iter().find(|declared| declared.name.hash == arg.0) {
+                Some(declared) => declared.name.val.clone(),
+                None => "this".to_string(),
+            };
 
-            ret_type = Some(lowered)
-        } else {
-            ret_type = None;
+            context.global_scope.enforce_not_here(
+                EntryKey {
+                    name_hash: arg.0,
+                    name: name.clone(),
+                },
+                node,
+            )?;
+
+            match curr_ctx.introduce_variable(arg.0, arg.1.clone(), true) {
+                Ok(_) => {}
+                Err(_) => return Err(make_already_in_scope(node, &name).into()),
+            }
         }
 
-        let mut curr_ctx = HIRBranchedContext::new(None); // TODO: remove type reference
-        let body = lower_ast_body(context, &mut curr_ctx, body, true)?;
+        name_function_arguments(&mut curr_ctx, &args, node);
 
-        let ind = container
-            .functions
-            .append(func_name.hash, (arguments.clone(), ret_type.clone()));
+        let body = lower_ast_body(context, &mut curr_ctx, body, false)?;
+
+        curr_ctx.end_branch(branch);
+
+        if !curr_ctx.meets_ending_point() {
+            return Err(make_ending_point_missing(node).into());
+        }
 
         let implementation = Box::new(HIRNode::new(
             HIRNodeKind::StructFunctionDeclaration {
                 func_name: ind,
-                arguments: arguments.clone(),
-                return_type: ret_type.clone(),
+                arguments: arguments
+                    .into_iter()
+                    .map(|arg| (arg.0, TypeReference::Resolved(arg.1)))
+                    .collect(),
+                return_type: ret_type.map(TypeReference::Resolved),
                 body,
                 ctx: curr_ctx.clone(),
                 requires_this,
@@ -82,37 +439,47 @@ fn lower_ast_struct_function_decl(
             &node.end,
         ));
 
-        let mut hasher = DefaultHasher::new();
-        ty.hash(&mut hasher);
+        context
+            .global_scope
+            .implement_struct_function(ind, implementation, curr_ctx);
 
-        let fnname = format!("{}$${}", hasher.finish(), func_name.hash);
+        return Ok(());
+    }
 
-        let ret_type2;
-        let mut arguments2 = vec![];
+    panic!("Invalid node type")
+}
 
-        if let Some(v) = ret_type {
-            ret_type2 = Some(v.as_resolved()) // TODO: This unsupports generics, maybe fix later
-        } else {
-            ret_type2 = None;
-        }
+/// Ensures that the type at the given global index does not contain itself by value.
+pub(crate) fn check_type_cycle<K: DiagnosticSpanOrigin>(
+    context: &HIRContext,
+    ind: usize,
+    origin: &K,
+) -> MaybeDiagnostic {
+    let cycle = match find_type_cycle(ind, &context.global_scope.scope) {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    let mut path = vec![];
+
+    for (t, field) in cycle {
+        path.push(format!(
+            "{}.{}",
+            get_declared_type_name(context, t),
+            context.global_scope.member_names[&(t, field)]
+        ));
+    }
 
-        for arg in &arguments {
-            arguments2.push((arg.0, arg.1.clone().as_resolved()));
-        }
+    let name = get_declared_type_name(context, ind);
+    path.push(name.clone());
 
-        context.global_scope.append_struct_function(
-            EntryKey {
-                name_hash: HashedString::new(fnname.clone()).hash,
-            },
-            (ret_type2, arguments2, fnname),
-            implementation,
-            curr_ctx,
-            ty,
-            &*node,
-        )?;
-    }
+    return Err(make_infinite_size_type(origin, &name, &path.join(" -> ")).into());
+}
 
-    panic!("Invalid node type")
+fn get_declared_type_name(context: &HIRContext, ind: usize) -> String {
+    return context.global_scope.scope.entries[ind]
+        .as_type_unsafe()
+        .to_string();
 }
 
 pub fn lower_ast_struct_declaration(
@@ -122,57 +489,86 @@ pub fn lower_ast_struct_declaration(
     if let ASTTreeNodeKind::StructLayoutDeclaration {
         name,
         layout,
+        union,
         members,
         type_params,
+        type_bounds,
     } = node.kind.clone()
     {
+        check_type_bounds(context, &type_bounds, &node)?;
+
         let mut container = RawStructTypeContainer {
+            name: name.clone(),
             fields: IndexStorage::new(),
             functions: IndexStorage::new(),
             type_params,
+            type_bounds,
             function_ids: vec![],
             self_ref: context.global_scope.scope.entries.len(),
+            is_union: union,
+            bitfields: HashMap::new(),
         };
 
         let base = RawType::Struct(layout, container.clone());
 
-        let ind = match context.global_scope.append(
+        let ind = context.global_scope.append(
             EntryKey {
                 name_hash: name.hash,
+                name: name.val.clone(),
             },
             GlobalStorageEntryType::Type(base),
             &*node,
-        ) {
-            Ok(v) => v,
-            Err(_) => return Err(make_already_in_scope(&*node, &name.val).into()),
-        };
+        )?;
+
+        register_declaration_attributes(context, ind, &node, &name.val);
+
+        let mut functions = vec![];
 
         for member in members {
             match &member.kind {
-                &ASTTreeNodeKind::StructFieldMember { .. } => {
-                    lower_ast_struct_member(context, member, &mut container)?;
+                ASTTreeNodeKind::StructFieldMember { name, .. } => {
+                    register_member_attributes(context, ind, &member, name.hash, &name.val);
 
-                    context.global_scope.scope.entries[ind].entry_type =
-                        GlobalStorageEntryType::Type(RawType::Struct(layout, container.clone()));
+                    context
+                        .global_scope
+                        .member_names
+                        .insert((ind, name.hash), name.val.clone());
+
+                    lower_ast_struct_member(context, member, &mut container, layout)?;
                 }
-                &ASTTreeNodeKind::FunctionDeclaration { .. } => {
-                    let body = lower_ast_struct_function_decl(
+                ASTTreeNodeKind::FunctionDeclaration { func_name, .. } => {
+                    register_member_attributes(
                         context,
-                        member,
-                        &mut container,
-                        context.global_scope.scope.entries[ind].as_type_unsafe(),
-                    )?;
+                        ind,
+                        &member,
+                        func_name.hash,
+                        &func_name.val,
+                    );
+
+                    let (hash, function) =
+                        lower_ast_struct_function_signature(context, &member, &container)?;
 
-                    container.function_ids.push(body.1);
+                    container.functions.append(hash, function);
 
-                    context.global_scope.scope.entries[ind].entry_type =
-                        GlobalStorageEntryType::Type(RawType::Struct(layout, container.clone()));
+                    functions.push(member);
                 }
 
                 _ => panic!("Invalid node type"),
             };
+
+            context.global_scope.scope.entries[ind].entry_type =
+                GlobalStorageEntryType::Type(RawType::Struct(layout, container.clone()));
         }
 
+        check_type_cycle(context, ind, &*node)?;
+
+        lower_ast_struct_function_bodies(context, &functions, ind)?;
+
+        let container = match context.global_scope.scope.entries[ind].as_type_unsafe() {
+            RawType::Struct(_, container) => container,
+            _ => panic!("Invalid struct type"),
+        };
+
         return Ok(Box::new(HIRNode::new(
             HIRNodeKind::StructDeclaration {
                 type_name: ind,
@@ -0,0 +1,192 @@
+use std::collections::HashSet;
+
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
+use astoir_hir::ctx::HIRContext;
+use compiler_global_scope::{entry::GlobalStorageEntryType, key::EntryKey};
+use compiler_typing::{
+    TypeParameterBounds,
+    bounds::traits::{Trait, TraitBound, TraitBoundMember},
+    raw::RawType,
+    traits::{RawTraitContainer, TraitFunction},
+    tree::Type,
+};
+use compiler_utils::{hash::HashedString, utils::indexed::IndexStorage};
+use diagnostics::{
+    DiagnosticResult, DiagnosticSpanOrigin, MaybeDiagnostic, builders::make_req_type_kind,
+    move_current_diagnostic_pos,
+};
+
+use crate::{
+    structs::{lower_ast_struct_function_bodies, lower_ast_struct_function_signature},
+    types::lower_ast_type,
+};
+
+/// Makes sure that every user declared trait referred to by the given bounds exists. Traits must be declared before the
+/// types bounding their type parameters with them.
+pub fn check_type_bounds(
+    context: &HIRContext,
+    bounds: &TypeParameterBounds,
+    origin: &ASTTreeNode,
+) -> MaybeDiagnostic {
+    for bound in bounds {
+        for member in &bound.members {
+            if let TraitBoundMember::Select(Trait::Custom(name))
+            | TraitBoundMember::Exclude(Trait::Custom(name)) = member
+            {
+                move_current_diagnostic_pos(origin.get_pos());
+
+                TraitBound::get_custom_trait(name, &context.global_scope.scope)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn lower_ast_trait_declaration(
+    context: &mut HIRContext,
+    node: Box<ASTTreeNode>,
+) -> DiagnosticResult<bool> {
+    if let ASTTreeNodeKind::TraitDeclaration { name, functions } = node.kind.clone() {
+        let mut container = RawTraitContainer {
+            name: name.clone(),
+            functions: IndexStorage::new(),
+            self_ref: context.global_scope.scope.entries.len(),
+            implementations: HashSet::new(),
+        };
+
+        for function in functions {
+            if let ASTTreeNodeKind::TraitFunctionDeclaration {
+                func_name,
+                args,
+                return_type,
+                requires_this,
+            } = function.kind.clone()
+            {
+                let mut arguments = vec![];
+
+                for arg in args {
+                    let t = lower_ast_type(context, arg.argument_type, &*function)?;
+
+                    arguments.push((arg.name.hash, t));
+                }
+
+                let ret_type = match return_type {
+                    Some(t) => Some(lower_ast_type(context, t, &*function)?),
+                    None => None,
+                };
+
+                container.functions.append(
+                    func_name.hash,
+                    TraitFunction {
+                        name: func_name.val,
+                        signature: (arguments, ret_type),
+                        requires_this,
+                    },
+                );
+            }
+        }
+
+        context.global_scope.append(
+            EntryKey {
+                name_hash: name.hash,
+                name: name.val.clone(),
+            },
+            GlobalStorageEntryType::Type(RawType::Trait(container)),
+            &*node,
+        )?;
+
+        return Ok(true);
+    }
+
+    panic!("Invalid node type")
+}
+
+pub fn lower_ast_trait_implementation(
+    context: &mut HIRContext,
+    node: Box<ASTTreeNode>,
+) -> DiagnosticResult<bool> {
+    if let ASTTreeNodeKind::TraitImplementation {
+        trait_name,
+        target,
+        functions,
+    } = node.kind.clone()
+    {
+        move_current_diagnostic_pos(node.get_pos());
+
+        let tr = TraitBound::get_custom_trait(&trait_name, &context.global_scope.scope)?;
+
+        let name = target.get_generic_name();
+
+        let ind = context.global_scope.get_ind(
+            EntryKey {
+                name_hash: HashedString::new(name.clone()).hash,
+                name,
+            },
+            &*node,
+        )?;
+
+        match context.global_scope.scope.entries[ind].as_type(&*node)? {
+            RawType::Struct(_, _) | RawType::Enum(_) => {}
+            _ => return Err(make_req_type_kind(&*node, &"struct or enum".to_string()).into()),
+        }
+
+        for function in &functions {
+            append_function_signature(context, function, ind)?;
+        }
+
+        move_current_diagnostic_pos(node.get_pos());
+
+        let t = Type::Generic(
+            context.global_scope.scope.entries[ind].as_type_unsafe(),
+            vec![],
+            vec![],
+        );
+
+        tr.check_implementation(&t, &context.global_scope.scope)?;
+
+        // Recorded before lowering the bodies as these might already rely on the implementation
+        if let GlobalStorageEntryType::Type(RawType::Trait(container)) =
+            &mut context.global_scope.scope.entries[tr.self_ref].entry_type
+        {
+            container.implementations.insert(ind);
+        }
+
+        lower_ast_struct_function_bodies(context, &functions, ind)?;
+
+        return Ok(true);
+    }
+
+    panic!("Invalid node type")
+}
+
+/// Appends the signature of a function implemented within an `impl` block to the struct or enum at the given global index
+fn append_function_signature(
+    context: &mut HIRContext,
+    function: &ASTTreeNode,
+    ind: usize,
+) -> MaybeDiagnostic {
+    let mut raw = context.global_scope.scope.entries[ind].as_type_unsafe();
+
+    match &mut raw {
+        RawType::Struct(_, container) => {
+            let (hash, signature) =
+                lower_ast_struct_function_signature(context, function, container)?;
+
+            container.functions.append(hash, signature);
+        }
+
+        RawType::Enum(container) => {
+            let (hash, signature) =
+                lower_ast_struct_function_signature(context, function, container)?;
+
+            container.functions.append(hash, signature);
+        }
+
+        _ => panic!("Invalid struct type"),
+    }
+
+    context.global_scope.scope.entries[ind].entry_type = GlobalStorageEntryType::Type(raw);
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+//! Lowering of tuples.
+//!
+//! Tuples are anonymous structs whose fields are named after their index. Their elements are thus accessed through the
+//! usual `StructLRU` chains, `.0` reaching the first element.
+
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
+use astoir_hir::{
+    ctx::{HIRBranchedContext, HIRContext},
+    nodes::{HIRNode, HIRNodeKind},
+};
+use compiler_global_scope::key::EntryKey;
+use compiler_typing::tree::Type;
+use compiler_utils::hash::HashedString;
+use diagnostics::{
+    DiagnosticResult, DiagnosticSpanOrigin,
+    builders::{
+        make_already_in_scope, make_req_type_kind, make_struct_missing_field,
+        make_tuple_destructure_count,
+    },
+};
+
+use crate::{closures::track_closure_assign, values::lower_ast_value};
+
+/// Gets the index and type of the tuple element with the given name
+pub fn get_tuple_element<K: DiagnosticSpanOrigin>(
+    t: &Type,
+    name: &HashedString,
+    origin: &K,
+) -> DiagnosticResult<(usize, Type)> {
+    if let Type::Tuple(elements) = t
+        && let Ok(ind) = name.val.parse::<usize>()
+        && ind < elements.len()
+    {
+        return Ok((ind, *elements[ind].clone()));
+    }
+
+    return Err(make_struct_missing_field(origin, t, &name.val).into());
+}
+
+pub fn lower_ast_tuple_literal(
+    context: &mut HIRContext,
+    curr_ctx: &mut HIRBranchedContext,
+    node: Box<ASTTreeNode>,
+) -> DiagnosticResult<Box<HIRNode>> {
+    if let ASTTreeNodeKind::TupleLiteral { values } = node.kind.clone() {
+        let mut hir_values = vec![];
+        let mut elements = vec![];
+
+        for value in values {
+            let hir = lower_ast_value(context, curr_ctx, value)?;
+
+            let t = match hir.get_node_type(context, curr_ctx) {
+                Some(v) => v,
+                None => return Err(make_req_type_kind(&*hir, &"known".to_string()).into()),
+            };
+
+            elements.push(Box::new(t));
+            hir_values.push(hir);
+        }
+
+        return Ok(Box::new(HIRNode::new(
+            HIRNodeKind::TupleInitializer {
+                t: Type::Tuple(elements),
+                values: hir_values,
+            },
+            &node.start,
+            &node.end,
+        )));
+    }
+
+    panic!("Invalid node passed!");
+}
+
+pub fn lower_ast_tuple_destructuring(
+    context: &mut HIRContext,
+    curr_ctx: &mut HIRBranchedContext,
+    node: Box<ASTTreeNode>,
+) -> DiagnosticResult<Box<HIRNode>> {
+    if let ASTTreeNodeKind::TupleDestructuring { names, value } = node.kind.clone() {
+        let value = lower_ast_value(context, curr_ctx, value)?;
+
+        let t = value.get_node_type(context, curr_ctx);
+
+        let elements = match &t {
+            Some(Type::Tuple(elements)) => elements.clone(),
+            _ => return Err(make_req_type_kind(&*node, &"tuple".to_string()).into()),
+        };
+
+        if elements.len() != names.len() {
+            return Err(make_tuple_destructure_count(&*node, &t.unwrap(), &names.len()).into());
+        }
+
+        let mut variables = vec![];
+
+        for (name, element) in names.into_iter().zip(elements) {
+            context.global_scope.enforce_not_here(
+                EntryKey {
+                    name_hash: name.hash,
+                    name: name.val.clone(),
+                },
+                &*node,
+            )?;
+
+            let ind = match curr_ctx.introduce_variable(name.hash, *element.clone(), true) {
+                Ok(v) => v,
+                Err(_) => return Err(make_already_in_scope(&*node, &name.val).into()),
+            };
+
+            curr_ctx.name_variable(ind, name.val.clone(), node.get_pos(), false);
+
+            track_closure_assign(curr_ctx, ind, &value);
+
+            variables.push(Box::new(HIRNode::new(
+                HIRNodeKind::VarDeclaration {
+                    variable: ind,
+                    var_type: *element,
+                    default_val: None,
+                },
+                &node.start,
+                &node.end,
+            )));
+        }
+
+        return Ok(Box::new(HIRNode::new(
+            HIRNodeKind::TupleDestructuring { value, variables },
+            &node.start,
+            &node.end,
+        )));
+    }
+
+    panic!("Invalid node passed!");
+}
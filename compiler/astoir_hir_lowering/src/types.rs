@@ -1,16 +1,26 @@
 use ast::types::ASTType;
 use astoir_hir::ctx::HIRContext;
-use compiler_global_scope::{entry::GlobalStorageEntryType, key::EntryKey};
-use compiler_typing::{TypeParamType, raw::RawType, references::TypeReference, tree::Type};
+use compiler_global_scope::key::EntryKey;
+use compiler_typing::{
+    TypeParamType,
+    bounds::traits::Trait,
+    raw::{MAX_INTEGER_SIZE, RawType},
+    references::TypeReference,
+    tree::Type,
+};
 use compiler_utils::hash::HashedString;
 use diagnostics::{
     DiagnosticResult, DiagnosticSpanOrigin,
     builders::{
-        make_cannot_find_type, make_diff_size_specifiers, make_diff_type_specifiers,
-        make_req_type_kind,
+        make_cpu_emulated_type, make_diff_size_specifiers, make_diff_type_specifiers,
+        make_expected_simple_error, make_req_type_kind, make_unsupported_float_width,
+        make_unsupported_integer_width,
     },
+    move_current_diagnostic_pos,
 };
 
+use crate::{attributes::use_global_entry, structs::lower_ast_struct_instantiation};
+
 pub fn lower_ast_type<K: DiagnosticSpanOrigin>(
     context: &mut HIRContext,
     t: ASTType,
@@ -18,16 +28,28 @@ pub fn lower_ast_type<K: DiagnosticSpanOrigin>(
 ) -> DiagnosticResult<Type> {
     return match t {
         ASTType::Generic(type_id, type_params, size_params, specifier) => {
-            let hash = HashedString::new(type_id).hash;
-
-            let mut t = match context
-                .global_scope
-                .get_type(EntryKey { name_hash: hash }, origin)
-            {
-                Ok(v) => v,
-                Err(_) => return Err(make_cannot_find_type(origin, &hash).into()),
+            let name = HashedString::new(type_id);
+            let hash = name.hash;
+
+            if let Some(param) = context.type_params.get(&hash) {
+                return Ok(param.clone());
+            }
+
+            let key = EntryKey {
+                name_hash: hash,
+                name: name.val,
             };
 
+            let mut t = context.global_scope.get_type(key.clone(), origin)?;
+
+            if let Some(ind) = context.global_scope.scope.entry_to_ind.get(&key).copied() {
+                use_global_entry(context, ind, origin);
+            }
+
+            if let RawType::Trait(_) = t {
+                return Err(make_expected_simple_error(origin, &"type", &"trait").into());
+            }
+
             if specifier.is_some() {
                 let container = match t {
                     RawType::Enum(v) => v,
@@ -52,38 +74,34 @@ pub fn lower_ast_type<K: DiagnosticSpanOrigin>(
                 t_params.push(Box::new(lower_ast_type(context, *type_param, origin)?));
             }
 
+            let bounds = t.get_type_params_bounds(&context.global_scope.scope, origin)?;
+
+            move_current_diagnostic_pos(origin.get_pos());
+
+            for (bound, param) in bounds.iter().zip(t_params.iter()) {
+                // Bounds are checked once the type parameters are substituted
+                if param.has_type_parameters() {
+                    continue;
+                }
+
+                bound.check(param, &context.global_scope.scope)?;
+            }
+
             let res = Type::Generic(t.clone(), t_params, size_params);
 
+            // Sized types are directly lowered into the concrete type of the given width
             if t.is_sized() {
-                let lower = lower_sized_base_type(&res, origin)?;
-
-                if context
-                    .global_scope
-                    .scope
-                    .value_to_ind
-                    .contains_key(&GlobalStorageEntryType::Type(lower.clone()))
-                {
-                    return Ok(Type::Generic(t, vec![], vec![]));
-                } else {
-
-					println!("{}: {:#?}", hash, lower);
-					println!("{:#?}", context.global_scope.get_type(EntryKey { name_hash: hash }, origin));
-
-                    let ind = match context.global_scope.append(
-                        EntryKey { name_hash: hash },
-                        GlobalStorageEntryType::Type(lower),
-                        origin,
-                    ) {
-                        Ok(v) => v,
-                        Err(_) => panic!("Generic lowering type cannot be found on type_to_hash"),
-                    };
-
-                    return Ok(Type::Generic(
-                        context.global_scope.scope.entries[ind].as_type_unsafe(),
-                        vec![],
-                        vec![],
-                    ));
-                }
+                let lower = Type::Generic(lower_sized_base_type(&res, origin)?, vec![], vec![]);
+
+                check_cpu_support(&lower, origin)?;
+
+                return Ok(lower);
+            }
+
+            check_cpu_support(&res, origin)?;
+
+            if !res.has_type_parameters() {
+                lower_ast_struct_instantiation(context, &res, origin)?;
             }
 
             return Ok(res);
@@ -100,9 +118,68 @@ pub fn lower_ast_type<K: DiagnosticSpanOrigin>(
             size,
             Box::new(lower_ast_type(context, *inner, origin)?),
         )),
+        ASTType::Slice(inner) => Ok(Type::Slice(Box::new(lower_ast_type(
+            context, *inner, origin,
+        )?))),
+        ASTType::Function(args, ret) => {
+            let mut lowered_args = vec![];
+
+            for arg in args {
+                lowered_args.push(Box::new(lower_ast_type(context, *arg, origin)?));
+            }
+
+            let ret = match ret {
+                Some(ret) => Some(Box::new(lower_ast_type(context, *ret, origin)?)),
+                None => None,
+            };
+
+            Ok(Type::Function(lowered_args, ret))
+        }
+        ASTType::Closure(args, ret) => {
+            let mut lowered_args = vec![];
+
+            for arg in args {
+                lowered_args.push(Box::new(lower_ast_type(context, *arg, origin)?));
+            }
+
+            let ret = match ret {
+                Some(ret) => Some(Box::new(lower_ast_type(context, *ret, origin)?)),
+                None => None,
+            };
+
+            Ok(Type::Closure(lowered_args, ret))
+        }
+        ASTType::Tuple(elements) => {
+            let mut lowered_elements = vec![];
+
+            for element in elements {
+                lowered_elements.push(Box::new(lower_ast_type(context, *element, origin)?));
+            }
+
+            Ok(Type::Tuple(lowered_elements))
+        }
     };
 }
 
+/// Warns about floats emulated in software and rejects the widths that can neither be emulated nor natively used
+fn check_cpu_support<K: DiagnosticSpanOrigin>(t: &Type, origin: &K) -> DiagnosticResult<()> {
+    let raw = t.get_generic();
+
+    if let RawType::Floating(size, _) = raw {
+        if raw.has_trait(Trait::CpuSupported, t) {
+            return Ok(());
+        }
+
+        if size != 8 {
+            return Err(make_unsupported_float_width(origin, size).into());
+        }
+
+        make_cpu_emulated_type(origin, &raw);
+    }
+
+    return Ok(());
+}
+
 pub fn lower_ast_type_struct<K: DiagnosticSpanOrigin, T: TypeParamType>(
     context: &mut HIRContext,
     t: ASTType,
@@ -119,7 +196,21 @@ pub fn lower_ast_type_struct<K: DiagnosticSpanOrigin, T: TypeParamType>(
         }
     }
 
-    return Ok(TypeReference::Resolved(lower_ast_type(context, t, origin)?));
+    // Type parameters contained deeper within the type are kept as `RawType::TypeParameter` until resolved
+    let previous = context.type_params.clone();
+
+    for (param, ind) in container.get_type_params() {
+        context.type_params.insert(
+            param.hash,
+            Type::Generic(RawType::TypeParameter(*ind), vec![], vec![]),
+        );
+    }
+
+    let res = lower_ast_type(context, t, origin);
+
+    context.type_params = previous;
+
+    return Ok(TypeReference::Resolved(res?));
 }
 
 pub fn lower_sized_base_type<K: DiagnosticSpanOrigin>(
@@ -134,6 +225,10 @@ pub fn lower_sized_base_type<K: DiagnosticSpanOrigin>(
                 return Err(make_diff_size_specifiers(origin, &1, &data.1.len()).into());
             }
 
+            if data.1[0] == 0 || data.1[0] > MAX_INTEGER_SIZE {
+                return Err(make_unsupported_integer_width(origin, data.1[0]).into());
+            }
+
             return Ok(RawType::Integer(data.1[0], e));
         }
 
@@ -0,0 +1,46 @@
+use ast::tree::{ASTTreeNode, ASTTreeNodeKind};
+use astoir_hir::{
+    ctx::{HIRBranchedContext, HIRContext},
+    nodes::{HIRNode, HIRNodeKind},
+};
+use diagnostics::DiagnosticResult;
+
+use crate::{lower_ast_body_node, values::lower_ast_value};
+
+/// Lowers a value opting-out of runtime safety checks such as `unchecked(arr[i])`
+pub fn lower_ast_unchecked_value(
+    context: &mut HIRContext,
+    curr_ctx: &mut HIRBranchedContext,
+    node: Box<ASTTreeNode>,
+) -> DiagnosticResult<Box<HIRNode>> {
+    if let ASTTreeNodeKind::UncheckedValue { val } = node.kind.clone() {
+        let val = lower_ast_value(context, curr_ctx, val)?;
+
+        return Ok(Box::new(HIRNode::new(
+            HIRNodeKind::UncheckedValue { val },
+            &node.start,
+            &node.end,
+        )));
+    }
+
+    panic!("Invalid node passed!");
+}
+
+/// Lowers a statement opting-out of runtime safety checks such as `unchecked(arr[i] = 5)`
+pub fn lower_ast_unchecked_statement(
+    context: &mut HIRContext,
+    curr_ctx: &mut HIRBranchedContext,
+    node: Box<ASTTreeNode>,
+) -> DiagnosticResult<Box<HIRNode>> {
+    if let ASTTreeNodeKind::UncheckedValue { val } = node.kind.clone() {
+        let val = lower_ast_body_node(context, curr_ctx, val)?;
+
+        return Ok(Box::new(HIRNode::new(
+            HIRNodeKind::UncheckedValue { val },
+            &node.start,
+            &node.end,
+        )));
+    }
+
+    panic!("Invalid node passed!");
+}
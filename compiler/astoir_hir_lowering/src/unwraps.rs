@@ -3,7 +3,7 @@ use astoir_hir::{
     ctx::{HIRBranchedContext, HIRContext},
     nodes::{HIRNode, HIRNodeKind},
 };
-use diagnostics::DiagnosticResult;
+use diagnostics::{DiagnosticResult, DiagnosticSpanOrigin, builders::make_already_in_scope};
 
 use crate::{types::lower_ast_type, values::lower_ast_value, var::lower_ast_variable_reference};
 
@@ -22,6 +22,15 @@ pub fn lower_ast_condition_unwrap(
         let original = lower_ast_variable_reference(context, curr_ctx, original, true)?;
         let target_type = lower_ast_type(context, target_type, &*node)?;
 
+        // The hint or niche of the enum is read through the address of the variable
+        if let HIRNodeKind::VariableReference {
+            index,
+            is_static: false,
+        } = original.kind
+        {
+            curr_ctx.introduce_variable_refer(index);
+        }
+
         if target_var.is_none() {
             return Ok(Box::new(HIRNode::new(
                 HIRNodeKind::UnwrapCondition {
@@ -35,11 +44,18 @@ pub fn lower_ast_condition_unwrap(
             )));
         }
 
-        let ind = curr_ctx.introduce_variable_next_era(
-            target_var.unwrap().hash,
+        let target_var = target_var.unwrap();
+
+        let ind = match curr_ctx.introduce_variable_next_era(
+            target_var.hash,
             target_type.clone(),
             true,
-        )?;
+        ) {
+            Ok(v) => v,
+            Err(_) => return Err(make_already_in_scope(&*node, &target_var.val).into()),
+        };
+
+        curr_ctx.name_variable(ind, target_var.val, node.get_pos(), false);
 
         return Ok(Box::new(HIRNode::new(
             HIRNodeKind::UnwrapCondition {
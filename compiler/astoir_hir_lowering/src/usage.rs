@@ -0,0 +1,173 @@
+//! Usage analysis of the lowered code.
+//!
+//! Ran once the whole file is lowered, reports the variables, arguments, functions, types and `use` clauses that are
+//! never used. Names starting with an underscore are never reported, marking them as intentionally unused.
+//!
+//! Every module is lowered on its own, so the functions and types of library modules are only used by the other modules
+//! of the program. Unused declarations are thus only reported within the file declaring the entry function.
+
+use std::collections::HashSet;
+
+use ast::{ctx::ParserCtx, tree::ASTTreeNodeKind};
+use astoir_hir::ctx::HIRContext;
+use compiler_global_scope::key::EntryKey;
+use compiler_utils::hash::HashedString;
+use diagnostics::{
+    builders::{
+        make_unused_argument, make_unused_function, make_unused_local, make_unused_type,
+        make_unused_use,
+    },
+    diagnostic::SpanPosition,
+    move_current_diagnostic_pos,
+};
+
+/// The name of the function the program starts from
+const ENTRY_FUNCTION: &str = "main";
+
+/// The name of the argument holding the instance within struct functions
+const THIS_ARGUMENT: &str = "this";
+
+/// Whether the given name can be reported as unused
+fn is_reported(name: &str) -> bool {
+    return !name.starts_with('_');
+}
+
+/// Finds the position of the given name within the first line of the declaration at the given position
+fn find_name_pos(pos: &SpanPosition, name: &str) -> Option<SpanPosition> {
+    let line = pos.get_line_content().ok()?;
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+
+    let mut start = pos.col;
+
+    while let Some(found) = line.get(start..)?.find(name) {
+        let col = start + found;
+        let end_col = col + name.len();
+
+        let before = line[..col].chars().next_back();
+        let after = line[end_col..].chars().next();
+
+        if !before.is_some_and(is_ident) && !after.is_some_and(is_ident) {
+            return Some(SpanPosition {
+                line: pos.line,
+                col,
+                file_path: pos.file_path.clone(),
+                end_col,
+            });
+        }
+
+        start = end_col;
+    }
+
+    return None;
+}
+
+/// Whether the global entry with the given name is referenced by the lowered code. Unknown entries count as used
+fn is_entry_used(context: &HIRContext, name: &HashedString) -> bool {
+    return match context.global_scope.scope.entry_to_ind.get(&EntryKey {
+        name_hash: name.hash,
+        name: name.val.clone(),
+    }) {
+        Some(ind) => context.used_entries.contains(ind),
+        None => true,
+    };
+}
+
+/// Reports the unused variables and arguments of every lowered function
+pub fn check_unused_variables(context: &HIRContext) {
+    // Functions of types with type parameters are lowered once per instantiation
+    let mut reported = HashSet::new();
+
+    for ctx in &context.global_scope.contexts {
+        let mut indexes: Vec<&usize> = ctx.variable_origins.keys().collect();
+        indexes.sort();
+
+        for ind in indexes {
+            let origin = &ctx.variable_origins[ind];
+
+            if ctx.variables[*ind].usage_count > 0
+                || !is_reported(&origin.name)
+                || origin.name == THIS_ARGUMENT
+            {
+                continue;
+            }
+
+            let key = (
+                origin.pos.file_path.clone(),
+                origin.pos.line,
+                origin.pos.col,
+                origin.name.clone(),
+            );
+
+            if !reported.insert(key) {
+                continue;
+            }
+
+            move_current_diagnostic_pos(origin.pos.clone());
+
+            let name_pos = find_name_pos(&origin.pos, &origin.name);
+
+            if origin.argument {
+                let _ = make_unused_argument(&origin.name, name_pos);
+            } else {
+                let _ = make_unused_local(&origin.name, name_pos);
+            }
+        }
+    }
+}
+
+/// Whether the file declares the function the program starts from
+fn is_entry_file(ctx: &ParserCtx) -> bool {
+    ctx.iter_order.iter().any(|name| match &ctx.map[name].kind {
+        ASTTreeNodeKind::FunctionDeclaration { func_name, .. } => func_name.val == ENTRY_FUNCTION,
+        _ => false,
+    })
+}
+
+/// Reports the unused top level functions and types of the entry file as well as the unused `use` clauses of the file
+pub fn check_unused_declarations(context: &HIRContext, ctx: &ParserCtx) {
+    let declarations: &[String] = if is_entry_file(ctx) {
+        &ctx.iter_order
+    } else {
+        &[]
+    };
+
+    for name in declarations {
+        let node = &ctx.map[name];
+
+        match &node.kind {
+            ASTTreeNodeKind::FunctionDeclaration { func_name, .. } => {
+                if func_name.val == ENTRY_FUNCTION
+                    || !is_reported(&func_name.val)
+                    || is_entry_used(context, func_name)
+                {
+                    continue;
+                }
+
+                make_unused_function(&**node, &func_name.val);
+            }
+
+            ASTTreeNodeKind::StructLayoutDeclaration { name, .. }
+            | ASTTreeNodeKind::EnumDeclaration { name, .. } => {
+                if !is_reported(&name.val) || is_entry_used(context, name) {
+                    continue;
+                }
+
+                make_unused_type(&**node, &name.val);
+            }
+
+            _ => {}
+        }
+    }
+
+    for node in &ctx.uses {
+        if let ASTTreeNodeKind::UseStatement { use_clauses, .. } = &node.kind {
+            for clause in use_clauses {
+                if is_entry_used(context, clause) {
+                    continue;
+                }
+
+                make_unused_use(&**node, &clause.val);
+            }
+        }
+    }
+}
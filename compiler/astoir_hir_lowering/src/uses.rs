@@ -12,15 +12,14 @@ use compiler_typing::{raw::RawType, tree::Type};
 use compiler_utils::hash::HashedString;
 use diagnostics::{
     DiagnosticResult, DiagnosticSpanOrigin, MaybeDiagnostic,
-    builders::{
-        make_cannot_find_type, make_diff_type_specifiers, make_req_type_kind, make_use_not_found,
-    },
+    builders::{make_diff_type_specifiers, make_req_type_kind, make_use_not_found},
+    move_current_diagnostic_pos,
 };
 use lexer::lexer::lexer_parse_file;
 
 use crate::{
     lower_ast_toplevel,
-    structs::lower_ast_struct_declaration,
+    structs::{lower_ast_struct_declaration, lower_ast_struct_instantiation},
     types::{lower_ast_type, lower_sized_base_type},
 };
 
@@ -35,6 +34,8 @@ pub fn handle_ast_use_statement_function_decl(
         body: _,
         return_type,
         requires_this: _,
+        unchecked: _,
+        fast: _,
     } = node.kind.clone()
     {
         let ret_type;
@@ -60,6 +61,7 @@ pub fn handle_ast_use_statement_function_decl(
         let _ = context.global_scope.append_implless_function(
             EntryKey {
                 name_hash: func_name.hash,
+                name: func_name.val.clone(),
             },
             (ret_type.clone(), arguments.clone(), func_name.val.clone()),
             &*node,
@@ -122,25 +124,22 @@ pub fn gather_type_use<K: DiagnosticSpanOrigin>(
     pass: bool,
     ctx: &ParserCtx,
 ) -> DiagnosticResult<RawType> {
-    match context.global_scope.get_type(
-        EntryKey {
-            name_hash: val.hash,
-        },
-        origin,
-    ) {
-        Ok(v) => return Ok(v),
-        Err(_) => {
-            if pass {
-                if ctx.map.contains_key(&val.val) {
-                    lower_ast_struct_declaration(context, ctx.map[&val.val].clone())?;
-
-                    return gather_type_use(context, val, origin, false, ctx);
-                }
-            }
+    let key = EntryKey {
+        name_hash: val.hash,
+        name: val.val.clone(),
+    };
 
-            return Err(make_cannot_find_type(origin, &val.val).into());
-        }
+    // Types declared later in the file are lowered on demand
+    if pass
+        && !context.global_scope.scope.entry_to_ind.contains_key(&key)
+        && ctx.map.contains_key(&val.val)
+    {
+        lower_ast_struct_declaration(context, ctx.map[&val.val].clone())?;
+
+        return gather_type_use(context, val, origin, false, ctx);
     }
+
+    context.global_scope.get_type(key, origin)
 }
 
 pub fn lower_ast_type_use_statement<K: DiagnosticSpanOrigin>(
@@ -180,6 +179,19 @@ pub fn lower_ast_type_use_statement<K: DiagnosticSpanOrigin>(
                 t_params.push(Box::new(lower_ast_type(context, *type_param, origin)?));
             }
 
+            let bounds = t.get_type_params_bounds(&context.global_scope.scope, origin)?;
+
+            move_current_diagnostic_pos(origin.get_pos());
+
+            for (bound, param) in bounds.iter().zip(t_params.iter()) {
+                // Bounds are checked once the type parameters are substituted
+                if param.has_type_parameters() {
+                    continue;
+                }
+
+                bound.check(param, &context.global_scope.scope)?;
+            }
+
             let res = Type::Generic(t.clone(), t_params, size_params);
 
             if t.is_sized() {
@@ -194,7 +206,10 @@ pub fn lower_ast_type_use_statement<K: DiagnosticSpanOrigin>(
                     return Ok(Type::Generic(t, vec![], vec![]));
                 } else {
                     let ind = match context.global_scope.append(
-                        EntryKey { name_hash: hash },
+                        EntryKey {
+                            name_hash: hash,
+                            name: type_id,
+                        },
                         GlobalStorageEntryType::Type(lower),
                         origin,
                     ) {
@@ -212,6 +227,10 @@ pub fn lower_ast_type_use_statement<K: DiagnosticSpanOrigin>(
                 }
             }
 
+            if !res.has_type_parameters() {
+                lower_ast_struct_instantiation(context, &res, origin)?;
+            }
+
             return Ok(res);
         }
 
@@ -226,5 +245,9 @@ pub fn lower_ast_type_use_statement<K: DiagnosticSpanOrigin>(
             size,
             Box::new(lower_ast_type(context, *inner, origin)?),
         )),
+        ASTType::Function(_, _)
+        | ASTType::Closure(_, _)
+        | ASTType::Tuple(_)
+        | ASTType::Slice(_) => lower_ast_type(context, t, origin),
     };
 }
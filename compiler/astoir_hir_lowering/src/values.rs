@@ -8,22 +8,30 @@ use astoir_hir::{
     structs::{HIRRange, StructLRUStep},
 };
 use compiler_global_scope::key::EntryKey;
-use compiler_typing::tree::Type;
+use compiler_typing::{raw::RawType, tree::Type};
+use compiler_utils::hash::HashedString;
 use diagnostics::{
     DiagnosticResult, DiagnosticSpanOrigin,
     builders::{
-        make_expected_simple_error, make_invalid_pointing, make_struct_missing_field,
-        make_struct_missing_func,
+        make_expected_simple_error, make_invalid_pointing, make_struct_missing_func,
+        make_unsafe_union_access,
     },
 };
 
 use crate::{
     arrays::lower_ast_array_index_access,
+    attributes::{use_global_entry, use_type_member},
     bools::{lower_ast_boolean_condition, lower_ast_operator_condition},
-    func::lower_ast_function_call,
+    casts::lower_ast_cast_value,
+    closures::{check_closure_escape, lower_ast_closure},
+    func::{get_function_by_name, lower_ast_function_call},
+    intrinsics::lower_ast_layout_intrinsic,
     literals::lower_ast_literal,
     math::lower_ast_math_operation,
-    structs::lower_ast_struct_initializer,
+    slices::{get_slice_element, lower_ast_sub_slice},
+    structs::{get_struct_function_ind, lower_ast_struct_initializer},
+    tuples::{get_tuple_element, lower_ast_tuple_literal},
+    unchecked::lower_ast_unchecked_value,
     unwraps::{lower_ast_condition_unwrap, lower_ast_unwrap_value},
     var::lower_ast_variable_reference,
 };
@@ -34,6 +42,7 @@ pub(crate) fn lower_ast_lru_base(
     node: Box<ASTTreeNode>,
     curr_steps: &mut Vec<StructLRUStep>,
     curr_type: &mut Option<Type>,
+    unsafe_access: bool,
 ) -> DiagnosticResult<bool> {
     match node.clone().kind {
         ASTTreeNodeKind::FunctionCall { func, args } => {
@@ -41,9 +50,10 @@ pub(crate) fn lower_ast_lru_base(
             let ind: usize;
 
             if let Some(curr_type_val) = curr_type {
-                let res = match curr_type_val.get_function(&context.global_scope.scope, func.hash) {
-                    Ok(v) => v,
-                    Err(_) => {
+                let res = match curr_type_val.find_function(&context.global_scope.scope, func.hash)
+                {
+                    Some(v) => v,
+                    None => {
                         return Err(
                             make_struct_missing_func(&*node, curr_type_val, &func.val).into()
                         );
@@ -68,19 +78,49 @@ pub(crate) fn lower_ast_lru_base(
 
                 func_type = (resolved_ret, resolved_args, func.val.clone());
 
-                ind = res.0;
+                // Struct and enum functions are referenced by their global scope entry
+                ind = match curr_type_val.get_generic() {
+                    RawType::EnumEntry(container) => {
+                        let (params, sizes) = curr_type_val.get_generic_info();
+                        let parent = Type::Generic(
+                            context.global_scope.scope.entries[container.parent].as_type_unsafe(),
+                            params,
+                            sizes,
+                        );
+
+                        get_struct_function_ind(context, &parent, res.0, &*node)?
+                    }
+                    _ => get_struct_function_ind(context, curr_type_val, res.0, &*node)?,
+                };
             } else {
                 let entry = EntryKey {
                     name_hash: func.hash,
+                    name: func.val.clone(),
                 };
 
                 ind = context.global_scope.get_ind(entry.clone(), &*node)?;
                 func_type = context.global_scope.get_function_base(entry, &*node)?;
             }
 
+            use_global_entry(context, ind, &*node);
+
+            if let Some(curr_type_val) = curr_type {
+                use_type_member(context, curr_type_val, func.hash, &*node);
+            }
+
             let mut hir_args = vec![];
             let mut iind = 0;
 
+            // The `this` argument is given by the current value of the chain
+            if curr_type.is_some()
+                && func_type
+                    .1
+                    .first()
+                    .is_some_and(|arg| arg.0 == HashedString::new("this".to_string()).hash)
+            {
+                iind += 1;
+            }
+
             for a in args {
                 let lowered = lower_ast_value(context, curr_ctx, a)?;
 
@@ -111,20 +151,40 @@ pub(crate) fn lower_ast_lru_base(
             let var_type;
             let ind: usize;
 
+            if let Some(Type::Tuple(_)) = curr_type {
+                let (ind, element) = get_tuple_element(curr_type.as_ref().unwrap(), &str, &*node)?;
+
+                curr_steps.push(StructLRUStep::VariableStep { variable: ind });
+                *curr_type = Some(element);
+
+                return Ok(true);
+            }
+
+            if let Some(Type::Slice(_)) = curr_type {
+                let (ind, element) = get_slice_element(curr_type.as_ref().unwrap(), &str, &*node)?;
+
+                curr_steps.push(StructLRUStep::VariableStep { variable: ind });
+                *curr_type = Some(element);
+
+                return Ok(true);
+            }
+
             if let Some(curr_type_val) = curr_type {
-                let res = match curr_type_val.get_field(&context.global_scope.scope, str.hash) {
-                    Ok(v) => v,
-                    Err(_) => {
-                        return Err(
-                            make_struct_missing_field(&*node, curr_type_val, &str.val).into()
-                        );
-                    }
-                };
+                if let Type::Generic(RawType::Struct(_, container), _, _) = curr_type_val
+                    && container.is_union
+                    && !unsafe_access
+                {
+                    return Err(make_unsafe_union_access(&*node, &str.val).into());
+                }
+
+                let res = curr_type_val.get_named_field(&context.global_scope.scope, &str)?;
 
                 ind = res.0;
                 var_type = res.1.resolve(curr_type_val);
+
+                use_type_member(context, curr_type_val, str.hash, &*node);
             } else {
-                let r = get_variable(context, curr_ctx, str.hash, &*node)?;
+                let r = get_variable(context, curr_ctx, &str, &*node)?;
 
                 ind = r.2;
                 var_type = r.1;
@@ -137,15 +197,15 @@ pub(crate) fn lower_ast_lru_base(
         }
 
         ASTTreeNodeKind::StructLRFunction { l, r } => {
-            lower_ast_lru_base(context, curr_ctx, l, curr_steps, curr_type)?;
-            lower_ast_lru_base(context, curr_ctx, r, curr_steps, curr_type)?;
+            lower_ast_lru_base(context, curr_ctx, l, curr_steps, curr_type, unsafe_access)?;
+            lower_ast_lru_base(context, curr_ctx, r, curr_steps, curr_type, unsafe_access)?;
 
             return Ok(true);
         }
 
         ASTTreeNodeKind::StructLRVariable { l, r } => {
-            lower_ast_lru_base(context, curr_ctx, l, curr_steps, curr_type)?;
-            lower_ast_lru_base(context, curr_ctx, r, curr_steps, curr_type)?;
+            lower_ast_lru_base(context, curr_ctx, l, curr_steps, curr_type, unsafe_access)?;
+            lower_ast_lru_base(context, curr_ctx, r, curr_steps, curr_type, unsafe_access)?;
 
             return Ok(true);
         }
@@ -158,16 +218,33 @@ pub fn lower_ast_lru(
     context: &mut HIRContext,
     curr_ctx: &mut HIRBranchedContext,
     node: Box<ASTTreeNode>,
+) -> DiagnosticResult<Box<HIRNode>> {
+    return lower_ast_lru_full(context, curr_ctx, node, false);
+}
+
+/// Lowers a chain of accesses. `unsafe_access` acknowledges the accesses to union fields within the chain
+pub fn lower_ast_lru_full(
+    context: &mut HIRContext,
+    curr_ctx: &mut HIRBranchedContext,
+    node: Box<ASTTreeNode>,
+    unsafe_access: bool,
 ) -> DiagnosticResult<Box<HIRNode>> {
     let mut steps: Vec<StructLRUStep> = vec![];
     let mut curr_type: Option<Type> = None;
 
-    lower_ast_lru_base(context, curr_ctx, node.clone(), &mut steps, &mut curr_type)?;
+    lower_ast_lru_base(
+        context,
+        curr_ctx,
+        node.clone(),
+        &mut steps,
+        &mut curr_type,
+        unsafe_access,
+    )?;
 
     return Ok(Box::new(HIRNode::new(
         HIRNodeKind::StructLRU {
             steps,
-            last: curr_type.unwrap(),
+            last: curr_type,
         },
         &node.start,
         &node.end,
@@ -184,6 +261,10 @@ pub fn lower_ast_value(
             return lower_ast_lru(context, curr_ctx, node);
         }
 
+        ASTTreeNodeKind::UnsafeFieldAccess { access } => {
+            return lower_ast_lru_full(context, curr_ctx, access, true);
+        }
+
         ASTTreeNodeKind::MathResult { .. } => {
             return lower_ast_math_operation(context, curr_ctx, node, false);
         }
@@ -200,6 +281,8 @@ pub fn lower_ast_value(
             return lower_ast_array_index_access(context, curr_ctx, node);
         }
 
+        ASTTreeNodeKind::SubSlice { .. } => lower_ast_sub_slice(context, curr_ctx, node),
+
         ASTTreeNodeKind::ArrayVariableInitializerValue { .. }
         | ASTTreeNodeKind::ArrayVariableInitializerValueSameValue { .. } => {
             return lower_ast_array_init(context, curr_ctx, node);
@@ -209,10 +292,20 @@ pub fn lower_ast_value(
             return lower_ast_struct_initializer(context, curr_ctx, node);
         }
 
+        ASTTreeNodeKind::TupleLiteral { .. } => lower_ast_tuple_literal(context, curr_ctx, node),
+
         ASTTreeNodeKind::IntegerLit { .. } | ASTTreeNodeKind::StringLit(_) => {
             return lower_ast_literal(context, node);
         }
 
+        ASTTreeNodeKind::CastValue { .. } => lower_ast_cast_value(context, curr_ctx, node),
+
+        ASTTreeNodeKind::UncheckedValue { .. } => {
+            return lower_ast_unchecked_value(context, curr_ctx, node);
+        }
+
+        ASTTreeNodeKind::LayoutIntrinsic { .. } => lower_ast_layout_intrinsic(context, node),
+
         ASTTreeNodeKind::FunctionCall { .. } => {
             return lower_ast_function_call(context, curr_ctx, node);
         }
@@ -232,6 +325,8 @@ pub fn lower_ast_value(
             return lower_ast_unwrap_value(context, curr_ctx, node);
         }
 
+        ASTTreeNodeKind::ClosureExpression { .. } => lower_ast_closure(context, curr_ctx, node),
+
         _ => panic!("Invalid AST value node"),
     }
 }
@@ -356,6 +451,8 @@ pub fn lower_ast_pointer_modify(
             None,
         )?;
 
+        check_closure_escape(curr_ctx, &val, &*node)?;
+
         return Ok(Box::new(HIRNode::new(
             HIRNodeKind::DereferenceModify {
                 pointer: ptr,
@@ -377,6 +474,18 @@ pub fn lower_ast_reference(
     node: Box<ASTTreeNode>,
 ) -> DiagnosticResult<Box<HIRNode>> {
     if let ASTTreeNodeKind::ReferenceGrab(val) = node.kind.clone() {
+        if let ASTTreeNodeKind::VariableReference(name) = &val.kind
+            && let Some(index) = get_function_by_name(context, curr_ctx, name)
+        {
+            use_global_entry(context, index, &*node);
+
+            return Ok(Box::new(HIRNode::new(
+                HIRNodeKind::FunctionReference { index },
+                &node.start,
+                &node.end,
+            )));
+        }
+
         let val = lower_ast_value(context, curr_ctx, val)?;
 
         if !val.is_variable_representative() {
@@ -6,11 +6,19 @@ use astoir_hir::{
 use compiler_global_scope::key::EntryKey;
 use compiler_typing::tree::Type;
 use diagnostics::{
-    DiagnosticResult,
-    builders::{make_expected_simple_error, make_variable_uninit},
+    DiagnosticResult, DiagnosticSpanOrigin,
+    builders::{
+        make_already_in_scope, make_expected_simple_error, make_slice_length_assign,
+        make_variable_uninit,
+    },
 };
 
-use crate::{arrays::lower_ast_array_index_access, types::lower_ast_type, values::lower_ast_value};
+use crate::{
+    arrays::lower_ast_array_index_access,
+    closures::{check_closure_escape, track_closure_assign},
+    types::lower_ast_type,
+    values::lower_ast_value,
+};
 
 pub fn lower_ast_variable_declaration(
     context: &mut HIRContext,
@@ -28,6 +36,7 @@ pub fn lower_ast_variable_declaration(
         context.global_scope.enforce_not_here(
             EntryKey {
                 name_hash: var_name.hash,
+                name: var_name.val.clone(),
             },
             &*node,
         )?;
@@ -40,11 +49,16 @@ pub fn lower_ast_variable_declaration(
             }
         }
 
-        let name_ind = curr_ctx.introduce_variable(
+        let name_ind = match curr_ctx.introduce_variable(
             var_name.hash,
             lowered.clone(),
             value.is_some() || force_default,
-        )?;
+        ) {
+            Ok(v) => v,
+            Err(_) => return Err(make_already_in_scope(&*node, &var_name.val).into()),
+        };
+
+        curr_ctx.name_variable(name_ind, var_name.val.clone(), node.get_pos(), false);
 
         let default_val;
 
@@ -57,6 +71,8 @@ pub fn lower_ast_variable_declaration(
                 None,
             )?);
 
+            track_closure_assign(curr_ctx, name_ind, &hir_val);
+
             default_val = Some(hir_val);
         } else {
             default_val = None;
@@ -83,7 +99,7 @@ pub fn lower_ast_variable_reference(
     requires_value: bool,
 ) -> DiagnosticResult<Box<HIRNode>> {
     if let ASTTreeNodeKind::VariableReference(str) = node.kind.clone() {
-        let var = get_variable(context, curr_ctx, str.hash, &*node)?;
+        let var = get_variable(context, curr_ctx, &str, &*node)?;
 
         if var.0 == VariableKind::STATIC {
             return Ok(Box::new(HIRNode::new(
@@ -125,6 +141,12 @@ pub fn lower_ast_variable_assign(
     node: Box<ASTTreeNode>,
 ) -> DiagnosticResult<Box<HIRNode>> {
     if let ASTTreeNodeKind::VarValueChange { var, value } = node.kind.clone() {
+        if let ASTTreeNodeKind::StructLRVariable { .. }
+        | ASTTreeNodeKind::UnsafeFieldAccess { .. } = &var.kind
+        {
+            return lower_ast_field_assign(context, curr_ctx, node.clone(), var, value);
+        }
+
         let value = lower_ast_value(context, curr_ctx, value)?;
 
         let variable_reference =
@@ -147,6 +169,10 @@ pub fn lower_ast_variable_assign(
 
         if !var.1 {
             curr_ctx.introduce_variable_assign(var.0);
+
+            track_closure_assign(curr_ctx, var.0, &value);
+        } else {
+            check_closure_escape(curr_ctx, &value, &*node)?;
         }
 
         return Ok(Box::new(HIRNode::new(
@@ -161,3 +187,62 @@ pub fn lower_ast_variable_assign(
 
     panic!("Invalid node passed!");
 }
+
+/// Lowers an assignment to a field reached by an access chain such as `a.b.c = val`
+fn lower_ast_field_assign(
+    context: &mut HIRContext,
+    curr_ctx: &mut HIRBranchedContext,
+    node: Box<ASTTreeNode>,
+    var: Box<ASTTreeNode>,
+    value: Box<ASTTreeNode>,
+) -> DiagnosticResult<Box<HIRNode>> {
+    // The element count of a slice guards its accesses and thus cannot be changed
+    if let ASTTreeNodeKind::StructLRVariable { l, r } = &var.kind
+        && let ASTTreeNodeKind::VariableReference(name) = &r.kind
+        && name.val == "len"
+        && lower_ast_value(context, curr_ctx, l.clone())?
+            .get_node_type(context, curr_ctx)
+            .is_some_and(|t| t.is_slice())
+    {
+        return Err(make_slice_length_assign(&*var).into());
+    }
+
+    let value = lower_ast_value(context, curr_ctx, value)?;
+    let lru = lower_ast_value(context, curr_ctx, var.clone())?;
+
+    // The field is written through the address of the variable at the root of the chain
+    let mut root = &var;
+
+    loop {
+        match &root.kind {
+            ASTTreeNodeKind::UnsafeFieldAccess { access } => root = access,
+            ASTTreeNodeKind::StructLRVariable { l, .. } => root = l,
+            _ => break,
+        }
+    }
+
+    if let ASTTreeNodeKind::VariableReference(str) = &root.kind {
+        let (kind, _, ind) = get_variable(context, curr_ctx, str, &**root)?;
+
+        if let VariableKind::LOCAL = kind {
+            curr_ctx.introduce_variable_refer(ind);
+        }
+    }
+
+    let field_type = match &lru.kind {
+        HIRNodeKind::StructLRU {
+            last: Some(last), ..
+        } => last.clone(),
+        _ => return Err(make_expected_simple_error(&*var, &"field", &*var).into()),
+    };
+
+    let value = Box::new(value.use_as(context, curr_ctx, field_type, &*var, Some(&*node))?);
+
+    check_closure_escape(curr_ctx, &value, &*node)?;
+
+    return Ok(Box::new(HIRNode::new(
+        HIRNodeKind::StructLRUModify { lru, val: value },
+        &node.start,
+        &node.end,
+    )));
+}
@@ -0,0 +1,84 @@
+//! Trait bounds of type parameters, selecting and excluding types.
+
+mod common;
+
+use common::{lower, lower_errors};
+use diagnostics::errors::{TRAIT_MISSING, UNKNOWN_TRAIT};
+
+fn make_bounded(bound: &str, t: &str, value: &str) -> String {
+    format!(
+        "struct holder<T: {}> {{\n\tT v\n}}\n\nfunc main() -> s32 {{\n\tvar holder<{}> h = {{ v: {} }};\n\tret 0;\n}}\n",
+        bound, t, value
+    )
+}
+
+#[test]
+fn accepts_types_with_selected_trait() {
+    for (bound, t, value) in [
+        ("!numeric", "s32", "1"),
+        ("!numeric", "u8", "1"),
+        ("!integer", "u64", "1"),
+        ("!signed", "s16", "1"),
+        ("!floating", "f64", "1"),
+    ] {
+        let source = make_bounded(bound, t, value);
+
+        assert!(lower(&source).is_ok(), "{} failed to lower", source);
+    }
+}
+
+#[test]
+fn rejects_types_without_selected_trait() {
+    for (bound, t, value) in [
+        ("!numeric", "bool", "1 < 2"),
+        ("!signed", "u32", "1"),
+        ("!floating", "s32", "1"),
+    ] {
+        assert_eq!(
+            lower_errors(&make_bounded(bound, t, value)),
+            vec![TRAIT_MISSING.0]
+        );
+    }
+}
+
+#[test]
+fn accepts_types_without_excluded_trait() {
+    for (bound, t) in [("~!signed", "u32"), ("~!floating", "s32")] {
+        let source = make_bounded(bound, t, "1");
+
+        assert!(lower(&source).is_ok(), "{} failed to lower", source);
+    }
+}
+
+#[test]
+fn rejects_types_with_excluded_trait() {
+    for (bound, t) in [("~!signed", "s32"), ("~!integer", "u8")] {
+        assert_eq!(
+            lower_errors(&make_bounded(bound, t, "1")),
+            vec![TRAIT_MISSING.0]
+        );
+    }
+}
+
+#[test]
+fn combines_selected_and_excluded_traits() {
+    let bound = "!integer ~!signed";
+
+    assert!(lower(&make_bounded(bound, "u16", "1")).is_ok());
+    assert_eq!(
+        lower_errors(&make_bounded(bound, "s16", "1")),
+        vec![TRAIT_MISSING.0]
+    );
+}
+
+#[test]
+fn rejects_unknown_bound_traits() {
+    assert_eq!(
+        lower_errors(&make_bounded("!bogus", "s32", "1")),
+        vec![UNKNOWN_TRAIT.0]
+    );
+    assert_eq!(
+        lower_errors(&make_bounded("~!bogus", "s32", "1")),
+        vec![UNKNOWN_TRAIT.0]
+    );
+}
@@ -0,0 +1,107 @@
+//! Closures outliving or escaping the variables they capture.
+
+mod common;
+
+use common::lower_errors;
+use diagnostics::errors::{CLOSURE_ESCAPES, CLOSURE_OUTLIVES_CAPTURE};
+
+/// Assigns a closure capturing a variable of an inner scope to a variable of the outer scope
+fn make_inner_capture(capture: &str) -> String {
+    format!(
+        "func main() -> s32 {{\n\tvar s32 base = 1;\n\tvar func[](s32) -> s32 op = func(s32 v) -> s32 {{\n\t\tret v;\n\t}};\n\n\tif (base == 1) {{\n\t\tvar s32 inner = 3;\n\n\t\top = func[{}](s32 v) -> s32 {{\n\t\t\tret inner + v;\n\t\t}};\n\t}}\n\n\tret op(2);\n}}\n",
+        capture
+    )
+}
+
+/// Returns a closure with the given captures from a function
+fn make_returned(captures: &str) -> String {
+    format!(
+        "func make(s32 base) -> func[](s32) -> s32 {{\n\tret func{}(s32 v) -> s32 {{\n\t\tret v;\n\t}};\n}}\n\nfunc main() -> s32 {{\n\tvar func[](s32) -> s32 op = make(1);\n\tret op(2);\n}}\n",
+        captures
+    )
+}
+
+#[test]
+fn accepts_captures_within_their_scope() {
+    let source = "func main() -> s32 {\n\tvar s32 offset = 5;\n\tvar s32 counter = 0;\n\n\tvar func[](s32) -> s32 add = func[offset](s32 v) -> s32 {\n\t\tret v + offset;\n\t};\n\n\tvar func[](s32) -> s32 bump = func[&counter](s32 v) -> s32 {\n\t\tcounter = counter + v;\n\t\tret counter;\n\t};\n\n\tbump(3);\n\tret add(counter);\n}\n";
+
+    assert!(lower_errors(source).is_empty());
+}
+
+#[test]
+fn accepts_closures_outliving_values_captured_by_copy() {
+    assert!(lower_errors(&make_inner_capture("inner")).is_empty());
+}
+
+#[test]
+fn rejects_closures_outliving_values_captured_by_reference() {
+    assert_eq!(
+        lower_errors(&make_inner_capture("&inner")),
+        vec![CLOSURE_OUTLIVES_CAPTURE.0]
+    );
+}
+
+#[test]
+fn accepts_returned_closures_without_captures() {
+    assert!(lower_errors(&make_returned("")).is_empty());
+}
+
+#[test]
+fn rejects_returned_closures_with_captures() {
+    for captures in ["[base]", "[&base]"] {
+        assert_eq!(
+            lower_errors(&make_returned(captures)),
+            vec![CLOSURE_ESCAPES.0]
+        );
+    }
+}
+
+/// Declares a struct holding a closure along with the given function
+fn make_with_holder(function: &str) -> String {
+    format!(
+        "struct holder {{\n\tfunc[](s32) -> s32 op\n}}\n\n{}\n\nfunc main() -> s32 {{\n\tret 0;\n}}\n",
+        function
+    )
+}
+
+const CAPTURING: &str = "func[base](s32 v) -> s32 {\n\t\tret base + v;\n\t}";
+
+#[test]
+fn rejects_closures_escaping_within_aggregates() {
+    for function in [
+        "func make(s32 base) -> holder {\n\tvar holder h = { op: CLOSURE };\n\tret h;\n}",
+        "func make(s32 base) -> func[](s32) -> s32 {\n\tvar holder h = { op: CLOSURE };\n\tret h.op;\n}",
+        "func make(s32 base) -> (func[](s32) -> s32, s32) {\n\tret (CLOSURE, 1);\n}",
+        "func make(s32 base) -> func[](s32) -> s32 {\n\tvar (op, other) = (CLOSURE, 1);\n\tret op;\n}",
+    ] {
+        assert_eq!(
+            lower_errors(&make_with_holder(&function.replace("CLOSURE", CAPTURING))),
+            vec![CLOSURE_ESCAPES.0]
+        );
+    }
+}
+
+#[test]
+fn rejects_closures_stored_through_fields() {
+    let function = format!(
+        "func store(holder* h, s32 base) -> s32 {{\n\th.op = {};\n\tret 0;\n}}",
+        CAPTURING
+    );
+
+    assert_eq!(
+        lower_errors(&make_with_holder(&function)),
+        vec![CLOSURE_ESCAPES.0]
+    );
+}
+
+#[test]
+fn accepts_closures_within_local_aggregates() {
+    let local = format!(
+        "func run(s32 base) -> s32 {{\n\tvar holder h = {{ op: {} }};\n\tvar func[](s32) -> s32 op = h.op;\n\tret op(2);\n}}",
+        CAPTURING
+    );
+    let stored = "func store(holder* h) -> s32 {\n\th.op = func(s32 v) -> s32 {\n\t\tret v;\n\t};\n\tret 0;\n}";
+
+    assert!(lower_errors(&make_with_holder(&local)).is_empty());
+    assert!(lower_errors(&make_with_holder(stored)).is_empty());
+}
@@ -0,0 +1,121 @@
+//! Lowers Quickfall sources for the tests and inspects the result.
+
+#![allow(dead_code)]
+
+use ast_parser::parse_ast_ctx;
+use astoir_hir::{
+    ctx::{HIRBranchedContext, HIRContext},
+    nodes::{HIRNode, HIRNodeKind},
+};
+use astoir_hir_lowering::lower_ast;
+use diagnostics::{DiagnosticResult, clear_diagnostics, diagnostic::Level, get_diagnostics};
+use lexer::lexer::lexer_parse;
+
+/// Lowers the given source into HIR. Diagnostics from previous calls on this thread are discarded
+pub fn lower(source: &str) -> DiagnosticResult<HIRContext> {
+    clear_diagnostics();
+
+    let tokens = lexer_parse(source.to_string(), &"test.qf".to_string())?;
+    let ast = parse_ast_ctx(&tokens)?;
+
+    lower_ast(ast)
+}
+
+/// The codes of the diagnostics of the given level emitted so far
+pub fn get_codes(level: Level) -> Vec<usize> {
+    get_diagnostics()
+        .iter()
+        .filter(|d| d.level == level)
+        .map(|d| d.code)
+        .collect()
+}
+
+/// Lowers the given source and returns the codes of the emitted errors
+pub fn lower_errors(source: &str) -> Vec<usize> {
+    let _ = lower(source);
+
+    get_codes(Level::Error)
+}
+
+/// Lowers the given source and returns the messages of the emitted errors
+pub fn lower_error_messages(source: &str) -> Vec<String> {
+    let _ = lower(source);
+
+    get_diagnostics()
+        .iter()
+        .filter(|d| d.level == Level::Error)
+        .map(|d| d.message.clone())
+        .collect()
+}
+
+/// Lowers the given source and returns the codes of the emitted warnings. Panics if the source fails to lower
+pub fn lower_warnings(source: &str) -> Vec<usize> {
+    assert!(lower(source).is_ok(), "{} failed to lower", source);
+
+    get_codes(Level::Warning)
+}
+
+/// Lowers the given declarations followed by a function declaring a variable per given value. Returns the values of
+/// the variables that are integer literals once lowered, such as the layout intrinsics
+pub fn get_integer_values(declarations: &str, values: &[&str]) -> Vec<i128> {
+    let mut source = format!("{}\nfunc main() -> s32 {{\n", declarations);
+
+    for (i, value) in values.iter().enumerate() {
+        source += &format!("\tvar u64 v{} = {};\n", i, value);
+    }
+
+    source += "\tret 0;\n}\n";
+
+    let hir = lower(&source).unwrap_or_else(|_| panic!("{} failed to lower", source));
+    let mut literals = vec![];
+
+    visit_nodes(&hir, |node, _| {
+        if let HIRNodeKind::VarDeclaration {
+            default_val: Some(val),
+            ..
+        } = &node.kind
+            && let HIRNodeKind::IntegerLiteral { value, .. } = &val.kind
+        {
+            literals.push(*value);
+        }
+    });
+
+    literals
+}
+
+/// Calls `f` on every node of the functions bodies along with the context of the function
+pub fn visit_nodes<F: FnMut(&HIRNode, &HIRBranchedContext)>(hir: &HIRContext, mut f: F) {
+    for implementation in &hir.global_scope.implementations {
+        if let HIRNodeKind::FunctionDeclaration { body, ctx, .. } = &implementation.kind {
+            for node in body {
+                visit_node(node, ctx, &mut f);
+            }
+        }
+    }
+}
+
+fn visit_node<F: FnMut(&HIRNode, &HIRBranchedContext)>(
+    node: &HIRNode,
+    ctx: &HIRBranchedContext,
+    f: &mut F,
+) {
+    f(node, ctx);
+
+    match &node.kind {
+        HIRNodeKind::VarDeclaration {
+            default_val: Some(val),
+            ..
+        } => visit_node(val, ctx, f),
+
+        HIRNodeKind::CastValue { value, .. } => visit_node(value, ctx, f),
+
+        HIRNodeKind::MathOperation { left, right, .. } => {
+            visit_node(left, ctx, f);
+            visit_node(right, ctx, f);
+        }
+
+        HIRNodeKind::ReturnStatement { value: Some(val) } => visit_node(val, ctx, f),
+
+        _ => {}
+    }
+}
@@ -0,0 +1,61 @@
+//! Detection of recursive types of infinite size.
+
+mod common;
+
+use common::lower_errors;
+use diagnostics::errors::INFINITE_SIZE_TYPE;
+
+fn make_program(declarations: &str) -> String {
+    format!("{}\nfunc main() -> s32 {{\n\tret 0;\n}}\n", declarations)
+}
+
+#[test]
+fn accepts_recursion_through_pointers() {
+    for declarations in [
+        "struct node {\n\ts32 v\n\tnode* next\n}\n",
+        "struct node {\n\ts32 v\n\tnode& next\n}\n",
+        "struct wrap<T> {\n\tT v\n}\n\nstruct node {\n\ts32 v\n\twrap<node*> w\n}\n",
+        "enum list {\n\tnil\n\tcons(s32 value, list* rest)\n}\n",
+    ] {
+        assert!(lower_errors(&make_program(declarations)).is_empty());
+    }
+}
+
+#[test]
+fn rejects_types_containing_themselves() {
+    for declarations in [
+        "struct node {\n\ts32 v\n\tnode next\n}\n",
+        "enum list {\n\tnil\n\tcons(s32 value, list rest)\n}\n",
+    ] {
+        assert_eq!(
+            lower_errors(&make_program(declarations)),
+            vec![INFINITE_SIZE_TYPE.0]
+        );
+    }
+}
+
+#[test]
+fn rejects_types_containing_themselves_through_other_types() {
+    for declarations in [
+        "struct wrap<T> {\n\tT v\n}\n\nstruct node {\n\ts32 v\n\twrap<node> w\n}\n",
+        "enum maybe<T> {\n\tsome(T val)\n\tnone\n}\n\nstruct node {\n\ts32 v\n\tmaybe<node> next\n}\n",
+    ] {
+        assert_eq!(
+            lower_errors(&make_program(declarations)),
+            vec![INFINITE_SIZE_TYPE.0]
+        );
+    }
+}
+
+#[test]
+fn checks_every_instantiation_of_generic_types() {
+    for declarations in [
+        "struct wrap<T> {\n\tT v\n}\n\nstruct node {\n\twrap<s32> a\n\twrap<node> b\n}\n",
+        "enum maybe<T> {\n\tsome(T val)\n\tnone\n}\n\nstruct node {\n\tmaybe<s32> a\n\tmaybe<node> b\n}\n",
+    ] {
+        assert_eq!(
+            lower_errors(&make_program(declarations)),
+            vec![INFINITE_SIZE_TYPE.0]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Explicit enum discriminants, their representation and integer conversions.
+
+mod common;
+
+use common::{get_integer_values, lower_errors};
+use diagnostics::errors::{
+    DISCRIMINANT_OVERFLOW, DUPLICATE_DISCRIMINANT, INVALID_CAST, INVALID_TYPE_REQ,
+};
+
+/// Declares the given enum `e` and converts it from and into an integer
+fn make_conversions(declaration: &str) -> String {
+    format!(
+        "{}\nfunc main() -> s32 {{\n\tvar e v = cast<0, e>;\n\tvar s64 n = cast<v, s64>;\n\tret 0;\n}}\n",
+        declaration
+    )
+}
+
+#[test]
+fn accepts_discriminants_within_representation() {
+    for declaration in [
+        "enum e { a = 5  b  c = 7 }",
+        "enum e { a = -3  b }",
+        "enum e: s8 { a = -128  b = 127 }",
+        "enum e: u8 { a = 254  b }",
+        "enum e { a = -170141183460469231731687303715884105727  b = 170141183460469231731687303715884105727 }",
+    ] {
+        assert!(lower_errors(&make_conversions(declaration)).is_empty());
+    }
+}
+
+#[test]
+fn rejects_discriminants_outside_representation() {
+    for declaration in [
+        "enum e: s8 { a = 128 }",
+        "enum e: u8 { a = -1 }",
+        "enum e: u8 { a = 255  b }",
+        "enum e: u16 { a = 65536 }",
+        "enum e { a = 170141183460469231731687303715884105727  b }",
+        "enum e: s128 { a = 170141183460469231731687303715884105727  b }",
+    ] {
+        assert_eq!(
+            lower_errors(&make_conversions(declaration)),
+            vec![DISCRIMINANT_OVERFLOW.0]
+        );
+    }
+}
+
+#[test]
+fn rejects_duplicate_discriminants() {
+    for declaration in ["enum e { a = 1  b = 1 }", "enum e { a = 5  b  c = 6 }"] {
+        assert_eq!(
+            lower_errors(&make_conversions(declaration)),
+            vec![DUPLICATE_DISCRIMINANT.0]
+        );
+    }
+}
+
+#[test]
+fn stores_enums_as_their_representation() {
+    let declarations = "enum small { a  b }\nenum wide { a = 1000 }\nenum fixed: u32 { a }\n";
+
+    assert_eq!(
+        get_integer_values(
+            declarations,
+            &["sizeof(small)", "sizeof(wide)", "sizeof(fixed)"]
+        ),
+        vec![1, 2, 4]
+    );
+}
+
+#[test]
+fn stores_enums_spanning_every_discriminant() {
+    let declarations = "enum full { a = -170141183460469231731687303715884105727  b = 170141183460469231731687303715884105727 }\nenum high { a = 170141183460469231731687303715884105727 }\n";
+
+    assert_eq!(
+        get_integer_values(declarations, &["sizeof(full)", "sizeof(high)"]),
+        vec![16, 16]
+    );
+}
+
+#[test]
+fn rejects_integer_conversions_of_dataful_enums() {
+    let source = "enum sh { circle(s32 r) sq(s32 s) }\nfunc main() -> s32 {\n\tvar sh v = cast<1, sh>;\n\tret 0;\n}\n";
+
+    assert_eq!(lower_errors(source), vec![INVALID_TYPE_REQ.0]);
+}
+
+#[test]
+fn rejects_integer_conversions_of_structs() {
+    let source = "struct pt { s32 a }\nfunc main() -> s32 {\n\tvar pt p = { a: 1 };\n\tvar s32 v = cast<p, s32>;\n\tret v;\n}\n";
+
+    assert_eq!(lower_errors(source), vec![INVALID_CAST.0]);
+}
@@ -0,0 +1,156 @@
+//! Promotion, width checks and casts of arbitrary width integers.
+
+mod common;
+
+use astoir_hir::nodes::HIRNodeKind;
+use common::{lower, lower_errors, lower_warnings, visit_nodes};
+use compiler_typing::{
+    raw::{MAX_INTEGER_SIZE, RawType},
+    tree::Type,
+};
+use diagnostics::{errors::UNSUPPORTED_INTEGER_WIDTH, warnings::LOSSY_PROMOTION};
+
+const WIDTHS: [usize; 9] = [1, 7, 8, 24, 33, 64, 65, 127, 128];
+
+fn integer_name(size: usize, signed: bool) -> String {
+    match signed {
+        true => format!("s.{}", size),
+        false => format!("u.{}", size),
+    }
+}
+
+fn integer(size: usize, signed: bool) -> Type {
+    Type::Generic(RawType::Integer(size, signed), vec![], vec![])
+}
+
+fn make_addition(left: &str, right: &str) -> String {
+    format!(
+        "func main() -> s32 {{\n\tvar {} a = 1;\n\tvar {} b = 1;\n\tvar s.128 c = a + b;\n\tret 0;\n}}\n",
+        left, right
+    )
+}
+
+/// Lowers `a + b` for operands of the given types and returns the type both operands were promoted to
+fn get_promoted_type(left: &str, right: &str) -> Type {
+    let source = make_addition(left, right);
+    let hir = lower(&source).unwrap_or_else(|_| panic!("{} failed to lower", source));
+
+    let mut promoted = None;
+
+    visit_nodes(&hir, |node, ctx| {
+        if let HIRNodeKind::MathOperation { left, right, .. } = &node.kind {
+            let left = left.get_node_type(&hir, ctx).unwrap();
+            let right = right.get_node_type(&hir, ctx).unwrap();
+
+            assert_eq!(left, right, "operands of {} + {} differ", left, right);
+
+            promoted = Some(left);
+        }
+    });
+
+    promoted.expect("no math operation was lowered")
+}
+
+#[test]
+fn promotes_same_signedness_to_widest() {
+    for signed in [false, true] {
+        for left in WIDTHS {
+            for right in WIDTHS {
+                let promoted =
+                    get_promoted_type(&integer_name(left, signed), &integer_name(right, signed));
+
+                assert_eq!(promoted, integer(left.max(right), signed));
+            }
+        }
+    }
+}
+
+#[test]
+fn promotes_mixed_signedness_to_wider_signed() {
+    for signed in WIDTHS {
+        for unsigned in WIDTHS.iter().copied().filter(|u| *u < MAX_INTEGER_SIZE) {
+            let expected = integer(signed.max(unsigned + 1), true);
+
+            let s = integer_name(signed, true);
+            let u = integer_name(unsigned, false);
+
+            assert_eq!(get_promoted_type(&s, &u), expected);
+            assert_eq!(get_promoted_type(&u, &s), expected);
+
+            assert!(!lower_warnings(&make_addition(&s, &u)).contains(&LOSSY_PROMOTION.0));
+        }
+    }
+}
+
+#[test]
+fn caps_mixed_promotion_at_widest_integer() {
+    for signed in WIDTHS {
+        let s = integer_name(signed, true);
+        let u = integer_name(MAX_INTEGER_SIZE, false);
+
+        assert_eq!(get_promoted_type(&u, &s), integer(MAX_INTEGER_SIZE, true));
+        assert!(lower_warnings(&make_addition(&u, &s)).contains(&LOSSY_PROMOTION.0));
+    }
+}
+
+#[test]
+fn accepts_supported_widths() {
+    for size in 1..=MAX_INTEGER_SIZE {
+        for signed in [false, true] {
+            let source = format!(
+                "func main() -> s32 {{\n\tvar {} a = 1;\n\tret 0;\n}}\n",
+                integer_name(size, signed)
+            );
+
+            assert!(lower(&source).is_ok(), "{} failed to lower", source);
+        }
+    }
+}
+
+#[test]
+fn rejects_unsupported_widths() {
+    for size in [0, MAX_INTEGER_SIZE + 1, 200] {
+        for signed in [false, true] {
+            let source = format!(
+                "func main() -> s32 {{\n\tvar {} a = 1;\n\tret 0;\n}}\n",
+                integer_name(size, signed)
+            );
+
+            assert_eq!(lower_errors(&source), vec![UNSUPPORTED_INTEGER_WIDTH.0]);
+        }
+    }
+}
+
+#[test]
+fn lowers_explicit_casts() {
+    for from in WIDTHS {
+        for to in WIDTHS {
+            let source = format!(
+                "func main() -> s32 {{\n\tvar u.{} a = 1;\n\tvar s.{} b = cast<a, s.{}>;\n\tret 0;\n}}\n",
+                from, to, to
+            );
+
+            let hir = lower(&source).unwrap_or_else(|_| panic!("{} failed to lower", source));
+            let mut casts = vec![];
+
+            visit_nodes(&hir, |node, _| {
+                if let HIRNodeKind::CastValue {
+                    old_type, new_type, ..
+                } = &node.kind
+                {
+                    casts.push((old_type.clone(), new_type.clone()));
+                }
+            });
+
+            assert_eq!(casts, vec![(integer(from, false), integer(to, true))]);
+        }
+    }
+}
+
+#[test]
+fn rejects_casts_to_unsupported_widths() {
+    let source =
+        "func main() -> s32 {\n\tvar u.8 a = 1;\n\tvar u.8 b = cast<a, u.129>;\n\tret 0;\n}\n";
+
+    assert_eq!(lower_errors(source), vec![UNSUPPORTED_INTEGER_WIDTH.0]);
+}
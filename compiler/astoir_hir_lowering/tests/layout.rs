@@ -0,0 +1,48 @@
+//! Layouts of enums and slices, as exposed by the layout intrinsics.
+
+mod common;
+
+use common::get_integer_values;
+use compiler_typing::utils::get_pointer_size;
+
+/// The size and alignment of the given type, in bytes
+fn get_layout(declarations: &str, t: &str) -> (i128, i128) {
+    let values = get_integer_values(
+        declarations,
+        &[&format!("sizeof({})", t), &format!("alignof({})", t)],
+    );
+
+    (values[0], values[1])
+}
+
+#[test]
+fn stores_enum_hint_once() {
+    let shape = "enum shape {\n\tcircle(s32 radius)\n\tsquare(s64 side)\n}\n";
+
+    // The hint followed by the payload aligned on its most aligned entry
+    assert_eq!(get_layout(shape, "shape"), (16, 8));
+}
+
+#[test]
+fn aligns_enum_payload_on_its_fields() {
+    let bytes = "enum bytes {\n\tpair(s8 a, s8 b)\n\tsingle(s8 c)\n}\n";
+    let mixed = "enum mixed {\n\tsmall(s8 a, s16 b)\n\tbig(s32 c)\n}\n";
+
+    assert_eq!(get_layout(bytes, "bytes"), (3, 1));
+    assert_eq!(get_layout(mixed, "mixed"), (8, 4));
+}
+
+#[test]
+fn stores_fieldless_enums_as_their_hint() {
+    let color = "enum color {\n\tred\n\tgreen\n\tblue\n}\n";
+
+    assert_eq!(get_layout(color, "color"), (1, 1));
+}
+
+#[test]
+fn stores_pointer_sized_slice_lengths() {
+    let pointer = (get_pointer_size() / 8) as i128;
+
+    assert_eq!(get_layout("", "s32[]"), (pointer * 2, pointer));
+    assert_eq!(get_layout("", "s8[]"), (pointer * 2, pointer));
+}
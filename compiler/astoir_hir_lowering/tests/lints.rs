@@ -0,0 +1,116 @@
+//! Warning levels set globally and through the `@allow` attribute.
+
+mod common;
+
+use common::{get_codes, lower, lower_errors, lower_warnings};
+use diagnostics::{
+    diagnostic::Level,
+    errors::{INVALID_ATTRIBUTE, UNKNOWN_ATTRIBUTE, UNKNOWN_WARNING},
+    lints::{LintLevel, set_deny_warnings, set_lint_level},
+    warnings::{UNUSED_FUNCTION, UNUSED_VAR},
+};
+
+/// Declares an unused variable within `main` with the given attributes
+fn make_unused_variable(attributes: &str) -> String {
+    format!(
+        "func main() -> s32 {{\n\t{}var s32 scratch = 5;\n\tret 0;\n}}\n",
+        attributes
+    )
+}
+
+#[test]
+fn reports_unused_declarations() {
+    let source = "func helper() -> s32 {\n\tret 1;\n}\n\nfunc main() -> s32 {\n\tvar s32 scratch = 5;\n\tret 0;\n}\n";
+
+    assert_eq!(
+        lower_warnings(source),
+        vec![UNUSED_VAR.0, UNUSED_FUNCTION.0]
+    );
+}
+
+#[test]
+fn reports_unused_declarations_only_in_the_entry_file() {
+    // Library modules are lowered on their own, their declarations are used by the other modules
+    let source = "struct point {\n\ts32 px\n}\n\nfunc helper() -> s32 {\n\tvar s32 scratch = 5;\n\tret 1;\n}\n";
+
+    assert_eq!(lower_warnings(source), vec![UNUSED_VAR.0]);
+}
+
+#[test]
+fn allows_warnings_on_the_attributed_node() {
+    assert!(lower_warnings(&make_unused_variable("@allow(unused_variable)\n\t")).is_empty());
+}
+
+#[test]
+fn allows_warnings_only_within_the_attributed_node() {
+    let source = "@allow(unused_variable)\nfunc helper() -> s32 {\n\tvar s32 scratch = 5;\n\tret 1;\n}\n\nfunc main() -> s32 {\n\tvar s32 other = 5;\n\tret helper();\n}\n";
+
+    assert_eq!(lower_warnings(source), vec![UNUSED_VAR.0]);
+}
+
+#[test]
+fn forgets_allowed_regions_of_previous_lowerings() {
+    // The empty line keeps the variable at the same position once the attribute is removed
+    let source = "func main() -> s32 {\n\tvar s32 scratch = 5;\n\tret 0;\n}\n";
+
+    lower(&format!("@allow(unused_variable)\n{}", source)).unwrap();
+
+    assert_eq!(lower_warnings(&format!("\n{}", source)), vec![UNUSED_VAR.0]);
+}
+
+#[test]
+fn allows_only_the_given_warnings() {
+    assert_eq!(
+        lower_warnings(&make_unused_variable("@allow(unused_function)\n\t")),
+        vec![UNUSED_VAR.0]
+    );
+}
+
+#[test]
+fn rejects_unknown_warnings() {
+    assert_eq!(
+        lower_errors(&make_unused_variable("@allow(unused_thing)\n\t")),
+        vec![UNKNOWN_WARNING.0]
+    );
+}
+
+#[test]
+fn rejects_unknown_and_misplaced_attributes() {
+    assert_eq!(
+        lower_errors(&make_unused_variable("@forbid(unused_variable)\n\t")),
+        vec![UNKNOWN_ATTRIBUTE.0]
+    );
+    assert_eq!(
+        lower_errors(&make_unused_variable("@unsafe\n\t")),
+        vec![INVALID_ATTRIBUTE.0]
+    );
+}
+
+#[test]
+fn silences_globally_allowed_warnings() {
+    set_lint_level(UNUSED_VAR.0, LintLevel::Allow);
+
+    assert!(lower_warnings(&make_unused_variable("")).is_empty());
+}
+
+#[test]
+fn reports_denied_warnings_as_errors() {
+    set_lint_level(UNUSED_VAR.0, LintLevel::Deny);
+    let _ = lower(&make_unused_variable(""));
+
+    assert!(get_codes(Level::Warning).is_empty());
+    assert_eq!(get_codes(Level::Error), vec![UNUSED_VAR.0]);
+}
+
+#[test]
+fn denies_every_warning_left_at_the_default_level() {
+    set_deny_warnings(true);
+    set_lint_level(UNUSED_FUNCTION.0, LintLevel::Warn);
+    set_lint_level(UNUSED_VAR.0, LintLevel::Allow);
+
+    let source = "func helper() -> s32 {\n\tret 1;\n}\n\nfunc main() -> s32 {\n\tvar s32 scratch = 5;\n\tret 0;\n}\n";
+    let _ = lower(source);
+
+    assert!(get_codes(Level::Warning).is_empty());
+    assert_eq!(get_codes(Level::Error), vec![UNUSED_FUNCTION.0]);
+}
@@ -0,0 +1,87 @@
+//! Diagnostics refer to declarations by their names rather than by the hashes of their names.
+
+mod common;
+
+use common::lower_error_messages;
+
+const POINT: &str = "struct point {\n\ts32 x\n}\n";
+
+#[test]
+fn names_structs() {
+    let source = format!(
+        "{}\nfunc main() -> s32 {{\n\tvar point p = {{ x: 1 }};\n\tvar s32 v = p;\n\tret v;\n}}\n",
+        POINT
+    );
+
+    assert_eq!(
+        lower_error_messages(&source),
+        vec!["expected s32 but got point"]
+    );
+}
+
+#[test]
+fn names_enum_entries() {
+    let source = "enum maybe {\n\tsome(s32 val)\n\tnone\n}\n\nfunc main() -> s32 {\n\tvar maybe::some m = { val: 3 };\n\tvar s32 v = m;\n\tret v;\n}\n";
+
+    assert_eq!(
+        lower_error_messages(source),
+        vec!["expected s32 but got maybe::some"]
+    );
+}
+
+#[test]
+fn names_missing_fields() {
+    let source = format!(
+        "{}\nfunc main() -> s32 {{\n\tvar point p = {{ x: 1 }};\n\tret p.y;\n}}\n",
+        POINT
+    );
+
+    assert_eq!(
+        lower_error_messages(&source),
+        vec!["cannot find field y in type point"]
+    );
+}
+
+#[test]
+fn names_missing_functions() {
+    let source = format!(
+        "{}\nfunc main() -> s32 {{\n\tvar point p = {{ x: 1 }};\n\tret p.nope();\n}}\n",
+        POINT
+    );
+
+    assert_eq!(
+        lower_error_messages(&source),
+        vec!["func nope was not found in type point"]
+    );
+}
+
+#[test]
+fn reports_duplicate_declarations_once() {
+    let source = "trait hashable {\n\tfunc hash(this) -> s32;\n}\n\ntrait hashable {\n\tfunc hash(this) -> s32;\n}\n\nfunc main() -> s32 {\n\tret 0;\n}\n";
+
+    assert_eq!(
+        lower_error_messages(source),
+        vec!["hashable was already found in this scope"]
+    );
+}
+
+#[test]
+fn names_unknown_types() {
+    let source = "trait hashable {\n\tfunc hash(this) -> s32;\n}\n\nimpl hashable for nothing {\n\tfunc hash(this) -> s32 {\n\t\tret 1;\n\t}\n}\n\nfunc main() -> s32 {\n\tret 0;\n}\n";
+
+    assert_eq!(
+        lower_error_messages(source),
+        vec!["cannot find nothing in the current scope"]
+    );
+}
+
+#[test]
+fn names_variables_clashing_with_types() {
+    // `x` is the prelude's fixed point type
+    let source = "func main() -> s32 {\n\tvar (x, y) = (1, 2);\n\tret y;\n}\n";
+
+    assert_eq!(
+        lower_error_messages(source),
+        vec!["x was already found in this scope"]
+    );
+}
@@ -0,0 +1,58 @@
+//! Sizes of niche filled enums.
+
+mod common;
+
+use common::get_integer_values;
+use compiler_typing::utils::get_pointer_size;
+
+const TRI: &str = "enum tri {\n\ta\n\tb\n\tc\n}\n";
+const QUAD: &str = "enum quad {\n\ta\n\tb\n\tc\n\td\n}\n";
+
+fn get_size(declarations: &str, t: &str) -> i128 {
+    get_integer_values(declarations, &[&format!("sizeof({})", t)])[0]
+}
+
+#[test]
+fn stores_fieldless_entries_in_null_pointers() {
+    let maybe = "enum maybe {\n\tsome(s32* v)\n\tnone\n}\n";
+
+    assert_eq!(get_size(maybe, "maybe"), (get_pointer_size() / 8) as i128);
+}
+
+#[test]
+fn stores_fieldless_entries_in_invalid_booleans() {
+    let flag = "enum flag {\n\ton(bool b)\n\toff\n\tunknown\n}\n";
+
+    assert_eq!(get_size(flag, "flag"), 1);
+}
+
+#[test]
+fn stores_fieldless_entries_in_unused_hint_values() {
+    let wrapped = format!("{}enum wrapped {{\n\tval(tri t)\n\tempty\n}}\n", TRI);
+
+    assert_eq!(get_size(&wrapped, "wrapped"), 1);
+}
+
+#[test]
+fn keeps_hint_without_niche() {
+    // Every hint value of quad is used, s32 does not have any invalid value
+    let full = format!("{}enum full {{\n\tval(quad q)\n\tempty\n}}\n", QUAD);
+    let plain = "enum plain {\n\tsome(s32 v)\n\tnone\n}\n";
+
+    assert_eq!(get_size(&full, "full"), 2);
+    assert_eq!(get_size(plain, "plain"), 8);
+}
+
+#[test]
+fn keeps_hint_with_several_dataful_entries() {
+    let two = "enum two {\n\ta(s32* v)\n\tb(s32* w)\n\tnone\n}\n";
+
+    assert_eq!(get_size(two, "two"), (get_pointer_size() / 8 * 2) as i128);
+}
+
+#[test]
+fn keeps_hint_of_enums_wrapping_128_bits_hints() {
+    let wide = "enum wide: u128 {\n\ta\n\tb\n}\nenum wrapped {\n\tval(wide w)\n\tempty\n}\n";
+
+    assert_eq!(get_size(wide, "wrapped"), 32);
+}
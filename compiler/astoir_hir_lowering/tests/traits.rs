@@ -0,0 +1,115 @@
+//! User declared traits, their implementations and their use as bounds.
+
+mod common;
+
+use common::lower_errors;
+use diagnostics::errors::{TRAIT_MISSING, TRAIT_NOT_IMPLEMENTED, UNKNOWN_TRAIT};
+
+const HASHABLE: &str = "trait hashable {\n\tfunc hash(this) -> s32;\n}\n\nstruct point {\n\ts32 x\n\ts32 y\n}\n\nimpl hashable for point {\n\tfunc hash(this) -> s32 {\n\t\tret this.x + this.y;\n\t}\n}\n\nstruct plain {\n\ts32 x\n}\n";
+
+/// Declares `holder` with the given bound and stores a value of the given type in it, either `point` or `plain`
+fn make_holder(bound: &str, t: &str) -> String {
+    let value = match t {
+        "point" => "{ x: 1, y: 2 }",
+        _ => "{ x: 1 }",
+    };
+
+    format!(
+        "{}\nstruct holder<A: {}> {{\n\tA val\n}}\n\nfunc main() -> s32 {{\n\tvar {} v = {};\n\tvar holder<{}> h = {{ val: v }};\n\tret 0;\n}}\n",
+        HASHABLE, bound, t, value, t
+    )
+}
+
+#[test]
+fn calls_implemented_functions() {
+    let source = format!(
+        "{}\nfunc main() -> s32 {{\n\tvar point p = {{ x: 1, y: 2 }};\n\tret p.hash();\n}}\n",
+        HASHABLE
+    );
+
+    assert!(lower_errors(&source).is_empty());
+}
+
+#[test]
+fn rejects_incomplete_implementations() {
+    let source = "trait shape {\n\tfunc area(this) -> s32;\n\tfunc sides(this) -> s32;\n}\n\nstruct square {\n\ts32 side\n}\n\nimpl shape for square {\n\tfunc area(this) -> s32 {\n\t\tret this.side * this.side;\n\t}\n}\n\nfunc main() -> s32 {\n\tvar square s = { side: 2 };\n\tret s.area();\n}\n";
+
+    assert_eq!(lower_errors(source), vec![TRAIT_NOT_IMPLEMENTED.0]);
+}
+
+#[test]
+fn accepts_types_implementing_selected_trait() {
+    assert!(lower_errors(&make_holder("!hashable", "point")).is_empty());
+}
+
+#[test]
+fn rejects_types_not_implementing_selected_trait() {
+    assert_eq!(
+        lower_errors(&make_holder("!hashable", "plain")),
+        vec![TRAIT_NOT_IMPLEMENTED.0]
+    );
+}
+
+#[test]
+fn accepts_types_not_implementing_excluded_trait() {
+    // Checking the trait must not leave any diagnostic behind
+    assert!(lower_errors(&make_holder("~!hashable", "plain")).is_empty());
+}
+
+#[test]
+fn rejects_types_implementing_excluded_trait() {
+    assert_eq!(
+        lower_errors(&make_holder("~!hashable", "point")),
+        vec![TRAIT_MISSING.0]
+    );
+}
+
+#[test]
+fn rejects_unknown_traits_in_bounds() {
+    assert_eq!(
+        lower_errors(&make_holder("!bogus", "point")),
+        vec![UNKNOWN_TRAIT.0]
+    );
+
+    let source = "enum opt<A: ~!bogus> {\n\tnone\n}\n\nfunc main() -> s32 {\n\tret 0;\n}\n";
+
+    assert_eq!(lower_errors(source), vec![UNKNOWN_TRAIT.0]);
+}
+
+#[test]
+fn rejects_types_providing_functions_without_implementation() {
+    let source = "trait hashable {\n\tfunc hash(this) -> s32;\n}\n\nstruct point {\n\ts32 x\n\n\tfunc hash(this) -> s32 {\n\t\tret this.x;\n\t}\n}\n\nstruct holder<A: !hashable> {\n\tA val\n}\n\nfunc main() -> s32 {\n\tvar point p = { x: 1 };\n\tvar holder<point> h = { val: p };\n\tret 0;\n}\n";
+
+    assert_eq!(lower_errors(source), vec![TRAIT_NOT_IMPLEMENTED.0]);
+}
+
+#[test]
+fn calls_trait_functions_of_bounded_type_parameters() {
+    let source = format!(
+        "{}\nstruct holder<A: !hashable> {{\n\tA val\n\n\tfunc get(this) -> s32 {{\n\t\tret this.val.hash();\n\t}}\n}}\n\nfunc main() -> s32 {{\n\tvar point p = {{ x: 1, y: 2 }};\n\tvar holder<point> h = {{ val: p }};\n\tret h.get();\n}}\n",
+        HASHABLE
+    );
+
+    assert!(lower_errors(&source).is_empty());
+}
+
+#[test]
+fn implements_traits_for_enums() {
+    let source = "trait hashable {\n\tfunc hash(this) -> s32;\n}\n\nenum opt {\n\tnone\n\tsome(s32 v)\n}\n\nimpl hashable for opt {\n\tfunc hash(this) -> s32 {\n\t\tret 1;\n\t}\n}\n\nstruct holder<A: !hashable> {\n\tA val\n}\n\nfunc main() -> s32 {\n\tvar opt::some sm = { v: 3 };\n\tvar opt o = sm;\n\tvar holder<opt> h = { val: o };\n\tret h.val.hash();\n}\n";
+
+    assert!(lower_errors(source).is_empty());
+}
+
+#[test]
+fn implements_traits_for_instantiated_generic_types() {
+    let source = "trait hashable {\n\tfunc hash(this) -> s32;\n}\n\nstruct wrap<T> {\n\tT v\n\n\tfunc get(this) -> T {\n\t\tret this.v;\n\t}\n}\n\nstruct user {\n\twrap<s32> w\n}\n\nimpl hashable for wrap<T> {\n\tfunc hash(this) -> s32 {\n\t\tret 1;\n\t}\n}\n\nfunc main() -> s32 {\n\tvar wrap<s32> w = { v: 1 };\n\tvar user _u = { w: w };\n\tret w.hash() + w.get();\n}\n";
+
+    assert!(lower_errors(source).is_empty());
+}
+
+#[test]
+fn checks_implementations_for_generic_types() {
+    let source = "trait hashable {\n\tfunc hash(this) -> s32;\n}\n\nstruct wrap<T> {\n\tT v\n}\n\nimpl hashable for wrap<T> {\n\tfunc hash(this) -> T {\n\t\tret this.v;\n\t}\n}\n\nfunc main() -> s32 {\n\tret 0;\n}\n";
+
+    assert_eq!(lower_errors(source), vec![TRAIT_NOT_IMPLEMENTED.0]);
+}
@@ -0,0 +1,68 @@
+//! Untagged unions, their layout and the diagnostics of their accesses.
+
+mod common;
+
+use common::{get_integer_values, lower_errors};
+use diagnostics::errors::{UNION_INIT_FIELD_COUNT, UNSAFE_UNION_ACCESS};
+
+const WORD: &str = "union word {\n\tu32 full\n\tu8 low\n}\n";
+
+fn make_program(body: &str) -> String {
+    format!("{}\nfunc main() -> s32 {{\n{}\tret 0;\n}}\n", WORD, body)
+}
+
+#[test]
+fn stores_every_field_at_the_same_offset() {
+    assert_eq!(
+        get_integer_values(
+            WORD,
+            &[
+                "sizeof(word)",
+                "alignof(word)",
+                "offsetof(word, full)",
+                "offsetof(word, low)"
+            ]
+        ),
+        vec![4, 4, 0, 0]
+    );
+}
+
+#[test]
+fn accepts_unsafe_accesses() {
+    let body = "\tvar word w = { full: 258 };\n\tunsafe_unwrap<w.full> = 513;\n\tvar u8 low = unsafe_unwrap<w.low>;\n";
+
+    assert!(lower_errors(&make_program(body)).is_empty());
+}
+
+#[test]
+fn rejects_safe_reads() {
+    let body = "\tvar word w = { full: 258 };\n\tvar u8 low = w.low;\n";
+
+    assert_eq!(
+        lower_errors(&make_program(body)),
+        vec![UNSAFE_UNION_ACCESS.0]
+    );
+}
+
+#[test]
+fn rejects_safe_writes() {
+    let body = "\tvar word w = { full: 258 };\n\tw.low = 3;\n";
+
+    assert_eq!(
+        lower_errors(&make_program(body)),
+        vec![UNSAFE_UNION_ACCESS.0]
+    );
+}
+
+#[test]
+fn rejects_initializers_without_exactly_one_field() {
+    for body in [
+        "\tvar word w = { full: 5, low: 2 };\n",
+        "\tvar word w = {};\n",
+    ] {
+        assert_eq!(
+            lower_errors(&make_program(body)),
+            vec![UNION_INIT_FIELD_COUNT.0]
+        );
+    }
+}
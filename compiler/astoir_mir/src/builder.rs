@@ -6,7 +6,7 @@ use diagnostics::{DiagnosticResult, MaybeDiagnostic, unsure_panic};
 use crate::{
     blocks::{hints::MIRValueHint, refer::MIRBlockReference},
     ctx::MIRContext,
-    insts::MIRInstruction,
+    insts::{MIRInstruction, MIROverflowOperation},
     vals::{
         arrays::MIRArrayValue, base::BaseMIRValue, float::MIRFloatValue, int::MIRIntValue,
         ptr::MIRPointerValue, structs::MIRStructValue,
@@ -149,6 +149,60 @@ pub fn build_upcast_float(
     return res.as_float();
 }
 
+/// Converts the integer into a float of the given size. Negative values are clamped to zero for unsigned floats
+pub fn build_int_to_float(
+    ctx: &mut MIRContext,
+    val: MIRIntValue,
+    size: usize,
+    signed: bool,
+) -> DiagnosticResult<MIRFloatValue> {
+    let res = ctx
+        .append_inst(MIRInstruction::IntegerToFloat { val, size, signed })
+        .get()?;
+
+    return res.as_float();
+}
+
+/// Converts the float into an integer of the given size, rounding towards zero
+pub fn build_float_to_int(
+    ctx: &mut MIRContext,
+    val: MIRFloatValue,
+    size: usize,
+    signed: bool,
+) -> DiagnosticResult<MIRIntValue> {
+    let res = ctx
+        .append_inst(MIRInstruction::FloatToInteger { val, size, signed })
+        .get()?;
+
+    return res.as_int();
+}
+
+/// Converts the float into a float of the given size and signedness. Negative values are clamped to zero for unsigned floats
+pub fn build_float_convert(
+    ctx: &mut MIRContext,
+    val: MIRFloatValue,
+    size: usize,
+    signed: bool,
+) -> DiagnosticResult<MIRFloatValue> {
+    let res = ctx
+        .append_inst(MIRInstruction::FloatConvert { val, size, signed })
+        .get()?;
+
+    return res.as_float();
+}
+
+/// Clamps the float to zero if it is negative
+pub fn build_float_clamp_zero(
+    ctx: &mut MIRContext,
+    val: MIRFloatValue,
+) -> DiagnosticResult<MIRFloatValue> {
+    let res = ctx
+        .append_inst(MIRInstruction::FloatClampZero { val })
+        .get()?;
+
+    return res.as_float();
+}
+
 pub fn build_int_add(
     ctx: &mut MIRContext,
     left: MIRIntValue,
@@ -288,6 +342,54 @@ pub fn build_int_shift_right(
     return res.as_int();
 }
 
+/// Checks whether the given operation overflows the integer type
+pub fn build_int_overflows(
+    ctx: &mut MIRContext,
+    operation: MIROverflowOperation,
+    left: MIRIntValue,
+    right: MIRIntValue,
+    signed: bool,
+) -> DiagnosticResult<MIRIntValue> {
+    if left.size != right.size {
+        unsure_panic!("Tried using iovf on different sized integers");
+    }
+
+    let res = ctx
+        .append_inst(MIRInstruction::IntegerOverflows {
+            operation,
+            signed,
+            left,
+            right,
+        })
+        .get()?;
+
+    return res.as_int();
+}
+
+/// Executes the given operation, clamping the result to the bounds of the integer type
+pub fn build_int_saturating(
+    ctx: &mut MIRContext,
+    operation: MIROverflowOperation,
+    left: MIRIntValue,
+    right: MIRIntValue,
+    signed: bool,
+) -> DiagnosticResult<MIRIntValue> {
+    if left.size != right.size {
+        unsure_panic!("Tried using isat on different sized integers");
+    }
+
+    let res = ctx
+        .append_inst(MIRInstruction::IntegerSaturating {
+            operation,
+            signed,
+            left,
+            right,
+        })
+        .get()?;
+
+    return res.as_int();
+}
+
 pub fn build_int_neg(ctx: &mut MIRContext, val: MIRIntValue) -> DiagnosticResult<MIRIntValue> {
     let res = ctx.append_inst(MIRInstruction::IntegerNeg { val }).get()?;
 
@@ -423,8 +525,8 @@ pub fn build_bitwise_and(
     a: MIRIntValue,
     b: MIRIntValue,
 ) -> DiagnosticResult<MIRIntValue> {
-    if a.size != 1 || b.size != 1 {
-        unsure_panic!("Tried using and on a non 1 bit value");
+    if a.size != b.size {
+        unsure_panic!("Tried using and on values of different sizes");
     }
 
     let res = ctx.append_inst(MIRInstruction::BitwiseAnd { a, b }).get()?;
@@ -437,8 +539,8 @@ pub fn build_bitwise_or(
     a: MIRIntValue,
     b: MIRIntValue,
 ) -> DiagnosticResult<MIRIntValue> {
-    if a.size != 1 || b.size != 1 {
-        unsure_panic!("Tried using or on a non 1 bit value");
+    if a.size != b.size {
+        unsure_panic!("Tried using or on values of different sizes");
     }
 
     let res = ctx.append_inst(MIRInstruction::BitwiseOr { a, b }).get()?;
@@ -451,8 +553,8 @@ pub fn build_bitwise_xor(
     a: MIRIntValue,
     b: MIRIntValue,
 ) -> DiagnosticResult<MIRIntValue> {
-    if a.size != 1 || b.size != 1 {
-        unsure_panic!("Tried using xor on a non 1 bit value");
+    if a.size != b.size {
+        unsure_panic!("Tried using xor on values of different sizes");
     }
 
     let res = ctx.append_inst(MIRInstruction::BitwiseXor { a, b }).get()?;
@@ -461,10 +563,6 @@ pub fn build_bitwise_xor(
 }
 
 pub fn build_bitwise_not(ctx: &mut MIRContext, a: MIRIntValue) -> DiagnosticResult<MIRIntValue> {
-    if a.size != 1 {
-        unsure_panic!("Tried using not on a non 1 bit value");
-    }
-
     let res = ctx
         .append_inst(MIRInstruction::BitwiseNot { val: a })
         .get()?;
@@ -683,7 +781,11 @@ pub fn build_index_pointer(
         .get()?;
     let base: BaseMIRValue = MIRPointerValue::into(val);
 
-    let t = base.vtype.get_inner_type();
+    // The pointer points to the array itself, the result points to an element
+    let t = match ctx.ssa_hints.get_hint(base.get_ssa_index()).as_pointer() {
+        Type::Array(_, inner) => inner,
+        _ => base.vtype.get_inner_type(),
+    };
 
     let ind = ctx.ssa_hints.append_hint(MIRValueHint::Pointer(*t));
 
@@ -694,6 +796,19 @@ pub fn build_index_pointer(
     res.as_ptr()
 }
 
+/// Aborts the program with the given message when the given condition is false
+pub fn build_assert(
+    ctx: &mut MIRContext,
+    cond: MIRIntValue,
+    msg: String,
+) -> DiagnosticResult<bool> {
+    let msg = build_static_string_const(ctx, msg)?;
+
+    ctx.append_inst(MIRInstruction::Assert { cond, msg });
+
+    Ok(true)
+}
+
 pub fn build_marker_era_drop(ctx: &mut MIRContext, val: BaseMIRValue) -> DiagnosticResult<bool> {
     ctx.append_inst(MIRInstruction::MarkerEraDrop { value: val });
 
@@ -871,6 +986,81 @@ pub fn build_call(
     return Ok(res);
 }
 
+pub fn build_indirect_call(
+    ctx: &mut MIRContext,
+    callee: BaseMIRValue,
+    args: Vec<BaseMIRValue>,
+) -> DiagnosticResult<Option<BaseMIRValue>> {
+    let arg_types = match &callee.vtype {
+        Type::Function(args, _) => args.clone(),
+        _ => unsure_panic!("tried calling a non function pointer value!"),
+    };
+
+    for (arg, t) in args.iter().zip(arg_types.iter()) {
+        if !arg.vtype.is_truly_eq(t) {
+            unsure_panic!("invalid function argument types!");
+        }
+    }
+
+    let res = ctx
+        .append_inst(MIRInstruction::IndirectCall {
+            callee,
+            arguments: args,
+        })
+        .val;
+
+    return Ok(res);
+}
+
+pub fn build_closure_call(
+    ctx: &mut MIRContext,
+    closure: BaseMIRValue,
+    args: Vec<BaseMIRValue>,
+) -> DiagnosticResult<Option<BaseMIRValue>> {
+    let arg_types = match &closure.vtype {
+        Type::Closure(args, _) => args.clone(),
+        _ => unsure_panic!("tried calling a non closure value!"),
+    };
+
+    for (arg, t) in args.iter().zip(arg_types.iter()) {
+        if !arg.vtype.is_truly_eq(t) {
+            unsure_panic!("invalid closure argument types!");
+        }
+    }
+
+    let res = ctx
+        .append_inst(MIRInstruction::ClosureCall {
+            closure,
+            arguments: args,
+        })
+        .val;
+
+    return Ok(res);
+}
+
+pub fn build_closure_create(
+    ctx: &mut MIRContext,
+    func: usize,
+    env: Option<MIRPointerValue>,
+) -> DiagnosticResult<BaseMIRValue> {
+    let res = ctx
+        .append_inst(MIRInstruction::ClosureCreate {
+            function: func,
+            env,
+        })
+        .get()?;
+
+    return Ok(res);
+}
+
+pub fn build_function_address(ctx: &mut MIRContext, func: usize) -> DiagnosticResult<BaseMIRValue> {
+    let res = ctx
+        .append_inst(MIRInstruction::FunctionAddress { function: func })
+        .get()?;
+
+    return Ok(res);
+}
+
 pub fn build_phi(
     ctx: &mut MIRContext,
     choices: Vec<(MIRBlockReference, BaseMIRValue)>,
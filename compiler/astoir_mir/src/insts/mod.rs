@@ -1,5 +1,7 @@
 //! The definitions for instructions within the MIR.
 
+use std::fmt::Display;
+
 use compiler_typing::{raw::RawType, tree::Type};
 
 use crate::{
@@ -12,6 +14,26 @@ use crate::{
 
 pub mod val;
 
+/// The integer operations which can overflow
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MIROverflowOperation {
+    Add,
+    Sub,
+    Mul,
+}
+
+impl Display for MIROverflowOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Add => "add",
+            Self::Sub => "sub",
+            Self::Mul => "mul",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
 /// An instruction inside of the MIR.
 #[derive(Clone)]
 pub enum MIRInstruction {
@@ -51,6 +73,25 @@ pub enum MIRInstruction {
         size: usize,
     },
 
+    IntegerToFloat {
+        val: MIRIntValue,
+        size: usize,
+        signed: bool,
+    }, // negative values are clamped to zero for unsigned floats
+    FloatToInteger {
+        val: MIRFloatValue,
+        size: usize,
+        signed: bool,
+    }, // rounds towards zero
+    FloatConvert {
+        val: MIRFloatValue,
+        size: usize,
+        signed: bool,
+    }, // negative values are clamped to zero for unsigned floats
+    FloatClampZero {
+        val: MIRFloatValue,
+    },
+
     // Arithmetrics
     IntegerAdd {
         signed: bool,
@@ -86,6 +127,21 @@ pub enum MIRInstruction {
         val: MIRIntValue,
     },
 
+    /// Whether the given operation overflows the integer type
+    IntegerOverflows {
+        operation: MIROverflowOperation,
+        signed: bool,
+        left: MIRIntValue,
+        right: MIRIntValue,
+    },
+    /// The given operation clamped to the bounds of the integer type
+    IntegerSaturating {
+        operation: MIROverflowOperation,
+        signed: bool,
+        left: MIRIntValue,
+        right: MIRIntValue,
+    },
+
     FloatAdd {
         signed: bool,
         fast: bool,
@@ -240,6 +296,25 @@ pub enum MIRInstruction {
         function: usize,
         arguments: Vec<BaseMIRValue>,
     },
+    /// Calls the function pointed to by a function pointer value
+    IndirectCall {
+        callee: BaseMIRValue,
+        arguments: Vec<BaseMIRValue>,
+    },
+    /// Obtains a function pointer to the given function
+    FunctionAddress {
+        function: usize,
+    },
+    /// Creates a closure from the given function and environment. The function takes the environment as first argument
+    ClosureCreate {
+        function: usize,
+        env: Option<MIRPointerValue>,
+    },
+    /// Calls the function of the closure with the closure environment and the given arguments
+    ClosureCall {
+        closure: BaseMIRValue,
+        arguments: Vec<BaseMIRValue>,
+    },
 
     // Pointer utils
     FieldPointer {
@@ -271,6 +346,12 @@ pub enum MIRInstruction {
         sz: usize,
     },
 
+    /// Aborts the program with the given message if the given condition doesn't hold. Used by the runtime checks such as bounds checks
+    Assert {
+        cond: MIRIntValue,
+        msg: MIRPointerValue,
+    },
+
     /// Indicates to the IR processor that this given value's era is finished and thus we drop the value
     MarkerEraDrop {
         value: BaseMIRValue,
@@ -287,6 +368,7 @@ impl MIRInstruction {
     pub fn has_return(&self, ctx: &MIRContext) -> bool {
         match self {
             Self::MarkerEraDrop { .. }
+            | Self::Assert { .. }
             | Self::UnconditionalBranch { .. }
             | Self::ConditionalBranch { .. }
             | Self::Return { .. }
@@ -304,6 +386,22 @@ impl MIRInstruction {
                 return func.return_type.is_some();
             }
 
+            Self::IndirectCall {
+                callee,
+                arguments: _,
+            } => match &callee.vtype {
+                Type::Function(_, ret) => return ret.is_some(),
+                _ => return false,
+            },
+
+            Self::ClosureCall {
+                closure,
+                arguments: _,
+            } => match &closure.vtype {
+                Type::Closure(_, ret) => return ret.is_some(),
+                _ => return false,
+            },
+
             _ => true,
         }
     }
@@ -351,6 +449,25 @@ impl MIRInstruction {
                 return Type::GenericLowered(RawType::Floating(*size, val.signed));
             }
 
+            Self::IntegerToFloat {
+                val: _,
+                size,
+                signed,
+            } => return Type::GenericLowered(RawType::Floating(*size, *signed)),
+            Self::FloatToInteger {
+                val: _,
+                size,
+                signed,
+            } => return Type::GenericLowered(RawType::Integer(*size, *signed)),
+            Self::FloatConvert {
+                val: _,
+                size,
+                signed,
+            } => return Type::GenericLowered(RawType::Floating(*size, *signed)),
+            Self::FloatClampZero { val } => {
+                return Type::GenericLowered(RawType::Floating(val.size, val.signed));
+            }
+
             Self::IntegerAdd {
                 signed,
                 fast: _,
@@ -385,6 +502,11 @@ impl MIRInstruction {
                 return Type::GenericLowered(RawType::Integer(val.size, true));
             }
 
+            Self::IntegerOverflows { .. } => return Type::GenericLowered(RawType::Boolean),
+            Self::IntegerSaturating { signed, left, .. } => {
+                return Type::GenericLowered(RawType::Integer(left.size, *signed));
+            }
+
             Self::FloatAdd {
                 signed,
                 fast: _,
@@ -494,6 +616,44 @@ impl MIRInstruction {
                 return func.return_type.clone().unwrap();
             }
 
+            Self::IndirectCall {
+                callee,
+                arguments: _,
+            } => match &callee.vtype {
+                Type::Function(_, Some(ret)) => return *ret.clone(),
+                _ => panic!("Tried using get_return_type on an indirect call without return type!"),
+            },
+
+            Self::FunctionAddress { function } => {
+                let func = &ctx.functions[function];
+
+                return Type::Function(
+                    func.arguments.iter().map(|t| Box::new(t.clone())).collect(),
+                    func.return_type.clone().map(Box::new),
+                );
+            }
+
+            Self::ClosureCreate { function, env: _ } => {
+                let func = &ctx.functions[function];
+
+                // The environment argument isn't part of the closure signature
+                return Type::Closure(
+                    func.arguments[1..]
+                        .iter()
+                        .map(|t| Box::new(t.clone()))
+                        .collect(),
+                    func.return_type.clone().map(Box::new),
+                );
+            }
+
+            Self::ClosureCall {
+                closure,
+                arguments: _,
+            } => match &closure.vtype {
+                Type::Closure(_, Some(ret)) => return *ret.clone(),
+                _ => panic!("Tried using get_return_type on a closure call without return type!"),
+            },
+
             Self::FieldPointer { .. } => return Type::GenericLowered(RawType::Pointer),
             Self::IndexPointer { .. } => return Type::GenericLowered(RawType::Pointer),
 
@@ -528,6 +688,16 @@ impl DisplayAstoIR for MIRInstruction {
             Self::DowncastFloat { val, size } => writeln!(f, "dfcast {} {}", val, size)?,
             Self::UpcastInteger { val, size } => writeln!(f, "uintcast {} {}", val, size)?,
             Self::UpcastFloat { val, size } => writeln!(f, "ufcast {} {}", val, size)?,
+            Self::IntegerToFloat { val, size, signed } => {
+                writeln!(f, "itof s{} {} {}", signed, val, size)?
+            }
+            Self::FloatToInteger { val, size, signed } => {
+                writeln!(f, "ftoi s{} {} {}", signed, val, size)?
+            }
+            Self::FloatConvert { val, size, signed } => {
+                writeln!(f, "fconv s{} {} {}", signed, val, size)?
+            }
+            Self::FloatClampZero { val } => writeln!(f, "fclampz {}", val)?,
 
             Self::IntegerAdd {
                 signed,
@@ -560,6 +730,18 @@ impl DisplayAstoIR for MIRInstruction {
                 right,
             } => writeln!(f, "imod s{} f{} {} {}", signed, fast, left, right)?,
             Self::IntegerNeg { val } => writeln!(f, "ineg {}", val)?,
+            Self::IntegerOverflows {
+                operation,
+                signed,
+                left,
+                right,
+            } => writeln!(f, "iovf {} s{} {} {}", operation, signed, left, right)?,
+            Self::IntegerSaturating {
+                operation,
+                signed,
+                left,
+                right,
+            } => writeln!(f, "isat {} s{} {} {}", operation, signed, left, right)?,
 
             Self::FloatAdd {
                 signed,
@@ -698,6 +880,33 @@ impl DisplayAstoIR for MIRInstruction {
                 write!(f, "\n")?;
             }
 
+            Self::IndirectCall { callee, arguments } => {
+                write!(f, "icall {}", callee)?;
+
+                for arg in arguments {
+                    write!(f, " {}", arg)?;
+                }
+
+                writeln!(f)?;
+            }
+
+            Self::FunctionAddress { function } => writeln!(f, "funcaddr {}", function)?,
+
+            Self::ClosureCreate { function, env } => match env {
+                Some(env) => writeln!(f, "closure {} {}", function, env)?,
+                None => writeln!(f, "closure {}", function)?,
+            },
+
+            Self::ClosureCall { closure, arguments } => {
+                write!(f, "closurecall {}", closure)?;
+
+                for arg in arguments {
+                    write!(f, " {}", arg)?;
+                }
+
+                writeln!(f)?;
+            }
+
             Self::FieldPointer { val, field } => writeln!(f, "fieldptr {} {}", val, field)?,
             Self::IndexPointer { val, index } => writeln!(f, "indptr {} {}", val, index)?,
 
@@ -706,6 +915,7 @@ impl DisplayAstoIR for MIRInstruction {
 
             Self::FuncArgumentGrab { ind, argtype: _ } => writeln!(f, "funcarg {}", ind)?,
 
+            Self::Assert { cond, msg } => writeln!(f, "assert {} {}", cond, msg)?,
             Self::MarkerEraDrop { value } => writeln!(f, ".marker_era_drop {}", value)?,
             Self::IRCast { val, to } => writeln!(f, ".ircastzz {} {}", val, to)?,
         }
@@ -22,6 +22,17 @@ impl MIRIntValue {
             });
         }
 
+        // Fixed point numbers are stored as integers of their full width
+        if let RawType::FixedPoint(number, fraction, signed) =
+            base.vtype.clone().as_generic_lowered()
+        {
+            return Ok(MIRIntValue {
+                base: base.clone(),
+                size: number + fraction,
+                signed,
+            });
+        }
+
         if let RawType::Boolean = base.vtype.clone().as_generic_lowered() {
             return Ok(MIRIntValue {
                 base: base.clone(),
@@ -1,35 +1,97 @@
 use astoir_hir::nodes::{HIRNode, HIRNodeKind};
 use astoir_mir::{
-    blocks::refer::MIRBlockReference,
-    builder::{build_index_pointer, build_load, build_store},
-    vals::base::BaseMIRValue,
+    blocks::{hints::MIRValueHint, refer::MIRBlockReference},
+    builder::{
+        build_index_pointer, build_load, build_stack_alloc, build_store, build_unsigned_int_const,
+    },
+    vals::{base::BaseMIRValue, ptr::MIRPointerValue, refer::MIRVariableReference},
 };
-use diagnostics::{DiagnosticResult, unsure_panic};
+use compiler_typing::{SizedType, tree::Type};
+use diagnostics::{DiagnosticResult, DiagnosticSpanOrigin, unsure_panic};
 
-use crate::{MIRLoweringContext, values::lower_hir_value};
+use crate::{
+    MIRLoweringContext, safety::are_runtime_checks_enabled, slices::build_bounds_check,
+    values::lower_hir_value, vars::lower_hir_variable_reference,
+};
 
-pub fn lower_hir_aray_index_access(
+/// Gets a pointer to the given array or pointer of arrays alongside its element count if known.
+/// The pointer is always hinted as an array to be indexable
+pub fn lower_hir_array_pointer(
     block: MIRBlockReference,
     node: Box<HIRNode>,
     ctx: &mut MIRLoweringContext,
-) -> DiagnosticResult<BaseMIRValue> {
-    if let HIRNodeKind::ArrayIndexAccess { val, index } = node.kind {
-        let array = lower_hir_value(block, val, ctx)?;
+) -> DiagnosticResult<(MIRPointerValue, Option<usize>)> {
+    // Arrays held by variables are accessed in place
+    if let HIRNodeKind::VariableReference { .. } = node.kind
+        && let MIRVariableReference::PointerReference(ptr) =
+            lower_hir_variable_reference(block, &node, ctx)?
+    {
+        let base: BaseMIRValue = ptr.clone().into();
 
-        if ctx
+        if let Type::Array(size, _) = ctx
             .mir_ctx
             .ssa_hints
-            .get_hint(array.get_ssa_index())
-            .is_pointer()
+            .get_hint(base.get_ssa_index())
+            .as_pointer()
         {
-            let index = lower_hir_value(block, index, ctx)?.as_int()?;
+            return Ok((ptr, Some(size)));
+        }
+    }
+
+    let val = lower_hir_value(block, node, ctx)?;
+
+    match val.vtype.clone() {
+        Type::Pointer(true, inner) => {
+            ctx.mir_ctx.ssa_hints.vec[val.get_ssa_index()] =
+                MIRValueHint::Pointer(Type::Array(0, inner));
 
-            let res = build_index_pointer(&mut ctx.mir_ctx, array.as_ptr()?, index)?;
+            return Ok((val.as_ptr()?, None));
+        }
+
+        Type::Array(size, _) => {
+            let ptr = build_stack_alloc(
+                &mut ctx.mir_ctx,
+                val.vtype
+                    .get_size(&val.vtype, false, &ctx.hir_ctx.global_scope.scope),
+                val.vtype.clone(),
+            )?;
+
+            build_store(
+                &mut ctx.mir_ctx,
+                &ctx.hir_ctx.global_scope.scope,
+                ptr.clone(),
+                val,
+            )?;
 
-            return build_load(&mut ctx.mir_ctx, res);
-        } else {
-            unsure_panic!("tried lowering a non SSA array!")
+            return Ok((ptr, Some(size)));
         }
+
+        _ => unsure_panic!("tried lowering a non array value as an array!"),
+    }
+}
+
+pub fn lower_hir_aray_index_access(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    let pos = node.get_pos();
+
+    if let HIRNodeKind::ArrayIndexAccess { val, index } = node.kind {
+        let (array, size) = lower_hir_array_pointer(block, val, ctx)?;
+        let index = lower_hir_value(block, index, ctx)?.as_int()?;
+
+        if let Some(size) = size
+            && are_runtime_checks_enabled(ctx)
+        {
+            let len = build_unsigned_int_const(&mut ctx.mir_ctx, size as u128, index.size)?;
+
+            build_bounds_check(ctx, &pos, index.clone(), len)?;
+        }
+
+        let res = build_index_pointer(&mut ctx.mir_ctx, array, index)?;
+
+        return build_load(&mut ctx.mir_ctx, res);
     }
 
     panic!("Invalid node type")
@@ -40,16 +102,26 @@ pub fn lower_hir_array_modify(
     node: Box<HIRNode>,
     ctx: &mut MIRLoweringContext,
 ) -> DiagnosticResult<bool> {
+    let pos = node.get_pos();
+
     if let HIRNodeKind::ArrayIndexModify {
         array,
         index,
         new_val,
     } = node.kind.clone()
     {
-        let array = lower_hir_value(block, array, ctx)?.as_ptr()?;
+        let (array, size) = lower_hir_array_pointer(block, array, ctx)?;
         let index = lower_hir_value(block, index, ctx)?.as_int()?;
         let val = lower_hir_value(block, new_val, ctx)?;
 
+        if let Some(size) = size
+            && are_runtime_checks_enabled(ctx)
+        {
+            let len = build_unsigned_int_const(&mut ctx.mir_ctx, size as u128, index.size)?;
+
+            build_bounds_check(ctx, &pos, index.clone(), len)?;
+        }
+
         let index_pointer = build_index_pointer(&mut ctx.mir_ctx, array, index)?;
 
         build_store(
@@ -0,0 +1,101 @@
+//! Lowering of bitfield members. Bitfields cannot be pointed to, they are instead read and written through their
+//! storage unit using shift and mask sequences. See `compiler_typing::bitfields` for the placement of bitfields.
+
+use astoir_mir::{
+    builder::{
+        build_bitwise_and, build_bitwise_or, build_int_shift_left, build_int_shift_right,
+        build_load, build_store, build_unsigned_int_const,
+    },
+    vals::{base::BaseMIRValue, int::MIRIntValue, ptr::MIRPointerValue},
+};
+use compiler_typing::{bitfields::BitfieldPlacement, raw::RawType, tree::Type};
+use diagnostics::{DiagnosticResult, unsure_panic};
+
+use crate::{MIRLoweringContext, casts::convert_int};
+
+/// Gets the size and signedness of the integer type of a bitfield
+fn get_bitfield_type(field_type: &Type) -> (usize, bool) {
+    match field_type.get_generic() {
+        RawType::Integer(size, signed) => (size, signed),
+        _ => unsure_panic!("bitfield was not an integer"),
+    }
+}
+
+/// Reads the bitfield from its storage unit. The value is extended to the type of the bitfield
+pub fn lower_bitfield_read(
+    ctx: &mut MIRLoweringContext,
+    unit_ptr: MIRPointerValue,
+    placement: &BitfieldPlacement,
+    field_type: &Type,
+) -> DiagnosticResult<BaseMIRValue> {
+    let (size, signed) = get_bitfield_type(field_type);
+
+    let mut val = build_load(&mut ctx.mir_ctx, unit_ptr)?.as_int()?;
+
+    if placement.shift != 0 {
+        let shift = build_unsigned_int_const(
+            &mut ctx.mir_ctx,
+            placement.shift as u128,
+            placement.unit_size,
+        )?;
+
+        val = build_int_shift_right(&mut ctx.mir_ctx, val, shift)?;
+    }
+
+    // Truncating first makes the extension use the sign bit of the bitfield
+    let val = convert_int(ctx, val, placement.width, false)?;
+    let val = convert_int(ctx, val, placement.width, signed)?;
+
+    return Ok(convert_int(ctx, val, size, signed)?.into());
+}
+
+/// Replaces the bits of the bitfield within the storage unit value by the given value
+pub fn build_bitfield_insert(
+    ctx: &mut MIRLoweringContext,
+    unit: MIRIntValue,
+    placement: &BitfieldPlacement,
+    val: MIRIntValue,
+) -> DiagnosticResult<MIRIntValue> {
+    let val = convert_int(ctx, val, placement.width, false)?;
+    let mut val = convert_int(ctx, val, placement.unit_size, false)?;
+
+    if placement.shift != 0 {
+        let shift = build_unsigned_int_const(
+            &mut ctx.mir_ctx,
+            placement.shift as u128,
+            placement.unit_size,
+        )?;
+
+        val = build_int_shift_left(&mut ctx.mir_ctx, val, shift)?;
+    }
+
+    let unit_mask = (1u128 << placement.unit_size) - 1;
+    let mask = !(((1u128 << placement.width) - 1) << placement.shift) & unit_mask;
+
+    let mask = build_unsigned_int_const(&mut ctx.mir_ctx, mask, placement.unit_size)?;
+
+    let cleared = build_bitwise_and(&mut ctx.mir_ctx, unit, mask)?;
+
+    return build_bitwise_or(&mut ctx.mir_ctx, cleared, val);
+}
+
+/// Writes the value into the bitfield by rewriting its whole storage unit
+pub fn lower_bitfield_write(
+    ctx: &mut MIRLoweringContext,
+    unit_ptr: MIRPointerValue,
+    placement: &BitfieldPlacement,
+    val: BaseMIRValue,
+) -> DiagnosticResult<bool> {
+    let unit = build_load(&mut ctx.mir_ctx, unit_ptr.clone())?.as_int()?;
+
+    let unit = build_bitfield_insert(ctx, unit, placement, val.as_int()?)?;
+
+    build_store(
+        &mut ctx.mir_ctx,
+        &ctx.hir_ctx.global_scope.scope,
+        unit_ptr,
+        unit.into(),
+    )?;
+
+    return Ok(true);
+}
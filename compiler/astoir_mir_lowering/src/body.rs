@@ -8,13 +8,18 @@ use diagnostics::{
 use crate::{
     MIRLoweringContext,
     arrays::lower_hir_array_modify,
+    closures::lower_hir_closure_environment,
     control::{
         forloop::{lower_hir_for_loop, lower_hir_ranged_for_loop},
         ifstatement::lower_hir_if_statement,
     },
-    funcs::lower_hir_function_call,
+    funcs::{lower_hir_function_call, lower_hir_indirect_call},
     introductions::handle_var_introduction_queue,
-    math::lower_hir_math_operation,
+    lru::{lower_hir_lru_base, lower_hir_lru_modify},
+    math::{lower_hir_math_operation, lower_hir_overflow_operation},
+    safety::lower_hir_unchecked_statement,
+    slices::lower_hir_slice_index_modify,
+    tuples::lower_hir_tuple_destructuring,
     values::lower_hir_value,
     vars::{lower_hir_deref_modify, lower_hir_variable_assignment, lower_hir_variable_declaration},
 };
@@ -37,6 +42,10 @@ pub fn lower_hir_body_member(
             let _ = lower_hir_variable_declaration(block, node, ctx, None)?;
             Ok(true)
         }
+        HIRNodeKind::TupleDestructuring { .. } => {
+            lower_hir_tuple_destructuring(block, node, ctx)?;
+            Ok(true)
+        }
         HIRNodeKind::MathOperation {
             left: _,
             right: _,
@@ -50,8 +59,17 @@ pub fn lower_hir_body_member(
 
             return Ok(true);
         }
+        HIRNodeKind::OverflowOperation { .. } => {
+            lower_hir_overflow_operation(block, node, ctx)?;
+
+            return Ok(true);
+        }
 
         HIRNodeKind::ArrayIndexModify { .. } => lower_hir_array_modify(block, node, ctx),
+        HIRNodeKind::SliceIndexModify { .. } => {
+            lower_hir_slice_index_modify(block, node, ctx)?;
+            Ok(true)
+        }
 
         HIRNodeKind::RangedForBlock { .. } => {
             let _ = lower_hir_ranged_for_loop(block, node, ctx)?;
@@ -66,6 +84,25 @@ pub fn lower_hir_body_member(
 
             return Ok(true);
         }
+        HIRNodeKind::IndirectCall { .. } => {
+            lower_hir_indirect_call(block, node, ctx)?;
+
+            return Ok(true);
+        }
+
+        HIRNodeKind::ClosureEnvironment { .. } => {
+            lower_hir_closure_environment(block, node, ctx)?;
+
+            return Ok(true);
+        }
+
+        HIRNodeKind::StructLRU { .. } => {
+            lower_hir_lru_base(block, node, ctx)?;
+
+            return Ok(true);
+        }
+
+        HIRNodeKind::StructLRUModify { .. } => lower_hir_lru_modify(block, node, ctx),
 
         HIRNodeKind::ReturnStatement { value } => {
             if value.is_some() {
@@ -88,6 +125,8 @@ pub fn lower_hir_body_member(
             return Ok(true);
         }
 
+        HIRNodeKind::UncheckedValue { .. } => lower_hir_unchecked_statement(block, node, ctx),
+
         _ => panic!("Invalid node"),
     };
 }
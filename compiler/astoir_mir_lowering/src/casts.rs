@@ -1,12 +1,26 @@
 use astoir_hir::nodes::{HIRNode, HIRNodeKind};
 use astoir_mir::{
     blocks::{hints::MIRValueHint, refer::MIRBlockReference},
-    vals::base::BaseMIRValue,
+    builder::{
+        build_downcast_int, build_field_pointer, build_float_convert, build_float_to_int,
+        build_int_to_float, build_ir_cast, build_load, build_select, build_signed_int_const,
+        build_stack_alloc, build_store, build_unsigned_int_const, build_upcast_int,
+    },
+    vals::{base::BaseMIRValue, int::MIRIntValue, ptr::MIRPointerValue},
 };
-use compiler_typing::raw::RawType;
+use compiler_typing::{SizedType, niches::EnumNiche, raw::RawType, tree::Type};
 use diagnostics::DiagnosticResult;
 
-use crate::{MIRLoweringContext, lower_hir_type, values::lower_hir_value};
+use crate::{
+    MIRLoweringContext,
+    fixed::{
+        convert_fixed_to_fixed, convert_fixed_to_float, convert_fixed_to_int,
+        convert_float_to_fixed, convert_int_to_fixed,
+    },
+    lower_hir_type,
+    type_tools::{build_enum_niche_pointer, is_niche_of_kind},
+    values::lower_hir_value,
+};
 
 pub fn lower_cast(
     block: MIRBlockReference,
@@ -26,18 +40,7 @@ pub fn lower_cast(
         let new_type = lower_hir_type(ctx, new_type)?;
 
         if old_type.get_generic().is_enum_child() && new_type.get_generic().is_enum_parent() {
-            match ctx.mir_ctx.ssa_hints.vec[value.get_ssa_index()] {
-                MIRValueHint::Pointer(_) => {
-                    ctx.mir_ctx.ssa_hints.vec[value.get_ssa_index()] =
-                        MIRValueHint::Pointer(new_type)
-                }
-                MIRValueHint::Value(_) => {
-                    ctx.mir_ctx.ssa_hints.vec[value.get_ssa_index()] = MIRValueHint::Value(new_type)
-                }
-                _ => panic!("constant enum cast"),
-            }
-
-            return Ok(value);
+            return Ok(cast_enum_child_to_parent(ctx, value, new_type));
         }
 
         if old_type.is_generic_direct()
@@ -59,8 +62,240 @@ pub fn lower_cast(
             return Ok(value);
         }
 
+        if (old_type.get_generic().is_enum_parent() || old_type.get_generic().is_enum_child())
+            && let RawType::Integer(size, signed) = new_type.get_generic()
+        {
+            return lower_enum_to_int_cast(value, size, signed, ctx);
+        }
+
+        if new_type.get_generic().is_enum_parent() && old_type.get_generic().is_integer() {
+            return lower_int_to_enum_cast(value.as_int()?, new_type, ctx);
+        }
+
+        if let Some(res) =
+            lower_numeric_cast(ctx, value, old_type.get_generic(), new_type.get_generic())?
+        {
+            return Ok(res);
+        }
+
         panic!("Bad cast {:#?} -> {:#?}", old_type, new_type);
     }
 
     panic!("Invalid node or cast!")
 }
+
+/// Uses an enum entry value as its parent enum. Both share the same storage, only the type of the value changes
+pub fn cast_enum_child_to_parent(
+    ctx: &mut MIRLoweringContext,
+    value: BaseMIRValue,
+    new_type: Type,
+) -> BaseMIRValue {
+    match ctx.mir_ctx.ssa_hints.vec[value.get_ssa_index()] {
+        MIRValueHint::Pointer(_) => {
+            ctx.mir_ctx.ssa_hints.vec[value.get_ssa_index()] =
+                MIRValueHint::Pointer(new_type.clone())
+        }
+        MIRValueHint::Value(_) => {
+            ctx.mir_ctx.ssa_hints.vec[value.get_ssa_index()] = MIRValueHint::Value(new_type.clone())
+        }
+        _ => panic!("constant enum cast"),
+    }
+
+    // The value itself must also carry the parent type, as function calls check it against their arguments
+    BaseMIRValue::new(value.get_ssa_index(), new_type)
+}
+
+/// Converts between integers, floats and fixed point numbers. Returns `None` if the types aren't both numeric
+fn lower_numeric_cast(
+    ctx: &mut MIRLoweringContext,
+    value: BaseMIRValue,
+    old_type: RawType,
+    new_type: RawType,
+) -> DiagnosticResult<Option<BaseMIRValue>> {
+    let res: BaseMIRValue = match (old_type, new_type) {
+        (RawType::Integer(_, _), RawType::Integer(size, signed)) => {
+            convert_int(ctx, value.as_int()?, size, signed)?.into()
+        }
+
+        (RawType::Integer(_, _), RawType::Floating(size, signed)) => {
+            build_int_to_float(&mut ctx.mir_ctx, value.as_int()?, size, signed)?.into()
+        }
+        (RawType::Floating(_, _), RawType::Integer(size, signed)) => {
+            build_float_to_int(&mut ctx.mir_ctx, value.as_float()?, size, signed)?.into()
+        }
+
+        (RawType::Integer(_, _), RawType::FixedPoint(number, fraction, signed)) => {
+            convert_int_to_fixed(ctx, value.as_int()?, number, fraction, signed)?
+        }
+        (RawType::FixedPoint(_, fraction, _), RawType::Integer(size, signed)) => {
+            convert_fixed_to_int(ctx, value.as_int()?, fraction, size, signed)?.into()
+        }
+
+        (
+            RawType::FixedPoint(_, fraction, _),
+            RawType::FixedPoint(new_number, new_fraction, new_signed),
+        ) => convert_fixed_to_fixed(
+            ctx,
+            value.as_int()?,
+            fraction,
+            new_number,
+            new_fraction,
+            new_signed,
+        )?,
+
+        (RawType::FixedPoint(_, fraction, _), RawType::Floating(size, signed)) => {
+            convert_fixed_to_float(ctx, value.as_int()?, fraction, size, signed)?.into()
+        }
+
+        (RawType::Floating(_, _), RawType::Floating(size, signed)) => {
+            build_float_convert(&mut ctx.mir_ctx, value.as_float()?, size, signed)?.into()
+        }
+        (RawType::Floating(_, _), RawType::FixedPoint(number, fraction, signed)) => {
+            convert_float_to_fixed(ctx, value.as_float()?, number, fraction, signed)?
+        }
+
+        _ => return Ok(None),
+    };
+
+    return Ok(Some(res));
+}
+
+/// Converts the integer into an integer of the given size and signedness by either upcasting or downcasting it
+pub(crate) fn convert_int(
+    ctx: &mut MIRLoweringContext,
+    val: MIRIntValue,
+    size: usize,
+    signed: bool,
+) -> DiagnosticResult<MIRIntValue> {
+    let mut val = val;
+
+    if val.size < size {
+        val = build_upcast_int(&mut ctx.mir_ctx, val, size)?;
+    } else if val.size > size {
+        val = build_downcast_int(&mut ctx.mir_ctx, val, size)?;
+    }
+
+    if val.signed != signed {
+        return build_ir_cast(
+            &mut ctx.mir_ctx,
+            val.into(),
+            Type::GenericLowered(RawType::Integer(size, signed)),
+        )?
+        .as_int();
+    }
+
+    return Ok(val);
+}
+
+/// Extracts the hint of the enum value as an integer of the given type
+fn lower_enum_to_int_cast(
+    value: BaseMIRValue,
+    size: usize,
+    signed: bool,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    let hint = ctx.mir_ctx.ssa_hints.get_hint(value.get_ssa_index());
+
+    let ptr = match hint {
+        MIRValueHint::Pointer(_) => value.as_ptr()?,
+        _ => {
+            let t = hint.get_type();
+
+            let ptr = build_stack_alloc(
+                &mut ctx.mir_ctx,
+                t.get_size(&t, false, &ctx.hir_ctx.global_scope.scope),
+                t,
+            )?;
+
+            build_store(
+                &mut ctx.mir_ctx,
+                &ctx.hir_ctx.global_scope.scope,
+                ptr.clone(),
+                value,
+            )?;
+
+            ptr
+        }
+    };
+
+    if let RawType::LoweredStruct(_, container) = hint.get_type().get_generic()
+        && let Some(niche) = container.lowered_enum_niche
+    {
+        return lower_niche_to_int_cast(ptr, &niche, size, signed, ctx);
+    }
+
+    let field_ptr = build_field_pointer(&mut ctx.mir_ctx, ptr, 0)?; // 0 = hint type index
+    let tag = build_load(&mut ctx.mir_ctx, field_ptr)?.as_int()?;
+
+    return Ok(convert_int(ctx, tag, size, signed)?.into());
+}
+
+/// Rebuilds the discriminant of a niche filled enum from its niche value
+fn lower_niche_to_int_cast(
+    ptr: MIRPointerValue,
+    niche: &EnumNiche,
+    size: usize,
+    signed: bool,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    let niche_ptr = build_enum_niche_pointer(ctx, ptr, niche)?;
+    let niche_val = build_load(&mut ctx.mir_ctx, niche_ptr)?.as_int()?;
+
+    let mut res = build_int_const(ctx, niche.dataful, size, signed)?;
+
+    for (discriminant, _) in &niche.values {
+        let cond = is_niche_of_kind(ctx, niche_val.clone(), niche, *discriminant)?;
+        let val = build_int_const(ctx, *discriminant, size, signed)?;
+
+        res = build_select(&mut ctx.mir_ctx, cond, val, res)?;
+    }
+
+    return Ok(res);
+}
+
+fn build_int_const(
+    ctx: &mut MIRLoweringContext,
+    val: i128,
+    size: usize,
+    signed: bool,
+) -> DiagnosticResult<BaseMIRValue> {
+    if signed {
+        return Ok(build_signed_int_const(&mut ctx.mir_ctx, val, size)?.into());
+    }
+
+    return Ok(build_unsigned_int_const(&mut ctx.mir_ctx, val as u128, size)?.into());
+}
+
+/// Builds an enum value whose hint is the given integer. Only fieldless enums can be built from an integer
+fn lower_int_to_enum_cast(
+    value: MIRIntValue,
+    enum_type: Type,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    let (hint_size, hint_signed) = match enum_type.get_generic() {
+        RawType::LoweredStruct(_, container) => match container.fields.vals[0].get_generic() {
+            RawType::Integer(size, signed) => (size, signed),
+            _ => panic!("Enum hint was not an integer"),
+        },
+        _ => panic!("Enum was not lowered as a struct"),
+    };
+
+    let ptr = build_stack_alloc(
+        &mut ctx.mir_ctx,
+        enum_type.get_size(&enum_type, false, &ctx.hir_ctx.global_scope.scope),
+        enum_type,
+    )?;
+
+    let tag = convert_int(ctx, value, hint_size, hint_signed)?;
+
+    let field_ptr = build_field_pointer(&mut ctx.mir_ctx, ptr.clone(), 0)?; // 0 = hint type index
+
+    build_store(
+        &mut ctx.mir_ctx,
+        &ctx.hir_ctx.global_scope.scope,
+        field_ptr,
+        tag.into(),
+    )?;
+
+    return build_load(&mut ctx.mir_ctx, ptr);
+}
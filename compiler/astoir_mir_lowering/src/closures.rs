@@ -0,0 +1,164 @@
+//! Closure related lowering.
+//!
+//! The environment of a closure is lowered into a layout struct allocated on the stack of the function creating the
+//! closure. Each field either holds a copy of a variable captured by value or a pointer to a variable captured by reference.
+
+use std::collections::HashMap;
+
+use astoir_hir::{
+    nodes::{HIRNode, HIRNodeKind},
+    structs::HIRClosureCapture,
+};
+use astoir_mir::{
+    blocks::{
+        MIRBlockVariableSSAHint, MIRBlockVariableType, hints::MIRValueHint,
+        refer::MIRBlockReference,
+    },
+    builder::{
+        build_closure_create, build_field_pointer, build_ir_cast, build_load, build_stack_alloc,
+        build_store,
+    },
+    vals::base::BaseMIRValue,
+};
+use compiler_typing::{SizedType, raw::RawType, structs::LoweredStructTypeContainer, tree::Type};
+use compiler_utils::utils::indexed::IndexStorage;
+use diagnostics::{DiagnosticResult, MaybeDiagnostic};
+
+use crate::{MIRLoweringContext, lower_hir_type};
+
+/// Lowers the type of the environment containing the given captures
+pub fn lower_closure_environment(
+    ctx: &MIRLoweringContext,
+    captures: &Vec<HIRClosureCapture>,
+) -> DiagnosticResult<Type> {
+    let mut container = LoweredStructTypeContainer {
+        fields: IndexStorage::new(),
+        functions: IndexStorage::new(),
+        is_lowered_enum_child: false,
+        is_lowered_enum_parent: false,
+        lowered_enum_child: None,
+        lowered_enum_parent: None,
+        lowered_enum_niche: None,
+        lowered_union_members: None,
+        lowered_bitfields: HashMap::new(),
+        hir_mir_indexes: HashMap::new(),
+    };
+
+    for capture in captures {
+        let lowered = lower_hir_type(ctx, capture.variable_type.clone())?;
+
+        if capture.by_ref {
+            container
+                .fields
+                .vals
+                .push(Type::Pointer(false, Box::new(lowered)));
+        } else {
+            container.fields.vals.push(lowered);
+        }
+    }
+
+    return Ok(Type::GenericLowered(RawType::LoweredStruct(
+        true, container,
+    )));
+}
+
+pub fn lower_hir_closure(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    if let HIRNodeKind::ClosureExpression {
+        function,
+        captures,
+        closure_type: _,
+    } = node.kind.clone()
+    {
+        if captures.is_empty() {
+            return build_closure_create(&mut ctx.mir_ctx, function, None);
+        }
+
+        let env_type = lower_closure_environment(ctx, &captures)?;
+
+        let env = build_stack_alloc(
+            &mut ctx.mir_ctx,
+            env_type.get_size(&env_type, false, &ctx.hir_ctx.global_scope.scope),
+            env_type,
+        )?;
+
+        for (ind, capture) in captures.iter().enumerate() {
+            let field = build_field_pointer(&mut ctx.mir_ctx, env.clone(), ind)?;
+            let var = ctx.mir_ctx.blocks[block].get_variable_ref(capture.outer)?;
+
+            let val: BaseMIRValue = if capture.by_ref {
+                var.as_pointer_ref()?.into()
+            } else {
+                var.read(block, &mut ctx.mir_ctx)?
+            };
+
+            build_store(
+                &mut ctx.mir_ctx,
+                &ctx.hir_ctx.global_scope.scope,
+                field,
+                val,
+            )?;
+        }
+
+        return build_closure_create(&mut ctx.mir_ctx, function, Some(env));
+    }
+
+    panic!("Invalid node")
+}
+
+/// Introduces the captured variables within the closure function from its environment
+pub fn lower_hir_closure_environment(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> MaybeDiagnostic {
+    if let HIRNodeKind::ClosureEnvironment { env, captures } = node.kind.clone() {
+        if captures.is_empty() {
+            return Ok(());
+        }
+
+        let env_type = lower_closure_environment(ctx, &captures)?;
+
+        let env_val = ctx.mir_ctx.blocks[block].get_variable_ref(env)?;
+        let env_val = env_val.read(block, &mut ctx.mir_ctx)?;
+
+        let env_ptr = build_ir_cast(
+            &mut ctx.mir_ctx,
+            env_val,
+            Type::GenericLowered(RawType::Pointer),
+        )?;
+
+        ctx.mir_ctx.ssa_hints.vec[env_ptr.get_ssa_index()] = MIRValueHint::Pointer(env_type);
+
+        let env_ptr = env_ptr.as_ptr()?;
+
+        for (ind, capture) in captures.iter().enumerate() {
+            let mut ptr = build_field_pointer(&mut ctx.mir_ctx, env_ptr.clone(), ind)?;
+
+            // The field holds the address of the variable captured by reference
+            if capture.by_ref {
+                let lowered = lower_hir_type(ctx, capture.variable_type.clone())?;
+                let loaded = build_load(&mut ctx.mir_ctx, ptr)?;
+
+                ctx.mir_ctx.ssa_hints.vec[loaded.get_ssa_index()] = MIRValueHint::Pointer(lowered);
+
+                ptr = loaded.as_ptr()?;
+            }
+
+            ctx.mir_ctx.blocks[block].variables.insert(
+                capture.inner,
+                MIRBlockVariableSSAHint {
+                    kind: MIRBlockVariableType::Pointer,
+                    hint: Some(ptr.into()),
+                },
+            );
+        }
+
+        return Ok(());
+    }
+
+    panic!("Invalid node")
+}
@@ -0,0 +1,289 @@
+//! Fixed point related lowering.
+//!
+//! Fixed point numbers are stored as integers of their full width holding the value scaled by `2^fraction`. Additions,
+//! subtractions and comparisons are therefore done on the raw integers directly. Multiplications and divisions are
+//! done on twice the width before being rescaled and rounded to the nearest representable value.
+
+use astoir_hir::nodes::HIRNode;
+use astoir_mir::{
+    builder::{
+        build_comp_eq, build_comp_lt, build_float_div, build_float_mul, build_float_to_int,
+        build_int_add, build_int_div, build_int_mul, build_int_to_float, build_ir_cast,
+        build_select, build_shift_left, build_shift_right, build_signed_float_const,
+        build_signed_int_const, build_unsigned_float_const, build_unsigned_int_const,
+        build_upcast_int,
+    },
+    vals::{base::BaseMIRValue, float::MIRFloatValue, int::MIRIntValue},
+};
+use compiler_typing::{raw::RawType, tree::Type};
+use compiler_utils::operators::{MathOperator, MathOperatorType};
+use diagnostics::{DiagnosticResult, DiagnosticSpanOrigin, builders::make_req_type_kind};
+
+use crate::{
+    MIRLoweringContext,
+    casts::convert_int,
+    math::lower_hir_math_operation_int,
+    safety::{are_runtime_checks_enabled, build_runtime_check},
+};
+
+/// Reinterprets the raw integer as a fixed point number of the given format
+fn build_fixed_from_raw(
+    ctx: &mut MIRLoweringContext,
+    raw: MIRIntValue,
+    number: usize,
+    fraction: usize,
+    signed: bool,
+) -> DiagnosticResult<BaseMIRValue> {
+    return build_ir_cast(
+        &mut ctx.mir_ctx,
+        raw.into(),
+        Type::GenericLowered(RawType::FixedPoint(number, fraction, signed)),
+    );
+}
+
+fn build_raw_const(
+    ctx: &mut MIRLoweringContext,
+    val: i128,
+    size: usize,
+    signed: bool,
+) -> DiagnosticResult<MIRIntValue> {
+    if signed {
+        return build_signed_int_const(&mut ctx.mir_ctx, val, size);
+    }
+
+    return build_unsigned_int_const(&mut ctx.mir_ctx, val as u128, size);
+}
+
+/// Narrows a widened result back into the given size, trapping if it doesn't fit when runtime checks are enabled
+fn build_fixed_narrow(
+    ctx: &mut MIRLoweringContext,
+    wide: MIRIntValue,
+    size: usize,
+    node: &HIRNode,
+) -> DiagnosticResult<MIRIntValue> {
+    let signed = wide.signed;
+    let wide_size = wide.size;
+
+    let res = convert_int(ctx, wide.clone(), size, signed)?;
+
+    if are_runtime_checks_enabled(ctx) {
+        let back = build_upcast_int(&mut ctx.mir_ctx, res.clone(), wide_size)?;
+        let cond = build_comp_eq(&mut ctx.mir_ctx, back, wide)?;
+
+        build_runtime_check(ctx, &node.get_pos(), cond, "arithmetic overflow")?;
+    }
+
+    return Ok(res);
+}
+
+/// Multiplies two raw fixed point values, rounding the result half up
+fn build_fixed_mul(
+    ctx: &mut MIRLoweringContext,
+    left: MIRIntValue,
+    right: MIRIntValue,
+    fraction: usize,
+    node: &HIRNode,
+) -> DiagnosticResult<MIRIntValue> {
+    let size = left.size;
+    let signed = left.signed;
+
+    let left = build_upcast_int(&mut ctx.mir_ctx, left, size * 2)?;
+    let right = build_upcast_int(&mut ctx.mir_ctx, right, size * 2)?;
+
+    // The product of two values always fits in twice their width
+    let mut res = build_int_mul(&mut ctx.mir_ctx, left, right, signed, false)?;
+
+    if fraction > 0 {
+        let half = build_raw_const(ctx, 1 << (fraction - 1), size * 2, signed)?;
+        let shift = build_raw_const(ctx, fraction as i128, size * 2, signed)?;
+
+        res = build_int_add(&mut ctx.mir_ctx, res, half, signed, false)?;
+        res = build_shift_right(&mut ctx.mir_ctx, res, shift)?;
+    }
+
+    return build_fixed_narrow(ctx, res, size, node);
+}
+
+/// Divides two raw fixed point values, rounding the result half away from zero
+fn build_fixed_div(
+    ctx: &mut MIRLoweringContext,
+    left: MIRIntValue,
+    right: MIRIntValue,
+    fraction: usize,
+    node: &HIRNode,
+) -> DiagnosticResult<MIRIntValue> {
+    let size = left.size;
+    let signed = left.signed;
+
+    let left = build_upcast_int(&mut ctx.mir_ctx, left, size * 2)?;
+    let right = build_upcast_int(&mut ctx.mir_ctx, right, size * 2)?;
+
+    // Computes the quotient with an extra fractional bit which is then used to round
+    let shift = build_raw_const(ctx, fraction as i128 + 1, size * 2, signed)?;
+    let left = build_shift_left(&mut ctx.mir_ctx, left, shift)?;
+
+    let quotient = build_int_div(&mut ctx.mir_ctx, left, right, signed, false)?;
+
+    let one = build_raw_const(ctx, 1, size * 2, signed)?;
+    let mut bias = one.clone();
+
+    if signed {
+        let zero = build_raw_const(ctx, 0, size * 2, signed)?;
+        let minus_one = build_raw_const(ctx, -1, size * 2, signed)?;
+
+        let negative = build_comp_lt(&mut ctx.mir_ctx, quotient.clone(), zero)?;
+
+        bias = build_select(&mut ctx.mir_ctx, negative, minus_one.into(), one.into())?.as_int()?;
+    }
+
+    let two = build_raw_const(ctx, 2, size * 2, signed)?;
+
+    let res = build_int_add(&mut ctx.mir_ctx, quotient, bias, signed, false)?;
+    let res = build_int_div(&mut ctx.mir_ctx, res, two, signed, false)?;
+
+    return build_fixed_narrow(ctx, res, size, node);
+}
+
+pub fn lower_hir_math_operation_fixed(
+    left: BaseMIRValue,
+    right: BaseMIRValue,
+    operator: MathOperator,
+    ctx: &mut MIRLoweringContext,
+    node: &HIRNode,
+) -> DiagnosticResult<BaseMIRValue> {
+    let (number, fraction, signed) = match left.vtype.get_generic() {
+        RawType::FixedPoint(number, fraction, signed) => (number, fraction, signed),
+        _ => return Err(make_req_type_kind(node, &"fixed point".to_string()).into()),
+    };
+
+    let res = match operator.operator {
+        MathOperatorType::Multiply => {
+            build_fixed_mul(ctx, left.as_int()?, right.as_int()?, fraction, node)?
+        }
+        MathOperatorType::Divide => {
+            build_fixed_div(ctx, left.as_int()?, right.as_int()?, fraction, node)?
+        }
+
+        MathOperatorType::ShiftLeft | MathOperatorType::ShiftRight => {
+            return Err(make_req_type_kind(node, &"integer".to_string()).into());
+        }
+
+        _ => lower_hir_math_operation_int(left, right, operator, ctx, node)?.as_int()?,
+    };
+
+    return build_fixed_from_raw(ctx, res, number, fraction, signed);
+}
+
+/// Converts a raw fixed point value into another fixed point format. Integers are fixed point values without any fraction bits
+fn convert_fixed_raw(
+    ctx: &mut MIRLoweringContext,
+    val: MIRIntValue,
+    fraction: usize,
+    new_size: usize,
+    new_fraction: usize,
+    new_signed: bool,
+) -> DiagnosticResult<MIRIntValue> {
+    let signed = val.signed;
+    let mut val = convert_int(ctx, val.clone(), val.size.max(new_size), signed)?;
+
+    if new_fraction > fraction {
+        let shift = build_raw_const(ctx, (new_fraction - fraction) as i128, val.size, signed)?;
+
+        val = build_shift_left(&mut ctx.mir_ctx, val, shift)?;
+    } else if new_fraction < fraction {
+        // Discarding fraction bits rounds towards negative infinity
+        let shift = build_raw_const(ctx, (fraction - new_fraction) as i128, val.size, signed)?;
+
+        val = build_shift_right(&mut ctx.mir_ctx, val, shift)?;
+    }
+
+    return convert_int(ctx, val, new_size, new_signed);
+}
+
+pub fn convert_int_to_fixed(
+    ctx: &mut MIRLoweringContext,
+    val: MIRIntValue,
+    number: usize,
+    fraction: usize,
+    signed: bool,
+) -> DiagnosticResult<BaseMIRValue> {
+    let raw = convert_fixed_raw(ctx, val, 0, number + fraction, fraction, signed)?;
+
+    return build_fixed_from_raw(ctx, raw, number, fraction, signed);
+}
+
+pub fn convert_fixed_to_int(
+    ctx: &mut MIRLoweringContext,
+    val: MIRIntValue,
+    fraction: usize,
+    size: usize,
+    signed: bool,
+) -> DiagnosticResult<MIRIntValue> {
+    return convert_fixed_raw(ctx, val, fraction, size, 0, signed);
+}
+
+pub fn convert_fixed_to_fixed(
+    ctx: &mut MIRLoweringContext,
+    val: MIRIntValue,
+    fraction: usize,
+    new_number: usize,
+    new_fraction: usize,
+    new_signed: bool,
+) -> DiagnosticResult<BaseMIRValue> {
+    let raw = convert_fixed_raw(
+        ctx,
+        val,
+        fraction,
+        new_number + new_fraction,
+        new_fraction,
+        new_signed,
+    )?;
+
+    return build_fixed_from_raw(ctx, raw, new_number, new_fraction, new_signed);
+}
+
+/// Builds the float constant fixed point values of the given fraction bits are scaled by
+fn build_fixed_scale(
+    ctx: &mut MIRLoweringContext,
+    fraction: usize,
+    size: usize,
+    signed: bool,
+) -> DiagnosticResult<MIRFloatValue> {
+    let scale = 2f64.powi(fraction as i32);
+
+    if signed {
+        return build_signed_float_const(&mut ctx.mir_ctx, scale, size);
+    }
+
+    return build_unsigned_float_const(&mut ctx.mir_ctx, scale, size);
+}
+
+pub fn convert_fixed_to_float(
+    ctx: &mut MIRLoweringContext,
+    val: MIRIntValue,
+    fraction: usize,
+    size: usize,
+    signed: bool,
+) -> DiagnosticResult<MIRFloatValue> {
+    let val = build_int_to_float(&mut ctx.mir_ctx, val, size, signed)?;
+    let scale = build_fixed_scale(ctx, fraction, size, signed)?;
+
+    return build_float_div(&mut ctx.mir_ctx, val, scale, signed, false);
+}
+
+/// Converts the float into a fixed point value, rounding towards zero
+pub fn convert_float_to_fixed(
+    ctx: &mut MIRLoweringContext,
+    val: MIRFloatValue,
+    number: usize,
+    fraction: usize,
+    signed: bool,
+) -> DiagnosticResult<BaseMIRValue> {
+    let float_signed = val.signed;
+    let scale = build_fixed_scale(ctx, fraction, val.size, float_signed)?;
+
+    let val = build_float_mul(&mut ctx.mir_ctx, val, scale, float_signed, false)?;
+    let raw = build_float_to_int(&mut ctx.mir_ctx, val, number + fraction, signed)?;
+
+    return build_fixed_from_raw(ctx, raw, number, fraction, signed);
+}
@@ -1,11 +1,14 @@
 use astoir_hir::nodes::{HIRNode, HIRNodeKind};
 use astoir_mir::{
-    blocks::refer::MIRBlockReference,
-    builder::{build_argument_grab, build_call},
+    blocks::{MIRBlockVariableSSAHint, MIRBlockVariableType, refer::MIRBlockReference},
+    builder::{
+        build_argument_grab, build_call, build_closure_call, build_function_address,
+        build_indirect_call, build_stack_alloc, build_store,
+    },
     funcs::MIRFunction,
     vals::base::BaseMIRValue,
 };
-use compiler_typing::TypedGlobalScopeEntry;
+use compiler_typing::{SizedType, TypedGlobalScopeEntry, tree::Type};
 use diagnostics::{DiagnosticResult, builders::make_expected_simple_error_originless};
 
 use crate::{MIRLoweringContext, body::lower_hir_body, lower_hir_type, values::lower_hir_value};
@@ -37,44 +40,47 @@ pub fn lower_hir_function_decl(
             ret_type = None
         }
 
-        let fns_ind = match &cctx.hir_ctx.global_scope.scope.entries[func_name].entry_type {
-            TypedGlobalScopeEntry::Function {
-                descriptor_ind,
-                impl_ind: _,
-            } => descriptor_ind,
-            TypedGlobalScopeEntry::ImplLessFunction(ind) => ind,
-            TypedGlobalScopeEntry::StructFunction {
-                descriptor_ind,
-                impl_ind: _,
-                struct_type: _,
-            } => descriptor_ind,
+        let block = lower_hir_function_entry(func_name, args, ret_type, requires_this, cctx)?;
+        lower_hir_body(block, body, cctx)?;
 
-            _ => {
-                return Err(make_expected_simple_error_originless(
-                    &"function".to_string(),
-                    &cctx.hir_ctx.global_scope.scope.entries[func_name].entry_type,
-                )
-                .into());
-            }
-        };
+        return Ok(true);
+    }
 
-        let fns = cctx.hir_ctx.global_scope.descriptors[*fns_ind].clone();
+    panic!("Invalid node")
+}
 
-        let name = fns.2.clone();
+pub fn lower_hir_struct_function_decl(
+    node: Box<HIRNode>,
+    cctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<bool> {
+    if let HIRNodeKind::StructFunctionDeclaration {
+        func_name,
+        arguments: _,
+        return_type: _,
+        body,
+        ctx: _,
+        requires_this,
+    } = node.kind.clone()
+    {
+        // The descriptor contains the resolved version of the arguments
+        let descriptor_ind = cctx.hir_ctx.global_scope.scope.entries[func_name]
+            .as_struct_function_unsafe()
+            .0;
 
-        let mut func = MIRFunction::new(name, args, ret_type, requires_this, func_name);
-        let block = func.append_entry_block(&mut cctx.mir_ctx);
+        let descriptor = cctx.hir_ctx.global_scope.descriptors[descriptor_ind].clone();
 
-        cctx.mir_ctx.writer.move_end(block);
+        let mut args = vec![];
 
-        let mut ind = 0;
-        for arg in &func.arguments {
-            build_argument_grab(&mut cctx.mir_ctx, ind, arg.clone())?;
-            ind += 1;
+        for argument in descriptor.1 {
+            args.push(lower_hir_type(cctx, argument.1)?);
         }
 
-        cctx.mir_ctx.append_function(func);
+        let ret_type = match descriptor.0 {
+            Some(t) => Some(lower_hir_type(cctx, t)?),
+            None => None,
+        };
 
+        let block = lower_hir_function_entry(func_name, args, ret_type, requires_this, cctx)?;
         lower_hir_body(block, body, cctx)?;
 
         return Ok(true);
@@ -83,6 +89,116 @@ pub fn lower_hir_function_decl(
     panic!("Invalid node")
 }
 
+/// Declares the given function and grabs its arguments. Gives back the entry block to lower the body into
+fn lower_hir_function_entry(
+    func_name: usize,
+    args: Vec<Type>,
+    ret_type: Option<Type>,
+    requires_this: bool,
+    cctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<MIRBlockReference> {
+    let fns_ind = match &cctx.hir_ctx.global_scope.scope.entries[func_name].entry_type {
+        TypedGlobalScopeEntry::Function {
+            descriptor_ind,
+            impl_ind: _,
+        } => descriptor_ind,
+        TypedGlobalScopeEntry::ImplLessFunction(ind) => ind,
+        TypedGlobalScopeEntry::StructFunction {
+            descriptor_ind,
+            impl_ind: _,
+            struct_type: _,
+        } => descriptor_ind,
+
+        _ => {
+            return Err(make_expected_simple_error_originless(
+                &"function".to_string(),
+                &cctx.hir_ctx.global_scope.scope.entries[func_name].entry_type,
+            )
+            .into());
+        }
+    };
+
+    let fns = cctx.hir_ctx.global_scope.descriptors[*fns_ind].clone();
+
+    let name = fns.2.clone();
+
+    let local_ctx = match &cctx.hir_ctx.global_scope.scope.entries[func_name].entry_type {
+        TypedGlobalScopeEntry::Function {
+            descriptor_ind: _,
+            impl_ind,
+        } => Some(cctx.hir_ctx.global_scope.contexts[*impl_ind].clone()),
+        TypedGlobalScopeEntry::StructFunction {
+            descriptor_ind: _,
+            impl_ind,
+            struct_type: _,
+        } => Some(cctx.hir_ctx.global_scope.contexts[*impl_ind].clone()),
+
+        _ => None,
+    };
+
+    let mut func = MIRFunction::new(name, args, ret_type, requires_this, func_name);
+    let block = func.append_entry_block(&mut cctx.mir_ctx);
+
+    cctx.mir_ctx.writer.move_end(block);
+
+    let mut ind = 0;
+    for arg in &func.arguments {
+        let val = build_argument_grab(&mut cctx.mir_ctx, ind, arg.clone())?;
+
+        let requires_address = match &local_ctx {
+            Some(local_ctx) => local_ctx
+                .variables
+                .get(ind)
+                .is_some_and(|var| var.requires_address),
+            None => false,
+        };
+
+        // Arguments whose address is taken, for instance when captured by reference, are moved to the stack
+        if requires_address {
+            let ptr = build_stack_alloc(
+                &mut cctx.mir_ctx,
+                arg.get_size(arg, false, &cctx.hir_ctx.global_scope.scope),
+                arg.clone(),
+            )?;
+
+            build_store(
+                &mut cctx.mir_ctx,
+                &cctx.hir_ctx.global_scope.scope,
+                ptr.clone(),
+                val,
+            )?;
+
+            cctx.mir_ctx.blocks[block].variables.insert(
+                ind,
+                MIRBlockVariableSSAHint {
+                    kind: MIRBlockVariableType::Pointer,
+                    hint: Some(ptr.into()),
+                },
+            );
+
+            ind += 1;
+            continue;
+        }
+
+        // Arguments are SSA variables holding the grabbed value
+        cctx.mir_ctx.blocks[block].variables.insert(
+            ind,
+            MIRBlockVariableSSAHint {
+                kind: MIRBlockVariableType::SSA,
+                hint: Some(val),
+            },
+        );
+
+        ind += 1;
+    }
+
+    cctx.mir_ctx.append_function(func);
+
+    cctx.unchecked = local_ctx.is_some_and(|local_ctx| local_ctx.unchecked);
+
+    return Ok(block);
+}
+
 pub fn lower_hir_extern_decl(
     node: Box<HIRNode>,
     ctx: &mut MIRLoweringContext,
@@ -172,3 +288,40 @@ pub fn lower_hir_function_call(
 
     panic!("Invalid node")
 }
+
+pub fn lower_hir_indirect_call(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<Option<BaseMIRValue>> {
+    if let HIRNodeKind::IndirectCall { callee, arguments } = node.kind.clone() {
+        let callee = lower_hir_value(block, callee, ctx)?;
+
+        let mut args = vec![];
+
+        for arg in arguments {
+            let mir_val = lower_hir_value(block, arg, ctx)?;
+
+            args.push(mir_val);
+        }
+
+        if callee.vtype.is_closure() {
+            return build_closure_call(&mut ctx.mir_ctx, callee, args);
+        }
+
+        return build_indirect_call(&mut ctx.mir_ctx, callee, args);
+    }
+
+    panic!("Invalid node")
+}
+
+pub fn lower_hir_function_reference(
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    if let HIRNodeKind::FunctionReference { index } = node.kind.clone() {
+        return build_function_address(&mut ctx.mir_ctx, index);
+    }
+
+    panic!("Invalid node")
+}
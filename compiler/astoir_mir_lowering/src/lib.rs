@@ -6,21 +6,37 @@ use astoir_hir::{
 };
 use astoir_mir::{ctx::MIRContext, funcs::MIRFunction};
 use compiler_typing::{
-    SizedType, TypedGlobalScopeEntry, raw::RawType, structs::LoweredStructTypeContainer, tree::Type,
+    TypedGlobalScopeEntry,
+    bitfields::StorageMember,
+    layout::TypeLayout,
+    raw::RawType,
+    structs::{LoweredStructTypeContainer, RawStructTypeContainer},
+    tree::Type,
 };
 use compiler_utils::utils::indexed::IndexStorage;
-use diagnostics::{DiagnosticResult, unsure_panic};
+use diagnostics::{DiagnosticResult, MaybeDiagnostic, unsure_panic};
 
-use crate::funcs::{lower_hir_extern_decl, lower_hir_function_decl};
+use crate::{
+    funcs::{lower_hir_extern_decl, lower_hir_function_decl, lower_hir_struct_function_decl},
+    safety::SafetyLevel,
+    slices::lower_hir_slice,
+    tuples::lower_hir_tuple,
+};
 
 pub mod arrays;
+pub mod bitfields;
 pub mod body;
 pub mod casts;
+pub mod closures;
 pub mod control;
+pub mod fixed;
 pub mod funcs;
 pub mod introductions;
 pub mod lru;
 pub mod math;
+pub mod safety;
+pub mod slices;
+pub mod tuples;
 pub mod type_tools;
 pub mod values;
 pub mod vars;
@@ -29,6 +45,11 @@ pub struct MIRLoweringContext {
     pub hir_ctx: HIRContext,
     pub mir_ctx: MIRContext,
     pub block_introduction_var_queue: Vec<Box<HIRNode>>,
+
+    /// The compiler-wide safety level
+    pub safety: SafetyLevel,
+    /// Whether the element currently being lowered opted-out of runtime checks
+    pub unchecked: bool,
 }
 
 pub fn lower_hir_top_level(
@@ -38,6 +59,7 @@ pub fn lower_hir_top_level(
     return match node.kind {
         HIRNodeKind::FunctionDeclaration { .. } => lower_hir_function_decl(node, ctx),
         HIRNodeKind::ExternFunctionDeclaration { .. } => lower_hir_extern_decl(node, ctx),
+        HIRNodeKind::StructFunctionDeclaration { .. } => lower_hir_struct_function_decl(node, ctx),
         HIRNodeKind::StructDeclaration { .. } => {
             // Since Struct declarations are already fulled lowered in HIR, we do need handling here!
 
@@ -48,13 +70,48 @@ pub fn lower_hir_top_level(
     };
 }
 
-pub fn lower_hir(ctx: HIRContext) -> DiagnosticResult<MIRContext> {
+/// Declares every function with an implementation beforehand. Allows for functions to be called before being lowered
+fn declare_hir_functions(ctx: &mut MIRLoweringContext) -> MaybeDiagnostic {
+    for entry in ctx.hir_ctx.global_scope.scope.entries.clone() {
+        let descriptor_ind = match entry.entry_type {
+            TypedGlobalScopeEntry::Function { descriptor_ind, .. } => descriptor_ind,
+            TypedGlobalScopeEntry::StructFunction { descriptor_ind, .. } => descriptor_ind,
+
+            _ => continue,
+        };
+
+        let descriptor = ctx.hir_ctx.global_scope.descriptors[descriptor_ind].clone();
+
+        let mut args = vec![];
+
+        for argument in descriptor.1 {
+            args.push(lower_hir_type(ctx, argument.1)?);
+        }
+
+        let ret_type = match descriptor.0 {
+            Some(t) => Some(lower_hir_type(ctx, t)?),
+            None => None,
+        };
+
+        let func = MIRFunction::new(descriptor.2, args, ret_type, false, entry.parent_index);
+
+        ctx.mir_ctx.append_function(func);
+    }
+
+    return Ok(());
+}
+
+pub fn lower_hir(ctx: HIRContext, safety: SafetyLevel) -> DiagnosticResult<MIRContext> {
     let mut lowering_ctx = MIRLoweringContext {
         hir_ctx: ctx,
         mir_ctx: MIRContext::new(),
         block_introduction_var_queue: vec![],
+        safety,
+        unchecked: false,
     };
 
+    declare_hir_functions(&mut lowering_ctx)?;
+
     for entry in lowering_ctx.hir_ctx.global_scope.scope.entries.clone() {
         match entry.entry_type {
             TypedGlobalScopeEntry::Function {
@@ -66,6 +123,16 @@ pub fn lower_hir(ctx: HIRContext) -> DiagnosticResult<MIRContext> {
                 lower_hir_top_level(node, &mut lowering_ctx)?;
             }
 
+            TypedGlobalScopeEntry::StructFunction {
+                descriptor_ind: _,
+                impl_ind,
+                struct_type: _,
+            } => {
+                let node = lowering_ctx.hir_ctx.global_scope.implementations[impl_ind].clone();
+
+                lower_hir_top_level(node, &mut lowering_ctx)?;
+            }
+
             TypedGlobalScopeEntry::TypeAlias(_) => continue,
             TypedGlobalScopeEntry::Type(_) => continue,
 
@@ -118,14 +185,52 @@ pub fn lower_hir_generic(
                 is_lowered_enum_parent: false,
                 lowered_enum_child: None,
                 lowered_enum_parent: None,
+                lowered_enum_niche: None,
+                lowered_union_members: None,
+                lowered_bitfields: HashMap::new(),
                 hir_mir_indexes: HashMap::new(),
             };
 
-            for field in &b.fields.vals {
-                lowered_container
-                    .fields
-                    .vals
-                    .push(lower_hir_type(ctx, field.clone().resolve(t))?);
+            if b.is_union {
+                return lower_hir_union(ctx, t, generic, b, lowered_container);
+            }
+
+            // Non layout structs may reorder their fields, see `compiler_typing::layout`
+            let order = b.get_field_order(t, !*a, &ctx.hir_ctx.global_scope.scope);
+
+            // Consecutive bitfields share a single storage unit, see `compiler_typing::bitfields`
+            let (members, placements) = b.get_storage_members(&order);
+
+            for member in members {
+                let lowered_ind = lowered_container.fields.vals.len();
+
+                match member {
+                    StorageMember::Field(ind) => {
+                        lowered_container.append_hir_index_conv(ind, lowered_ind);
+
+                        lowered_container
+                            .fields
+                            .vals
+                            .push(lower_hir_type(ctx, b.fields.vals[ind].clone().resolve(t))?);
+                    }
+
+                    StorageMember::Unit(size, contained) => {
+                        for ind in contained {
+                            let field_type =
+                                lower_hir_type(ctx, b.fields.vals[ind].clone().resolve(t))?;
+
+                            lowered_container.append_hir_index_conv(ind, lowered_ind);
+                            lowered_container
+                                .lowered_bitfields
+                                .insert(ind, (placements[&ind], field_type));
+                        }
+
+                        lowered_container
+                            .fields
+                            .vals
+                            .push(Type::GenericLowered(RawType::Integer(size, false)));
+                    }
+                }
             }
 
             return Ok(Type::GenericLowered(RawType::LoweredStruct(
@@ -135,6 +240,14 @@ pub fn lower_hir_generic(
         }
 
         RawType::EnumEntry(container) => {
+            let parent =
+                match &ctx.hir_ctx.global_scope.scope.entries[container.parent].as_type_unsafe() {
+                    RawType::Enum(container) => container.clone(),
+                    _ => panic!("Enum parent not enum"),
+                };
+
+            let niche = parent.get_niche(t, &ctx.hir_ctx.global_scope.scope);
+
             let mut lowered_container = LoweredStructTypeContainer {
                 fields: IndexStorage::new(),
                 functions: IndexStorage::new(),
@@ -142,30 +255,40 @@ pub fn lower_hir_generic(
                 is_lowered_enum_parent: false,
                 lowered_enum_child: Some(container.clone()),
                 lowered_enum_parent: None,
+                lowered_enum_niche: niche.clone(),
+                lowered_union_members: None,
+                lowered_bitfields: HashMap::new(),
                 hir_mir_indexes: HashMap::new(),
             };
 
-            let parent =
-                match &ctx.hir_ctx.global_scope.scope.entries[container.parent].as_type_unsafe() {
-                    RawType::Enum(container) => container.clone(),
-                    _ => panic!("Enum parent not enum"),
-                };
-
-            lowered_container
-                .fields
-                .vals
-                .push(Type::GenericLowered(parent.get_hint_type())); // Enum entry hint
+            // Every entry of a niche filled enum shares the fields of the dataful entry and has no hint
+            let fields = match &niche {
+                Some(niche) => parent
+                    .get_entry_by_discriminant(niche.dataful)
+                    .unwrap()
+                    .fields
+                    .vals
+                    .clone(),
+                None => {
+                    lowered_container
+                        .fields
+                        .vals
+                        .push(Type::GenericLowered(parent.get_hint_type())); // Enum entry hint
+
+                    container.fields.vals.clone()
+                }
+            };
 
-            let mut ind = 0;
-            for field in &container.fields.vals {
-                lowered_container.append_hir_index_conv(ind, lowered_container.fields.vals.len()); // Allow for LRU to work correctly
+            for (ind, field) in fields.into_iter().enumerate() {
+                if ind < container.fields.vals.len() {
+                    lowered_container
+                        .append_hir_index_conv(ind, lowered_container.fields.vals.len()); // Allow for LRU to work correctly
+                }
 
                 lowered_container
                     .fields
                     .vals
                     .push(lower_hir_type(ctx, field.clone().resolve(t))?);
-
-                ind += 1;
             }
 
             return Ok(Type::GenericLowered(RawType::LoweredStruct(
@@ -175,6 +298,16 @@ pub fn lower_hir_generic(
         }
 
         RawType::Enum(container) => {
+            // The container contained in the type might be outdated, for instance lacking the functions of the enum
+            let container = &match ctx.hir_ctx.global_scope.scope.entries[container.self_ref]
+                .as_type_unsafe()
+            {
+                RawType::Enum(container) => container,
+                _ => panic!("Enum type not enum"),
+            };
+
+            let niche = container.get_niche(t, &ctx.hir_ctx.global_scope.scope);
+
             let mut lowered_container = LoweredStructTypeContainer {
                 fields: IndexStorage::new(),
                 functions: IndexStorage::new(),
@@ -182,24 +315,35 @@ pub fn lower_hir_generic(
                 is_lowered_enum_parent: true,
                 lowered_enum_parent: Some(container.clone()),
                 lowered_enum_child: None,
+                lowered_enum_niche: niche.clone(),
+                lowered_union_members: None,
+                lowered_bitfields: HashMap::new(),
                 hir_mir_indexes: HashMap::new(),
             };
 
-            let mut entry_size = 0;
-
             let info = t.get_generic_info();
 
-            for entry in &container.entries {
+            // Niche filled enums are stored as their dataful entry
+            if let Some(niche) = niche {
+                let dataful = container.get_entry_by_discriminant(niche.dataful).unwrap();
+
                 let lowered = lower_hir_type(
                     ctx,
-                    Type::Generic(entry.1.clone(), info.0.clone(), info.1.clone()),
+                    Type::Generic(
+                        RawType::EnumEntry(dataful.clone()),
+                        info.0.clone(),
+                        info.1.clone(),
+                    ),
                 )?;
 
-                entry_size = entry_size.max(lowered.get_generic().get_size(
-                    &lowered,
+                if let RawType::LoweredStruct(_, entry) = lowered.get_generic() {
+                    lowered_container.fields = entry.fields;
+                }
+
+                return Ok(Type::GenericLowered(RawType::LoweredStruct(
                     false,
-                    &ctx.hir_ctx.global_scope.scope,
-                ))
+                    lowered_container,
+                )));
             }
 
             lowered_container
@@ -207,10 +351,19 @@ pub fn lower_hir_generic(
                 .vals
                 .push(Type::GenericLowered(container.get_hint_type()));
 
-            lowered_container
-                .fields
-                .vals
-                .push(Type::GenericLowered(RawType::Integer(entry_size, false)));
+            // Fieldless enums are only made of their hint
+            if !container.is_fieldless() {
+                let payload = container.get_payload_layout(t, &ctx.hir_ctx.global_scope.scope);
+
+                // The payload is stored as integers of its alignment in order to keep the alignment of every entry
+                lowered_container.fields.vals.push(Type::Array(
+                    payload.size / payload.alignment,
+                    Box::new(Type::GenericLowered(RawType::Integer(
+                        payload.alignment,
+                        false,
+                    ))),
+                ));
+            }
 
             return Ok(Type::GenericLowered(RawType::LoweredStruct(
                 false,
@@ -222,6 +375,57 @@ pub fn lower_hir_generic(
     };
 }
 
+/// Lowers a union. Unions are stored as their most aligned member followed by enough padding to contain every other member.
+/// Members are then accessed by casting the pointer to the union, see `lru::lower_hir_lru_step`
+fn lower_hir_union(
+    ctx: &MIRLoweringContext,
+    t: &Type,
+    generic: &RawType,
+    container: &RawStructTypeContainer,
+    mut lowered_container: LoweredStructTypeContainer,
+) -> DiagnosticResult<Type> {
+    let storage = &ctx.hir_ctx.global_scope.scope;
+
+    let mut members = vec![];
+
+    for field in &container.fields.vals {
+        members.push(lower_hir_type(ctx, field.clone().resolve(t))?);
+    }
+
+    let mut base: Option<(Type, TypeLayout)> = None;
+
+    for member in &members {
+        let layout = member.get_layout(storage);
+
+        if base
+            .as_ref()
+            .is_none_or(|(_, b)| layout.alignment > b.alignment)
+        {
+            base = Some((member.clone(), layout));
+        }
+    }
+
+    if let Some((member, member_layout)) = base {
+        let size = generic.get_layout(t, storage).size;
+
+        lowered_container.fields.vals.push(member);
+
+        if member_layout.size < size {
+            lowered_container.fields.vals.push(Type::Array(
+                (size - member_layout.size) / 8,
+                Box::new(Type::GenericLowered(RawType::Integer(8, false))),
+            ));
+        }
+    }
+
+    lowered_container.lowered_union_members = Some(members);
+
+    return Ok(Type::GenericLowered(RawType::LoweredStruct(
+        true,
+        lowered_container,
+    )));
+}
+
 pub fn lower_hir_type(ctx: &MIRLoweringContext, t: Type) -> DiagnosticResult<Type> {
     match &t {
         Type::Generic(a, _, _) => return lower_hir_generic(ctx, &t, a),
@@ -241,6 +445,36 @@ pub fn lower_hir_type(ctx: &MIRLoweringContext, t: Type) -> DiagnosticResult<Typ
                 *inner.clone(),
             )?)));
         }
+        Type::Function(args, ret) => {
+            let mut lowered_args = vec![];
+
+            for arg in args {
+                lowered_args.push(Box::new(lower_hir_type(ctx, *arg.clone())?));
+            }
+
+            let lowered_ret = match ret {
+                Some(v) => Some(Box::new(lower_hir_type(ctx, *v.clone())?)),
+                None => None,
+            };
+
+            return Ok(Type::Function(lowered_args, lowered_ret));
+        }
+        Type::Closure(args, ret) => {
+            let mut lowered_args = vec![];
+
+            for arg in args {
+                lowered_args.push(Box::new(lower_hir_type(ctx, *arg.clone())?));
+            }
+
+            let lowered_ret = match ret {
+                Some(v) => Some(Box::new(lower_hir_type(ctx, *v.clone())?)),
+                None => None,
+            };
+
+            return Ok(Type::Closure(lowered_args, lowered_ret));
+        }
+        Type::Tuple(elements) => return lower_hir_tuple(ctx, elements),
+        Type::Slice(inner) => return lower_hir_slice(ctx, *inner.clone()),
 
         _ => unsure_panic!("type is already lowered"),
     }
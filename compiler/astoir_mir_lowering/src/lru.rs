@@ -4,26 +4,66 @@ use astoir_hir::{
 };
 use astoir_mir::{
     blocks::refer::MIRBlockReference,
-    builder::build_field_pointer,
+    builder::{build_call, build_field_pointer, build_stack_alloc, build_store},
     vals::{base::BaseMIRValue, refer::MIRVariableReference},
 };
-use compiler_typing::raw::RawType;
+use compiler_typing::{
+    SizedType, TypedGlobalScopeEntry, bitfields::BitfieldPlacement, raw::RawType, tree::Type,
+};
+use compiler_utils::hash::HashedString;
 use diagnostics::{DiagnosticResult, unsure_panic};
 
-use crate::MIRLoweringContext;
+use crate::{
+    MIRLoweringContext,
+    bitfields::{lower_bitfield_read, lower_bitfield_write},
+    casts::cast_enum_child_to_parent,
+    type_tools::build_union_member_pointer,
+    values::lower_hir_value,
+};
+
+/// Stores the given value on the stack in order to obtain a pointer to it. Used on struct values that are not stored in variables (arguments, function results)
+fn lower_hir_lru_spill(
+    val: BaseMIRValue,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    let ptr = build_stack_alloc(
+        &mut ctx.mir_ctx,
+        val.vtype
+            .get_size(&val.vtype, false, &ctx.hir_ctx.global_scope.scope),
+        val.vtype.clone(),
+    )?;
+
+    build_store(
+        &mut ctx.mir_ctx,
+        &ctx.hir_ctx.global_scope.scope,
+        ptr.clone(),
+        val,
+    )?;
+
+    return Ok(ptr.into());
+}
 
+/// A bitfield reached by the chain alongside its type
+type LRUBitfield = (BitfieldPlacement, Type);
+
+/// Lowers a variable step. Bitfields cannot be pointed to, a pointer to their storage unit is returned alongside their placement instead.
 pub fn lower_hir_lru_step(
     block: MIRBlockReference,
     step: StructLRUStep,
     ctx: &mut MIRLoweringContext,
     curr: Option<BaseMIRValue>,
-) -> DiagnosticResult<BaseMIRValue> {
+) -> DiagnosticResult<(BaseMIRValue, Option<LRUBitfield>)> {
     if let StructLRUStep::VariableStep { variable } = step {
         if curr.is_none() {
-            return Ok(ctx.mir_ctx.blocks[block]
-                .get_variable_ref(variable)?
-                .as_pointer_ref()?
-                .into());
+            let var = ctx.mir_ctx.blocks[block].get_variable_ref(variable)?;
+
+            if !var.is_pointer_ref() {
+                let val = var.read(block, &mut ctx.mir_ctx)?;
+
+                return Ok((lower_hir_lru_spill(val, ctx)?, None));
+            }
+
+            return Ok((var.as_pointer_ref()?.into(), None));
         }
 
         let curr = curr.unwrap();
@@ -40,33 +80,186 @@ pub fn lower_hir_lru_step(
             _ => unsure_panic!("lower_hir_lru_step curr was not an actual thing"),
         };
 
-        return Ok(build_field_pointer(
+        if let Some(members) = &struct_type.lowered_union_members {
+            let member = members[variable].clone();
+
+            return Ok((build_union_member_pointer(ctx, ptr, member)?.into(), None));
+        }
+
+        let field_ptr = build_field_pointer(
             &mut ctx.mir_ctx,
             ptr,
             struct_type.resolve_hir_index(variable),
-        )?
-        .into());
+        )?;
+
+        return Ok((
+            field_ptr.into(),
+            struct_type.lowered_bitfields.get(&variable).cloned(),
+        ));
     }
 
     panic!("Invalid step!")
 }
 
-pub fn lower_hir_lru(
+/// Lowers a function call step. Struct functions requiring `this` are given the current value of the chain as first argument.
+pub fn lower_hir_lru_call(
+    block: MIRBlockReference,
+    step: StructLRUStep,
+    ctx: &mut MIRLoweringContext,
+    curr: Option<BaseMIRValue>,
+) -> DiagnosticResult<Option<BaseMIRValue>> {
+    if let StructLRUStep::FunctionCall { func, args } = step {
+        let descriptor_ind = match &ctx.hir_ctx.global_scope.scope.entries[func].entry_type {
+            TypedGlobalScopeEntry::Function { descriptor_ind, .. } => *descriptor_ind,
+            TypedGlobalScopeEntry::ImplLessFunction(ind) => *ind,
+            TypedGlobalScopeEntry::StructFunction { descriptor_ind, .. } => *descriptor_ind,
+
+            _ => unsure_panic!("lower_hir_lru_call func was not a function"),
+        };
+
+        let requires_this = ctx.hir_ctx.global_scope.descriptors[descriptor_ind]
+            .1
+            .first()
+            .is_some_and(|arg| arg.0 == HashedString::new("this".to_string()).hash);
+
+        let mut arguments = vec![];
+
+        if let Some(curr) = curr
+            && requires_this
+        {
+            let val = MIRVariableReference::from(curr.as_ptr()?);
+            let mut val = val.read(block, &mut ctx.mir_ctx)?;
+
+            // Enum functions called on an entry are given the entry as the enum itself
+            let this_type = ctx.mir_ctx.functions[&func].arguments[0].clone();
+
+            if val.vtype.get_generic().is_enum_child() && this_type.get_generic().is_enum_parent() {
+                val = cast_enum_child_to_parent(ctx, val, this_type);
+            }
+
+            arguments.push(val);
+        }
+
+        for arg in args {
+            arguments.push(lower_hir_value(block, arg, ctx)?);
+        }
+
+        return build_call(&mut ctx.mir_ctx, func, func, arguments);
+    }
+
+    panic!("Invalid step!")
+}
+
+/// Lowers the steps of the LRU chain. Returns the last value of the chain, whether said value is a value rather than a pointer and the bitfield it points to if any
+fn lower_hir_lru_steps(
     block: MIRBlockReference,
     node: Box<HIRNode>,
     ctx: &mut MIRLoweringContext,
-) -> DiagnosticResult<BaseMIRValue> {
+) -> DiagnosticResult<(Option<BaseMIRValue>, bool, Option<LRUBitfield>)> {
     if let HIRNodeKind::StructLRU { steps, last: _ } = node.kind {
-        let mut curr = lower_hir_lru_step(block, steps[0].clone(), ctx, None)?;
+        let mut curr: Option<BaseMIRValue> = None;
+
+        // Function call results are values rather than pointers
+        let mut curr_is_value = false;
+        let mut bitfield: Option<LRUBitfield> = None;
+
+        for step in steps {
+            if let Some((placement, field_type)) = bitfield.take() {
+                let unit_ptr = curr.unwrap().as_ptr()?;
+
+                curr = Some(lower_bitfield_read(ctx, unit_ptr, &placement, &field_type)?);
+                curr_is_value = true;
+            }
+
+            if curr_is_value {
+                curr = Some(lower_hir_lru_spill(curr.unwrap(), ctx)?);
+            }
+
+            match step {
+                StructLRUStep::FunctionCall { .. } => {
+                    curr = lower_hir_lru_call(block, step, ctx, curr)?;
+                    curr_is_value = true;
+                }
+
+                StructLRUStep::VariableStep { .. } => {
+                    let (val, field_bitfield) = lower_hir_lru_step(block, step, ctx, curr)?;
+
+                    curr = Some(val);
+                    curr_is_value = false;
+                    bitfield = field_bitfield;
+                }
+            }
+        }
+
+        return Ok((curr, curr_is_value, bitfield));
+    }
+
+    panic!("Invalid node!")
+}
+
+/// Lowers the LRU chain. Returns nothing if the chain ends with a function call without any return value.
+pub fn lower_hir_lru_base(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<Option<BaseMIRValue>> {
+    let (curr, curr_is_value, bitfield) = lower_hir_lru_steps(block, node, ctx)?;
+
+    if curr_is_value || curr.is_none() {
+        return Ok(curr);
+    }
 
-        for i in 1..steps.len() {
-            curr = lower_hir_lru_step(block, steps[i].clone(), ctx, Some(curr))?
+    if let Some((placement, field_type)) = bitfield {
+        let unit_ptr = curr.unwrap().as_ptr()?;
+
+        return Ok(Some(lower_bitfield_read(
+            ctx,
+            unit_ptr,
+            &placement,
+            &field_type,
+        )?));
+    }
+
+    let val = MIRVariableReference::from(curr.unwrap().as_ptr()?);
+
+    return Ok(Some(val.read(block, &mut ctx.mir_ctx)?));
+}
+
+/// Lowers a write into the field reached by the LRU chain
+pub fn lower_hir_lru_modify(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<bool> {
+    if let HIRNodeKind::StructLRUModify { lru, val } = node.kind {
+        let (curr, curr_is_value, bitfield) = lower_hir_lru_steps(block, lru, ctx)?;
+
+        let ptr = match curr {
+            Some(v) if !curr_is_value => v.as_ptr()?,
+            _ => unsure_panic!("lower_hir_lru_modify chain did not end with a field"),
+        };
+
+        let val = lower_hir_value(block, val, ctx)?;
+
+        if let Some((placement, _)) = bitfield {
+            return lower_bitfield_write(ctx, ptr, &placement, val);
         }
 
-        let val = MIRVariableReference::from(curr.as_ptr()?);
+        build_store(&mut ctx.mir_ctx, &ctx.hir_ctx.global_scope.scope, ptr, val)?;
 
-        return Ok(val.read(block, &mut ctx.mir_ctx)?);
+        return Ok(true);
     }
 
     panic!("Invalid node!")
 }
+
+pub fn lower_hir_lru(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    return match lower_hir_lru_base(block, node, ctx)? {
+        Some(v) => Ok(v),
+        None => unsure_panic!("lower_hir_lru used a function call without value as value"),
+    };
+}
@@ -2,21 +2,30 @@ use astoir_hir::nodes::{HIRNode, HIRNodeKind};
 use astoir_mir::{
     blocks::refer::MIRBlockReference,
     builder::{
-        build_float_add, build_float_div, build_float_mod, build_float_mul, build_float_sub,
-        build_int_add, build_int_div, build_int_mod, build_int_mul, build_int_sub,
+        build_bitwise_not, build_float_add, build_float_clamp_zero, build_float_div,
+        build_float_mod, build_float_mul, build_float_sub, build_int_add, build_int_div,
+        build_int_mod, build_int_mul, build_int_overflows, build_int_saturating, build_int_sub,
         build_shift_left, build_shift_right,
     },
+    insts::MIROverflowOperation,
     vals::base::BaseMIRValue,
 };
-use compiler_typing::raw::RawType;
-use compiler_utils::operators::{MathOperator, MathOperatorType};
+use compiler_typing::{raw::RawType, tree::Type};
+use compiler_utils::operators::{MathOperator, MathOperatorType, OverflowBehavior};
 use diagnostics::{
-    DiagnosticResult,
+    DiagnosticResult, DiagnosticSpanOrigin,
     builders::{make_math_operation_req_assign, make_req_type_kind},
     unsure_panic,
 };
 
-use crate::{MIRLoweringContext, values::lower_hir_value, vars::lower_hir_variable_reference};
+use crate::{
+    MIRLoweringContext,
+    fixed::lower_hir_math_operation_fixed,
+    safety::{are_runtime_checks_enabled, build_runtime_check},
+    tuples::{build_tuple, make_lowered_tuple},
+    values::lower_hir_value,
+    vars::lower_hir_variable_reference,
+};
 
 pub fn lower_hir_math_operation(
     block: MIRBlockReference,
@@ -29,7 +38,7 @@ pub fn lower_hir_math_operation(
         operation,
     } = node.clone().kind
     {
-        if !operation.assigns && !left.is_variable_reference() {
+        if operation.assigns && !left.is_variable_reference() {
             return Err(make_math_operation_req_assign(&*node).into());
         }
 
@@ -45,8 +54,11 @@ pub fn lower_hir_math_operation(
         let right_val = lower_hir_value(block, right, ctx)?;
 
         let val = match left_val.vtype.get_generic() {
-            RawType::Integer(_, _) | RawType::FixedPoint(_, _, _) => {
-                lower_hir_math_operation_int(left_val, right_val, operation.clone(), ctx)?
+            RawType::Integer(_, _) => {
+                lower_hir_math_operation_int(left_val, right_val, operation.clone(), ctx, &node)?
+            }
+            RawType::FixedPoint(_, _, _) => {
+                lower_hir_math_operation_fixed(left_val, right_val, operation.clone(), ctx, &node)?
             }
             RawType::Floating(_, _) => {
                 lower_hir_math_operation_float(left_val, right_val, operation.clone(), ctx, &*node)?
@@ -72,17 +84,44 @@ pub fn lower_hir_math_operation(
     panic!("Invalid node")
 }
 
+/// Gets the MIR operation of the given operator if it can overflow
+fn get_overflow_operation(operator: &MathOperatorType) -> Option<MIROverflowOperation> {
+    return match operator {
+        MathOperatorType::Add => Some(MIROverflowOperation::Add),
+        MathOperatorType::Subtract => Some(MIROverflowOperation::Sub),
+        MathOperatorType::Multiply => Some(MIROverflowOperation::Mul),
+
+        _ => None,
+    };
+}
+
 pub fn lower_hir_math_operation_int(
     left: BaseMIRValue,
     right: BaseMIRValue,
     operator: MathOperator,
     ctx: &mut MIRLoweringContext,
+    node: &HIRNode,
 ) -> DiagnosticResult<BaseMIRValue> {
     let left = left.as_int()?;
     let right = right.as_int()?;
 
     let signed = left.signed;
 
+    if let Some(operation) = get_overflow_operation(&operator.operator)
+        && are_runtime_checks_enabled(ctx)
+    {
+        let overflows = build_int_overflows(
+            &mut ctx.mir_ctx,
+            operation,
+            left.clone(),
+            right.clone(),
+            signed,
+        )?;
+        let cond = build_bitwise_not(&mut ctx.mir_ctx, overflows)?;
+
+        build_runtime_check(ctx, &node.get_pos(), cond, "arithmetic overflow")?;
+    }
+
     let res = match operator.operator {
         MathOperatorType::Add => {
             build_int_add(&mut ctx.mir_ctx, left, right, signed, operator.fast)?
@@ -138,5 +177,70 @@ pub fn lower_hir_math_operation_float(
         _ => return Err(make_req_type_kind(node, &"integer".to_string()).into()),
     };
 
+    // Unsigned floats cannot go below zero
+    if !signed && operator.operator == MathOperatorType::Subtract && are_runtime_checks_enabled(ctx)
+    {
+        return Ok(build_float_clamp_zero(&mut ctx.mir_ctx, res)?.into());
+    }
+
     return Ok(res.into());
 }
+
+/// Lowers an arithmetic builtin with an explicit overflow behavior. These are never checked at runtime
+pub fn lower_hir_overflow_operation(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    if let HIRNodeKind::OverflowOperation {
+        left,
+        right,
+        operator,
+        behavior,
+    } = node.kind.clone()
+    {
+        let left = lower_hir_value(block, left, ctx)?.as_int()?;
+        let right = lower_hir_value(block, right, ctx)?.as_int()?;
+
+        let signed = left.signed;
+
+        let operation = match get_overflow_operation(&operator) {
+            Some(v) => v,
+            None => unsure_panic!("invalid overflow operator"),
+        };
+
+        if behavior == OverflowBehavior::Saturating {
+            return Ok(
+                build_int_saturating(&mut ctx.mir_ctx, operation, left, right, signed)?.into(),
+            );
+        }
+
+        // Operations without any flags wrap around
+        let res = match operation {
+            MIROverflowOperation::Add => {
+                build_int_add(&mut ctx.mir_ctx, left.clone(), right.clone(), signed, false)?
+            }
+            MIROverflowOperation::Sub => {
+                build_int_sub(&mut ctx.mir_ctx, left.clone(), right.clone(), signed, false)?
+            }
+            MIROverflowOperation::Mul => {
+                build_int_mul(&mut ctx.mir_ctx, left.clone(), right.clone(), signed, false)?
+            }
+        };
+
+        if behavior == OverflowBehavior::Wrapping {
+            return Ok(res.into());
+        }
+
+        let overflows = build_int_overflows(&mut ctx.mir_ctx, operation, left, right, signed)?;
+
+        let t = make_lowered_tuple(vec![
+            Type::GenericLowered(RawType::Integer(res.size, signed)),
+            Type::GenericLowered(RawType::Boolean),
+        ]);
+
+        return Ok(build_tuple(ctx, t, vec![res.into(), overflows.into()])?.into());
+    }
+
+    panic!("Invalid node")
+}
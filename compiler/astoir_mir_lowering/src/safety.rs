@@ -0,0 +1,81 @@
+//! Runtime safety checks.
+//!
+//! Checks are lowered into `assert` instructions carrying the source location of the checked expression. They can be
+//! disabled for the whole compilation (`--safety=unchecked`), for a single function (`unchecked func`) or for a single
+//! expression (`unchecked(...)`).
+
+use astoir_hir::nodes::{HIRNode, HIRNodeKind};
+use astoir_mir::{
+    blocks::refer::MIRBlockReference, builder::build_assert, vals::base::BaseMIRValue,
+    vals::int::MIRIntValue,
+};
+use diagnostics::{DiagnosticResult, MaybeDiagnostic, diagnostic::SpanPosition};
+
+use crate::{MIRLoweringContext, body::lower_hir_body_member, values::lower_hir_value};
+
+/// The compiler-wide level of runtime safety checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafetyLevel {
+    /// Every runtime check is emitted unless opted-out locally
+    Checked,
+
+    /// No runtime check is emitted
+    Unchecked,
+}
+
+/// Whether runtime checks should be emitted for the element currently being lowered
+pub fn are_runtime_checks_enabled(ctx: &MIRLoweringContext) -> bool {
+    return ctx.safety == SafetyLevel::Checked && !ctx.unchecked;
+}
+
+/// Aborts the program with the location and the reason of the failed check if the condition is false
+pub fn build_runtime_check(
+    ctx: &mut MIRLoweringContext,
+    pos: &SpanPosition,
+    cond: MIRIntValue,
+    reason: &str,
+) -> MaybeDiagnostic {
+    let msg = format!("{}:{}:{}: {}", pos.file_path, pos.line, pos.col, reason);
+
+    build_assert(&mut ctx.mir_ctx, cond, msg)?;
+
+    return Ok(());
+}
+
+pub fn lower_hir_unchecked_value(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    if let HIRNodeKind::UncheckedValue { val } = node.kind {
+        let previous = ctx.unchecked;
+        ctx.unchecked = true;
+
+        let res = lower_hir_value(block, val, ctx);
+
+        ctx.unchecked = previous;
+
+        return res;
+    }
+
+    panic!("Invalid node")
+}
+
+pub fn lower_hir_unchecked_statement(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<bool> {
+    if let HIRNodeKind::UncheckedValue { val } = node.kind {
+        let previous = ctx.unchecked;
+        ctx.unchecked = true;
+
+        let res = lower_hir_body_member(block, val, ctx);
+
+        ctx.unchecked = previous;
+
+        return res;
+    }
+
+    panic!("Invalid node")
+}
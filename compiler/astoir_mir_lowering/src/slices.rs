@@ -0,0 +1,278 @@
+//! Slice related lowering.
+//!
+//! Slices are lowered into an anonymous layout struct containing the pointer to the first element followed by the
+//! element count. Every element access is bounds checked against said count.
+
+use std::collections::HashMap;
+
+use astoir_hir::nodes::{HIRNode, HIRNodeKind};
+use astoir_mir::{
+    blocks::{hints::MIRValueHint, refer::MIRBlockReference},
+    builder::{
+        build_comp_le, build_comp_lt, build_field_pointer, build_index_pointer, build_int_sub,
+        build_load, build_stack_alloc, build_store, build_unsigned_int_const,
+    },
+    vals::{
+        base::BaseMIRValue, int::MIRIntValue, ptr::MIRPointerValue, refer::MIRVariableReference,
+    },
+};
+use compiler_typing::{
+    SizedType,
+    raw::RawType,
+    structs::LoweredStructTypeContainer,
+    tree::Type,
+    utils::{get_index_type, get_pointer_size},
+};
+use compiler_utils::utils::indexed::IndexStorage;
+use diagnostics::{
+    DiagnosticResult, DiagnosticSpanOrigin, MaybeDiagnostic, diagnostic::SpanPosition,
+};
+
+use crate::{
+    MIRLoweringContext,
+    arrays::lower_hir_array_pointer,
+    lower_hir_type,
+    safety::{are_runtime_checks_enabled, build_runtime_check},
+    values::lower_hir_value,
+    vars::lower_hir_variable_reference,
+};
+
+/// Lowers the slice of the given element type into an anonymous struct
+pub fn lower_hir_slice(ctx: &MIRLoweringContext, inner: Type) -> DiagnosticResult<Type> {
+    let mut container = LoweredStructTypeContainer {
+        fields: IndexStorage::new(),
+        functions: IndexStorage::new(),
+        is_lowered_enum_child: false,
+        is_lowered_enum_parent: false,
+        lowered_enum_child: None,
+        lowered_enum_parent: None,
+        lowered_enum_niche: None,
+        lowered_union_members: None,
+        lowered_bitfields: HashMap::new(),
+        hir_mir_indexes: HashMap::new(),
+    };
+
+    let inner = lower_hir_type(ctx, inner)?;
+
+    container
+        .fields
+        .vals
+        .push(Type::Pointer(true, Box::new(inner)));
+    container
+        .fields
+        .vals
+        .push(Type::GenericLowered(get_index_type()));
+
+    return Ok(Type::GenericLowered(RawType::LoweredStruct(
+        true, container,
+    )));
+}
+
+/// Aborts the program if the index isn't contained within the element count. Nothing is emitted if runtime checks are disabled
+pub fn build_bounds_check(
+    ctx: &mut MIRLoweringContext,
+    pos: &SpanPosition,
+    index: MIRIntValue,
+    len: MIRIntValue,
+) -> MaybeDiagnostic {
+    if !are_runtime_checks_enabled(ctx) {
+        return Ok(());
+    }
+
+    let cond = build_comp_lt(&mut ctx.mir_ctx, index, len)?;
+
+    return build_runtime_check(ctx, pos, cond, "index out of bounds");
+}
+
+/// Gets the element pointer and element count of the given slice. The element pointer is hinted as an array to be indexable
+pub fn lower_hir_slice_parts(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<(MIRPointerValue, MIRIntValue)> {
+    let mut ptr = None;
+
+    if let HIRNodeKind::VariableReference { .. } = node.kind
+        && let MIRVariableReference::PointerReference(p) =
+            lower_hir_variable_reference(block, &node, ctx)?
+    {
+        ptr = Some(p);
+    }
+
+    // Slices that aren't held by variables are spilled to be accessed
+    let ptr = match ptr {
+        Some(v) => v,
+        None => {
+            let val = lower_hir_value(block, node, ctx)?;
+
+            let ptr = build_stack_alloc(
+                &mut ctx.mir_ctx,
+                val.vtype
+                    .get_size(&val.vtype, false, &ctx.hir_ctx.global_scope.scope),
+                val.vtype.clone(),
+            )?;
+
+            build_store(
+                &mut ctx.mir_ctx,
+                &ctx.hir_ctx.global_scope.scope,
+                ptr.clone(),
+                val,
+            )?;
+
+            ptr
+        }
+    };
+
+    let data_ptr = build_field_pointer(&mut ctx.mir_ctx, ptr.clone(), 0)?;
+    let data = build_load(&mut ctx.mir_ctx, data_ptr)?;
+
+    let inner = data.vtype.get_inner_type();
+
+    ctx.mir_ctx.ssa_hints.vec[data.get_ssa_index()] = MIRValueHint::Pointer(Type::Array(0, inner));
+
+    let len_ptr = build_field_pointer(&mut ctx.mir_ctx, ptr, 1)?;
+    let len = build_load(&mut ctx.mir_ctx, len_ptr)?.as_int()?;
+
+    return Ok((data.as_ptr()?, len));
+}
+
+pub fn lower_hir_sub_slice(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    let pos = node.get_pos();
+
+    if let HIRNodeKind::SubSlice {
+        val,
+        range,
+        from_slice,
+        t,
+    } = node.kind
+    {
+        let (data, len) = if from_slice {
+            let (data, len) = lower_hir_slice_parts(block, val, ctx)?;
+
+            (data, Some(len))
+        } else {
+            let (data, len) = lower_hir_array_pointer(block, val, ctx)?;
+
+            let len = match len {
+                Some(v) => Some(build_unsigned_int_const(
+                    &mut ctx.mir_ctx,
+                    v as u128,
+                    get_pointer_size(),
+                )?),
+                None => None,
+            };
+
+            (data, len)
+        };
+
+        // Whole conversions are always within bounds
+        let checked = range.is_some() && are_runtime_checks_enabled(ctx);
+
+        let (min, max) = match range {
+            Some(range) => (
+                lower_hir_value(block, range.min, ctx)?.as_int()?,
+                lower_hir_value(block, range.max, ctx)?.as_int()?,
+            ),
+            None => (
+                build_unsigned_int_const(&mut ctx.mir_ctx, 0, get_pointer_size())?,
+                len.clone().unwrap(),
+            ),
+        };
+
+        if checked {
+            let cond = build_comp_le(&mut ctx.mir_ctx, min.clone(), max.clone())?;
+            build_runtime_check(ctx, &pos, cond, "slice range start after its end")?;
+
+            // Pointers of arrays do not know their element count
+            if let Some(len) = len {
+                let cond = build_comp_le(&mut ctx.mir_ctx, max.clone(), len)?;
+                build_runtime_check(ctx, &pos, cond, "slice range out of bounds")?;
+            }
+        }
+
+        let start = build_index_pointer(&mut ctx.mir_ctx, data, min.clone())?;
+        let count = build_int_sub(&mut ctx.mir_ctx, max, min, false, false)?;
+
+        let lowered = lower_hir_type(ctx, t)?;
+
+        let ptr = build_stack_alloc(
+            &mut ctx.mir_ctx,
+            lowered.get_size(&lowered, false, &ctx.hir_ctx.global_scope.scope),
+            lowered,
+        )?;
+
+        let start_ptr = build_field_pointer(&mut ctx.mir_ctx, ptr.clone(), 0)?;
+        build_store(
+            &mut ctx.mir_ctx,
+            &ctx.hir_ctx.global_scope.scope,
+            start_ptr,
+            start.into(),
+        )?;
+
+        let count_ptr = build_field_pointer(&mut ctx.mir_ctx, ptr.clone(), 1)?;
+        build_store(
+            &mut ctx.mir_ctx,
+            &ctx.hir_ctx.global_scope.scope,
+            count_ptr,
+            count.into(),
+        )?;
+
+        return build_load(&mut ctx.mir_ctx, ptr);
+    }
+
+    panic!("Invalid node")
+}
+
+pub fn lower_hir_slice_index_access(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<BaseMIRValue> {
+    let pos = node.get_pos();
+
+    if let HIRNodeKind::SliceIndexAccess { slice, index } = node.kind {
+        let (data, len) = lower_hir_slice_parts(block, slice, ctx)?;
+        let index = lower_hir_value(block, index, ctx)?.as_int()?;
+
+        build_bounds_check(ctx, &pos, index.clone(), len)?;
+
+        let ptr = build_index_pointer(&mut ctx.mir_ctx, data, index)?;
+
+        return build_load(&mut ctx.mir_ctx, ptr);
+    }
+
+    panic!("Invalid node")
+}
+
+pub fn lower_hir_slice_index_modify(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> MaybeDiagnostic {
+    let pos = node.get_pos();
+
+    if let HIRNodeKind::SliceIndexModify {
+        slice,
+        index,
+        new_val,
+    } = node.kind
+    {
+        let (data, len) = lower_hir_slice_parts(block, slice, ctx)?;
+        let index = lower_hir_value(block, index, ctx)?.as_int()?;
+        let val = lower_hir_value(block, new_val, ctx)?;
+
+        build_bounds_check(ctx, &pos, index.clone(), len)?;
+
+        let ptr = build_index_pointer(&mut ctx.mir_ctx, data, index)?;
+
+        build_store(&mut ctx.mir_ctx, &ctx.hir_ctx.global_scope.scope, ptr, val)?;
+
+        return Ok(());
+    }
+
+    panic!("Invalid node")
+}
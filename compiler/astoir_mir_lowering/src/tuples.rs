@@ -0,0 +1,135 @@
+//! Tuple related lowering. Tuples are lowered as anonymous layout structs.
+
+use std::collections::HashMap;
+
+use astoir_hir::nodes::{HIRNode, HIRNodeKind};
+use astoir_mir::{
+    blocks::refer::MIRBlockReference,
+    builder::{build_field_pointer, build_load, build_stack_alloc, build_store},
+    vals::{base::BaseMIRValue, structs::MIRStructValue},
+};
+use compiler_typing::{SizedType, raw::RawType, structs::LoweredStructTypeContainer, tree::Type};
+use compiler_utils::utils::indexed::IndexStorage;
+use diagnostics::{DiagnosticResult, MaybeDiagnostic};
+
+use crate::{
+    MIRLoweringContext, lower_hir_type, values::lower_hir_value,
+    vars::lower_hir_variable_declaration,
+};
+
+/// Lowers the given tuple elements into an anonymous struct
+pub fn lower_hir_tuple(
+    ctx: &MIRLoweringContext,
+    elements: &Vec<Box<Type>>,
+) -> DiagnosticResult<Type> {
+    let mut fields = vec![];
+
+    for element in elements {
+        fields.push(lower_hir_type(ctx, *element.clone())?);
+    }
+
+    return Ok(make_lowered_tuple(fields));
+}
+
+/// Makes the anonymous struct of a tuple with the given already lowered elements
+pub fn make_lowered_tuple(elements: Vec<Type>) -> Type {
+    let mut container = LoweredStructTypeContainer {
+        fields: IndexStorage::new(),
+        functions: IndexStorage::new(),
+        is_lowered_enum_child: false,
+        is_lowered_enum_parent: false,
+        lowered_enum_child: None,
+        lowered_enum_parent: None,
+        lowered_enum_niche: None,
+        lowered_union_members: None,
+        lowered_bitfields: HashMap::new(),
+        hir_mir_indexes: HashMap::new(),
+    };
+
+    container.fields.vals = elements;
+
+    return Type::GenericLowered(RawType::LoweredStruct(true, container));
+}
+
+/// Builds a tuple by writing every one of its elements
+pub fn lower_hir_tuple_init(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<MIRStructValue> {
+    if let HIRNodeKind::TupleInitializer { t, values } = node.kind {
+        let mut vals = vec![];
+
+        for value in values {
+            vals.push(lower_hir_value(block, value, ctx)?);
+        }
+
+        let t = lower_hir_type(ctx, t)?;
+
+        return build_tuple(ctx, t, vals);
+    }
+
+    panic!("Invalid node")
+}
+
+/// Builds a tuple of the given lowered type from its already lowered elements
+pub fn build_tuple(
+    ctx: &mut MIRLoweringContext,
+    lowered: Type,
+    values: Vec<BaseMIRValue>,
+) -> DiagnosticResult<MIRStructValue> {
+    let ptr = build_stack_alloc(
+        &mut ctx.mir_ctx,
+        lowered.get_size(&lowered, false, &ctx.hir_ctx.global_scope.scope),
+        lowered,
+    )?;
+
+    for (ind, val) in values.into_iter().enumerate() {
+        let element_ptr = build_field_pointer(&mut ctx.mir_ctx, ptr.clone(), ind)?;
+
+        build_store(
+            &mut ctx.mir_ctx,
+            &ctx.hir_ctx.global_scope.scope,
+            element_ptr,
+            val,
+        )?;
+    }
+
+    return build_load(&mut ctx.mir_ctx, ptr)?.as_struct();
+}
+
+/// Declares every variable of the destructuring with the matching element of the tuple
+pub fn lower_hir_tuple_destructuring(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> MaybeDiagnostic {
+    if let HIRNodeKind::TupleDestructuring { value, variables } = node.kind {
+        let val = lower_hir_value(block, value, ctx)?;
+
+        let ptr = build_stack_alloc(
+            &mut ctx.mir_ctx,
+            val.vtype
+                .get_size(&val.vtype, false, &ctx.hir_ctx.global_scope.scope),
+            val.vtype.clone(),
+        )?;
+
+        build_store(
+            &mut ctx.mir_ctx,
+            &ctx.hir_ctx.global_scope.scope,
+            ptr.clone(),
+            val,
+        )?;
+
+        for (ind, variable) in variables.into_iter().enumerate() {
+            let element_ptr = build_field_pointer(&mut ctx.mir_ctx, ptr.clone(), ind)?;
+            let element: BaseMIRValue = build_load(&mut ctx.mir_ctx, element_ptr)?;
+
+            lower_hir_variable_declaration(block, variable, ctx, Some(element))?;
+        }
+
+        return Ok(());
+    }
+
+    panic!("Invalid node")
+}
@@ -1,12 +1,15 @@
 use astoir_hir::nodes::{HIRNode, HIRNodeKind};
 use astoir_mir::{
-    blocks::refer::MIRBlockReference,
+    blocks::{hints::MIRValueHint, refer::MIRBlockReference},
     builder::{
-        build_comp_eq, build_field_pointer, build_ir_cast, build_load, build_unsigned_int_const,
+        build_bitwise_and, build_comp_eq, build_comp_neg, build_field_pointer, build_ir_cast,
+        build_load, build_signed_int_const, build_unsigned_int_const,
     },
-    vals::{base::BaseMIRValue, int::MIRIntValue},
+    vals::{base::BaseMIRValue, int::MIRIntValue, ptr::MIRPointerValue},
+};
+use compiler_typing::{
+    SizedType, enums::RawEnumTypeContainer, niches::EnumNiche, raw::RawType, tree::Type,
 };
-use compiler_typing::{SizedType, raw::RawType, tree::Type};
 use diagnostics::{
     DiagnosticResult, DiagnosticSpanOrigin,
     builders::{make_req_type_kind, make_type_not_partof},
@@ -16,6 +19,92 @@ use crate::{
     MIRLoweringContext, lower_hir_type, values::lower_hir_value, vars::lower_hir_variable_reference,
 };
 
+/// Builds the hint constant of an enum entry using its discriminant
+pub fn build_enum_hint_const(
+    ctx: &mut MIRLoweringContext,
+    enum_type: &RawEnumTypeContainer,
+    discriminant: i128,
+) -> DiagnosticResult<MIRIntValue> {
+    let hint_type = enum_type.get_hint_type();
+
+    let size = hint_type.get_size(
+        &Type::GenericLowered(hint_type.clone()),
+        true,
+        &ctx.hir_ctx.global_scope.scope,
+    );
+
+    if hint_type.is_signed() {
+        return build_signed_int_const(&mut ctx.mir_ctx, discriminant, size);
+    }
+
+    return build_unsigned_int_const(&mut ctx.mir_ctx, discriminant as u128, size);
+}
+
+/// Builds a pointer to the niche of the niche filled enum pointed by `ptr`. The niche is accessed as an unsigned integer
+pub fn build_enum_niche_pointer(
+    ctx: &mut MIRLoweringContext,
+    ptr: MIRPointerValue,
+    niche: &EnumNiche,
+) -> DiagnosticResult<MIRPointerValue> {
+    let mut niche_ptr = build_field_pointer(&mut ctx.mir_ctx, ptr, niche.field)?;
+
+    if niche.nested {
+        niche_ptr = build_field_pointer(&mut ctx.mir_ctx, niche_ptr, 0)?; // 0 = hint type index
+    }
+
+    let base: BaseMIRValue = niche_ptr.clone().into();
+
+    ctx.mir_ctx.ssa_hints.vec[base.get_ssa_index()] =
+        MIRValueHint::Pointer(Type::GenericLowered(RawType::Integer(niche.size, false)));
+
+    return Ok(niche_ptr);
+}
+
+/// Obtains a pointer to the given member of a union. Every member of a union is stored at its start
+pub fn build_union_member_pointer(
+    ctx: &mut MIRLoweringContext,
+    ptr: MIRPointerValue,
+    member: Type,
+) -> DiagnosticResult<MIRPointerValue> {
+    let member_ptr = build_ir_cast(
+        &mut ctx.mir_ctx,
+        ptr.into(),
+        Type::GenericLowered(RawType::Pointer),
+    )?;
+
+    ctx.mir_ctx.ssa_hints.vec[member_ptr.get_ssa_index()] = MIRValueHint::Pointer(member);
+
+    return member_ptr.as_ptr();
+}
+
+/// Checks if the niche value represents the entry with the given discriminant. The dataful entry is represented by every value that isn't a niche value
+pub fn is_niche_of_kind(
+    ctx: &mut MIRLoweringContext,
+    niche_val: MIRIntValue,
+    niche: &EnumNiche,
+    discriminant: i128,
+) -> DiagnosticResult<MIRIntValue> {
+    if let Some(val) = niche.get_value(discriminant) {
+        let expected = build_unsigned_int_const(&mut ctx.mir_ctx, val, niche.size)?;
+
+        return build_comp_eq(&mut ctx.mir_ctx, niche_val, expected);
+    }
+
+    let mut res: Option<MIRIntValue> = None;
+
+    for (_, val) in &niche.values {
+        let other = build_unsigned_int_const(&mut ctx.mir_ctx, *val, niche.size)?;
+        let cond = build_comp_neg(&mut ctx.mir_ctx, niche_val.clone(), other)?;
+
+        res = match res {
+            Some(prev) => Some(build_bitwise_and(&mut ctx.mir_ctx, prev, cond)?),
+            None => Some(cond),
+        };
+    }
+
+    return Ok(res.unwrap());
+}
+
 pub fn is_enum_value_of_kind<K: DiagnosticSpanOrigin>(
     _block: MIRBlockReference,
     val: BaseMIRValue,
@@ -23,20 +112,23 @@ pub fn is_enum_value_of_kind<K: DiagnosticSpanOrigin>(
     ctx: &mut MIRLoweringContext,
     origin: &K,
 ) -> DiagnosticResult<MIRIntValue> {
-    let enum_type = match ctx
+    let (enum_type, niche) = match ctx
         .mir_ctx
         .ssa_hints
         .get_hint(val.get_ssa_index())
         .get_type()
         .as_generic_lowered_safe(origin)?
     {
-        RawType::Enum(v) => v,
+        RawType::Enum(v) => (v, None),
         RawType::LoweredStruct(_, container) => {
             if !container.is_lowered_enum_parent {
                 return Err(make_req_type_kind(origin, &"enum parent".to_string()).into());
             }
 
-            container.lowered_enum_parent.unwrap()
+            (
+                container.lowered_enum_parent.unwrap(),
+                container.lowered_enum_niche,
+            )
         }
         _ => return Err(make_req_type_kind(origin, &"enum parent".to_string()).into()),
     };
@@ -57,20 +149,17 @@ pub fn is_enum_value_of_kind<K: DiagnosticSpanOrigin>(
         return Err(make_type_not_partof(origin, &enum_entry.child, &enum_type.self_ref).into());
     }
 
-    let hint_type = enum_type.get_hint_type();
+    if let Some(niche) = niche {
+        let niche_ptr = build_enum_niche_pointer(ctx, val.as_ptr()?, &niche)?;
+        let niche_val = build_load(&mut ctx.mir_ctx, niche_ptr)?.as_int()?;
+
+        return is_niche_of_kind(ctx, niche_val, &niche, enum_entry.discriminant);
+    }
 
     let field_ptr = build_field_pointer(&mut ctx.mir_ctx, val.as_ptr()?, 0)?; // 0 = hint type index
     let hint_val = build_load(&mut ctx.mir_ctx, field_ptr)?.as_int()?;
 
-    let hint_true = build_unsigned_int_const(
-        &mut ctx.mir_ctx,
-        enum_entry.child as u128,
-        hint_type.get_size(
-            &Type::GenericLowered(hint_type.clone()),
-            false,
-            &ctx.hir_ctx.global_scope.scope,
-        ),
-    )?;
+    let hint_true = build_enum_hint_const(ctx, &enum_type, enum_entry.discriminant)?;
 
     return build_comp_eq(&mut ctx.mir_ctx, hint_val, hint_true);
 }
@@ -1,9 +1,13 @@
 use astoir_hir::nodes::{HIRNode, HIRNodeKind};
 use astoir_mir::{
-    builder::{build_signed_int_const, build_static_string_const, build_unsigned_int_const},
+    builder::{
+        build_signed_fixed_const, build_signed_float_const, build_signed_int_const,
+        build_static_string_const, build_unsigned_fixed_const, build_unsigned_float_const,
+        build_unsigned_int_const,
+    },
     vals::base::BaseMIRValue,
 };
-use compiler_typing::SizedType;
+use compiler_typing::{SizedType, raw::RawType};
 use diagnostics::DiagnosticResult;
 
 use crate::MIRLoweringContext;
@@ -14,6 +18,36 @@ pub fn lower_hir_literal(
 ) -> DiagnosticResult<BaseMIRValue> {
     match node.kind {
         HIRNodeKind::IntegerLiteral { value, int_type } => {
+            match int_type.get_generic() {
+                RawType::FixedPoint(number, fraction, signed) => {
+                    let val = if signed {
+                        build_signed_fixed_const(&mut ctx.mir_ctx, value as f64, number, fraction)?
+                    } else {
+                        build_unsigned_fixed_const(
+                            &mut ctx.mir_ctx,
+                            value as f64,
+                            number,
+                            fraction,
+                        )?
+                    };
+
+                    return Ok(val.into());
+                }
+
+                RawType::Floating(size, signed) => {
+                    let val = if signed {
+                        build_signed_float_const(&mut ctx.mir_ctx, value as f64, size)?
+                    } else {
+                        // Unsigned floats cannot hold negative values
+                        build_unsigned_float_const(&mut ctx.mir_ctx, (value as f64).max(0.0), size)?
+                    };
+
+                    return Ok(val.into());
+                }
+
+                _ => {}
+            }
+
             if int_type.get_generic().is_signed() {
                 let val = build_signed_int_const(
                     &mut ctx.mir_ctx,
@@ -12,14 +12,18 @@ use crate::{
     MIRLoweringContext,
     arrays::lower_hir_aray_index_access,
     casts::lower_cast,
-    funcs::lower_hir_function_call,
+    closures::lower_hir_closure,
+    funcs::{lower_hir_function_call, lower_hir_function_reference, lower_hir_indirect_call},
     lru::lower_hir_lru,
-    math::lower_hir_math_operation,
+    math::{lower_hir_math_operation, lower_hir_overflow_operation},
+    safety::lower_hir_unchecked_value,
+    slices::{lower_hir_slice_index_access, lower_hir_sub_slice},
+    tuples::lower_hir_tuple_init,
     type_tools::{lower_hir_unwrap_cond, lower_hir_unwrap_value},
     values::{
         booleans::{lower_hir_boolean_operator, lowering_hir_boolean_condition},
         consts::lower_hir_literal,
-        structs::lower_hir_struct_init,
+        structs::{lower_hir_struct_init, lower_hir_union_init},
     },
     vars::{lower_hir_variable_reference, lower_hir_variable_reference_value},
 };
@@ -71,12 +75,20 @@ pub fn lower_hir_value(
         HIRNodeKind::ArrayIndexAccess { .. } => {
             return Ok(lower_hir_aray_index_access(block, node, ctx)?);
         }
+        HIRNodeKind::SliceIndexAccess { .. } => lower_hir_slice_index_access(block, node, ctx),
+        HIRNodeKind::SubSlice { .. } => lower_hir_sub_slice(block, node, ctx),
         HIRNodeKind::StructInitializerTyped { .. } => {
             return Ok(lower_hir_struct_init(block, node, ctx)?.into());
         }
+        HIRNodeKind::UnionInitializerTyped { .. } => {
+            Ok(lower_hir_union_init(block, node, ctx)?.into())
+        }
+        HIRNodeKind::TupleInitializer { .. } => Ok(lower_hir_tuple_init(block, node, ctx)?.into()),
         HIRNodeKind::UnwrapValue { .. } => lower_hir_unwrap_value(block, node, ctx),
         HIRNodeKind::UnwrapCondition { .. } => lower_hir_unwrap_cond(block, node, ctx),
         HIRNodeKind::CastValue { .. } => lower_cast(block, node, ctx),
+        HIRNodeKind::OverflowOperation { .. } => lower_hir_overflow_operation(block, node, ctx),
+        HIRNodeKind::UncheckedValue { .. } => lower_hir_unchecked_value(block, node, ctx),
         HIRNodeKind::StructLRU { .. } => lower_hir_lru(block, node, ctx),
         HIRNodeKind::ArrayVariableInitializerValue { .. }
         | HIRNodeKind::ArrayVariableInitializerValueSameValue { .. } => {
@@ -91,6 +103,17 @@ pub fn lower_hir_value(
 
             return Ok(res.unwrap());
         }
+        HIRNodeKind::IndirectCall { .. } => {
+            let res = lower_hir_indirect_call(block, node, ctx)?;
+
+            if res.is_none() {
+                unsure_panic!("expected val func");
+            }
+
+            return Ok(res.unwrap());
+        }
+        HIRNodeKind::FunctionReference { .. } => lower_hir_function_reference(node, ctx),
+        HIRNodeKind::ClosureExpression { .. } => lower_hir_closure(block, node, ctx),
         _ => panic!("Invalid node {:#?}", node),
     }
 }
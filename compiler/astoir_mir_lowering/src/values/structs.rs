@@ -1,13 +1,22 @@
 use astoir_hir::nodes::{HIRNode, HIRNodeKind};
 use astoir_mir::{
     blocks::refer::MIRBlockReference,
-    builder::{build_static_struct_const, build_unsigned_int_const},
-    vals::structs::MIRStructValue,
+    builder::{
+        build_load, build_stack_alloc, build_static_struct_const, build_store,
+        build_unsigned_int_const,
+    },
+    vals::{base::BaseMIRValue, structs::MIRStructValue},
 };
-use compiler_typing::{SizedType, raw::RawType, tree::Type};
+use compiler_typing::{SizedType, niches::EnumNiche, raw::RawType, tree::Type};
 use diagnostics::DiagnosticResult;
 
-use crate::{MIRLoweringContext, lower_hir_type, values::lower_hir_value};
+use crate::{
+    MIRLoweringContext,
+    bitfields::build_bitfield_insert,
+    lower_hir_type,
+    type_tools::{build_enum_hint_const, build_enum_niche_pointer, build_union_member_pointer},
+    values::lower_hir_value,
+};
 
 pub fn lower_hir_struct_init(
     block: MIRBlockReference,
@@ -19,8 +28,37 @@ pub fn lower_hir_struct_init(
 
         match t.get_generic() {
             RawType::Struct(_, _) => {
-                for field in fields {
-                    values.push(lower_hir_value(block, field, ctx)?);
+                let lowered = match lower_hir_type(ctx, t.clone())?.get_generic() {
+                    RawType::LoweredStruct(_, container) => container,
+                    _ => panic!("Struct was not lowered as a struct"),
+                };
+
+                let mut ordered: Vec<Option<BaseMIRValue>> = vec![None; lowered.fields.vals.len()];
+
+                for (ind, field) in fields.into_iter().enumerate() {
+                    let val = lower_hir_value(block, field, ctx)?;
+                    let lowered_ind = lowered.resolve_hir_index(ind);
+
+                    // Bitfields sharing a storage unit are inserted one after each other into the unit
+                    if let Some((placement, _)) = lowered.lowered_bitfields.get(&ind) {
+                        let unit = match ordered[lowered_ind].take() {
+                            Some(v) => v.as_int()?,
+                            None => {
+                                build_unsigned_int_const(&mut ctx.mir_ctx, 0, placement.unit_size)?
+                            }
+                        };
+
+                        let unit = build_bitfield_insert(ctx, unit, placement, val.as_int()?)?;
+
+                        ordered[lowered_ind] = Some(unit.into());
+                        continue;
+                    }
+
+                    ordered[lowered_ind] = Some(val);
+                }
+
+                for value in ordered {
+                    values.push(value.unwrap());
                 }
             }
 
@@ -32,17 +70,26 @@ pub fn lower_hir_struct_init(
                     _ => panic!("Enum parent not enum"),
                 };
 
-                let hint = build_unsigned_int_const(
-                    &mut ctx.mir_ctx,
-                    container.child as u128,
-                    parent.get_hint_type().get_size(
-                        &Type::GenericLowered(parent.get_hint_type()),
-                        false,
-                        &ctx.hir_ctx.global_scope.scope,
-                    ),
-                )?;
+                let lowered = lower_hir_type(ctx, t.clone())?;
+
+                let niche = match lowered.get_generic() {
+                    RawType::LoweredStruct(_, container) => container.lowered_enum_niche,
+                    _ => panic!("Enum entry was not lowered as a struct"),
+                };
+
+                match niche {
+                    Some(niche) => {
+                        if let Some(val) = niche.get_value(container.discriminant) {
+                            return lower_niche_entry_init(ctx, lowered, &niche, val);
+                        }
+                    }
 
-                values.push(hint.into());
+                    None => {
+                        let hint = build_enum_hint_const(ctx, &parent, container.discriminant)?;
+
+                        values.push(hint.into());
+                    }
+                }
 
                 for field in fields {
                     values.push(lower_hir_value(block, field, ctx)?);
@@ -59,3 +106,69 @@ pub fn lower_hir_struct_init(
 
     panic!("Invalid node")
 }
+
+/// Builds a fieldless entry of a niche filled enum by writing its niche value, the rest of the value is left undefined
+fn lower_niche_entry_init(
+    ctx: &mut MIRLoweringContext,
+    lowered: Type,
+    niche: &EnumNiche,
+    val: u128,
+) -> DiagnosticResult<MIRStructValue> {
+    let ptr = build_stack_alloc(
+        &mut ctx.mir_ctx,
+        lowered.get_size(&lowered, false, &ctx.hir_ctx.global_scope.scope),
+        lowered,
+    )?;
+
+    let niche_ptr = build_enum_niche_pointer(ctx, ptr.clone(), niche)?;
+    let niche_val = build_unsigned_int_const(&mut ctx.mir_ctx, val, niche.size)?;
+
+    build_store(
+        &mut ctx.mir_ctx,
+        &ctx.hir_ctx.global_scope.scope,
+        niche_ptr,
+        niche_val.into(),
+    )?;
+
+    return build_load(&mut ctx.mir_ctx, ptr)?.as_struct();
+}
+
+/// Builds a union by writing its only initialized member, the rest of the value is left undefined
+pub fn lower_hir_union_init(
+    block: MIRBlockReference,
+    node: Box<HIRNode>,
+    ctx: &mut MIRLoweringContext,
+) -> DiagnosticResult<MIRStructValue> {
+    if let HIRNodeKind::UnionInitializerTyped { t, field, val } = node.kind {
+        let lowered = lower_hir_type(ctx, t)?;
+
+        let member = match lowered.get_generic() {
+            RawType::LoweredStruct(_, container) => match container.lowered_union_members {
+                Some(members) => members[field].clone(),
+                None => panic!("Union was not lowered as a union"),
+            },
+            _ => panic!("Union was not lowered as a struct"),
+        };
+
+        let val = lower_hir_value(block, val, ctx)?;
+
+        let ptr = build_stack_alloc(
+            &mut ctx.mir_ctx,
+            lowered.get_size(&lowered, false, &ctx.hir_ctx.global_scope.scope),
+            lowered,
+        )?;
+
+        let member_ptr = build_union_member_pointer(ctx, ptr.clone(), member)?;
+
+        build_store(
+            &mut ctx.mir_ctx,
+            &ctx.hir_ctx.global_scope.scope,
+            member_ptr,
+            val,
+        )?;
+
+        return build_load(&mut ctx.mir_ctx, ptr)?.as_struct();
+    }
+
+    panic!("Invalid node")
+}
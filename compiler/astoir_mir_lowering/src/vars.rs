@@ -74,7 +74,7 @@ pub fn lower_hir_variable_declaration(
                     variable,
                     MIRBlockVariableSSAHint {
                         kind: MIRBlockVariableType::SSA,
-                        hint: None,
+                        hint: override_val,
                     },
                 );
             }
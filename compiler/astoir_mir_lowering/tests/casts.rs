@@ -0,0 +1,72 @@
+//! Widening and narrowing casts of arbitrary width integers.
+
+mod common;
+
+use astoir_mir::insts::MIRInstruction;
+use astoir_mir_lowering::safety::SafetyLevel;
+use common::lower_instructions;
+
+const WIDTHS: [usize; 9] = [1, 7, 8, 24, 33, 64, 65, 127, 128];
+
+/// The integer resizes as `(signed, from, to)`
+fn get_integer_resizes(from: usize, to: usize, signed: bool) -> Vec<(bool, usize, usize)> {
+    let prefix = match signed {
+        true => "s",
+        false => "u",
+    };
+
+    let source = format!(
+        "func main() -> s32 {{\n\tvar {p}.{} a = 1;\n\tvar {p}.{} b = cast<a, {p}.{}>;\n\tret 0;\n}}\n",
+        from,
+        to,
+        to,
+        p = prefix
+    );
+
+    lower_instructions(&source, SafetyLevel::Checked)
+        .into_iter()
+        .filter_map(|inst| match inst {
+            MIRInstruction::UpcastInteger { val, size }
+            | MIRInstruction::DowncastInteger { val, size } => Some((val.signed, val.size, size)),
+
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn widens_integers() {
+    for signed in [false, true] {
+        for from in WIDTHS {
+            for to in WIDTHS.iter().copied().filter(|t| *t > from) {
+                assert_eq!(
+                    get_integer_resizes(from, to, signed),
+                    vec![(signed, from, to)]
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn narrows_integers() {
+    for signed in [false, true] {
+        for from in WIDTHS {
+            for to in WIDTHS.iter().copied().filter(|t| *t < from) {
+                assert_eq!(
+                    get_integer_resizes(from, to, signed),
+                    vec![(signed, from, to)]
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn keeps_integers_of_same_width() {
+    for signed in [false, true] {
+        for width in WIDTHS {
+            assert!(get_integer_resizes(width, width, signed).is_empty());
+        }
+    }
+}
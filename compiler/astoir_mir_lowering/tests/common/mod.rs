@@ -0,0 +1,38 @@
+//! Lowers Quickfall sources into MIR for the tests and inspects the result.
+
+#![allow(dead_code)]
+
+use ast_parser::parse_ast_ctx;
+use astoir_hir_lowering::lower_ast;
+use astoir_mir::insts::MIRInstruction;
+use astoir_mir_lowering::{lower_hir, safety::SafetyLevel};
+use lexer::lexer::lexer_parse;
+
+/// Lowers the given source into MIR and returns the instructions of every block. Panics if the source fails to lower
+pub fn lower_instructions(source: &str, safety: SafetyLevel) -> Vec<MIRInstruction> {
+    let tokens = lexer_parse(source.to_string(), &"test.qf".to_string()).unwrap();
+    let hir = lower_ast(parse_ast_ctx(&tokens).unwrap()).unwrap();
+
+    let mir = lower_hir(hir, safety).unwrap_or_else(|_| panic!("{} failed to lower", source));
+
+    mir.blocks
+        .into_iter()
+        .flat_map(|block| block.instructions)
+        .map(|inst| inst.into())
+        .collect()
+}
+
+/// The reasons of the runtime checks emitted for the given source, such as `index out of bounds`
+pub fn get_runtime_checks(source: &str, safety: SafetyLevel) -> Vec<String> {
+    lower_instructions(source, safety)
+        .into_iter()
+        .filter_map(|inst| match inst {
+            // Messages are prefixed by the location of the check
+            MIRInstruction::StaticStringConstant { raw } if raw.starts_with("test.qf:") => {
+                raw.splitn(4, ':').nth(3).map(|r| r.trim().to_string())
+            }
+
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,69 @@
+//! Bounds checks of array and slice accesses depending on the safety level.
+
+mod common;
+
+use astoir_mir_lowering::safety::SafetyLevel;
+use common::get_runtime_checks;
+
+const OUT_OF_BOUNDS: &str = "index out of bounds";
+
+fn make_program(body: &str) -> String {
+    format!(
+        "func main() -> s32 {{\n\tvar s32[4] arr = [7, 4];\n\tvar u64 i = 2;\n{}\tret 0;\n}}\n",
+        body
+    )
+}
+
+/// The amount of bounds checks emitted for the given source
+fn count_bounds_checks(source: &str, safety: SafetyLevel) -> usize {
+    get_runtime_checks(source, safety)
+        .iter()
+        .filter(|r| *r == OUT_OF_BOUNDS)
+        .count()
+}
+
+#[test]
+fn checks_array_accesses() {
+    let source = make_program("\tvar s32 v = arr[i];\n\tarr[i] = 3;\n");
+
+    assert_eq!(count_bounds_checks(&source, SafetyLevel::Checked), 2);
+}
+
+#[test]
+fn checks_slice_accesses() {
+    let source = make_program("\tvar s32[] part = arr;\n\tvar s32 v = part[i];\n\tpart[i] = 3;\n");
+
+    assert_eq!(count_bounds_checks(&source, SafetyLevel::Checked), 2);
+}
+
+#[test]
+fn checks_sub_slice_ranges() {
+    let source = make_program("\tvar s32[] part = arr[1..i];\n");
+    let checks = get_runtime_checks(&source, SafetyLevel::Checked);
+
+    assert!(checks.contains(&"slice range start after its end".to_string()));
+    assert!(checks.contains(&"slice range out of bounds".to_string()));
+}
+
+#[test]
+fn skips_checks_when_unchecked() {
+    let source = make_program(
+        "\tvar s32 v = arr[i];\n\tarr[i] = 3;\n\tvar s32[] part = arr[1..i];\n\tvar s32 w = part[i];\n",
+    );
+
+    assert!(get_runtime_checks(&source, SafetyLevel::Unchecked).is_empty());
+}
+
+#[test]
+fn skips_checks_of_unchecked_values() {
+    let source = make_program("\tvar s32 v = unchecked(arr[i]);\n\tunchecked(arr[i] = 3);\n");
+
+    assert_eq!(count_bounds_checks(&source, SafetyLevel::Checked), 0);
+}
+
+#[test]
+fn skips_checks_of_unchecked_functions() {
+    let source = "unchecked func get(s32[] values, u64 i) -> s32 {\n\tret values[i];\n}\n\nfunc main() -> s32 {\n\tvar s32[4] arr = [7, 4];\n\tret get(arr, 2);\n}\n";
+
+    assert_eq!(count_bounds_checks(source, SafetyLevel::Checked), 0);
+}
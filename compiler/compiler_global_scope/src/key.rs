@@ -4,6 +4,9 @@ use std::hash::Hash;
 #[derive(Debug, Clone)]
 pub struct EntryKey {
     pub name_hash: u64,
+
+    /// The name behind the hash. Only used by diagnostics, keys are compared by their hash
+    pub name: String,
 }
 
 impl Hash for EntryKey {
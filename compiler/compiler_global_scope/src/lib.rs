@@ -56,7 +56,7 @@ impl<T: Clone + Hash + Eq, R: Clone + Hash + Eq> GlobalScopeStorage<T, R> {
         origin: &K,
     ) -> DiagnosticResult<usize> {
         if self.entry_to_ind.contains_key(&name) {
-            return Err(make_already_in_scope(origin, &name.name_hash).into());
+            return Err(make_already_in_scope(origin, &name.name).into());
         }
 
         if let GlobalStorageEntryType::Function { .. } = entry {
@@ -101,7 +101,7 @@ impl<T: Clone + Hash + Eq, R: Clone + Hash + Eq> GlobalScopeStorage<T, R> {
         origin: &K,
     ) -> DiagnosticResult<GlobalStorageEntryType<T, R>> {
         if !self.entry_to_ind.contains_key(&name) {
-            return Err(make_cannot_find(origin, &name.name_hash).into());
+            return Err(make_cannot_find(origin, &name.name).into());
         }
 
         return Ok(self.entries[self.entry_to_ind[&name]].entry_type.clone());
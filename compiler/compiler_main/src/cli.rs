@@ -1,6 +1,11 @@
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand, ValueEnum};
+use astoir::SafetyLevel;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use diagnostics::{
+    lints::{LintLevel, set_deny_warnings, set_lint_level},
+    warnings::get_warning_codes,
+};
 
 #[derive(Parser)]
 pub struct Cli {
@@ -31,6 +36,94 @@ pub enum OutputFormat {
     IR,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum, Debug)]
+pub enum Safety {
+    /// Emits runtime checks such as bounds checks
+    Checked,
+
+    /// Removes every runtime check
+    Unchecked,
+}
+
+impl From<Safety> for SafetyLevel {
+    fn from(value: Safety) -> Self {
+        match value {
+            Safety::Checked => SafetyLevel::Checked,
+            Safety::Unchecked => SafetyLevel::Unchecked,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum, Debug)]
+pub enum MessageFormat {
+    /// Coloured text meant to be read by humans
+    Human,
+
+    /// One JSON object per diagnostic followed by a summary record
+    Json,
+
+    /// A single SARIF 2.1.0 log
+    Sarif,
+}
+
+impl From<MessageFormat> for diagnostics::MessageFormat {
+    fn from(value: MessageFormat) -> Self {
+        match value {
+            MessageFormat::Human => diagnostics::MessageFormat::Human,
+            MessageFormat::Json => diagnostics::MessageFormat::Json,
+            MessageFormat::Sarif => diagnostics::MessageFormat::Sarif,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct WarningLevels {
+    /// Reports the given warning as a warning
+    #[arg(short = 'W', value_name = "NAME")]
+    pub warn: Vec<String>,
+
+    /// Silences the given warning. Takes priority over `-W` and `-D`
+    #[arg(short = 'A', value_name = "NAME")]
+    pub allow: Vec<String>,
+
+    /// Reports the given warning as an error. Takes priority over `-W`
+    #[arg(short = 'D', value_name = "NAME")]
+    pub deny: Vec<String>,
+
+    /// Reports every warning that isn't allowed as an error
+    #[arg(long)]
+    pub deny_warnings: bool,
+}
+
+impl WarningLevels {
+    /// Applies the warning levels. Returns the first unknown warning name if any
+    pub fn apply(&self) -> Result<(), String> {
+        let levels = [
+            (&self.warn, LintLevel::Warn),
+            (&self.deny, LintLevel::Deny),
+            (&self.allow, LintLevel::Allow),
+        ];
+
+        for (names, level) in levels {
+            for name in names {
+                let codes = get_warning_codes(name);
+
+                if codes.is_empty() {
+                    return Err(name.clone());
+                }
+
+                for code in codes {
+                    set_lint_level(code, level);
+                }
+            }
+        }
+
+        set_deny_warnings(self.deny_warnings);
+
+        return Ok(());
+    }
+}
+
 #[derive(Subcommand)]
 pub enum CLICommand {
     #[command(visible_alias = "b", about = "Builds the given file(s)")]
@@ -47,6 +140,15 @@ pub enum CLICommand {
         #[arg(short = 'l', default_value = "ld")]
         linker: String,
 
+        #[arg(long, value_enum, default_value = "checked")]
+        safety: Safety,
+
+        #[command(flatten)]
+        warnings: WarningLevels,
+
+        #[arg(long, value_enum, default_value = "human")]
+        message_format: MessageFormat,
+
         #[arg(required = true)]
         input: Vec<PathBuf>,
     },
@@ -61,6 +163,21 @@ pub enum CLICommand {
 
         #[arg(long, value_enum, default_value = "mir")]
         layer: IRLayer,
+
+        #[arg(long, value_enum, default_value = "checked")]
+        safety: Safety,
+
+        #[command(flatten)]
+        warnings: WarningLevels,
+
+        #[arg(long, value_enum, default_value = "human")]
+        message_format: MessageFormat,
+    },
+
+    #[command(about = "Explains the given error or warning code (eg: E0025)")]
+    Explain {
+        #[arg(required = true)]
+        code: String,
     },
 
     #[command(about = "Runs language server analysis", visible_alias = "lscheck")]
@@ -1,7 +1,7 @@
 use std::{fs, path::PathBuf};
 
 use ast_parser::parse_ast_ctx;
-use astoir::run_astoir_mir;
+use astoir::{SafetyLevel, run_astoir_mir};
 use astoir_mir::fmt::DisplayWithCtx;
 use lexer::lexer::lexer_parse_file;
 
@@ -10,15 +10,15 @@ use llvm_ir_bridge::bridge_llvm;
 
 use crate::quietlyquit_if_errors;
 
-pub fn build_mir(path: String, out: PathBuf) {
+pub fn build_mir(path: String, out: PathBuf, safety: SafetyLevel) {
     let lexer = lexer_parse_file(&path);
-    quietlyquit_if_errors!();
+    quietlyquit_if_errors!(lexer);
 
     let ast = parse_ast_ctx(&lexer.unwrap());
-    quietlyquit_if_errors!();
+    quietlyquit_if_errors!(ast);
 
-    let mir = run_astoir_mir(ast.unwrap());
-    quietlyquit_if_errors!();
+    let mir = run_astoir_mir(ast.unwrap(), safety);
+    quietlyquit_if_errors!(mir);
 
     let mir = mir.unwrap();
 
@@ -26,15 +26,15 @@ pub fn build_mir(path: String, out: PathBuf) {
 }
 
 #[cfg(feature = "llvm")]
-pub fn build_llvm(path: String, out: PathBuf) {
+pub fn build_llvm(path: String, out: PathBuf, safety: SafetyLevel) {
     let lexer = lexer_parse_file(&path);
-    quietlyquit_if_errors!();
+    quietlyquit_if_errors!(lexer);
 
     let ast = parse_ast_ctx(&lexer.unwrap());
-    quietlyquit_if_errors!();
+    quietlyquit_if_errors!(ast);
 
-    let mir = run_astoir_mir(ast.unwrap());
-    quietlyquit_if_errors!();
+    let mir = run_astoir_mir(ast.unwrap(), safety);
+    quietlyquit_if_errors!(mir);
 
     let llvm = bridge_llvm(&mir.unwrap());
 
@@ -42,6 +42,6 @@ pub fn build_llvm(path: String, out: PathBuf) {
 }
 
 #[cfg(not(feature = "llvm"))]
-pub fn build_llvm(_path: String, _out: PathBuf) {
-    println!("LLVM bridge is disabled in this version of the compiler.")
+pub fn build_llvm(_path: String, _out: PathBuf, _safety: SafetyLevel) {
+    eprintln!("LLVM bridge is disabled in this version of the compiler.")
 }
@@ -0,0 +1,44 @@
+use std::io::{self, ErrorKind, Write};
+
+use diagnostics::registry::{RegisteredCode, find_registered_code};
+
+fn write_explanation(out: &mut impl Write, registered: &RegisteredCode) -> io::Result<()> {
+    writeln!(
+        out,
+        "{} ({}): {}\n",
+        registered.get_display_code(),
+        registered.name.to_lowercase(),
+        registered.template
+    )?;
+    write!(out, "{}", registered.explanation)?;
+
+    let names = registered.get_warning_names();
+
+    if !names.is_empty() {
+        writeln!(
+            out,
+            "\nThis warning is referred to as `{}` by `-W`, `-A`, `-D` and `@allow`.",
+            names.join("`, `")
+        )?;
+    }
+
+    out.flush()
+}
+
+pub fn run_explain(code: String) {
+    let registered = match find_registered_code(&code) {
+        Some(v) => v,
+        None => {
+            eprintln!("Unknown code {}", code);
+            std::process::exit(445);
+        }
+    };
+
+    // The explanation is often piped into a pager or `head`, which may close the output early
+    if let Err(e) = write_explanation(&mut io::stdout().lock(), &registered)
+        && e.kind() != ErrorKind::BrokenPipe
+    {
+        eprintln!("Couldn't print the explanation: {}", e);
+        std::process::exit(445);
+    }
+}
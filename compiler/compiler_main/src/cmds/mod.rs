@@ -1,11 +1,21 @@
 pub mod build;
 pub mod check;
+pub mod explain;
 
+/// Prints the diagnostics gathered so far, quitting if the given stage result is an `Err` or if any of the diagnostics is
+/// an error. Warnings alone never stop the compilation, but a stage can still fail while only reporting warnings
 #[macro_export]
 macro_rules! quietlyquit_if_errors {
-    () => {
-        if (diagnostics::has_diagnostics()) {
+    ($res: expr) => {
+        let failed = $res.is_err() || diagnostics::has_errors();
+
+        if diagnostics::has_diagnostics() {
             diagnostics::dump_diagnostics();
+            diagnostics::clear_diagnostics();
+        }
+
+        if failed {
+            diagnostics::dump_summary();
             std::process::exit(445);
         }
     };
@@ -14,7 +24,7 @@ macro_rules! quietlyquit_if_errors {
 #[macro_export]
 macro_rules! soft_panic {
     ($lit:literal) => {
-        println!($lit);
+        eprintln!($lit);
         std::process::exit(445)
     };
 }
@@ -1,12 +1,14 @@
 use std::{fs, path::PathBuf, time::Instant};
 
 use clap::Parser;
+use diagnostics::{dump_summary, get_dumped_counts, set_message_format};
 
 use crate::{
-    cli::{Bridge, CLICommand, Cli, OutputFormat},
+    cli::{Bridge, CLICommand, Cli, MessageFormat, OutputFormat, WarningLevels},
     cmds::{
         build::{build_llvm, build_mir},
         check::run_check,
+        explain::run_explain,
     },
     version::{GIT_HASH, VERSION},
 };
@@ -15,6 +17,23 @@ pub mod cli;
 pub mod cmds;
 pub mod version;
 
+fn apply_warning_levels(warnings: &WarningLevels) {
+    if let Err(name) = warnings.apply() {
+        eprintln!("Unknown warning {}", name);
+        std::process::exit(445);
+    }
+}
+
+/// Prints the summary before panicking. SARIF is rendered as a single document that would otherwise never be printed
+fn set_summary_panic_hook() {
+    let default = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        dump_summary();
+        default(info);
+    }));
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -23,19 +42,49 @@ fn main() {
             println!("Quickfall v{} (commit {})", VERSION, GIT_HASH);
         }
 
-        CLICommand::Check { input, layer } => {
+        CLICommand::Explain { code } => {
+            run_explain(code);
+        }
+
+        CLICommand::Check {
+            input,
+            layer,
+            safety,
+            warnings,
+            message_format,
+        } => {
+            apply_warning_levels(&warnings);
+            set_message_format(message_format.into());
+            set_summary_panic_hook();
+
             let start = Instant::now();
             let count = input.len();
 
             for file in input {
-                run_check(file, layer);
+                run_check(file, layer, safety.into());
+            }
+
+            if message_format != MessageFormat::Human {
+                dump_summary();
+                return;
             }
 
-            println!(
-                "No problems could be found in the {} provided files! Checked in {:?}",
-                count,
-                start.elapsed()
-            )
+            let (_, warnings) = get_dumped_counts();
+
+            if warnings == 0 {
+                println!(
+                    "No problems could be found in the {} provided files! Checked in {:?}",
+                    count,
+                    start.elapsed()
+                )
+            } else {
+                println!(
+                    "Checked the {} provided files with {} warning(s) in {:?}",
+                    count,
+                    warnings,
+                    start.elapsed()
+                )
+            }
         }
 
         CLICommand::Build {
@@ -43,8 +92,15 @@ fn main() {
             bridge,
             format,
             linker: _,
+            safety,
+            warnings,
+            message_format,
             input,
         } => {
+            apply_warning_levels(&warnings);
+            set_message_format(message_format.into());
+            set_summary_panic_hook();
+
             let needs_dir = input.len() > 1 && format != OutputFormat::Executable;
 
             if input.len() > 1 && out.extension().is_some() && format != OutputFormat::Executable {
@@ -69,7 +125,7 @@ fn main() {
 
                         let output_path = out.join(outfile);
 
-                        build_mir(i.to_str().unwrap().to_string(), output_path);
+                        build_mir(i.to_str().unwrap().to_string(), output_path, safety.into());
                     }
                 }
 
@@ -79,10 +135,12 @@ fn main() {
                         outfile.add_extension("ll");
 
                         let output_path = out.join(outfile);
-                        build_llvm(i.to_str().unwrap().to_string(), output_path);
+                        build_llvm(i.to_str().unwrap().to_string(), output_path, safety.into());
                     }
                 }
             }
+
+            dump_summary();
         }
 
         _ => todo!(),
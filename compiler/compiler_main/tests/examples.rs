@@ -0,0 +1,253 @@
+//! Runs the compiler over every example, making sure that it either succeeds or quits cleanly with the error exit code.
+//! Some examples use syntax that isn't supported yet and must be rejected without panicking.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// The exit code used by the compiler when quitting on errors, as seen by the parent process
+const ERROR_EXIT_CODE: i32 = 445 & 0xff;
+
+fn get_examples() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples");
+
+    let mut examples: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|e| e == "qf"))
+        .collect();
+
+    examples.sort();
+    examples
+}
+
+fn run_compiler(args: &[&str], example: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_compiler_main"))
+        .args(args)
+        .arg(example)
+        .current_dir(example.parent().unwrap())
+        .output()
+        .unwrap()
+}
+
+fn assert_quits_cleanly(output: &Output, example: &Path) {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(
+        !stderr.contains("panicked"),
+        "{} made the compiler panic:\n{}",
+        example.display(),
+        stderr
+    );
+
+    assert!(
+        output.status.success() || output.status.code() == Some(ERROR_EXIT_CODE),
+        "{} exited with {}",
+        example.display(),
+        output.status
+    );
+}
+
+#[test]
+fn check_examples() {
+    let examples = get_examples();
+    assert!(!examples.is_empty());
+
+    for example in examples {
+        let output = run_compiler(&["check"], &example);
+        assert_quits_cleanly(&output, &example);
+    }
+}
+
+#[test]
+fn build_examples() {
+    let out = std::env::temp_dir().join(format!("quickfall_examples_{}", std::process::id()));
+    fs::create_dir_all(&out).unwrap();
+
+    for example in get_examples() {
+        let output = run_compiler(
+            &["build", "-b", "asto-ir", "-t", "ir", "-o", out.to_str().unwrap()],
+            &example,
+        );
+        assert_quits_cleanly(&output, &example);
+    }
+
+    fs::remove_dir_all(&out).unwrap();
+}
+
+#[test]
+fn rejects_unparsable_examples() {
+    let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/helloworld.qf");
+    let output = run_compiler(&["check"], &example);
+
+    assert_eq!(output.status.code(), Some(ERROR_EXIT_CODE));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("No problems"));
+}
+
+#[test]
+fn reports_warning_count() {
+    let clean = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/warnings.qf");
+    let output = run_compiler(&["check"], &clean);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No problems"));
+
+    let warned = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/attributes.qf");
+    let output = run_compiler(&["check"], &warned);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(!stdout.contains("No problems"));
+    assert!(stdout.contains("with 5 warning(s)"));
+}
+
+#[test]
+fn explain_survives_closed_output() {
+    use std::process::Stdio;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_compiler_main"))
+        .args(["explain", "E0000"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // Closing the read end before the explanation gets written makes every write fail with a broken pipe
+    drop(child.stdout.take());
+
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("panicked"));
+}
+
+#[test]
+fn explain_rejects_unknown_codes() {
+    let output = Command::new(env!("CARGO_BIN_EXE_compiler_main"))
+        .args(["explain", "E0017"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(ERROR_EXIT_CODE));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown code E0017"));
+}
+
+#[test]
+fn applies_warning_levels() {
+    let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/attributes.qf");
+
+    let output = run_compiler(&["check", "-D", "deprecated"], &example);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(ERROR_EXIT_CODE));
+    assert!(stdout.contains("error[W0011]"));
+    assert!(stdout.contains("warning[W0009]"));
+
+    let output = run_compiler(
+        &[
+            "check",
+            "--deny-warnings",
+            "-A",
+            "deprecated",
+            "-A",
+            "experimental",
+            "-A",
+            "unsafe_function",
+        ],
+        &example,
+    );
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No problems"));
+}
+
+#[test]
+fn rejects_unknown_warning_names() {
+    let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/attributes.qf");
+    let output = run_compiler(&["check", "-A", "unused_thing"], &example);
+
+    assert_eq!(output.status.code(), Some(ERROR_EXIT_CODE));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown warning unused_thing"));
+}
+
+#[test]
+fn reports_json_diagnostics() {
+    let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/attributes.qf");
+    let output = run_compiler(&["check", "--message-format", "json"], &example);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert!(output.status.success());
+    assert_eq!(lines.len(), 6);
+    assert!(
+        lines[..5]
+            .iter()
+            .all(|l| l.starts_with("{\"type\":\"diagnostic\",\"level\":\"warning\""))
+    );
+    assert_eq!(
+        lines[5],
+        "{\"type\":\"summary\",\"errors\":0,\"warnings\":5}"
+    );
+}
+
+#[test]
+fn reports_json_errors() {
+    let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/math.qf");
+    let output = run_compiler(&["check", "--message-format", "json"], &example);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(output.status.code(), Some(ERROR_EXIT_CODE));
+    assert_eq!(lines.len(), 2);
+    assert!(
+        lines[0].starts_with("{\"type\":\"diagnostic\",\"level\":\"error\",\"code\":\"E0001\"")
+    );
+    assert_eq!(
+        lines[1],
+        "{\"type\":\"summary\",\"errors\":1,\"warnings\":0}"
+    );
+}
+
+#[test]
+fn builds_with_json_records_only() {
+    let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/attributes.qf");
+    let out = std::env::temp_dir().join(format!("quickfall_json_{}", std::process::id()));
+    fs::create_dir_all(&out).unwrap();
+
+    let output = run_compiler(
+        &[
+            "build",
+            "--message-format",
+            "json",
+            "-o",
+            out.to_str().unwrap(),
+        ],
+        &example,
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    fs::remove_dir_all(&out).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.lines().all(|l| l.starts_with("{\"type\":")));
+    assert!(
+        stdout
+            .lines()
+            .last()
+            .is_some_and(|l| l.starts_with("{\"type\":\"summary\""))
+    );
+}
+
+#[test]
+fn reports_sarif_log() {
+    let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/math.qf");
+    let output = run_compiler(&["check", "--message-format", "sarif"], &example);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(ERROR_EXIT_CODE));
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.starts_with("{\"$schema\":"));
+    assert!(stdout.contains("\"results\":[{\"ruleId\":\"E0001\""));
+}
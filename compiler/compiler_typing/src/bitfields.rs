@@ -0,0 +1,110 @@
+//! Bitfield members of `layout` types, written `u8 flags : 3`.
+//!
+//! Consecutive bitfields are packed into integer storage units in declaration order, starting from the least
+//! significant bit of the unit. A bitfield that does not fit within the remaining bits of the current unit starts a new
+//! one, a unit never holds more than 64 bits. Storage units are then stored like any other field, as the smallest power
+//! of two amount of bytes able to contain their bitfields.
+
+use std::collections::HashMap;
+
+use crate::{layout::TypeLayout, structs::RawStructTypeContainer};
+
+/// The maximal size of a storage unit, in bits.
+pub const MAX_STORAGE_UNIT_SIZE: usize = 64;
+
+/// The placement of a bitfield within its storage unit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BitfieldPlacement {
+    /// The index of the storage member containing the bitfield
+    pub unit: usize,
+
+    /// The size of the storage unit, in bits
+    pub unit_size: usize,
+
+    /// The offset of the first bit of the bitfield, starting from the least significant bit of the unit
+    pub shift: usize,
+
+    /// The amount of bits used by the bitfield
+    pub width: usize,
+}
+
+/// A member of a struct as stored in memory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageMember {
+    /// A regular field. Contains the field index
+    Field(usize),
+
+    /// A storage unit shared by consecutive bitfields. Contains the size of the unit in bits and the index of the contained fields
+    Unit(usize, Vec<usize>),
+}
+
+impl RawStructTypeContainer {
+    /// Groups the fields, in the given order, into storage members. Returns the storage members alongside the placement of every bitfield, indexed by field index.
+    pub fn get_storage_members(
+        &self,
+        order: &Vec<usize>,
+    ) -> (Vec<StorageMember>, HashMap<usize, BitfieldPlacement>) {
+        let mut members = vec![];
+        let mut placements = HashMap::new();
+
+        // The fields of the unit currently being filled alongside the amount of bits used
+        let mut unit: Vec<usize> = vec![];
+        let mut used = 0;
+
+        for ind in order {
+            let width = match self.bitfields.get(ind) {
+                Some(v) => *v,
+                None => {
+                    flush_unit(&mut members, &mut placements, &mut unit, &mut used);
+
+                    members.push(StorageMember::Field(*ind));
+                    continue;
+                }
+            };
+
+            if used + width > MAX_STORAGE_UNIT_SIZE {
+                flush_unit(&mut members, &mut placements, &mut unit, &mut used);
+            }
+
+            placements.insert(
+                *ind,
+                BitfieldPlacement {
+                    unit: members.len(),
+                    unit_size: 0,
+                    shift: used,
+                    width,
+                },
+            );
+
+            unit.push(*ind);
+            used += width;
+        }
+
+        flush_unit(&mut members, &mut placements, &mut unit, &mut used);
+
+        return (members, placements);
+    }
+}
+
+/// Closes the storage unit currently being filled, if any.
+fn flush_unit(
+    members: &mut Vec<StorageMember>,
+    placements: &mut HashMap<usize, BitfieldPlacement>,
+    unit: &mut Vec<usize>,
+    used: &mut usize,
+) {
+    if unit.is_empty() {
+        return;
+    }
+
+    let size = TypeLayout::scalar(*used).size;
+
+    for ind in unit.iter() {
+        placements.get_mut(ind).unwrap().unit_size = size;
+    }
+
+    members.push(StorageMember::Unit(size, unit.clone()));
+
+    unit.clear();
+    *used = 0;
+}
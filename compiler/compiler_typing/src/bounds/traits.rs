@@ -12,8 +12,10 @@
 //! - `!stringlike`: Is the type a string
 //! - `!static`: Is the type supposed to be statically stored
 //!
+//! Any other name refers to a user declared trait (see `crate::traits`), the type must then implement the trait.
+//!
 //! # Examples
-//! ```
+//! ```text
 //! struct test<A: !numeric ~!cpusupported> {
 //! 	// A can now only be a numeric type and not supported by the CPU
 //! }
@@ -21,10 +23,14 @@
 
 use std::fmt::Display;
 
-use compiler_utils::hash;
-use diagnostics::{MaybeDiagnostic, builders::make_bound_trait};
+use compiler_global_scope::{entry::GlobalStorageEntryType, key::EntryKey};
+use compiler_utils::{hash, hash::HashedString};
+use diagnostics::{
+    DiagnosticResult, MaybeDiagnostic,
+    builders::{make_bound_trait, make_unknown_trait},
+};
 
-use crate::tree::Type;
+use crate::{TypedGlobalScope, raw::RawType, traits::RawTraitContainer, tree::Type};
 
 pub const TRAIT_NUMERIC: u64 = hash!("numeric");
 pub const TRAIT_SIGNED: u64 = hash!("signed");
@@ -36,7 +42,7 @@ pub const TRAIT_CPU_SUPPORTED: u64 = hash!("cpusupported");
 pub const TRAIT_STRING: u64 = hash!("stringlike");
 pub const TRAIT_STATIC: u64 = hash!("static");
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Trait {
     Numeric,
     Signed,
@@ -47,6 +53,28 @@ pub enum Trait {
     CpuSupported,
     String,
     Static,
+
+    /// A user declared trait. Is resolved by name when checking the bound
+    Custom(HashedString),
+}
+
+impl Trait {
+    /// Obtains the trait matching the given name hash if any.
+    pub fn from_hash(hash: u64) -> Option<Trait> {
+        return match hash {
+            TRAIT_NUMERIC => Some(Trait::Numeric),
+            TRAIT_SIGNED => Some(Trait::Signed),
+            TRAIT_INTEGER => Some(Trait::Integer),
+            TRAIT_FLOATING => Some(Trait::Floating),
+            TRAIT_FIXED => Some(Trait::Fixed),
+            TRAIT_NON_INTEGER => Some(Trait::NonInteger),
+            TRAIT_CPU_SUPPORTED => Some(Trait::CpuSupported),
+            TRAIT_STRING => Some(Trait::String),
+            TRAIT_STATIC => Some(Trait::Static),
+
+            _ => None,
+        };
+    }
 }
 
 impl Display for Trait {
@@ -61,12 +89,14 @@ impl Display for Trait {
             Trait::CpuSupported => "cpusupported",
             Trait::String => "stringlike",
             Trait::Static => "static",
+            Trait::Custom(name) => name.val.as_str(),
         };
 
         write!(f, "!{}", s)
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TraitBoundMember {
     /// Selects a trait to require it
     Select(Trait),
@@ -76,22 +106,38 @@ pub enum TraitBoundMember {
 }
 
 /// Represents the actual trait bound. Is used to make sure that the type is compatible
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct TraitBound {
     pub members: Vec<TraitBoundMember>,
 }
 
 impl TraitBound {
-    pub fn check(&self, t: &Type) -> MaybeDiagnostic {
+    pub fn new() -> Self {
+        return TraitBound { members: vec![] };
+    }
+
+    #[must_use = "Must set the diagnostic position beforehand"]
+    pub fn check(&self, t: &Type, storage: &TypedGlobalScope) -> MaybeDiagnostic {
         for member in &self.members {
             match member {
+                TraitBoundMember::Select(Trait::Custom(name)) => {
+                    Self::get_custom_trait(name, storage)?.matches(t)?;
+                }
+
+                TraitBoundMember::Exclude(Trait::Custom(name)) => {
+                    if Self::get_custom_trait(name, storage)?.is_implemented_by(t) {
+                        return Err(make_bound_trait(&format!("~!{}", name.val), t).into());
+                    }
+                }
+
                 TraitBoundMember::Select(tt) => {
-                    if !t.as_generic().has_trait(tt.clone(), t) {
+                    if !Self::has_trait(t, tt) {
                         return Err(make_bound_trait(tt, t).into());
                     }
                 }
 
                 TraitBoundMember::Exclude(tt) => {
-                    if t.as_generic().has_trait(tt.clone(), t) {
+                    if Self::has_trait(t, tt) {
                         return Err(make_bound_trait(&format!("~{}", tt), t).into());
                     }
                 }
@@ -100,4 +146,34 @@ impl TraitBound {
 
         return Ok(());
     }
+
+    /// Gets the user declared trait with the given name
+    #[must_use = "Must set the diagnostic position beforehand"]
+    pub fn get_custom_trait(
+        name: &HashedString,
+        storage: &TypedGlobalScope,
+    ) -> DiagnosticResult<RawTraitContainer> {
+        let key = EntryKey {
+            name_hash: name.hash,
+            name: name.val.clone(),
+        };
+
+        if let Some(ind) = storage.entry_to_ind.get(&key)
+            && let GlobalStorageEntryType::Type(RawType::Trait(container)) =
+                &storage.entries[*ind].entry_type
+        {
+            return Ok(container.clone());
+        }
+
+        return Err(make_unknown_trait(&name.val).into());
+    }
+
+    /// Non generic types (pointers, arrays, references) do not carry any trait
+    fn has_trait(t: &Type, tt: &Trait) -> bool {
+        if !t.is_generic_direct() {
+            return false;
+        }
+
+        return t.as_generic().has_trait(tt.clone(), t);
+    }
 }
@@ -0,0 +1,187 @@
+//! Detection of recursive types. A type containing itself by value (directly or through other types) has an infinite size and cannot be represented.
+
+use std::collections::HashSet;
+
+use compiler_utils::utils::indexed::IndexStorage;
+
+use crate::{TypedGlobalScope, raw::RawType, references::TypeReference, tree::Type};
+
+/// A step of a type cycle.
+/// 0: The global index of the type
+/// 1: The hash of the field leading to the next type of the cycle
+pub type TypeCycleStep = (usize, u64);
+
+/// Finds a cycle of fields stored by value leading back to the type at the given global index. Pointers and references break cycles.
+/// Returns the steps of the cycle if the type has an infinite size.
+pub fn find_type_cycle(root: usize, storage: &TypedGlobalScope) -> Option<Vec<TypeCycleStep>> {
+    let raw = storage.entries[root].as_type_unsafe();
+    let t = Type::Generic(raw.clone(), vec![], vec![]);
+
+    let mut path = vec![];
+    let mut visited = HashSet::new();
+
+    let found = match &raw {
+        RawType::Struct(_, container) => visit_fields(
+            root,
+            &container.fields,
+            &t,
+            root,
+            storage,
+            &mut path,
+            &mut visited,
+        ),
+
+        RawType::Enum(container) => container.entries.values().any(|entry| match entry {
+            RawType::EnumEntry(entry) => visit_fields(
+                root,
+                &entry.fields,
+                &t,
+                root,
+                storage,
+                &mut path,
+                &mut visited,
+            ),
+            _ => false,
+        }),
+
+        _ => false,
+    };
+
+    if found {
+        return Some(path);
+    }
+
+    return None;
+}
+
+fn visit_type(
+    t: &Type,
+    root: usize,
+    storage: &TypedGlobalScope,
+    path: &mut Vec<TypeCycleStep>,
+    visited: &mut HashSet<(usize, Vec<Type>)>,
+) -> bool {
+    // Every instantiation of a generic type has its own fields
+    let params = match t {
+        Type::Generic(_, params, _) => params.iter().map(|p| *p.clone()).collect(),
+        _ => vec![],
+    };
+
+    match t {
+        Type::Array(_, inner) => visit_type(inner, root, storage, path, visited),
+        Type::Tuple(elements) => elements
+            .iter()
+            .any(|e| visit_type(e, root, storage, path, visited)),
+        // Closures only point to their environment
+        Type::Pointer(_, _)
+        | Type::Reference(_)
+        | Type::Function(_, _)
+        | Type::Closure(_, _)
+        | Type::Slice(_) => false,
+
+        Type::Generic(raw, _, _) | Type::GenericLowered(raw) => match raw {
+            RawType::Struct(_, container) => {
+                let ind = container.self_ref;
+
+                if ind == root {
+                    return true;
+                }
+
+                if !visited.insert((ind, params)) {
+                    return false;
+                }
+
+                // The type contained within the field might be a snapshot of a type still being declared
+                let fields = match storage.entries[ind].as_type_unsafe() {
+                    RawType::Struct(_, current) => current.fields,
+                    _ => container.fields.clone(),
+                };
+
+                return visit_fields(ind, &fields, t, root, storage, path, visited);
+            }
+
+            RawType::Enum(container) => {
+                let ind = container.self_ref;
+
+                if ind == root {
+                    return true;
+                }
+
+                if !visited.insert((ind, params)) {
+                    return false;
+                }
+
+                let entries = match storage.entries[ind].as_type_unsafe() {
+                    RawType::Enum(current) => current.entries,
+                    _ => container.entries.clone(),
+                };
+
+                return entries.values().any(|entry| match entry {
+                    RawType::EnumEntry(entry) => {
+                        visit_fields(ind, &entry.fields, t, root, storage, path, visited)
+                    }
+                    _ => false,
+                });
+            }
+
+            RawType::EnumEntry(container) => visit_fields(
+                container.parent,
+                &container.fields,
+                t,
+                root,
+                storage,
+                path,
+                visited,
+            ),
+
+            _ => false,
+        },
+    }
+}
+
+fn visit_fields(
+    ind: usize,
+    fields: &IndexStorage<TypeReference>,
+    t: &Type,
+    root: usize,
+    storage: &TypedGlobalScope,
+    path: &mut Vec<TypeCycleStep>,
+    visited: &mut HashSet<(usize, Vec<Type>)>,
+) -> bool {
+    for (field, hash) in fields.vals.iter().zip(&fields.entry_keys) {
+        let field_type = match resolve_field(field, t) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        path.push((ind, *hash));
+
+        if visit_type(&field_type, root, storage, path, visited) {
+            return true;
+        }
+
+        path.pop();
+    }
+
+    return false;
+}
+
+/// Resolves the field type without requiring every type parameter to be known.
+fn resolve_field(field: &TypeReference, t: &Type) -> Option<Type> {
+    let params = match t {
+        Type::Generic(_, params, _) => params.clone(),
+        _ => vec![],
+    };
+
+    match field {
+        TypeReference::Resolved(val) => {
+            if val.has_type_parameters() && !params.is_empty() {
+                return Some(val.substitute(&params));
+            }
+
+            return Some(val.clone());
+        }
+
+        TypeReference::Unresolved(ind) => params.get(*ind).map(|t| *t.clone()),
+    }
+}
@@ -4,47 +4,146 @@ use std::collections::HashMap;
 
 use compiler_utils::{hash::HashedString, utils::indexed::IndexStorage};
 use diagnostics::{
-    DiagnosticResult,
+    DiagnosticResult, MaybeDiagnostic,
     builders::{
         make_cannot_find_type_field, make_cannot_find_type_function, make_cannot_find_type_pos,
-        make_enum_parent_fields,
+        make_duplicate_discriminant, make_enum_parent_fields,
     },
+    unsure_panic,
 };
 
 use crate::{
-    RawTypeReference, SizedType, StructuredType, TypeParamType, TypeParameterContainer,
-    TypedFunction, TypedGlobalScope, raw::RawType, references::TypeReference, tree::Type,
+    RawTypeReference, SizedType, StructuredType, TypeParamType, TypeParameterBounds,
+    TypeParameterContainer, TypedFunction, TypedGlobalScope, raw::RawType,
+    references::TypeReference, tree::Type,
 };
 
 /// The container for the parent type of enum.
 ///
 /// # Safety
 /// This struct guarantees that every contained entry is of type RawType::EnumEntry
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug)]
 pub struct RawEnumTypeContainer {
+    pub name: HashedString,
     pub self_ref: usize,
     pub type_params: TypeParameterContainer,
+    pub type_bounds: TypeParameterBounds,
     pub functions: IndexStorage<TypedFunction>,
+    pub function_ids: Vec<usize>,
     pub entries: HashMap<HashedString, RawType>,
+
+    /// The declared integer type of the enum hint, if any
+    pub repr: Option<Box<RawType>>,
+
+    /// The discriminant given to the next entry that doesn't declare one. None once an entry used the biggest discriminant
+    pub next_discriminant: Option<i128>,
 }
 
+/// Enum types are nominal. Types obtained before functions were implemented for the enum still refer to the same enum
+impl PartialEq for RawEnumTypeContainer {
+    fn eq(&self, other: &Self) -> bool {
+        self.self_ref == other.self_ref
+    }
+}
+
+impl Eq for RawEnumTypeContainer {}
+
 impl RawEnumTypeContainer {
-    pub fn new(self_ref: usize, type_params: TypeParameterContainer) -> Self {
+    pub fn new(
+        name: HashedString,
+        self_ref: usize,
+        type_params: TypeParameterContainer,
+        type_bounds: TypeParameterBounds,
+    ) -> Self {
         RawEnumTypeContainer {
+            name,
             self_ref,
             entries: HashMap::new(),
             type_params,
+            type_bounds,
             functions: IndexStorage::new(),
+            function_ids: vec![],
+            repr: None,
+            next_discriminant: Some(0),
         }
     }
 
-    pub fn append_entry(&mut self, name: HashedString, fields: Vec<(u64, TypeReference)>) {
-        let mut entry_container =
-            RawEnumEntryContainer::new(self.self_ref, fields, self.type_params.clone());
+    /// Appends an entry to the enum. Entries without an explicit discriminant follow the previous entry, like in C.
+    #[must_use = "Must set the diagnostic position beforehand"]
+    pub fn append_entry(
+        &mut self,
+        name: HashedString,
+        fields: Vec<(u64, TypeReference)>,
+        discriminant: Option<i128>,
+    ) -> MaybeDiagnostic {
+        let discriminant = match discriminant.or(self.next_discriminant) {
+            Some(v) => v,
+            None => unsure_panic!(
+                "enum entries past the biggest discriminant must be rejected beforehand"
+            ),
+        };
+
+        for (other_name, other) in &self.entries {
+            if let RawType::EnumEntry(other) = other
+                && other.discriminant == discriminant
+            {
+                return Err(
+                    make_duplicate_discriminant(&discriminant, &name.val, &other_name.val).into(),
+                );
+            }
+        }
+
+        let mut entry_container = RawEnumEntryContainer::new(
+            format!("{}::{}", self.name.val, name.val),
+            self.self_ref,
+            fields,
+            self.type_params.clone(),
+        );
         entry_container.child = self.entries.len();
+        entry_container.discriminant = discriminant;
+
+        self.next_discriminant = discriminant.checked_add(1);
 
         self.entries
             .insert(name, RawType::EnumEntry(entry_container));
+
+        return Ok(());
+    }
+
+    /// Gets the smallest and biggest discriminants of the enum
+    pub fn get_discriminant_range(&self) -> (i128, i128) {
+        let mut min = 0;
+        let mut max = 0;
+
+        for entry in self.entries.values() {
+            if let RawType::EnumEntry(entry) = entry {
+                min = min.min(entry.discriminant);
+                max = max.max(entry.discriminant);
+            }
+        }
+
+        return (min, max);
+    }
+
+    /// Checks if every entry of the enum is fieldless, making the enum a plain integer tag
+    pub fn is_fieldless(&self) -> bool {
+        self.entries.values().all(|entry| match entry {
+            RawType::EnumEntry(entry) => entry.fields.vals.is_empty(),
+            _ => false,
+        })
+    }
+
+    /// Gets the entry using the given discriminant
+    pub fn get_entry_by_discriminant(&self, discriminant: i128) -> Option<&RawEnumEntryContainer> {
+        for entry in self.entries.values() {
+            if let RawType::EnumEntry(entry) = entry
+                && entry.discriminant == discriminant
+            {
+                return Some(entry);
+            }
+        }
+
+        return None;
     }
 
     #[must_use = "Must set the diagnostic position beforehand"]
@@ -56,13 +155,34 @@ impl RawEnumTypeContainer {
         return Err(make_cannot_find_type_pos(&format!("{}::{}", self.self_ref, name.val)).into());
     }
 
+    /// Gets the integer type used to store the discriminant. Either the declared representation or the smallest integer able to hold every discriminant
     pub fn get_hint_type(&self) -> RawType {
-        RawType::make_hint(self.entries.len())
+        if let Some(repr) = &self.repr {
+            return *repr.clone();
+        }
+
+        let (min, max) = self.get_discriminant_range();
+
+        // Counted through the leading bits as the range can span every discriminant
+        if min < 0 {
+            let bits = (128 - min.leading_ones()).max(128 - max.leading_zeros()) + 1;
+
+            return RawType::Integer(bits as usize, true);
+        }
+
+        RawType::Integer((128 - max.leading_zeros()) as usize, false)
     }
 }
 
 impl SizedType for RawEnumTypeContainer {
     fn get_size(&self, t: &Type, compacted_size: bool, storage: &TypedGlobalScope) -> usize {
+        // Niche filled enums are exactly as big as their dataful entry
+        if let Some(niche) = self.get_niche(t, storage) {
+            let dataful = self.get_entry_by_discriminant(niche.dataful).unwrap();
+
+            return dataful.get_size(t, compacted_size, storage);
+        }
+
         let mut entry_size = 0;
 
         if compacted_size {
@@ -77,7 +197,7 @@ impl SizedType for RawEnumTypeContainer {
             }
         }
 
-        let hint = RawType::make_hint(self.entries.len());
+        let hint = self.get_hint_type();
 
         return hint.get_size(t, compacted_size, storage) + entry_size;
     }
@@ -86,14 +206,20 @@ impl SizedType for RawEnumTypeContainer {
 /// The container for enum entries.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RawEnumEntryContainer {
+    /// The full name of the entry (eg: `maybe::some`)
+    pub name: String,
     pub parent: RawTypeReference,
     pub type_params: TypeParameterContainer,
     pub child: usize,
+
+    /// The value of the enum hint for this entry
+    pub discriminant: i128,
     pub fields: IndexStorage<TypeReference>,
 }
 
 impl RawEnumEntryContainer {
     pub fn new(
+        name: String,
         parent: RawTypeReference,
         fields: Vec<(u64, TypeReference)>,
         type_params: TypeParameterContainer,
@@ -105,9 +231,11 @@ impl RawEnumEntryContainer {
         }
 
         RawEnumEntryContainer {
+            name,
             parent,
             fields: storage,
             child: 0,
+            discriminant: 0,
             type_params,
         }
     }
@@ -154,7 +282,7 @@ impl StructuredType for RawEnumTypeContainer {
         let k = match self.functions.get_index(hash) {
             Some(v) => v,
             None => {
-                return Err(make_cannot_find_type_function(&hash, &"unnamed".to_string()).into());
+                return Err(make_cannot_find_type_function(&hash, &self.name.val).into());
             }
         };
 
@@ -165,7 +293,7 @@ impl StructuredType for RawEnumTypeContainer {
         let k = match self.functions.get_index(hash) {
             Some(v) => v,
             None => {
-                return Err(make_cannot_find_type_function(&hash, &"unnamed".to_string()).into());
+                return Err(make_cannot_find_type_function(&hash, &self.name.val).into());
             }
         };
 
@@ -181,13 +309,19 @@ impl TypeParamType for RawEnumTypeContainer {
     fn get_type_param_ind(&self, param: &HashedString) -> usize {
         self.type_params[param]
     }
+
+    fn get_type_params(&self) -> &TypeParameterContainer {
+        &self.type_params
+    }
 }
 
 impl StructuredType for RawEnumEntryContainer {
     fn get_field(&self, hash: u64, _storage: &TypedGlobalScope) -> DiagnosticResult<TypeReference> {
         let k = match self.fields.get_index(hash) {
             Some(v) => v,
-            None => return Err(make_cannot_find_type_field(&hash, &"unamed".to_string()).into()),
+            None => {
+                return Err(make_cannot_find_type_field(&hash, &self.name).into());
+            }
         };
 
         return Ok(self.fields.vals[k].clone());
@@ -196,7 +330,9 @@ impl StructuredType for RawEnumEntryContainer {
     fn get_field_hash(&self, hash: u64, _storage: &TypedGlobalScope) -> DiagnosticResult<usize> {
         let k = match self.fields.get_index(hash) {
             Some(v) => v,
-            None => return Err(make_cannot_find_type_field(&hash, &"unamed".to_string()).into()),
+            None => {
+                return Err(make_cannot_find_type_field(&hash, &self.name).into());
+            }
         };
 
         return Ok(k);
@@ -243,4 +379,8 @@ impl TypeParamType for RawEnumEntryContainer {
     fn get_type_param_ind(&self, param: &HashedString) -> usize {
         self.type_params[param]
     }
+
+    fn get_type_params(&self) -> &TypeParameterContainer {
+        &self.type_params
+    }
 }
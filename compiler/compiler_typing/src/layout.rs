@@ -0,0 +1,357 @@
+//! ABI layouts of types. Computes the size, alignment and field offsets of types the same way LLVM / C would.
+//!
+//! Every size, alignment and offset is in bits, just like `SizedType::get_size`.
+//!
+//! # Arbitrary width integers
+//! Integers of any width are stored like scalars, see `TypeLayout::scalar`: `u.24` is stored in 4 bytes and `s.65` in
+//! 16 bytes, the extra bits being padding. They are passed to and returned from extern functions as integers of that
+//! storage width.
+//!
+//! # Field order
+//! `layout` types are C-compatible and thus keep their fields in declaration order. `struct` types are free to reorder
+//! their fields in order to reduce padding (by decreasing alignment). `union` types store every field at offset 0.
+//!
+//! Bitfield members of `layout` types share integer storage units, see `compiler_typing::bitfields`.
+//!
+//! Tuples are laid out like `layout` types.
+
+use crate::{
+    TypedGlobalScope,
+    bitfields::StorageMember,
+    enums::{RawEnumEntryContainer, RawEnumTypeContainer},
+    raw::RawType,
+    structs::{LoweredStructTypeContainer, RawStructTypeContainer},
+    tree::Type,
+    utils::get_pointer_size,
+};
+
+/// The maximal alignment of scalar types, in bits.
+const MAX_SCALAR_ALIGNMENT: usize = 128;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TypeLayout {
+    pub size: usize,
+    pub alignment: usize,
+}
+
+impl TypeLayout {
+    pub fn new(size: usize, alignment: usize) -> Self {
+        TypeLayout { size, alignment }
+    }
+
+    /// Makes the layout of a scalar of the given amount of bits. Scalars are aligned on the smallest power of two amount of bytes that can contain them.
+    pub fn scalar(bits: usize) -> Self {
+        let bytes = bits.div_ceil(8).max(1);
+
+        let alignment = (bytes.next_power_of_two() * 8).min(MAX_SCALAR_ALIGNMENT);
+
+        return TypeLayout::new(align_to(bytes * 8, alignment), alignment);
+    }
+
+    /// Places the given fields one after each other with padding. Returns the layout of the whole and the offset of every field.
+    pub fn of_fields(fields: &Vec<TypeLayout>) -> (TypeLayout, Vec<usize>) {
+        let mut offsets = vec![];
+
+        let mut size = 0;
+        let mut alignment = 8;
+
+        for field in fields {
+            size = align_to(size, field.alignment);
+
+            offsets.push(size);
+
+            size += field.size;
+            alignment = alignment.max(field.alignment);
+        }
+
+        return (
+            TypeLayout::new(align_to(size, alignment), alignment),
+            offsets,
+        );
+    }
+
+    /// Places the given fields at the same offset. The whole is as big as the biggest field once aligned.
+    pub fn of_union(fields: &Vec<TypeLayout>) -> TypeLayout {
+        let mut size = 0;
+        let mut alignment = 8;
+
+        for field in fields {
+            size = size.max(field.size);
+            alignment = alignment.max(field.alignment);
+        }
+
+        return TypeLayout::new(align_to(size, alignment), alignment);
+    }
+}
+
+/// Rounds up the given value to a multiple of the alignment.
+pub fn align_to(val: usize, alignment: usize) -> usize {
+    if alignment == 0 {
+        return val;
+    }
+
+    return val.div_ceil(alignment) * alignment;
+}
+
+impl Type {
+    /// Gets the ABI layout of the type.
+    pub fn get_layout(&self, storage: &TypedGlobalScope) -> TypeLayout {
+        match self {
+            Type::Array(size, inner) => {
+                let inner = inner.get_layout(storage);
+
+                return TypeLayout::new(inner.size * size, inner.alignment);
+            }
+
+            Type::Pointer(_, _) | Type::Reference(_) | Type::Function(_, _) => {
+                TypeLayout::scalar(get_pointer_size())
+            }
+
+            // The function pointer followed by the environment pointer
+            Type::Closure(_, _) => TypeLayout::new(get_pointer_size() * 2, get_pointer_size()),
+
+            // The element pointer followed by the pointer sized element count
+            Type::Slice(_) => {
+                TypeLayout::of_fields(&vec![
+                    TypeLayout::scalar(get_pointer_size()),
+                    TypeLayout::scalar(get_pointer_size()),
+                ])
+                .0
+            }
+
+            // Tuples keep their elements in declaration order
+            Type::Tuple(elements) => {
+                let elements = elements.iter().map(|e| e.get_layout(storage)).collect();
+
+                return TypeLayout::of_fields(&elements).0;
+            }
+
+            Type::Generic(raw, _, _) | Type::GenericLowered(raw) => raw.get_layout(self, storage),
+        }
+    }
+}
+
+impl RawType {
+    /// Gets the ABI layout of the raw type. `t` is used to resolve the type parameters.
+    pub fn get_layout(&self, t: &Type, storage: &TypedGlobalScope) -> TypeLayout {
+        match self {
+            RawType::Integer(size, _) => TypeLayout::scalar(*size),
+            RawType::Floating(size, _) => TypeLayout::scalar(*size),
+            RawType::FixedPoint(a, b, _) => TypeLayout::scalar(a + b),
+            RawType::Boolean => TypeLayout::scalar(8),
+            RawType::Pointer | RawType::StaticString => TypeLayout::scalar(get_pointer_size()),
+
+            RawType::Struct(layout, container) => {
+                container.get_fields_layout(t, !*layout, storage).0
+            }
+            RawType::EnumEntry(container) => container.get_fields_layout(t, storage).0,
+            RawType::Enum(container) => container.get_layout(t, storage),
+            RawType::LoweredStruct(_, container) => container.get_layout(storage),
+
+            _ => TypeLayout::new(0, 8),
+        }
+    }
+}
+
+impl RawStructTypeContainer {
+    /// Gets the order in which the fields are stored in memory. Contains the field indexes.
+    pub fn get_field_order(
+        &self,
+        t: &Type,
+        reorder: bool,
+        storage: &TypedGlobalScope,
+    ) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.fields.vals.len()).collect();
+
+        if reorder {
+            let alignments: Vec<usize> = self
+                .fields
+                .vals
+                .iter()
+                .map(|field| field.clone().resolve(t).get_layout(storage).alignment)
+                .collect();
+
+            // Stable sort, fields with the same alignment keep their declaration order
+            order.sort_by(|a, b| alignments[*b].cmp(&alignments[*a]));
+        }
+
+        return order;
+    }
+
+    /// Gets the layout of the struct alongside the offset of every field. Offsets are indexed by field index.
+    pub fn get_fields_layout(
+        &self,
+        t: &Type,
+        reorder: bool,
+        storage: &TypedGlobalScope,
+    ) -> (TypeLayout, Vec<usize>) {
+        let order = self.get_field_order(t, reorder, storage);
+
+        let fields = order
+            .iter()
+            .map(|ind| {
+                self.fields.vals[*ind]
+                    .clone()
+                    .resolve(t)
+                    .get_layout(storage)
+            })
+            .collect();
+
+        if self.is_union {
+            return (TypeLayout::of_union(&fields), vec![0; order.len()]);
+        }
+
+        if !self.bitfields.is_empty() {
+            return self.get_bitfields_layout(&order, fields);
+        }
+
+        let (layout, ordered_offsets) = TypeLayout::of_fields(&fields);
+
+        let mut offsets = vec![0; order.len()];
+
+        for (ind, offset) in order.into_iter().zip(ordered_offsets) {
+            offsets[ind] = offset;
+        }
+
+        return (layout, offsets);
+    }
+
+    /// Gets the layout of a struct containing bitfields. The offset of a bitfield is the offset of its first bit.
+    fn get_bitfields_layout(
+        &self,
+        order: &Vec<usize>,
+        fields: Vec<TypeLayout>,
+    ) -> (TypeLayout, Vec<usize>) {
+        let (members, placements) = self.get_storage_members(order);
+
+        let member_layouts = members
+            .iter()
+            .map(|member| match member {
+                StorageMember::Field(ind) => fields[order.iter().position(|v| v == ind).unwrap()],
+                StorageMember::Unit(size, _) => TypeLayout::scalar(*size),
+            })
+            .collect();
+
+        let (layout, member_offsets) = TypeLayout::of_fields(&member_layouts);
+
+        let mut offsets = vec![0; order.len()];
+
+        for (member, offset) in members.iter().zip(member_offsets) {
+            match member {
+                StorageMember::Field(ind) => offsets[*ind] = offset,
+                StorageMember::Unit(_, contained) => {
+                    for ind in contained {
+                        offsets[*ind] = offset + placements[ind].shift;
+                    }
+                }
+            }
+        }
+
+        return (layout, offsets);
+    }
+}
+
+impl RawEnumEntryContainer {
+    /// Gets the layout of the enum entry alongside the offset of every field. The enum hint is always stored first.
+    ///
+    /// Entries of niche filled enums do not have any hint and share the layout of the dataful entry, see `compiler_typing::niches`.
+    pub fn get_fields_layout(
+        &self,
+        t: &Type,
+        storage: &TypedGlobalScope,
+    ) -> (TypeLayout, Vec<usize>) {
+        let parent = match storage.entries[self.parent].as_type_unsafe() {
+            RawType::Enum(container) => container,
+            _ => panic!("Parent type of enum entry was not an enum!"),
+        };
+
+        if let Some(niche) = parent.get_niche(t, storage) {
+            let dataful = parent.get_entry_by_discriminant(niche.dataful).unwrap();
+
+            let fields = dataful
+                .fields
+                .vals
+                .iter()
+                .map(|field| field.clone().resolve(t).get_layout(storage))
+                .collect();
+
+            let (layout, offsets) = TypeLayout::of_fields(&fields);
+
+            if self.discriminant != niche.dataful {
+                return (layout, vec![]);
+            }
+
+            return (layout, offsets);
+        }
+
+        let mut fields = vec![parent.get_hint_type().get_layout(t, storage)];
+
+        for field in &self.fields.vals {
+            fields.push(field.clone().resolve(t).get_layout(storage));
+        }
+
+        let (layout, mut offsets) = TypeLayout::of_fields(&fields);
+
+        offsets.remove(0);
+
+        return (layout, offsets);
+    }
+}
+
+impl RawEnumTypeContainer {
+    /// Gets the layout of the enum. Enums are stored as their hint followed by their payload, unless niche filled.
+    pub fn get_layout(&self, t: &Type, storage: &TypedGlobalScope) -> TypeLayout {
+        if let Some(niche) = self.get_niche(t, storage) {
+            let dataful = self.get_entry_by_discriminant(niche.dataful).unwrap();
+
+            return dataful.get_fields_layout(t, storage).0;
+        }
+
+        let mut fields = vec![self.get_hint_type().get_layout(t, storage)];
+
+        // Fieldless enums are only made of their hint
+        if !self.is_fieldless() {
+            fields.push(self.get_payload_layout(t, storage));
+        }
+
+        return TypeLayout::of_fields(&fields).0;
+    }
+
+    /// Gets the layout of the payload of the enum, the space following the hint that is able to contain the fields of any entry.
+    pub fn get_payload_layout(&self, t: &Type, storage: &TypedGlobalScope) -> TypeLayout {
+        let payloads = self
+            .entries
+            .values()
+            .map(|entry| match entry {
+                RawType::EnumEntry(container) => {
+                    let fields = container
+                        .fields
+                        .vals
+                        .iter()
+                        .map(|field| field.clone().resolve(t).get_layout(storage))
+                        .collect();
+
+                    TypeLayout::of_fields(&fields).0
+                }
+
+                _ => panic!("Enum entry was not an enum entry!"),
+            })
+            .collect();
+
+        TypeLayout::of_union(&payloads)
+    }
+}
+
+impl LoweredStructTypeContainer {
+    /// Gets the layout of the lowered struct. Lowered fields are already in memory order.
+    pub fn get_layout(&self, storage: &TypedGlobalScope) -> TypeLayout {
+        let fields = self
+            .fields
+            .vals
+            .iter()
+            .map(|field| field.get_layout(storage))
+            .collect();
+
+        return TypeLayout::of_fields(&fields).0;
+    }
+}
@@ -6,13 +6,18 @@ use compiler_global_scope::{GlobalScopeStorage, entry::GlobalStorageEntryType};
 use compiler_utils::hash::HashedString;
 use diagnostics::DiagnosticResult;
 
-use crate::{raw::RawType, references::TypeReference, tree::Type};
+use crate::{bounds::traits::TraitBound, raw::RawType, references::TypeReference, tree::Type};
 
+pub mod bitfields;
 pub mod bounds;
+pub mod cycles;
 pub mod enums;
+pub mod layout;
+pub mod niches;
 pub mod raw;
 pub mod references;
 pub mod structs;
+pub mod traits;
 pub mod transmutation;
 pub mod tree;
 pub mod utils;
@@ -26,11 +31,15 @@ pub type TypedResolvedFunction = (Vec<(u64, Type)>, Option<Type>);
 
 pub type TypeParameterContainer = HashMap<HashedString, usize>;
 
+/// The trait bounds of every type parameter. Indexed by the type parameter index.
+pub type TypeParameterBounds = Vec<TraitBound>;
+
 pub type RawTypeReference = usize;
 
 /// Represents a basic type that has a size.
 pub trait SizedType {
-    /// Obtains the size of the type. The `compacted_size` parameter determines if the compacted size should be returned or not
+    /// Obtains the size of the type. The `compacted_size` parameter determines if the compacted size should be returned or not.
+    /// The non compacted size is the ABI size of the type, padding included
     fn get_size(&self, t: &Type, compacted_size: bool, storage: &TypedGlobalScope) -> usize;
 }
 
@@ -41,6 +50,9 @@ pub trait TypeParamType {
 
     /// Gets the raw index for the given type parameter
     fn get_type_param_ind(&self, param: &HashedString) -> usize;
+
+    /// Gets every type parameter of the type alongside its raw index
+    fn get_type_params(&self) -> &TypeParameterContainer;
 }
 
 /// Represents types that can contain functions and more
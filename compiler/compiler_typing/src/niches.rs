@@ -0,0 +1,184 @@
+//! Niche filling for enums. A niche is a range of invalid values of a field (a null pointer, a boolean that is neither
+//! true nor false, an unused hint value of an enum). When an enum has only a single entry holding data, its fieldless
+//! entries can be represented by these invalid values instead of requiring a hint, making the enum as big as its data.
+
+use crate::{
+    TypedGlobalScope, enums::RawEnumTypeContainer, raw::RawType, references::TypeReference,
+    tree::Type, utils::get_pointer_size,
+};
+
+/// The niche used by an enum to store its fieldless entries.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EnumNiche {
+    /// The discriminant of the only entry holding data
+    pub dataful: i128,
+
+    /// The index of the field of the dataful entry containing the niche
+    pub field: usize,
+
+    /// Is the niche the hint of the enum stored within the field
+    pub nested: bool,
+
+    /// The size of the niche value, in bits
+    pub size: usize,
+
+    /// The niche value of every fieldless entry.
+    /// 0: The discriminant of the entry
+    /// 1: The niche value representing the entry
+    pub values: Vec<(i128, u128)>,
+}
+
+impl EnumNiche {
+    /// Gets the niche value representing the entry with the given discriminant, if said entry is a fieldless one
+    pub fn get_value(&self, discriminant: i128) -> Option<u128> {
+        return self
+            .values
+            .iter()
+            .find(|(d, _)| *d == discriminant)
+            .map(|(_, v)| *v);
+    }
+}
+
+/// Gets the invalid values of the given type if any.
+/// Returns the size of the values in bits, whether the values are the hint of a nested enum, the first invalid value and the amount of invalid values.
+fn get_type_niche(t: &Type, storage: &TypedGlobalScope) -> Option<(usize, bool, u128, u128)> {
+    match t {
+        // Functions are never stored at the null address either
+        Type::Pointer(_, _) | Type::Reference(_) | Type::Function(_, _) => {
+            Some((get_pointer_size(), false, 0, 1))
+        }
+
+        Type::Generic(raw, _, _) | Type::GenericLowered(raw) => match raw {
+            // Booleans take a whole byte of which only 0 and 1 are valid
+            RawType::Boolean => Some((8, false, 2, 254)),
+
+            RawType::Enum(container) => {
+                // The type contained within the field might be a snapshot of a type still being declared
+                let container = match storage.entries[container.self_ref].as_type_unsafe() {
+                    RawType::Enum(current) => current,
+                    _ => container.clone(),
+                };
+
+                // Niche filled enums do not have any hint left to use
+                if container.get_niche(t, storage).is_some() {
+                    return None;
+                }
+
+                let (size, signed) = match container.get_hint_type() {
+                    RawType::Integer(size, signed) => (size, signed),
+                    _ => return None,
+                };
+
+                let (_, max) = container.get_discriminant_range();
+
+                let bits = if signed { size - 1 } else { size };
+
+                // Every value of a 128 bits hint cannot be counted
+                if bits >= 128 {
+                    return None;
+                }
+
+                let limit = (1u128 << bits) - 1;
+
+                if size == 0 || max < 0 || max as u128 >= limit {
+                    return None;
+                }
+
+                return Some((size, true, max as u128 + 1, limit - max as u128));
+            }
+
+            _ => None,
+        },
+
+        _ => None,
+    }
+}
+
+impl RawEnumTypeContainer {
+    /// Finds the niche able to store every fieldless entry of the enum. `t` is used to resolve the type parameters.
+    ///
+    /// Only enums without a declared representation and with a single entry holding data can be niche filled.
+    pub fn get_niche(&self, t: &Type, storage: &TypedGlobalScope) -> Option<EnumNiche> {
+        if self.repr.is_some() {
+            return None;
+        }
+
+        let mut dataful = None;
+        let mut fieldless = vec![];
+
+        for entry in self.entries.values() {
+            if let RawType::EnumEntry(entry) = entry {
+                if entry.fields.vals.is_empty() {
+                    fieldless.push(entry.discriminant);
+                    continue;
+                }
+
+                if dataful.is_some() {
+                    return None;
+                }
+
+                dataful = Some(entry);
+            }
+        }
+
+        let dataful = dataful?;
+
+        if fieldless.is_empty() {
+            return None;
+        }
+
+        // Keeps the niche values stable no matter the order of the entries
+        fieldless.sort();
+
+        let params = match t {
+            Type::Generic(_, params, _) => params.clone(),
+            _ => vec![],
+        };
+
+        for (ind, field) in dataful.fields.vals.iter().enumerate() {
+            let field_type = match field {
+                TypeReference::Resolved(val) => {
+                    if val.has_type_parameters() {
+                        if params.is_empty() {
+                            continue;
+                        }
+
+                        val.substitute(&params)
+                    } else {
+                        val.clone()
+                    }
+                }
+
+                TypeReference::Unresolved(param) => match params.get(*param) {
+                    Some(v) => *v.clone(),
+                    None => continue,
+                },
+            };
+
+            let (size, nested, start, count) = match get_type_niche(&field_type, storage) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            if count < fieldless.len() as u128 {
+                continue;
+            }
+
+            let values = fieldless
+                .iter()
+                .enumerate()
+                .map(|(i, d)| (*d, start + i as u128))
+                .collect();
+
+            return Some(EnumNiche {
+                dataful: dataful.discriminant,
+                field: ind,
+                nested,
+                size,
+                values,
+            });
+        }
+
+        return None;
+    }
+}
@@ -5,14 +5,18 @@ use std::{fmt::Display, hash::Hash};
 use diagnostics::{DiagnosticResult, DiagnosticSpanOrigin};
 
 use crate::{
-    SizedType, TypedGlobalScope,
+    SizedType, TypeParameterBounds, TypedGlobalScope,
     bounds::traits::Trait,
     enums::{RawEnumEntryContainer, RawEnumTypeContainer},
     structs::{LoweredStructTypeContainer, RawStructTypeContainer},
+    traits::RawTraitContainer,
     tree::Type,
     utils::get_pointer_size,
 };
 
+/// The widest integer, in bits. Integers can have any width from 1 bit up to this one
+pub const MAX_INTEGER_SIZE: usize = 128;
+
 /// The raw types. Are also named generics
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RawType {
@@ -33,6 +37,12 @@ pub enum RawType {
 
     LoweredStruct(bool, LoweredStructTypeContainer),
 
+    Trait(RawTraitContainer),
+
+    /// A type parameter that has yet to be substituted. Used within the types of fields and functions of types with type parameters (eg: `K*`)
+    /// 0: The index of the type parameter
+    TypeParameter(usize),
+
     SizedInteger(bool),
     SizedFloating(bool),
     SizedFixedPoint(bool),
@@ -62,6 +72,23 @@ impl RawType {
         }
     }
 
+    /// Gets the trait bounds of the type parameters of this type.
+    pub fn get_type_params_bounds<K: DiagnosticSpanOrigin>(
+        &self,
+        storage: &TypedGlobalScope,
+        origin: &K,
+    ) -> DiagnosticResult<TypeParameterBounds> {
+        match self {
+            RawType::Enum(container) => Ok(container.type_bounds.clone()),
+            RawType::EnumEntry(container) => Ok(storage.entries[container.parent]
+                .as_type(origin)?
+                .get_type_params_bounds(storage, origin)?),
+            RawType::Struct(_, container) => Ok(container.type_bounds.clone()),
+
+            _ => Ok(vec![]),
+        }
+    }
+
     pub fn is_enum_parent(&self) -> bool {
         match self {
             Self::Enum(_) => true,
@@ -87,6 +114,14 @@ impl RawType {
         }
     }
 
+    /// Can the type contain functions
+    pub fn is_structured(&self) -> bool {
+        matches!(
+            self,
+            RawType::Struct(_, _) | RawType::Enum(_) | RawType::EnumEntry(_)
+        )
+    }
+
     pub fn is_signed(&self) -> bool {
         match self {
             Self::Integer(_, signed) => *signed,
@@ -160,19 +195,10 @@ impl RawType {
             Trait::Numeric => {
                 self.is_integer() || self.is_floating_point() || self.is_fixed_point()
             }
+            Trait::Custom(_) => false, // Requires the storage, see `TraitBound::check`
             Trait::CpuSupported => match self {
-                Self::Floating(size, _) => {
-                    let log = size.ilog2();
-
-                    return (log >= 4 && log <= 7) || *size == 80;
-                }
-
-                Self::SizedFloating(_) => {
-                    let size = raw_type.get_generic_info().1[0];
-                    let log = size.ilog2();
-
-                    return (log >= 4 && log <= 7) || size == 80;
-                }
+                Self::Floating(size, _) => is_cpu_float_width(*size),
+                Self::SizedFloating(_) => is_cpu_float_width(raw_type.get_generic_info().1[0]),
 
                 _ => return true,
             },
@@ -185,12 +211,23 @@ impl RawType {
             (Self::SizedInteger(_), Self::Integer(_, _)) => true,
             (Self::Integer(_, _), Self::SizedInteger(_)) => true,
 
+            (Self::Floating(_, _), Self::Floating(_, _)) => true,
+
             (Self::Floating(_, _), Self::Integer(_, _)) => true,
             (Self::Integer(_, _), Self::Floating(_, _)) => true,
 
             (Self::Floating(_, _), Self::SizedInteger(_)) => true,
             (Self::SizedInteger(_), Self::Floating(_, _)) => true,
 
+            (Self::FixedPoint(_, _, _), Self::FixedPoint(_, _, _)) => true,
+
+            (Self::FixedPoint(_, _, _), Self::Integer(_, _)) => true,
+            (Self::Integer(_, _), Self::FixedPoint(_, _, _)) => true,
+            (Self::SizedInteger(_), Self::FixedPoint(_, _, _)) => true,
+
+            (Self::FixedPoint(_, _, _), Self::Floating(_, _)) => true,
+            (Self::Floating(_, _), Self::FixedPoint(_, _, _)) => true,
+
             (Self::StaticString, Self::Pointer) => true,
 
             (Self::EnumEntry(container), Self::Enum(c2)) => {
@@ -202,8 +239,18 @@ impl RawType {
     }
 }
 
+/// Whether floats of the given width are natively supported. Other widths are either emulated or rejected
+pub fn is_cpu_float_width(size: usize) -> bool {
+    return matches!(size, 16 | 32 | 64 | 80 | 128);
+}
+
 impl SizedType for RawType {
     fn get_size(&self, t: &Type, compacted_size: bool, storage: &TypedGlobalScope) -> usize {
+        // The non compacted size contains the padding required by the alignment, see `RawType::get_layout`
+        if !compacted_size {
+            return self.get_layout(t, storage).size;
+        }
+
         match self {
             RawType::Integer(size, _) => *size,
             RawType::Floating(size, _) => *size,
@@ -233,7 +280,6 @@ impl SizedType for RawType {
 }
 
 impl Display for RawType {
-    // TODO: add display names for structs and enums
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
             Self::Integer(size, signed) => {
@@ -264,10 +310,12 @@ impl Display for RawType {
             Self::Pointer => "ptr".to_string(),
             Self::StaticString => "staticstr".to_string(),
 
-            Self::Struct(_, _) => "__struct__".to_string(),
-            Self::Enum(_) => "__enum__".to_string(),
-            Self::EnumEntry(_) => "__enum__child".to_string(),
+            Self::Struct(_, container) => container.name.val.clone(),
+            Self::Enum(container) => container.name.val.clone(),
+            Self::EnumEntry(container) => container.name.clone(),
             Self::LoweredStruct(_, _) => "__low__struct__".to_string(),
+            Self::Trait(container) => container.name.val.clone(),
+            Self::TypeParameter(ind) => format!("__param{}__", ind),
 
             Self::SizedInteger(signed) => {
                 if *signed {
@@ -362,6 +410,16 @@ impl Hash for RawType {
                 hasher.write_usize(container.self_ref);
             }
 
+            RawType::Trait(container) => {
+                hasher.write_usize(10);
+                hasher.write_usize(container.self_ref);
+            }
+
+            RawType::TypeParameter(ind) => {
+                hasher.write_usize(11);
+                hasher.write_usize(*ind);
+            }
+
             _ => panic!("Unhashable type {:#?}", self),
         }
     }
@@ -37,10 +37,16 @@ impl TypeReference {
         unsure_panic!("used as_resolved on a non resolved type")
     }
 
-    /// Attempts to resolve the type reference into a concrete `Type`.
+    /// Attempts to resolve the type reference into a concrete `Type`. Type parameters contained within resolved types are substituted as well.
     pub fn resolve(self, t: &Type) -> Type {
         match &self {
-            TypeReference::Resolved(val) => return val.clone(),
+            TypeReference::Resolved(val) => {
+                if val.has_type_parameters() {
+                    return val.substitute(&t.get_generic_info().0);
+                }
+
+                return val.clone();
+            }
             TypeReference::Unresolved(index) => {
                 let concrete_info = t.get_generic_info();
 
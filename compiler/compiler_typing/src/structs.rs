@@ -1,28 +1,47 @@
 use std::collections::HashMap;
 
-use compiler_utils::utils::indexed::IndexStorage;
+use compiler_utils::{hash::HashedString, utils::indexed::IndexStorage};
 use diagnostics::{
     DiagnosticResult,
     builders::{make_cannot_find_type_field, make_cannot_find_type_function},
 };
 
 use crate::{
-    SizedType, StructuredType, TypeParamType, TypeParameterContainer, TypeReference, TypedFunction,
-    TypedGlobalScope,
+    SizedType, StructuredType, TypeParamType, TypeParameterBounds, TypeParameterContainer,
+    TypeReference, TypedFunction, TypedGlobalScope,
+    bitfields::BitfieldPlacement,
     enums::{RawEnumEntryContainer, RawEnumTypeContainer},
+    niches::EnumNiche,
     tree::Type,
 };
 
 /// Container for structure types
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug)]
 pub struct RawStructTypeContainer {
+    pub name: HashedString,
     pub fields: IndexStorage<TypeReference>,
     pub type_params: TypeParameterContainer,
+    pub type_bounds: TypeParameterBounds,
     pub functions: IndexStorage<TypedFunction>,
     pub function_ids: Vec<usize>,
     pub self_ref: usize,
+
+    /// Do the fields share the same storage. Unions are always `layout` types
+    pub is_union: bool,
+
+    /// The bit width of every bitfield member, indexed by field index. See `compiler_typing::bitfields`
+    pub bitfields: HashMap<usize, usize>,
+}
+
+/// Struct types are nominal. Types obtained before functions were implemented for the struct still refer to the same struct
+impl PartialEq for RawStructTypeContainer {
+    fn eq(&self, other: &Self) -> bool {
+        self.self_ref == other.self_ref
+    }
 }
 
+impl Eq for RawStructTypeContainer {}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LoweredStructTypeContainer {
     pub fields: IndexStorage<Type>,
@@ -30,6 +49,15 @@ pub struct LoweredStructTypeContainer {
     pub is_lowered_enum_parent: bool,
     pub lowered_enum_parent: Option<RawEnumTypeContainer>,
     pub lowered_enum_child: Option<RawEnumEntryContainer>,
+
+    /// The niche replacing the hint of the lowered enum, if any. See `compiler_typing::niches`
+    pub lowered_enum_niche: Option<EnumNiche>,
+
+    /// The lowered type of every member if the lowered struct is a union. Indexed by `HIR` field index
+    pub lowered_union_members: Option<Vec<Type>>,
+
+    /// The placement of every bitfield member within its storage unit alongside the lowered type of the member. Indexed by `HIR` field index
+    pub lowered_bitfields: HashMap<usize, (BitfieldPlacement, Type)>,
     pub hir_mir_indexes: HashMap<usize, usize>,
     pub functions: IndexStorage<usize>,
 }
@@ -65,10 +93,20 @@ impl SizedType for RawStructTypeContainer {
     fn get_size(&self, t: &Type, compacted_size: bool, storage: &TypedGlobalScope) -> usize {
         let mut size = 0;
 
-        for field in &self.fields.vals {
+        for (ind, field) in self.fields.vals.iter().enumerate() {
             let base = field.clone().resolve(&t);
 
-            size += base.get_size(t, compacted_size, storage);
+            if let Some(width) = self.bitfields.get(&ind) {
+                size += width;
+                continue;
+            }
+
+            // Union members overlap each other
+            if self.is_union {
+                size = size.max(base.get_size(t, compacted_size, storage));
+            } else {
+                size += base.get_size(t, compacted_size, storage);
+            }
         }
 
         return size;
@@ -84,7 +122,7 @@ impl StructuredType for RawStructTypeContainer {
         let k = match self.functions.get_index(hash) {
             Some(v) => v,
             None => {
-                return Err(make_cannot_find_type_function(&hash, &"unnamed".to_string()).into());
+                return Err(make_cannot_find_type_function(&hash, &self.name.val).into());
             }
         };
 
@@ -103,7 +141,7 @@ impl StructuredType for RawStructTypeContainer {
         let k = match self.functions.get_index(hash) {
             Some(v) => v,
             None => {
-                return Err(make_cannot_find_type_function(&hash, &"unnamed".to_string()).into());
+                return Err(make_cannot_find_type_function(&hash, &self.name.val).into());
             }
         };
 
@@ -113,7 +151,9 @@ impl StructuredType for RawStructTypeContainer {
     fn get_field(&self, hash: u64, _storage: &TypedGlobalScope) -> DiagnosticResult<TypeReference> {
         let k = match self.fields.get_index(hash) {
             Some(v) => v,
-            None => return Err(make_cannot_find_type_field(&hash, &"unamed".to_string()).into()),
+            None => {
+                return Err(make_cannot_find_type_field(&hash, &self.name.val).into());
+            }
         };
 
         return Ok(self.fields.vals[k].clone());
@@ -122,7 +162,9 @@ impl StructuredType for RawStructTypeContainer {
     fn get_field_hash(&self, hash: u64, _storage: &TypedGlobalScope) -> DiagnosticResult<usize> {
         let k = match self.fields.get_index(hash) {
             Some(v) => v,
-            None => return Err(make_cannot_find_type_field(&hash, &"unamed".to_string()).into()),
+            None => {
+                return Err(make_cannot_find_type_field(&hash, &self.name.val).into());
+            }
         };
 
         return Ok(k);
@@ -137,4 +179,8 @@ impl TypeParamType for RawStructTypeContainer {
     fn get_type_param_ind(&self, param: &compiler_utils::hash::HashedString) -> usize {
         self.type_params[param]
     }
+
+    fn get_type_params(&self) -> &TypeParameterContainer {
+        &self.type_params
+    }
 }
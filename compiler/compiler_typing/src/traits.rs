@@ -0,0 +1,140 @@
+//! User declared traits. A trait lists function signatures that a type must provide in order to implement it.
+//!
+//! A type implements a trait once an `impl` block declares it. The block must provide every function of the trait (see
+//! `Type::find_function`) with the same signature.
+
+use std::collections::HashSet;
+
+use compiler_utils::{hash::HashedString, utils::indexed::IndexStorage};
+use diagnostics::{
+    MaybeDiagnostic,
+    builders::{make_trait_function_missing, make_trait_not_implemented},
+};
+
+use crate::{
+    TypedGlobalScope, TypedResolvedFunction, raw::RawType, references::TypeReference, tree::Type,
+};
+
+/// A function signature declared within a trait. The `this` argument is never stored within the arguments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraitFunction {
+    pub name: String,
+    pub signature: TypedResolvedFunction,
+    pub requires_this: bool,
+}
+
+/// Container for user declared traits
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawTraitContainer {
+    pub name: HashedString,
+    pub functions: IndexStorage<TraitFunction>,
+    pub self_ref: usize,
+
+    /// The global indexes of the types implementing the trait
+    pub implementations: HashSet<usize>,
+}
+
+impl RawTraitContainer {
+    /// Checks if an implementation of the trait was declared for the given type.
+    #[must_use = "Must set the diagnostic position beforehand"]
+    pub fn matches(&self, t: &Type) -> MaybeDiagnostic {
+        if self.is_implemented_by(t) {
+            return Ok(());
+        }
+
+        Err(make_trait_not_implemented(&self.name.val, t).into())
+    }
+
+    /// Checks if an implementation of the trait was declared for the given type without emitting any diagnostic.
+    pub fn is_implemented_by(&self, t: &Type) -> bool {
+        if !t.is_generic_direct() {
+            return false;
+        }
+
+        // Enum entries share the functions of their enum
+        let ind = match t.get_generic() {
+            RawType::Struct(_, container) => container.self_ref,
+            RawType::Enum(container) => container.self_ref,
+            RawType::EnumEntry(container) => container.parent,
+            _ => return false,
+        };
+
+        self.implementations.contains(&ind)
+    }
+
+    /// Checks if the given type provides every function of the trait with the right signature. The type must be the
+    /// declared type, its type parameters are never resolved.
+    #[must_use = "Must set the diagnostic position beforehand"]
+    pub fn check_implementation(&self, t: &Type, storage: &TypedGlobalScope) -> MaybeDiagnostic {
+        match self.find_missing_function(t, storage) {
+            Some(function) => {
+                Err(make_trait_function_missing(&self.name.val, t, &function.name).into())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Finds the first function of the trait that the given type doesn't provide with the right signature
+    fn find_missing_function(
+        &self,
+        t: &Type,
+        storage: &TypedGlobalScope,
+    ) -> Option<&TraitFunction> {
+        self.functions
+            .vals
+            .iter()
+            .zip(self.functions.entry_keys.iter())
+            .find(|(function, hash)| !self.matches_function(t, **hash, function, storage))
+            .map(|(function, _)| function)
+    }
+
+    fn matches_function(
+        &self,
+        t: &Type,
+        hash: u64,
+        function: &TraitFunction,
+        storage: &TypedGlobalScope,
+    ) -> bool {
+        if !t.is_generic_direct() || !t.get_generic().is_structured() {
+            return false;
+        }
+
+        let func = match t.find_function(storage, hash) {
+            Some(v) => v.1,
+            None => return false,
+        };
+
+        let mut args = func.0.iter();
+
+        if function.requires_this {
+            match args.next() {
+                Some((name, _)) if *name == HashedString::new("this".to_string()).hash => {}
+                _ => return false,
+            }
+        }
+
+        if args.len() != function.signature.0.len() {
+            return false;
+        }
+
+        for (arg, expected) in args.zip(function.signature.0.iter()) {
+            if !matches_type(&arg.1, &expected.1) {
+                return false;
+            }
+        }
+
+        return match (&func.1, &function.signature.1) {
+            (Some(ret), Some(expected)) => matches_type(ret, expected),
+            (None, None) => true,
+            _ => false,
+        };
+    }
+}
+
+/// Trait signatures only contain concrete types, a type depending on a type parameter can never match them
+fn matches_type(reference: &TypeReference, expected: &Type) -> bool {
+    match reference {
+        TypeReference::Resolved(t) => !t.has_type_parameters() && expected.is_truly_eq(t),
+        TypeReference::Unresolved(_) => false,
+    }
+}
@@ -2,8 +2,11 @@
 
 use std::fmt::Display;
 
+use compiler_utils::hash::HashedString;
 use diagnostics::{
-    DiagnosticResult, DiagnosticSpanOrigin, builders::make_req_type_kind, unsure_panic,
+    DiagnosticResult, DiagnosticSpanOrigin,
+    builders::{make_cannot_find_type_field, make_req_type_kind},
+    unsure_panic,
 };
 
 use crate::{
@@ -36,6 +39,24 @@ pub enum Type {
     /// 0: The size of the array
     /// 1: Inner type
     Array(usize, Box<Type>),
+
+    /// A function pointer type node. Represents the address of a function
+    /// 0: The argument types
+    /// 1: The return type
+    Function(Vec<Box<Type>>, Option<Box<Type>>),
+
+    /// A closure type node. Represents a function alongside the environment containing its captured variables
+    /// 0: The argument types
+    /// 1: The return type
+    Closure(Vec<Box<Type>>, Option<Box<Type>>),
+
+    /// An anonymous tuple type node. Represents its elements stored one after each other
+    /// 0: The element types
+    Tuple(Vec<Box<Type>>),
+
+    /// A slice type node. Represents a pointer to elements alongside the amount of elements
+    /// 0: The element type
+    Slice(Box<Type>),
 }
 
 impl Type {
@@ -67,6 +88,9 @@ impl Type {
             Self::GenericLowered(inner) => inner.is_struct(),
             Self::Generic(inner, _, _) => inner.is_struct(),
 
+            // Tuples are lowered as anonymous structs
+            Self::Tuple(_) => true,
+
             _ => false,
         }
     }
@@ -88,6 +112,30 @@ impl Type {
         }
     }
 
+    pub fn is_function(&self) -> bool {
+        matches!(self, Self::Function(_, _))
+    }
+
+    pub fn is_closure(&self) -> bool {
+        matches!(self, Self::Closure(_, _))
+    }
+
+    /// Gets the argument types of a callable type, either a function pointer or a closure
+    pub fn get_callable_arguments(&self) -> Option<&Vec<Box<Type>>> {
+        match self {
+            Self::Function(args, _) | Self::Closure(args, _) => Some(args),
+            _ => None,
+        }
+    }
+
+    pub fn is_tuple(&self) -> bool {
+        matches!(self, Self::Tuple(_))
+    }
+
+    pub fn is_slice(&self) -> bool {
+        matches!(self, Self::Slice(_))
+    }
+
     pub fn is_array(&self) -> bool {
         match self {
             Self::Array(_, _) => true,
@@ -132,6 +180,31 @@ impl Type {
                 return base == base2;
             }
 
+            (Self::Function(args, ret), Self::Function(args2, ret2))
+            | (Self::Closure(args, ret), Self::Closure(args2, ret2)) => {
+                if args.len() != args2.len() {
+                    return false;
+                }
+
+                let ret_eq = match (ret, ret2) {
+                    (Some(a), Some(b)) => a.is_truly_eq(b),
+                    (None, None) => true,
+                    _ => false,
+                };
+
+                return ret_eq && args.iter().zip(args2).all(|(a, b)| a.is_truly_eq(b));
+            }
+
+            (Self::Tuple(elements), Self::Tuple(elements2)) => {
+                return elements.len() == elements2.len()
+                    && elements
+                        .iter()
+                        .zip(elements2)
+                        .all(|(a, b)| a.is_truly_eq(b));
+            }
+
+            (Self::Slice(inner), Self::Slice(inner2)) => return inner.is_truly_eq(inner2),
+
             (Self::Pointer(_, _), Self::GenericLowered(base)) => return *base == RawType::Pointer,
             (Self::GenericLowered(base), Self::Pointer(_, _)) => return *base == RawType::Pointer,
 
@@ -180,6 +253,7 @@ impl Type {
     pub fn get_inner_type(&self) -> Box<Type> {
         match self {
             Type::Array(_, inner) => inner.clone(),
+            Type::Slice(inner) => inner.clone(),
             Type::Pointer(_, inner) => inner.clone(),
             Type::Reference(inner) => inner.clone(),
 
@@ -194,6 +268,7 @@ impl Type {
     pub fn can_use_index_access(&self) -> bool {
         match self {
             Type::Array(_, _) => true,
+            Type::Slice(_) => true,
             _ => false,
         }
     }
@@ -218,6 +293,58 @@ impl Type {
         return self.get_inner_type().get_generic();
     }
 
+    /// Checks if the type still contains type parameters that have yet to be substituted
+    pub fn has_type_parameters(&self) -> bool {
+        match self {
+            Type::Generic(RawType::TypeParameter(_), _, _) => true,
+            Type::Generic(_, types, _) => types.iter().any(|t| t.has_type_parameters()),
+            Type::GenericLowered(_) => false,
+            Type::Array(_, inner) => inner.has_type_parameters(),
+            Type::Pointer(_, inner) => inner.has_type_parameters(),
+            Type::Reference(inner) => inner.has_type_parameters(),
+            Type::Function(args, ret) | Type::Closure(args, ret) => {
+                args.iter().any(|t| t.has_type_parameters())
+                    || ret.as_ref().is_some_and(|t| t.has_type_parameters())
+            }
+            Type::Tuple(elements) => elements.iter().any(|t| t.has_type_parameters()),
+            Type::Slice(inner) => inner.has_type_parameters(),
+        }
+    }
+
+    /// Substitutes every type parameter contained within the type with the given concrete types
+    pub fn substitute(&self, types: &Vec<Box<Type>>) -> Type {
+        match self {
+            Type::Generic(RawType::TypeParameter(ind), _, _) => *types[*ind].clone(),
+            Type::Generic(raw, params, sizes) => Type::Generic(
+                raw.clone(),
+                params
+                    .iter()
+                    .map(|t| Box::new(t.substitute(types)))
+                    .collect(),
+                sizes.clone(),
+            ),
+            Type::GenericLowered(_) => self.clone(),
+            Type::Array(size, inner) => Type::Array(*size, Box::new(inner.substitute(types))),
+            Type::Pointer(arr, inner) => Type::Pointer(*arr, Box::new(inner.substitute(types))),
+            Type::Reference(inner) => Type::Reference(Box::new(inner.substitute(types))),
+            Type::Function(args, ret) => Type::Function(
+                args.iter().map(|t| Box::new(t.substitute(types))).collect(),
+                ret.as_ref().map(|t| Box::new(t.substitute(types))),
+            ),
+            Type::Closure(args, ret) => Type::Closure(
+                args.iter().map(|t| Box::new(t.substitute(types))).collect(),
+                ret.as_ref().map(|t| Box::new(t.substitute(types))),
+            ),
+            Type::Tuple(elements) => Type::Tuple(
+                elements
+                    .iter()
+                    .map(|t| Box::new(t.substitute(types)))
+                    .collect(),
+            ),
+            Type::Slice(inner) => Type::Slice(Box::new(inner.substitute(types))),
+        }
+    }
+
     /// Cheaply lowers the generic just to avoid a display crash
     pub fn faulty_lowering_generic(&self, storage: &TypedGlobalScope) -> Type {
         match self {
@@ -226,6 +353,27 @@ impl Type {
             Type::Reference(t) => Type::Reference(Box::new(t.faulty_lowering_generic(storage))),
             Type::Generic(t, _, _) => Type::GenericLowered(t.clone()),
             Type::GenericLowered(_) => self.clone(),
+            Type::Function(args, ret) => Type::Function(
+                args.iter()
+                    .map(|t| Box::new(t.faulty_lowering_generic(storage)))
+                    .collect(),
+                ret.as_ref()
+                    .map(|t| Box::new(t.faulty_lowering_generic(storage))),
+            ),
+            Type::Closure(args, ret) => Type::Closure(
+                args.iter()
+                    .map(|t| Box::new(t.faulty_lowering_generic(storage)))
+                    .collect(),
+                ret.as_ref()
+                    .map(|t| Box::new(t.faulty_lowering_generic(storage))),
+            ),
+            Type::Tuple(elements) => Type::Tuple(
+                elements
+                    .iter()
+                    .map(|t| Box::new(t.faulty_lowering_generic(storage)))
+                    .collect(),
+            ),
+            Type::Slice(inner) => Type::Slice(Box::new(inner.faulty_lowering_generic(storage))),
         }
     }
 
@@ -251,6 +399,30 @@ impl Type {
         };
     }
 
+    /// Looks up a function of the type along with its index without emitting any diagnostic, for speculative checks such
+    /// as trait matching
+    pub fn find_function(
+        &self,
+        storage: &TypedGlobalScope,
+        hash: u64,
+    ) -> Option<(usize, TypedFunction)> {
+        let functions = match self.get_generic() {
+            RawType::Struct(_, container) => container.functions,
+            RawType::Enum(container) => container.functions,
+            RawType::EnumEntry(container) => {
+                match storage.entries[container.parent].as_type_unsafe() {
+                    RawType::Enum(parent) => parent.functions,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        functions
+            .get_index(hash)
+            .map(|k| (k, functions.vals[k].clone()))
+    }
+
     pub fn get_field(
         &self,
         storage: &TypedGlobalScope,
@@ -273,6 +445,21 @@ impl Type {
         };
     }
 
+    /// Gets the field behind the given name. Unlike `get_field`, reports missing fields by their name
+    pub fn get_named_field(
+        &self,
+        storage: &TypedGlobalScope,
+        name: &HashedString,
+    ) -> DiagnosticResult<(usize, TypeReference)> {
+        if let RawType::Struct(_, _) | RawType::EnumEntry(_) = self.get_generic()
+            && !self.get_fields(storage).contains(&name.hash)
+        {
+            return Err(make_cannot_find_type_field(&name.val, self).into());
+        }
+
+        return self.get_field(storage, name.hash);
+    }
+
     pub fn get_fields(&self, storage: &TypedGlobalScope) -> Vec<u64> {
         return match self.get_generic() {
             RawType::Struct(_, container) => container.get_fields(storage),
@@ -321,6 +508,34 @@ impl Display for Type {
             Self::Reference(inner) => {
                 format!("{}&", inner)
             }
+
+            Self::Function(args, ret) => {
+                let args: Vec<String> = args.iter().map(|t| t.to_string()).collect();
+
+                match ret {
+                    Some(ret) => format!("func({}) -> {}", args.join(", "), ret),
+                    None => format!("func({})", args.join(", ")),
+                }
+            }
+
+            Self::Closure(args, ret) => {
+                let args: Vec<String> = args.iter().map(|t| t.to_string()).collect();
+
+                match ret {
+                    Some(ret) => format!("func[]({}) -> {}", args.join(", "), ret),
+                    None => format!("func[]({})", args.join(", ")),
+                }
+            }
+
+            Self::Tuple(elements) => {
+                let elements: Vec<String> = elements.iter().map(|t| t.to_string()).collect();
+
+                format!("({})", elements.join(", "))
+            }
+
+            Self::Slice(inner) => {
+                format!("{}[]", inner)
+            }
         };
 
         write!(f, "{}", s)
@@ -333,6 +548,19 @@ impl SizedType for Type {
             Self::Array(size, inner) => inner.clone().get_size(t, compacted_size, storage) * *size,
             Self::Pointer(_, _) => get_pointer_size(),
             Self::Reference(_) => get_pointer_size(),
+            Self::Function(_, _) => get_pointer_size(),
+            Self::Closure(_, _) => get_pointer_size() * 2,
+            Self::Slice(_) => self.get_layout(storage).size,
+            Self::Tuple(elements) => {
+                if compacted_size {
+                    elements
+                        .iter()
+                        .map(|e| e.get_size(e, compacted_size, storage))
+                        .sum()
+                } else {
+                    self.get_layout(storage).size
+                }
+            }
             Self::Generic(e, _, _) => e.get_size(t, compacted_size, storage),
             Self::GenericLowered(e) => e.get_size(t, compacted_size, storage),
         };
@@ -1,5 +1,7 @@
 //! Utilities related to types.
 
+use crate::raw::RawType;
+
 /// Determines the size of a pointer / memory addressess based on the current platform.
 pub fn get_pointer_size() -> usize {
     if cfg!(target_pointer_width = "32") {
@@ -10,3 +12,8 @@ pub fn get_pointer_size() -> usize {
         return 0;
     }
 }
+
+/// The unsigned pointer sized integer used for indexes and element counts.
+pub fn get_index_type() -> RawType {
+    RawType::Integer(get_pointer_size(), false)
+}
@@ -0,0 +1,43 @@
+//! Storage of arbitrary width integers.
+
+use compiler_typing::{
+    TypedGlobalScope,
+    layout::TypeLayout,
+    raw::{MAX_INTEGER_SIZE, RawType},
+    tree::Type,
+};
+
+fn get_integer_layout(size: usize, signed: bool) -> TypeLayout {
+    let storage = TypedGlobalScope::new();
+
+    Type::Generic(RawType::Integer(size, signed), vec![], vec![]).get_layout(&storage)
+}
+
+#[test]
+fn rounds_integer_storage_to_power_of_two_bytes() {
+    for size in 1..=MAX_INTEGER_SIZE {
+        let expected = size.div_ceil(8).next_power_of_two() * 8;
+
+        for signed in [false, true] {
+            let layout = get_integer_layout(size, signed);
+
+            assert_eq!(layout, TypeLayout::new(expected, expected), "{} bits", size);
+            assert!(layout.size >= size);
+        }
+    }
+}
+
+#[test]
+fn keeps_standard_integers_unpadded() {
+    for size in [8, 16, 32, 64, 128] {
+        assert_eq!(get_integer_layout(size, true), TypeLayout::new(size, size));
+    }
+}
+
+#[test]
+fn pads_odd_integers() {
+    assert_eq!(get_integer_layout(1, false), TypeLayout::new(8, 8));
+    assert_eq!(get_integer_layout(24, false), TypeLayout::new(32, 32));
+    assert_eq!(get_integer_layout(33, true), TypeLayout::new(64, 64));
+    assert_eq!(get_integer_layout(65, true), TypeLayout::new(128, 128));
+}
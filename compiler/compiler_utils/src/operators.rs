@@ -20,6 +20,17 @@ pub struct MathOperator {
     pub fast: bool,
 }
 
+/// The explicit behaviors of the overflowing arithmetic builtins such as `wrapping_add`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OverflowBehavior {
+    /// The result wraps around the bounds of the type
+    Wrapping,
+    /// The result is clamped to the bounds of the type
+    Saturating,
+    /// The wrapped result is returned alongside whether the operation overflowed
+    Checked,
+}
+
 /// The different comparing operators
 #[derive(Debug, PartialEq, Clone)]
 pub enum ComparingOperator {
@@ -4,16 +4,26 @@ use crate::{
     DiagnosticSpanOrigin,
     diagnostic::{Diagnostic, Level, Span, SpanKind, SpanPosition},
     errors::{
-        ALREADY_IN_SCOPE, ASSIGN_DIFF_TYPE_IR, BOUND_MISSING, CANNOT_FIND, CODE_UNREACHABLE,
-        DIFF_SIZE_SPECIFIERS, DIFF_TYPE_SPECIFIERS, ENDING_POINT_MISSING, ENUM_PARENT_FIELDS,
-        ERA_NOT_EXIST, EXPECTED_FREE, EXPECTED_TOKEN, EXPECTED_TYPE, FIELD_MISSING,
-        FIELD_STRUCT_INIT, FIND_TYPE, FIND_TYPE_FIELD, FIND_TYPE_FUNCTION, FIND_VAR, FUNC_MISSING,
-        INDEX_USAGE, INVALID_POINTING, INVALID_TYPE_REQ, IR_CAST, IR_INSTRUCTION_HELD_VAL,
-        MATH_OPERATION_ASSIGNS, NOT_FOUND_USE, RET_TYPE_NOT_MATCH, TRAIT_MISSING, TYPE_NOT_PART,
-        UNEXPECTED_TOKEN, VARIABLE_UNINIT,
+        ALREADY_IN_SCOPE, ASSIGN_DIFF_TYPE_IR, BITFIELD_NOT_INTEGER, BITFIELD_OUTSIDE_LAYOUT,
+        BITFIELD_WIDTH, BOUND_MISSING, CALL_ARGUMENT_COUNT, CANNOT_FIND, CAPTURE_NOT_LOCAL,
+        CLOSURE_ESCAPES, CLOSURE_OUTLIVES_CAPTURE, CODE_UNREACHABLE, DIFF_SIZE_SPECIFIERS,
+        DIFF_TYPE_SPECIFIERS, DISCRIMINANT_OVERFLOW, DUPLICATE_DISCRIMINANT, ENDING_POINT_MISSING,
+        ENUM_PARENT_FIELDS, ERA_NOT_EXIST, EXPECTED_FREE, EXPECTED_TOKEN, EXPECTED_TYPE,
+        FIELD_MISSING, FIELD_STRUCT_INIT, FIND_TYPE, FIND_TYPE_FIELD, FIND_TYPE_FUNCTION, FIND_VAR,
+        FUNC_MISSING, INDEX_USAGE, INFINITE_SIZE_TYPE, INVALID_ATTRIBUTE, INVALID_CAST,
+        INVALID_POINTING, INVALID_TYPE_REQ, IR_CAST, IR_INSTRUCTION_HELD_VAL,
+        MATH_OPERATION_ASSIGNS, NOT_FOUND_USE, RET_TYPE_NOT_MATCH, SLICE_LENGTH_ASSIGN,
+        TRAIT_MISSING, TRAIT_NOT_IMPLEMENTED, TUPLE_DESTRUCTURE_COUNT, TYPE_NOT_PART,
+        UNEXPECTED_TOKEN, UNION_INIT_FIELD_COUNT, UNKNOWN_ATTRIBUTE, UNKNOWN_TRAIT,
+        UNKNOWN_WARNING, UNSAFE_UNION_ACCESS, UNSUPPORTED_FLOAT_WIDTH, UNSUPPORTED_INTEGER_WIDTH,
+        UNSURE_PANIC, VARIABLE_UNINIT,
     },
     get_current_diagnostic_pos,
-    warnings::UNUSED_VAR,
+    warnings::{
+        CPU_SUPPORTED_TYPE, DEPRECATED, DEPRECATED_MSG, EXPERIMENTAL_FEAT, EXPERIMENTAL_FUNC,
+        EXPERIMENTAL_TYPE, LOSSY_PROMOTION, UNSAFE_FUNC, UNUSED_ARGUMENT, UNUSED_FUNCTION,
+        UNUSED_TYPE, UNUSED_USE, UNUSED_VAR,
+    },
 };
 
 pub fn make_expected_simple_error<K: DiagnosticSpanOrigin, E: Display, G: Display>(
@@ -69,7 +79,7 @@ pub fn make_unexpected_simple_error_outside<K: Display>(got: &K, pos: SpanPositi
 
     Diagnostic::new_base(
         Level::Error,
-        EXPECTED_TOKEN.0,
+        UNEXPECTED_TOKEN.0,
         format!("unexpected {}", got),
         span,
         vec![],
@@ -108,6 +118,310 @@ pub fn make_unused_variable<K: DiagnosticSpanOrigin, E: Display>(
     )
 }
 
+fn make_unused_notes<E: Display>(name: &E) -> (Vec<String>, Vec<String>) {
+    let notes = vec!["names starting with an underscore are never reported as unused".to_string()];
+    let help = vec![format!("if this is intentional, rename it to _{}", name)];
+
+    return (notes, help);
+}
+
+/// Suggests prefixing the name at the given position with an underscore
+fn make_unused_fix<E: Display>(name: &E, name_pos: Option<SpanPosition>) -> Vec<Span> {
+    return match name_pos {
+        Some(pos) => vec![Span::make_fix(
+            pos,
+            Some(format!("rename to _{}", name)),
+            format!("_{}", name),
+        )],
+        None => vec![],
+    };
+}
+
+#[must_use = "Must set the diagnostic position beforehand"]
+pub fn make_unused_local<E: Display>(var: &E, name_pos: Option<SpanPosition>) -> Diagnostic {
+    let (notes, help) = make_unused_notes(var);
+
+    return Diagnostic::new_base(
+        Level::Warning,
+        UNUSED_VAR.0,
+        format!("unused variable: {}", var),
+        Span::make_primary(get_current_diagnostic_pos(), None),
+        make_unused_fix(var, name_pos),
+        notes,
+        help,
+    );
+}
+
+#[must_use = "Must set the diagnostic position beforehand"]
+pub fn make_unused_argument<E: Display>(arg: &E, name_pos: Option<SpanPosition>) -> Diagnostic {
+    let (notes, help) = make_unused_notes(arg);
+
+    return Diagnostic::new_base(
+        Level::Warning,
+        UNUSED_ARGUMENT.0,
+        format!("unused argument: {}", arg),
+        Span::make_primary(get_current_diagnostic_pos(), None),
+        make_unused_fix(arg, name_pos),
+        notes,
+        help,
+    );
+}
+
+pub fn make_unused_function<K: DiagnosticSpanOrigin, E: Display>(
+    origin: &K,
+    func: &E,
+) -> Diagnostic {
+    let (notes, help) = make_unused_notes(func);
+
+    origin.make_simple_diagnostic(
+        UNUSED_FUNCTION.0,
+        Level::Warning,
+        format!("unused function: {}", func),
+        None,
+        vec![],
+        notes,
+        help,
+    )
+}
+
+pub fn make_unused_type<K: DiagnosticSpanOrigin, E: Display>(origin: &K, t: &E) -> Diagnostic {
+    let (notes, help) = make_unused_notes(t);
+
+    origin.make_simple_diagnostic(
+        UNUSED_TYPE.0,
+        Level::Warning,
+        format!("unused type: {}", t),
+        None,
+        vec![],
+        notes,
+        help,
+    )
+}
+
+pub fn make_unused_use<K: DiagnosticSpanOrigin, E: Display>(origin: &K, name: &E) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        UNUSED_USE.0,
+        Level::Warning,
+        format!("unused `use` statement: {}", name),
+        None,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_unknown_attribute<K: DiagnosticSpanOrigin, E: Display>(
+    origin: &K,
+    attribute: &E,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        UNKNOWN_ATTRIBUTE.0,
+        Level::Error,
+        format!("unknown attribute {}", attribute),
+        None,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_unknown_warning<K: DiagnosticSpanOrigin, E: Display>(
+    origin: &K,
+    warning: &E,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        UNKNOWN_WARNING.0,
+        Level::Error,
+        format!("unknown warning {}", warning),
+        None,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_invalid_attribute<K: DiagnosticSpanOrigin, E: Display, R: Display>(
+    origin: &K,
+    attribute: &E,
+    reason: &R,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        INVALID_ATTRIBUTE.0,
+        Level::Error,
+        format!("invalid attribute {}: {}", attribute, reason),
+        None,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+fn make_declaration_span<E: Display>(decl: SpanPosition, name: &E) -> Span {
+    return Span::make_secondary(decl, Some(format!("{} was declared here", name)));
+}
+
+pub fn make_deprecated_use<K: DiagnosticSpanOrigin, E: Display>(
+    origin: &K,
+    name: &E,
+    message: &Option<String>,
+    decl: SpanPosition,
+) -> Diagnostic {
+    let (code, msg) = match message {
+        Some(message) => (
+            DEPRECATED_MSG.0,
+            format!("{} was marked as deprecated: {}", name, message),
+        ),
+        None => (DEPRECATED.0, format!("{} was marked as deprecated", name)),
+    };
+
+    origin.make_simple_diagnostic(
+        code,
+        Level::Warning,
+        msg,
+        None,
+        vec![make_declaration_span(decl, name)],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_experimental_function<K: DiagnosticSpanOrigin, E: Display>(
+    origin: &K,
+    func: &E,
+    decl: SpanPosition,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        EXPERIMENTAL_FUNC.0,
+        Level::Warning,
+        format!("function {} was marked as experimental", func),
+        None,
+        vec![make_declaration_span(decl, func)],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_experimental_type<K: DiagnosticSpanOrigin, E: Display>(
+    origin: &K,
+    t: &E,
+    decl: SpanPosition,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        EXPERIMENTAL_TYPE.0,
+        Level::Warning,
+        format!("type {} was marked as experimental", t),
+        None,
+        vec![make_declaration_span(decl, t)],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_experimental_field<K: DiagnosticSpanOrigin, E: Display>(
+    origin: &K,
+    field: &E,
+    decl: SpanPosition,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        EXPERIMENTAL_FEAT.0,
+        Level::Warning,
+        format!("field {} was marked as experimental", field),
+        None,
+        vec![make_declaration_span(decl, field)],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_unsafe_function<K: DiagnosticSpanOrigin, E: Display>(
+    origin: &K,
+    func: &E,
+    decl: SpanPosition,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        UNSAFE_FUNC.0,
+        Level::Warning,
+        format!("function {} was marked as unsafe", func),
+        None,
+        vec![make_declaration_span(decl, func)],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_cpu_emulated_type<K: DiagnosticSpanOrigin, E: Display>(
+    origin: &K,
+    t: &E,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        CPU_SUPPORTED_TYPE.0,
+        Level::Warning,
+        format!("type {} is unsupported by the CPU", t),
+        None,
+        vec![],
+        vec![format!("operations on {} are emulated in software", t)],
+        vec!["use a wider floating point type to get native support".to_string()],
+    )
+}
+
+pub fn make_unsupported_float_width<K: DiagnosticSpanOrigin>(
+    origin: &K,
+    width: usize,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        UNSUPPORTED_FLOAT_WIDTH.0,
+        Level::Error,
+        format!(
+            "floating point numbers of width {} are not supported",
+            width
+        ),
+        None,
+        vec![],
+        vec![],
+        vec!["use a width of 8, 16, 32, 64, 80 or 128 bits".to_string()],
+    )
+}
+
+pub fn make_unsupported_integer_width<K: DiagnosticSpanOrigin>(
+    origin: &K,
+    width: usize,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        UNSUPPORTED_INTEGER_WIDTH.0,
+        Level::Error,
+        format!("integers of width {} are not supported", width),
+        None,
+        vec![],
+        vec![],
+        vec!["use a width between 1 and 128 bits".to_string()],
+    )
+}
+
+pub fn make_lossy_promotion<K: DiagnosticSpanOrigin, E: Display, P: Display>(
+    origin: &K,
+    t: &E,
+    promoted: &P,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        LOSSY_PROMOTION.0,
+        Level::Warning,
+        format!(
+            "{} cannot be fully represented once promoted to {}",
+            t, promoted
+        ),
+        None,
+        vec![],
+        vec![format!(
+            "the values of {} above the maximum of {} will wrap around",
+            t, promoted
+        )],
+        vec![format!(
+            "cast the operands to a common type explicitly, or use a narrower type than {}",
+            t
+        )],
+    )
+}
+
 pub fn make_already_in_scope<K: DiagnosticSpanOrigin, E: Display>(
     origin: &K,
     val: &E,
@@ -155,7 +469,7 @@ pub fn make_cannot_find_func<K: DiagnosticSpanOrigin, E: Display>(
     func: &E,
 ) -> Diagnostic {
     origin.make_simple_diagnostic(
-        FIND_TYPE.0,
+        CANNOT_FIND.0,
         Level::Error,
         format!("cannot find function {} in the current scope", func),
         None,
@@ -413,7 +727,7 @@ pub fn make_unsure_panic<M: Display>(msg: &M) -> Diagnostic {
 
     return Diagnostic::new_base(
         Level::Error,
-        99,
+        UNSURE_PANIC.0,
         format!("unsure panic: {}", msg),
         primary_span,
         vec![],
@@ -681,3 +995,365 @@ pub fn make_ret_type_kind<K: DiagnosticSpanOrigin>(origin: &K) -> Diagnostic {
         vec![],
     )
 }
+
+#[must_use = "Must set the diagnostic position beforehand"]
+pub fn make_unknown_trait<T: Display>(t: &T) -> Diagnostic {
+    let primary_span = Span::make_primary(get_current_diagnostic_pos(), None);
+
+    return Diagnostic::new_base(
+        Level::Error,
+        UNKNOWN_TRAIT.0,
+        format!("unknown bound trait {}", t),
+        primary_span,
+        vec![],
+        vec![],
+        vec!["available traits are numeric, signed, integer, floating, fixed, noninteger, cpusupported, stringlike, static and any declared trait".to_string()],
+    );
+}
+
+#[must_use = "Must set the diagnostic position beforehand"]
+pub fn make_trait_not_implemented<Tr: Display, T: Display>(trait_name: &Tr, t: &T) -> Diagnostic {
+    let primary_span = Span::make_primary(
+        get_current_diagnostic_pos(),
+        Some(format!("trait {} required for type {} here", trait_name, t)),
+    );
+
+    let help = vec![format!(
+        "declare an implementation with impl {} for {}",
+        trait_name, t
+    )];
+
+    return Diagnostic::new_base(
+        Level::Error,
+        TRAIT_NOT_IMPLEMENTED.0,
+        format!("type {} does not implement trait {}", t, trait_name),
+        primary_span,
+        vec![],
+        vec![],
+        help,
+    );
+}
+
+#[must_use = "Must set the diagnostic position beforehand"]
+pub fn make_trait_function_missing<Tr: Display, T: Display, F: Display>(
+    trait_name: &Tr,
+    t: &T,
+    func: &F,
+) -> Diagnostic {
+    let primary_span = Span::make_primary(
+        get_current_diagnostic_pos(),
+        Some(format!("trait {} required for type {} here", trait_name, t)),
+    );
+
+    let notes = vec![format!(
+        "function {} declared in trait {} is either missing or has a different signature",
+        func, trait_name
+    )];
+    let help = vec![format!(
+        "implement {} for {} with the signatures declared in the trait",
+        trait_name, t
+    )];
+
+    return Diagnostic::new_base(
+        Level::Error,
+        TRAIT_NOT_IMPLEMENTED.0,
+        format!("type {} does not implement trait {}", t, trait_name),
+        primary_span,
+        vec![],
+        notes,
+        help,
+    );
+}
+
+pub fn make_infinite_size_type<K: DiagnosticSpanOrigin, T: Display, P: Display>(
+    origin: &K,
+    t: &T,
+    cycle: &P,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        INFINITE_SIZE_TYPE.0,
+        Level::Error,
+        format!("recursive type {} has infinite size", t),
+        Some("recursive without indirection".to_string()),
+        vec![],
+        vec![format!("{} contains itself by value: {}", t, cycle)],
+        vec![format!(
+            "insert a pointer indirection (e.g. {}*) somewhere in the cycle to make {} representable",
+            t, t
+        )],
+    )
+}
+
+#[must_use = "Must set the diagnostic position beforehand"]
+pub fn make_duplicate_discriminant<D: Display, E: Display, O: Display>(
+    discriminant: &D,
+    entry: &E,
+    other: &O,
+) -> Diagnostic {
+    let primary_span = Span::make_primary(
+        get_current_diagnostic_pos(),
+        Some(format!("discriminant {} declared here", discriminant)),
+    );
+
+    return Diagnostic::new_base(
+        Level::Error,
+        DUPLICATE_DISCRIMINANT.0,
+        format!(
+            "discriminant {} of entry {} is already used by entry {}",
+            discriminant, entry, other
+        ),
+        primary_span,
+        vec![],
+        vec![],
+        vec!["every entry of an enum requires a unique discriminant".to_string()],
+    );
+}
+
+pub fn make_discriminant_overflow<K: DiagnosticSpanOrigin, D: Display, E: Display, T: Display>(
+    origin: &K,
+    discriminant: &D,
+    entry: &E,
+    t: &T,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        DISCRIMINANT_OVERFLOW.0,
+        Level::Error,
+        format!(
+            "discriminant {} of entry {} does not fit in {}",
+            discriminant, entry, t
+        ),
+        None,
+        vec![],
+        vec![],
+        vec![format!("use a wider representation than {}", t)],
+    )
+}
+
+pub fn make_invalid_cast<K: DiagnosticSpanOrigin, A: Display, B: Display>(
+    origin: &K,
+    from: &A,
+    to: &B,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        INVALID_CAST.0,
+        Level::Error,
+        format!("cannot cast a value of type {} into {}", from, to),
+        None,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_unsafe_union_access<K: DiagnosticSpanOrigin, F: Display>(
+    origin: &K,
+    field: &F,
+) -> Diagnostic {
+    let notes =
+        vec!["every field of a union shares the same storage, the field might not be the one that was written last".to_string()];
+    let help = vec![format!(
+        "acknowledge the access by using unsafe_unwrap<value.{}>",
+        field
+    )];
+
+    origin.make_simple_diagnostic(
+        UNSAFE_UNION_ACCESS.0,
+        Level::Error,
+        format!("access to union field {} is unsafe", field),
+        None,
+        vec![],
+        notes,
+        help,
+    )
+}
+
+pub fn make_union_init_field_count<K: DiagnosticSpanOrigin>(
+    origin: &K,
+    count: &usize,
+) -> Diagnostic {
+    let help = vec!["initialize a single field of the union".to_string()];
+
+    origin.make_simple_diagnostic(
+        UNION_INIT_FIELD_COUNT.0,
+        Level::Error,
+        format!(
+            "a union initializer must contain exactly one field, got {}",
+            count
+        ),
+        None,
+        vec![],
+        vec![],
+        help,
+    )
+}
+
+pub fn make_bitfield_outside_layout<K: DiagnosticSpanOrigin, N: Display>(
+    origin: &K,
+    name: &N,
+) -> Diagnostic {
+    let help = vec!["declare the type using layout instead".to_string()];
+
+    origin.make_simple_diagnostic(
+        BITFIELD_OUTSIDE_LAYOUT.0,
+        Level::Error,
+        format!("bitfield {} can only be declared in a layout type", name),
+        None,
+        vec![],
+        vec![],
+        help,
+    )
+}
+
+pub fn make_bitfield_not_integer<K: DiagnosticSpanOrigin, N: Display, T: Display>(
+    origin: &K,
+    name: &N,
+    t: &T,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        BITFIELD_NOT_INTEGER.0,
+        Level::Error,
+        format!("bitfield {} must be of an integer type, got {}", name, t),
+        None,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_bitfield_width<K: DiagnosticSpanOrigin, N: Display, T: Display>(
+    origin: &K,
+    name: &N,
+    width: &usize,
+    t: &T,
+    max: &usize,
+) -> Diagnostic {
+    let notes = vec![
+        "a bitfield uses at least 1 bit and at most as many bits as its type, up to 64".to_string(),
+    ];
+    let help = vec![format!("use a width between 1 and {}", max)];
+
+    origin.make_simple_diagnostic(
+        BITFIELD_WIDTH.0,
+        Level::Error,
+        format!("bitfield {} of width {} does not fit in {}", name, width, t),
+        None,
+        vec![],
+        notes,
+        help,
+    )
+}
+
+pub fn make_call_argument_count<K: DiagnosticSpanOrigin, T: Display>(
+    origin: &K,
+    t: &T,
+    expected: &usize,
+    got: &usize,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        CALL_ARGUMENT_COUNT.0,
+        Level::Error,
+        format!(
+            "function of type {} takes {} arguments, got {}",
+            t, expected, got
+        ),
+        None,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_closure_outlives_capture<K: DiagnosticSpanOrigin, V: Display>(
+    origin: &K,
+    var: &V,
+) -> Diagnostic {
+    let notes = vec![format!(
+        "{} was dropped at the end of its era while the closure is still in use",
+        var
+    )];
+    let help = vec![format!("capture {} by value instead", var)];
+
+    origin.make_simple_diagnostic(
+        CLOSURE_OUTLIVES_CAPTURE.0,
+        Level::Error,
+        format!(
+            "closure outlives the variable {} it captures by reference",
+            var
+        ),
+        None,
+        vec![],
+        notes,
+        help,
+    )
+}
+
+pub fn make_closure_escapes<K: DiagnosticSpanOrigin, V: Display>(
+    origin: &K,
+    var: &V,
+) -> Diagnostic {
+    let notes = vec![
+        "the environment of a closure lives on the stack of the function creating it".to_string(),
+    ];
+    let help = vec!["pass the captured values as arguments instead".to_string()];
+
+    origin.make_simple_diagnostic(
+        CLOSURE_ESCAPES.0,
+        Level::Error,
+        format!(
+            "closure capturing {} cannot escape the function creating it",
+            var
+        ),
+        None,
+        vec![],
+        notes,
+        help,
+    )
+}
+
+pub fn make_capture_not_local<K: DiagnosticSpanOrigin, V: Display>(
+    origin: &K,
+    var: &V,
+) -> Diagnostic {
+    let help = vec![format!("use {} directly within the closure", var)];
+
+    origin.make_simple_diagnostic(
+        CAPTURE_NOT_LOCAL.0,
+        Level::Error,
+        format!("cannot capture {} since it isn't a local variable", var),
+        None,
+        vec![],
+        vec![],
+        help,
+    )
+}
+
+pub fn make_tuple_destructure_count<K: DiagnosticSpanOrigin, T: Display>(
+    origin: &K,
+    t: &T,
+    got: &usize,
+) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        TUPLE_DESTRUCTURE_COUNT.0,
+        Level::Error,
+        format!(
+            "cannot destructure a tuple of type {} into {} variables",
+            t, got
+        ),
+        None,
+        vec![],
+        vec![],
+        vec![],
+    )
+}
+
+pub fn make_slice_length_assign<K: DiagnosticSpanOrigin>(origin: &K) -> Diagnostic {
+    origin.make_simple_diagnostic(
+        SLICE_LENGTH_ASSIGN.0,
+        Level::Error,
+        SLICE_LENGTH_ASSIGN.1.to_string(),
+        Some("the length is assigned here".to_string()),
+        vec![],
+        vec![],
+        vec!["create a sub-slice to change the amount of elements".to_string()],
+    )
+}
@@ -8,9 +8,12 @@ use std::{fmt::Display, fs, io::Error};
 use colored::{ColoredString, Colorize};
 use compiler_utils::Position;
 
-use crate::DIAGNOSTIC_CONTAINER;
+use crate::{
+    DIAGNOSTIC_CONTAINER,
+    lints::{LintLevel, get_lint_level},
+};
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum Level {
     Error,
     Warning,
@@ -48,6 +51,20 @@ impl Level {
             Self::Note => 'I',
         }
     }
+
+    /// Formats the given code of this level as displayed to the user (eg: `E0012`)
+    pub fn format_code(&self, code: usize) -> String {
+        return format!("{}{:04}", self.get_code_char(), code);
+    }
+
+    /// The name of the level within machine-readable outputs
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "note",
+        }
+    }
 }
 
 impl Display for Level {
@@ -71,6 +88,9 @@ pub struct Diagnostic {
     pub note: Vec<String>,
     pub help: Vec<String>,
 
+    /// Whether this is a warning reported as an error, see `lints`
+    pub denied: bool,
+
     #[cfg(feature = "compiler_diagbacktraces")]
     pub backtrace: Backtrace,
 }
@@ -91,6 +111,7 @@ impl Clone for Diagnostic {
             spans: self.spans.clone(),
             note: self.note.clone(),
             help: self.help.clone(),
+            denied: self.denied,
             backtrace: Backtrace::capture(),
         };
 
@@ -103,6 +124,7 @@ impl Clone for Diagnostic {
             spans: self.spans.clone(),
             note: self.note.clone(),
             help: self.help.clone(),
+            denied: self.denied,
         };
     }
 }
@@ -122,7 +144,7 @@ impl Diagnostic {
         }
 
         #[cfg(feature = "compiler_diagbacktraces")]
-        let d = Diagnostic {
+        let mut d = Diagnostic {
             level,
             code: decl.0,
             message: decl.1.to_string(),
@@ -130,11 +152,12 @@ impl Diagnostic {
             spans,
             note,
             help,
+            denied: false,
             backtrace: Backtrace::capture(),
         };
 
         #[cfg(not(feature = "compiler_diagbacktraces"))]
-        let d = Diagnostic {
+        let mut d = Diagnostic {
             level,
             code: decl.0,
             message: decl.1.to_string(),
@@ -142,9 +165,12 @@ impl Diagnostic {
             spans,
             note,
             help,
+            denied: false,
         };
 
-        d.push_to_storage();
+        if d.apply_lint_level() {
+            d.push_to_storage();
+        }
 
         return d;
     }
@@ -164,7 +190,7 @@ impl Diagnostic {
         }
 
         #[cfg(feature = "compiler_diagbacktraces")]
-        let d = Diagnostic {
+        let mut d = Diagnostic {
             level,
             code,
             message,
@@ -172,11 +198,12 @@ impl Diagnostic {
             spans,
             note,
             help,
+            denied: false,
             backtrace: Backtrace::capture(),
         };
 
         #[cfg(not(feature = "compiler_diagbacktraces"))]
-        let d = Diagnostic {
+        let mut d = Diagnostic {
             level,
             code,
             message,
@@ -184,13 +211,42 @@ impl Diagnostic {
             spans,
             note,
             help,
+            denied: false,
         };
 
-        d.push_to_storage();
+        if d.apply_lint_level() {
+            d.push_to_storage();
+        }
 
         return d;
     }
 
+    /// Applies the lint level of warnings. Returns whether the diagnostic should be reported
+    fn apply_lint_level(&mut self) -> bool {
+        if !matches!(self.level, Level::Warning) {
+            return true;
+        }
+
+        match get_lint_level(self.code, &self.primary_span.start) {
+            LintLevel::Allow => return false,
+            LintLevel::Warn => return true,
+            LintLevel::Deny => {
+                self.level = Level::Error;
+                self.denied = true;
+
+                return true;
+            }
+        }
+    }
+
+    /// The code of the diagnostic as displayed to the user (eg: `E0012`). Denied warnings keep their warning code
+    pub fn get_display_code(&self) -> String {
+        return match self.denied {
+            true => Level::Warning.format_code(self.code),
+            false => self.level.format_code(self.code),
+        };
+    }
+
     fn push_to_storage(&self) {
         DIAGNOSTIC_CONTAINER.with_borrow_mut(|f| {
             f.append(self.clone());
@@ -198,11 +254,7 @@ impl Diagnostic {
     }
 }
 
-impl Into<()> for Diagnostic {
-    fn into(self) -> () {}
-}
-
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct SpanPosition {
     pub line: usize,
     pub col: usize,
@@ -252,6 +304,9 @@ pub struct Span {
 
     pub label: Option<String>,
     pub kind: SpanKind,
+
+    /// The code suggested to replace the span with, making the span a suggested fix
+    pub replacement: Option<String>,
 }
 
 impl Span {
@@ -260,6 +315,7 @@ impl Span {
             start: pos,
             label,
             kind: SpanKind::Primary,
+            replacement: None,
         }
     }
 
@@ -268,6 +324,17 @@ impl Span {
             start: pos,
             label,
             kind: SpanKind::Secondary,
+            replacement: None,
+        }
+    }
+
+    /// Makes a secondary span suggesting to replace the code at the given position
+    pub fn make_fix(pos: SpanPosition, label: Option<String>, replacement: String) -> Self {
+        Span {
+            start: pos,
+            label,
+            kind: SpanKind::Secondary,
+            replacement: Some(replacement),
         }
     }
 }
@@ -330,12 +397,7 @@ impl Display for Span {
 
 impl Display for Diagnostic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let header = ColoredString::from(format!(
-            "{}[{}{}]",
-            self.level,
-            self.level.get_code_char(),
-            self.code
-        ));
+        let header = ColoredString::from(format!("{}[{}]", self.level, self.get_display_code()));
         let header = self.level.apply_color(header);
 
         writeln!(f, "{}: {}", header, self.message)?;
@@ -73,12 +73,6 @@ declare_error!(
     "field {} of type {} is missing in the initializer"
 );
 declare_error!(FIELD_MISSING, 25, "field {} was not found in type {}");
-declare_error!(FUNC_MISSING, 25, "function {} was not found in type {}");
-declare_error!(
-    FIND_VAR,
-    25,
-    "cannot find variable {} in the current context"
-);
 
 // IR internals
 declare_error!(LOWER_TYPE_IR, 26, "cannot cast MIR variable to given type");
@@ -110,3 +104,167 @@ declare_error!(
     36,
     "different return type kinds. one is empty and one is not."
 );
+declare_error!(UNKNOWN_TRAIT, 37, "unknown bound trait {}");
+declare_error!(
+    TRAIT_NOT_IMPLEMENTED,
+    38,
+    "type {} does not implement trait {}"
+);
+declare_error!(
+    INFINITE_SIZE_TYPE,
+    39,
+    "recursive type {} has infinite size"
+);
+declare_error!(
+    DUPLICATE_DISCRIMINANT,
+    40,
+    "discriminant {} of entry {} is already used by entry {}"
+);
+declare_error!(
+    DISCRIMINANT_OVERFLOW,
+    41,
+    "discriminant {} of entry {} does not fit in {}"
+);
+declare_error!(INVALID_CAST, 42, "cannot cast a value of type {} into {}");
+declare_error!(
+    UNSAFE_UNION_ACCESS,
+    43,
+    "access to union field {} is unsafe"
+);
+declare_error!(
+    UNION_INIT_FIELD_COUNT,
+    44,
+    "a union initializer must contain exactly one field, got {}"
+);
+declare_error!(
+    BITFIELD_OUTSIDE_LAYOUT,
+    45,
+    "bitfield {} can only be declared in a layout type"
+);
+declare_error!(
+    BITFIELD_NOT_INTEGER,
+    46,
+    "bitfield {} must be of an integer type, got {}"
+);
+declare_error!(
+    BITFIELD_WIDTH,
+    47,
+    "bitfield {} of width {} does not fit in {}"
+);
+declare_error!(
+    CALL_ARGUMENT_COUNT,
+    48,
+    "function of type {} takes {} arguments, got {}"
+);
+declare_error!(
+    CLOSURE_OUTLIVES_CAPTURE,
+    49,
+    "closure outlives the variable {} it captures by reference"
+);
+declare_error!(
+    CLOSURE_ESCAPES,
+    50,
+    "closure capturing {} cannot escape the function creating it"
+);
+declare_error!(
+    CAPTURE_NOT_LOCAL,
+    51,
+    "cannot capture {} since it isn't a local variable"
+);
+declare_error!(
+    TUPLE_DESTRUCTURE_COUNT,
+    52,
+    "cannot destructure a tuple of type {} into {} variables"
+);
+declare_error!(
+    SLICE_LENGTH_ASSIGN,
+    53,
+    "cannot assign the length of a slice"
+);
+declare_error!(
+    UNSUPPORTED_FLOAT_WIDTH,
+    54,
+    "floating point numbers of width {} are not supported"
+);
+declare_error!(UNKNOWN_ATTRIBUTE, 55, "unknown attribute {}");
+declare_error!(UNKNOWN_WARNING, 56, "unknown warning {}");
+declare_error!(INVALID_ATTRIBUTE, 57, "invalid attribute {}: {}");
+declare_error!(FUNC_MISSING, 58, "function {} was not found in type {}");
+declare_error!(
+    FIND_VAR,
+    59,
+    "cannot find variable {} in the current context"
+);
+declare_error!(
+    UNSUPPORTED_INTEGER_WIDTH,
+    60,
+    "integers of width {} are not supported"
+);
+
+// Compiler internals
+declare_error!(UNSURE_PANIC, 99, "unsure panic: {}");
+
+/// Every error along with the name of its constant. Used to describe the codes within machine-readable outputs
+pub const ERRORS: &[(&str, (usize, &str))] = &[
+    ("EXPECTED_TOKEN", EXPECTED_TOKEN),
+    ("UNEXPECTED_TOKEN", UNEXPECTED_TOKEN),
+    ("MISMATCHED_LITERAL", MISMATCHED_LITERAL),
+    ("MATH_OPERATION_ASSIGNS", MATH_OPERATION_ASSIGNS),
+    ("FIND_TYPE", FIND_TYPE),
+    ("EXPECTED_TYPE", EXPECTED_TYPE),
+    ("ALREADY_IN_SCOPE", ALREADY_IN_SCOPE),
+    ("ERA_NOT_EXIST", ERA_NOT_EXIST),
+    ("ENUM_PARENT_FIELDS", ENUM_PARENT_FIELDS),
+    ("ENUM_CHILD_FUNCTIONS", ENUM_CHILD_FUNCTIONS),
+    ("LAYOUT_FUNCTIONS", LAYOUT_FUNCTIONS),
+    ("INVALID_POINTING", INVALID_POINTING),
+    ("TRAIT_MISSING", TRAIT_MISSING),
+    ("BOUND_MISSING", BOUND_MISSING),
+    ("VARIABLE_UNINIT", VARIABLE_UNINIT),
+    ("TYPE_RESOLVE", TYPE_RESOLVE),
+    ("DIFF_SIZE_SPECIFIERS", DIFF_SIZE_SPECIFIERS),
+    ("DIFF_TYPE_SPECIFIERS", DIFF_TYPE_SPECIFIERS),
+    ("IR_CAST", IR_CAST),
+    ("EXPECTED_FREE", EXPECTED_FREE),
+    ("FIND_TYPE_FUNCTION", FIND_TYPE_FUNCTION),
+    ("FIND_TYPE_FIELD", FIND_TYPE_FIELD),
+    ("INDEX_USAGE", INDEX_USAGE),
+    ("FIELD_STRUCT_INIT", FIELD_STRUCT_INIT),
+    ("FIELD_MISSING", FIELD_MISSING),
+    ("LOWER_TYPE_IR", LOWER_TYPE_IR),
+    ("ASSIGN_DIFF_TYPE_IR", ASSIGN_DIFF_TYPE_IR),
+    ("IR_DIFF_SSA_INDEXES", IR_DIFF_SSA_INDEXES),
+    ("IR_INSTRUCTION_HELD_VAL", IR_INSTRUCTION_HELD_VAL),
+    ("INVALID_TYPE_REQ", INVALID_TYPE_REQ),
+    ("TYPE_NOT_PART", TYPE_NOT_PART),
+    ("NOT_FOUND_USE", NOT_FOUND_USE),
+    ("CANNOT_FIND", CANNOT_FIND),
+    ("ENDING_POINT_MISSING", ENDING_POINT_MISSING),
+    ("CODE_UNREACHABLE", CODE_UNREACHABLE),
+    ("RET_TYPE_NOT_MATCH", RET_TYPE_NOT_MATCH),
+    ("UNKNOWN_TRAIT", UNKNOWN_TRAIT),
+    ("TRAIT_NOT_IMPLEMENTED", TRAIT_NOT_IMPLEMENTED),
+    ("INFINITE_SIZE_TYPE", INFINITE_SIZE_TYPE),
+    ("DUPLICATE_DISCRIMINANT", DUPLICATE_DISCRIMINANT),
+    ("DISCRIMINANT_OVERFLOW", DISCRIMINANT_OVERFLOW),
+    ("INVALID_CAST", INVALID_CAST),
+    ("UNSAFE_UNION_ACCESS", UNSAFE_UNION_ACCESS),
+    ("UNION_INIT_FIELD_COUNT", UNION_INIT_FIELD_COUNT),
+    ("BITFIELD_OUTSIDE_LAYOUT", BITFIELD_OUTSIDE_LAYOUT),
+    ("BITFIELD_NOT_INTEGER", BITFIELD_NOT_INTEGER),
+    ("BITFIELD_WIDTH", BITFIELD_WIDTH),
+    ("CALL_ARGUMENT_COUNT", CALL_ARGUMENT_COUNT),
+    ("CLOSURE_OUTLIVES_CAPTURE", CLOSURE_OUTLIVES_CAPTURE),
+    ("CLOSURE_ESCAPES", CLOSURE_ESCAPES),
+    ("CAPTURE_NOT_LOCAL", CAPTURE_NOT_LOCAL),
+    ("TUPLE_DESTRUCTURE_COUNT", TUPLE_DESTRUCTURE_COUNT),
+    ("SLICE_LENGTH_ASSIGN", SLICE_LENGTH_ASSIGN),
+    ("UNSUPPORTED_FLOAT_WIDTH", UNSUPPORTED_FLOAT_WIDTH),
+    ("UNKNOWN_ATTRIBUTE", UNKNOWN_ATTRIBUTE),
+    ("UNKNOWN_WARNING", UNKNOWN_WARNING),
+    ("INVALID_ATTRIBUTE", INVALID_ATTRIBUTE),
+    ("FUNC_MISSING", FUNC_MISSING),
+    ("FIND_VAR", FIND_VAR),
+    ("UNSUPPORTED_INTEGER_WIDTH", UNSUPPORTED_INTEGER_WIDTH),
+    ("UNSURE_PANIC", UNSURE_PANIC),
+];
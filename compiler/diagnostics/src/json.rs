@@ -0,0 +1,108 @@
+//! Machine-readable rendering of diagnostics.
+//!
+//! Every diagnostic is rendered as a single line JSON object, followed by a summary record once the compilation ends:
+//! ```json
+//! {"type":"diagnostic","level":"warning","code":"W0000","message":"unused variable: a","primary_span":{...},"secondary_spans":[...],"notes":[],"help":[],"fixes":[]}
+//! {"type":"summary","errors":0,"warnings":1}
+//! ```
+//! Positions are the same as the ones of the human readable output.
+
+use crate::diagnostic::{Diagnostic, Span, SpanPosition};
+
+/// Escapes the given string into a JSON string literal
+pub fn escape_json(str: &str) -> String {
+    let mut res = String::with_capacity(str.len() + 2);
+    res.push('"');
+
+    for c in str.chars() {
+        match c {
+            '"' => res += "\\\"",
+            '\\' => res += "\\\\",
+            '\n' => res += "\\n",
+            '\r' => res += "\\r",
+            '\t' => res += "\\t",
+            c if (c as u32) < 0x20 => res += &format!("\\u{:04x}", c as u32),
+            c => res.push(c),
+        }
+    }
+
+    res.push('"');
+    return res;
+}
+
+/// Renders the given values as a JSON array
+pub fn make_json_array<T, F: FnMut(&T) -> String>(values: &[T], f: F) -> String {
+    let values: Vec<String> = values.iter().map(f).collect();
+
+    return format!("[{}]", values.join(","));
+}
+
+fn make_json_option(val: &Option<String>) -> String {
+    return match val {
+        Some(v) => escape_json(v),
+        None => "null".to_string(),
+    };
+}
+
+fn make_json_position(pos: &SpanPosition) -> String {
+    return format!(
+        "\"file\":{},\"line\":{},\"col\":{},\"end\":{}",
+        escape_json(&pos.file_path),
+        pos.line,
+        pos.col,
+        pos.end_col
+    );
+}
+
+fn make_json_span(span: &Span) -> String {
+    return format!(
+        "{{{},\"label\":{}}}",
+        make_json_position(&span.start),
+        make_json_option(&span.label)
+    );
+}
+
+fn make_json_fix(span: &Span) -> String {
+    return format!(
+        "{{{},\"message\":{},\"replacement\":{}}}",
+        make_json_position(&span.start),
+        make_json_option(&span.label),
+        make_json_option(&span.replacement)
+    );
+}
+
+impl Diagnostic {
+    /// Renders the diagnostic as a single line JSON object
+    pub fn to_json(&self) -> String {
+        let secondary: Vec<&Span> = self
+            .spans
+            .iter()
+            .filter(|s| s.replacement.is_none())
+            .collect();
+        let fixes: Vec<&Span> = self
+            .spans
+            .iter()
+            .filter(|s| s.replacement.is_some())
+            .collect();
+
+        return format!(
+            "{{\"type\":\"diagnostic\",\"level\":\"{}\",\"code\":\"{}\",\"message\":{},\"primary_span\":{},\"secondary_spans\":{},\"notes\":{},\"help\":{},\"fixes\":{}}}",
+            self.level.get_name(),
+            self.get_display_code(),
+            escape_json(&self.message),
+            make_json_span(&self.primary_span),
+            make_json_array(&secondary, |s| make_json_span(s)),
+            make_json_array(&self.note, |s| escape_json(s)),
+            make_json_array(&self.help, |s| escape_json(s)),
+            make_json_array(&fixes, |s| make_json_fix(s))
+        );
+    }
+}
+
+/// Renders the record ending the JSON output
+pub fn make_json_summary(errors: usize, warnings: usize) -> String {
+    return format!(
+        "{{\"type\":\"summary\",\"errors\":{},\"warnings\":{}}}",
+        errors, warnings
+    );
+}
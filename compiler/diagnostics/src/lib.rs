@@ -1,20 +1,60 @@
 use std::cell::RefCell;
 
-use crate::diagnostic::{Diagnostic, Level, Span, SpanKind, SpanPosition};
+use crate::{
+    diagnostic::{Diagnostic, Level, Span, SpanKind, SpanPosition},
+    json::make_json_summary,
+    sarif::make_sarif_log,
+};
 
 pub mod builders;
 pub mod diagnostic;
 pub mod errors;
 pub mod infos;
+pub mod json;
+pub mod lints;
+pub mod registry;
+pub mod sarif;
 pub mod warnings;
 
-pub type DiagnosticResult<K> = Result<K, ()>;
+/// The error of a failed operation. The diagnostic describing the failure is stored within the diagnostic container
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DiagnosticError;
+
+impl From<Diagnostic> for DiagnosticError {
+    fn from(_: Diagnostic) -> Self {
+        DiagnosticError
+    }
+}
+
+pub type DiagnosticResult<K> = Result<K, DiagnosticError>;
 pub type MaybeDiagnostic = DiagnosticResult<()>;
 
 thread_local! {
     static DIAGNOSTIC_CONTAINER: RefCell<DiagnosticContainer> = RefCell::new(DiagnosticContainer::new());
 }
 
+/// The way diagnostics are printed by `dump_diagnostics`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MessageFormat {
+    Human,
+    Json,
+    Sarif,
+}
+
+thread_local! {
+    static MESSAGE_FORMAT: RefCell<MessageFormat> = const { RefCell::new(MessageFormat::Human) };
+}
+
+thread_local! {
+    /// The amount of errors and warnings printed so far, reported by the summary
+    static DUMPED_COUNTS: RefCell<(usize, usize)> = const { RefCell::new((0, 0)) };
+}
+
+thread_local! {
+    /// The diagnostics printed so far when using a format rendered as a single document
+    static DUMPED_DIAGNOSTICS: RefCell<Vec<Diagnostic>> = const { RefCell::new(vec![]) };
+}
+
 thread_local! {
     /// Used whenever the position cannot be passed directly (eg: interacting with the typing system or MIR.)
     static CURR_DIAGNOSTIC_POS: RefCell<Option<SpanPosition>> = RefCell::new(None)
@@ -62,14 +102,54 @@ impl DiagnosticContainer {
     }
 }
 
+pub fn set_message_format(format: MessageFormat) {
+    MESSAGE_FORMAT.with_borrow_mut(|f| *f = format)
+}
+
+pub fn get_message_format() -> MessageFormat {
+    MESSAGE_FORMAT.with_borrow(|f| return *f)
+}
+
 pub fn dump_diagnostics() {
+    let format = get_message_format();
+
     DIAGNOSTIC_CONTAINER.with_borrow(|f| {
         for diagnostic in &f.diagnostics {
-            println!("{}", diagnostic);
+            match format {
+                MessageFormat::Human => println!("{}", diagnostic),
+                MessageFormat::Json => println!("{}", diagnostic.to_json()),
+                MessageFormat::Sarif => {
+                    DUMPED_DIAGNOSTICS.with_borrow_mut(|d| d.push(diagnostic.clone()))
+                }
+            }
+
+            DUMPED_COUNTS.with_borrow_mut(|counts| match diagnostic.level {
+                Level::Error => counts.0 += 1,
+                Level::Warning => counts.1 += 1,
+                Level::Note => {}
+            });
         }
     })
 }
 
+/// The amount of errors and warnings printed so far
+pub fn get_dumped_counts() -> (usize, usize) {
+    DUMPED_COUNTS.with_borrow(|f| return *f)
+}
+
+/// Prints the summary of every diagnostic printed so far. Only machine-readable formats have a summary, SARIF prints its whole log
+pub fn dump_summary() {
+    let (errors, warnings) = DUMPED_COUNTS.with_borrow(|f| return *f);
+
+    match get_message_format() {
+        MessageFormat::Human => {}
+        MessageFormat::Json => println!("{}", make_json_summary(errors, warnings)),
+        MessageFormat::Sarif => {
+            DUMPED_DIAGNOSTICS.with_borrow(|d| println!("{}", make_sarif_log(d)));
+        }
+    }
+}
+
 pub fn clear_diagnostics() {
     DIAGNOSTIC_CONTAINER.with_borrow_mut(|f| f.diagnostics.clear())
 }
@@ -78,6 +158,15 @@ pub fn has_diagnostics() -> bool {
     DIAGNOSTIC_CONTAINER.with_borrow(|f| return !f.diagnostics.is_empty())
 }
 
+/// Whether any of the diagnostics is an error. Warnings denied through the lint levels count as errors
+pub fn has_errors() -> bool {
+    DIAGNOSTIC_CONTAINER.with_borrow(|f| {
+        f.diagnostics
+            .iter()
+            .any(|d| matches!(d.level, Level::Error))
+    })
+}
+
 pub fn get_diagnostics() -> Vec<Diagnostic> {
     DIAGNOSTIC_CONTAINER.with_borrow(|f| return f.diagnostics.clone())
 }
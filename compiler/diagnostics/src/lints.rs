@@ -0,0 +1,87 @@
+//! Warning level control.
+//!
+//! Every warning is reported at the `Warn` level by default. The level of a warning can be changed for the whole
+//! compilation from the CLI (`-W`, `-A`, `-D` and `--deny-warnings`), or allowed on a specific region of the source code
+//! through the `@allow` attribute. Denied warnings are reported as errors.
+
+use std::{cell::RefCell, collections::HashMap};
+
+use compiler_utils::Position;
+
+use crate::diagnostic::SpanPosition;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+/// A region of the source code in which some warnings are allowed
+struct AllowedRegion {
+    code: usize,
+    start: Position,
+    end: Position,
+}
+
+impl AllowedRegion {
+    fn contains(&self, pos: &SpanPosition) -> bool {
+        if pos.file_path != self.start.file_path {
+            return false;
+        }
+
+        let pos = (pos.line, pos.col);
+
+        return (self.start.line, self.start.col) <= pos && pos <= (self.end.line, self.end.col);
+    }
+}
+
+thread_local! {
+    /// The levels set from the CLI. Indexed by warning code
+    static LINT_LEVELS: RefCell<HashMap<usize, LintLevel>> = RefCell::new(HashMap::new());
+
+    /// Whether the warnings left at the `Warn` level are reported as errors
+    static DENY_WARNINGS: RefCell<bool> = const { RefCell::new(false) };
+
+    static ALLOWED_REGIONS: RefCell<Vec<AllowedRegion>> = const { RefCell::new(vec![]) };
+}
+
+pub fn set_lint_level(code: usize, level: LintLevel) {
+    LINT_LEVELS.with_borrow_mut(|f| {
+        f.insert(code, level);
+    })
+}
+
+pub fn set_deny_warnings(deny: bool) {
+    DENY_WARNINGS.with_borrow_mut(|f| *f = deny)
+}
+
+/// Allows the warning with the given code between the two given positions
+pub fn allow_warning_in(code: usize, start: Position, end: Position) {
+    ALLOWED_REGIONS.with_borrow_mut(|f| f.push(AllowedRegion { code, start, end }))
+}
+
+/// Forgets every allowed region. Regions are registered again whenever the source code is lowered
+pub fn clear_allowed_regions() {
+    ALLOWED_REGIONS.with_borrow_mut(|f| f.clear())
+}
+
+/// Gets the level the warning with the given code should be reported at the given position
+pub fn get_lint_level(code: usize, pos: &SpanPosition) -> LintLevel {
+    let allowed = ALLOWED_REGIONS.with_borrow(|f| {
+        f.iter()
+            .any(|region| region.code == code && region.contains(pos))
+    });
+
+    if allowed {
+        return LintLevel::Allow;
+    }
+
+    let level = LINT_LEVELS.with_borrow(|f| f.get(&code).copied().unwrap_or(LintLevel::Warn));
+
+    if level == LintLevel::Warn && DENY_WARNINGS.with_borrow(|f| *f) {
+        return LintLevel::Deny;
+    }
+
+    return level;
+}
@@ -0,0 +1,287 @@
+//! The registry of every error and warning code.
+//!
+//! Codes are stable: once given to a diagnostic, a code is never reused by another one, even if the diagnostic gets
+//! removed. Every code comes with a long-form explanation written in `explanations/`, which is printed by
+//! `quickfall explain <code>`. Uniqueness, the presence of the explanations and the match between each code and the name
+//! of its explanation file are checked at compile time.
+
+use crate::{diagnostic::Level, errors::*, warnings::*};
+
+/// An error or warning code along with its explanation
+pub struct RegisteredCode {
+    pub level: Level,
+    pub code: usize,
+
+    /// The name of the constant declaring the code
+    pub name: &'static str,
+
+    /// The message template of the diagnostic
+    pub template: &'static str,
+
+    /// The long-form explanation of the code, written in Markdown
+    pub explanation: &'static str,
+}
+
+impl RegisteredCode {
+    /// The code as displayed to the user (eg: `E0012`)
+    pub fn get_display_code(&self) -> String {
+        return self.level.format_code(self.code);
+    }
+
+    /// The names the warning is referred to by within `-W`, `-A`, `-D` and `@allow`
+    pub fn get_warning_names(&self) -> Vec<&'static str> {
+        if self.level != Level::Warning {
+            return vec![];
+        }
+
+        return WARNING_NAMES
+            .iter()
+            .filter(|(_, code)| *code == self.code)
+            .map(|(name, _)| *name)
+            .collect();
+    }
+}
+
+macro_rules! explanation {
+    ($code: ident, $file: literal) => {
+        (
+            {
+                const {
+                    assert!(
+                        is_explanation_file($file, $code.0),
+                        concat!(
+                            "explanations/",
+                            $file,
+                            ".md does not match ",
+                            stringify!($code)
+                        )
+                    )
+                };
+
+                $code.0
+            },
+            include_str!(concat!("../explanations/", $file, ".md")),
+        )
+    };
+}
+
+const ERROR_EXPLANATIONS: &[(usize, &str)] = &[
+    explanation!(EXPECTED_TOKEN, "E0000"),
+    explanation!(UNEXPECTED_TOKEN, "E0001"),
+    explanation!(MISMATCHED_LITERAL, "E0002"),
+    explanation!(MATH_OPERATION_ASSIGNS, "E0003"),
+    explanation!(FIND_TYPE, "E0004"),
+    explanation!(EXPECTED_TYPE, "E0005"),
+    explanation!(ALREADY_IN_SCOPE, "E0006"),
+    explanation!(ERA_NOT_EXIST, "E0007"),
+    explanation!(ENUM_PARENT_FIELDS, "E0008"),
+    explanation!(ENUM_CHILD_FUNCTIONS, "E0009"),
+    explanation!(LAYOUT_FUNCTIONS, "E0010"),
+    explanation!(INVALID_POINTING, "E0011"),
+    explanation!(TRAIT_MISSING, "E0012"),
+    explanation!(BOUND_MISSING, "E0013"),
+    explanation!(VARIABLE_UNINIT, "E0014"),
+    explanation!(TYPE_RESOLVE, "E0015"),
+    explanation!(DIFF_SIZE_SPECIFIERS, "E0016"),
+    explanation!(DIFF_TYPE_SPECIFIERS, "E0018"),
+    explanation!(IR_CAST, "E0019"),
+    explanation!(EXPECTED_FREE, "E0020"),
+    explanation!(FIND_TYPE_FUNCTION, "E0021"),
+    explanation!(FIND_TYPE_FIELD, "E0022"),
+    explanation!(INDEX_USAGE, "E0023"),
+    explanation!(FIELD_STRUCT_INIT, "E0024"),
+    explanation!(FIELD_MISSING, "E0025"),
+    explanation!(LOWER_TYPE_IR, "E0026"),
+    explanation!(ASSIGN_DIFF_TYPE_IR, "E0027"),
+    explanation!(IR_DIFF_SSA_INDEXES, "E0028"),
+    explanation!(IR_INSTRUCTION_HELD_VAL, "E0029"),
+    explanation!(INVALID_TYPE_REQ, "E0030"),
+    explanation!(TYPE_NOT_PART, "E0031"),
+    explanation!(NOT_FOUND_USE, "E0032"),
+    explanation!(CANNOT_FIND, "E0033"),
+    explanation!(ENDING_POINT_MISSING, "E0034"),
+    explanation!(CODE_UNREACHABLE, "E0035"),
+    explanation!(RET_TYPE_NOT_MATCH, "E0036"),
+    explanation!(UNKNOWN_TRAIT, "E0037"),
+    explanation!(TRAIT_NOT_IMPLEMENTED, "E0038"),
+    explanation!(INFINITE_SIZE_TYPE, "E0039"),
+    explanation!(DUPLICATE_DISCRIMINANT, "E0040"),
+    explanation!(DISCRIMINANT_OVERFLOW, "E0041"),
+    explanation!(INVALID_CAST, "E0042"),
+    explanation!(UNSAFE_UNION_ACCESS, "E0043"),
+    explanation!(UNION_INIT_FIELD_COUNT, "E0044"),
+    explanation!(BITFIELD_OUTSIDE_LAYOUT, "E0045"),
+    explanation!(BITFIELD_NOT_INTEGER, "E0046"),
+    explanation!(BITFIELD_WIDTH, "E0047"),
+    explanation!(CALL_ARGUMENT_COUNT, "E0048"),
+    explanation!(CLOSURE_OUTLIVES_CAPTURE, "E0049"),
+    explanation!(CLOSURE_ESCAPES, "E0050"),
+    explanation!(CAPTURE_NOT_LOCAL, "E0051"),
+    explanation!(TUPLE_DESTRUCTURE_COUNT, "E0052"),
+    explanation!(SLICE_LENGTH_ASSIGN, "E0053"),
+    explanation!(UNSUPPORTED_FLOAT_WIDTH, "E0054"),
+    explanation!(UNKNOWN_ATTRIBUTE, "E0055"),
+    explanation!(UNKNOWN_WARNING, "E0056"),
+    explanation!(INVALID_ATTRIBUTE, "E0057"),
+    explanation!(FUNC_MISSING, "E0058"),
+    explanation!(FIND_VAR, "E0059"),
+    explanation!(UNSUPPORTED_INTEGER_WIDTH, "E0060"),
+    explanation!(UNSURE_PANIC, "E0099"),
+];
+
+const WARNING_EXPLANATIONS: &[(usize, &str)] = &[
+    explanation!(UNUSED_VAR, "W0000"),
+    explanation!(UNUSED_FUNCTION, "W0001"),
+    explanation!(UNUSED_TYPE, "W0002"),
+    explanation!(UNUSED_ARGUMENT, "W0003"),
+    explanation!(UNUSED_USE, "W0004"),
+    explanation!(CPU_SUPPORTED_TYPE, "W0005"),
+    explanation!(CPU_SUPPORTED_OP, "W0006"),
+    explanation!(EXPERIMENTAL_FEAT, "W0007"),
+    explanation!(EXPERIMENTAL_FUNC, "W0008"),
+    explanation!(EXPERIMENTAL_TYPE, "W0009"),
+    explanation!(DEPRECATED, "W0010"),
+    explanation!(DEPRECATED_MSG, "W0011"),
+    explanation!(UNSAFE_UNWRAP, "W0012"),
+    explanation!(UNSAFE_FUNC, "W0013"),
+    explanation!(SHADOWFUNC_INFINITEARGS, "W0014"),
+    explanation!(LOSSY_PROMOTION, "W0015"),
+];
+
+/// Checks that the name of an explanation file (eg: `E0012`) is the displayed form of the given code
+pub const fn is_explanation_file(file: &str, code: usize) -> bool {
+    let bytes = file.as_bytes();
+
+    if bytes.len() != 5 || (bytes[0] != b'E' && bytes[0] != b'W') {
+        return false;
+    }
+
+    let mut value = 0;
+    let mut i = 1;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return false;
+        }
+
+        value = value * 10 + (bytes[i] - b'0') as usize;
+        i += 1;
+    }
+
+    value == code
+}
+
+/// Checks that no two declarations share a code
+pub const fn are_codes_unique(declarations: &[(&str, (usize, &str))]) -> bool {
+    let mut i = 0;
+
+    while i < declarations.len() {
+        let mut j = i + 1;
+
+        while j < declarations.len() {
+            if declarations[i].1.0 == declarations[j].1.0 {
+                return false;
+            }
+
+            j += 1;
+        }
+
+        i += 1;
+    }
+
+    return true;
+}
+
+/// Checks that every declaration has exactly one explanation and that every explanation belongs to a declaration
+pub const fn are_codes_explained(
+    declarations: &[(&str, (usize, &str))],
+    explanations: &[(usize, &str)],
+) -> bool {
+    if declarations.len() != explanations.len() {
+        return false;
+    }
+
+    let mut i = 0;
+
+    while i < declarations.len() {
+        let mut found = false;
+        let mut j = 0;
+
+        while j < explanations.len() {
+            if explanations[j].0 == declarations[i].1.0 {
+                found = true;
+            }
+
+            j += 1;
+        }
+
+        if !found {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    return true;
+}
+
+const _: () = assert!(are_codes_unique(ERRORS), "error codes must be unique");
+const _: () = assert!(are_codes_unique(WARNINGS), "warning codes must be unique");
+
+const _: () = assert!(
+    are_codes_explained(ERRORS, ERROR_EXPLANATIONS),
+    "every error code must have an explanation"
+);
+const _: () = assert!(
+    are_codes_explained(WARNINGS, WARNING_EXPLANATIONS),
+    "every warning code must have an explanation"
+);
+
+fn get_explanation(explanations: &[(usize, &'static str)], code: usize) -> &'static str {
+    return explanations
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, e)| *e)
+        .unwrap_or_default();
+}
+
+/// Gets every registered code, errors first
+pub fn get_registered_codes() -> Vec<RegisteredCode> {
+    let declarations = ERRORS
+        .iter()
+        .map(|e| (e, Level::Error, ERROR_EXPLANATIONS))
+        .chain(
+            WARNINGS
+                .iter()
+                .map(|w| (w, Level::Warning, WARNING_EXPLANATIONS)),
+        );
+
+    return declarations
+        .map(
+            |((name, (code, template)), level, explanations)| RegisteredCode {
+                level,
+                code: *code,
+                name,
+                template,
+                explanation: get_explanation(explanations, *code),
+            },
+        )
+        .collect();
+}
+
+/// Finds the code written by the user, such as `E0025`, `e25` or `W12`
+pub fn find_registered_code(str: &str) -> Option<RegisteredCode> {
+    let str = str.trim();
+
+    let level = match str.chars().next()?.to_ascii_uppercase() {
+        'E' => Level::Error,
+        'W' => Level::Warning,
+        _ => return None,
+    };
+
+    let code: usize = str[1..].parse().ok()?;
+
+    return get_registered_codes()
+        .into_iter()
+        .find(|c| c.level == level && c.code == code);
+}
@@ -0,0 +1,133 @@
+//! SARIF 2.1.0 rendering of diagnostics.
+//!
+//! Unlike the other formats, SARIF is a single document: the diagnostics are gathered as they get dumped and the whole
+//! log is rendered once the compilation ends. Every error and warning code becomes a rule described by its message
+//! template and explanation, see `registry`.
+
+use crate::{
+    diagnostic::{Diagnostic, Span, SpanPosition},
+    json::{escape_json, make_json_array},
+    registry::{RegisteredCode, get_registered_codes},
+};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+const TOOL_NAME: &str = "quickfall";
+
+fn make_sarif_rule(rule: &RegisteredCode) -> String {
+    return format!(
+        "{{\"id\":{},\"name\":{},\"shortDescription\":{{\"text\":{}}},\"help\":{{\"text\":{},\"markdown\":{}}},\"defaultConfiguration\":{{\"level\":\"{}\"}}}}",
+        escape_json(&rule.get_display_code()),
+        escape_json(&rule.name.to_lowercase()),
+        escape_json(rule.template),
+        escape_json(rule.explanation),
+        escape_json(rule.explanation),
+        rule.level.get_name()
+    );
+}
+
+/// SARIF columns start at 1 and regions end on the column following their last character
+fn make_sarif_region(pos: &SpanPosition) -> String {
+    return format!(
+        "{{\"startLine\":{},\"startColumn\":{},\"endColumn\":{}}}",
+        pos.line,
+        pos.col + 1,
+        pos.end_col.max(pos.col) + 1
+    );
+}
+
+fn make_sarif_artifact(pos: &SpanPosition) -> String {
+    return format!("{{\"uri\":{}}}", escape_json(&pos.file_path));
+}
+
+fn make_sarif_location(span: &Span, id: Option<usize>) -> String {
+    let mut res = "{".to_string();
+
+    if let Some(id) = id {
+        res += &format!("\"id\":{},", id);
+    }
+
+    res += &format!(
+        "\"physicalLocation\":{{\"artifactLocation\":{},\"region\":{}}}",
+        make_sarif_artifact(&span.start),
+        make_sarif_region(&span.start)
+    );
+
+    if let Some(label) = &span.label {
+        res += &format!(",\"message\":{{\"text\":{}}}", escape_json(label));
+    }
+
+    res += "}";
+    return res;
+}
+
+fn make_sarif_fix(span: &Span) -> String {
+    let description = span.label.clone().unwrap_or_default();
+    let replacement = span.replacement.clone().unwrap_or_default();
+
+    return format!(
+        "{{\"description\":{{\"text\":{}}},\"artifactChanges\":[{{\"artifactLocation\":{},\"replacements\":[{{\"deletedRegion\":{},\"insertedContent\":{{\"text\":{}}}}}]}}]}}",
+        escape_json(&description),
+        make_sarif_artifact(&span.start),
+        make_sarif_region(&span.start),
+        escape_json(&replacement)
+    );
+}
+
+fn make_sarif_result(diagnostic: &Diagnostic, rules: &[RegisteredCode]) -> String {
+    let id = diagnostic.get_display_code();
+
+    let mut res = format!("{{\"ruleId\":{}", escape_json(&id));
+
+    if let Some(index) = rules.iter().position(|r| r.get_display_code() == id) {
+        res += &format!(",\"ruleIndex\":{}", index);
+    }
+
+    let related: Vec<&Span> = diagnostic
+        .spans
+        .iter()
+        .filter(|s| s.replacement.is_none())
+        .collect();
+    let fixes: Vec<&Span> = diagnostic
+        .spans
+        .iter()
+        .filter(|s| s.replacement.is_some())
+        .collect();
+
+    let mut ind = 0;
+
+    res += &format!(
+        ",\"level\":\"{}\",\"message\":{{\"text\":{}}},\"locations\":[{}],\"relatedLocations\":{},\"fixes\":{}",
+        diagnostic.level.get_name(),
+        escape_json(&diagnostic.message),
+        make_sarif_location(&diagnostic.primary_span, None),
+        make_json_array(&related, |s| {
+            ind += 1;
+            make_sarif_location(s, Some(ind))
+        }),
+        make_json_array(&fixes, |s| make_sarif_fix(s))
+    );
+
+    res += &format!(
+        ",\"properties\":{{\"notes\":{},\"help\":{}}}}}",
+        make_json_array(&diagnostic.note, |s| escape_json(s)),
+        make_json_array(&diagnostic.help, |s| escape_json(s))
+    );
+
+    return res;
+}
+
+/// Renders the SARIF log of the given diagnostics
+pub fn make_sarif_log(diagnostics: &[Diagnostic]) -> String {
+    let rules = get_registered_codes();
+
+    return format!(
+        "{{\"$schema\":{},\"version\":{},\"runs\":[{{\"tool\":{{\"driver\":{{\"name\":{},\"rules\":{}}}}},\"results\":{}}}]}}",
+        escape_json(SARIF_SCHEMA),
+        escape_json(SARIF_VERSION),
+        escape_json(TOOL_NAME),
+        make_json_array(&rules, make_sarif_rule),
+        make_json_array(diagnostics, |d| make_sarif_result(d, &rules))
+    );
+}
@@ -36,10 +36,70 @@ declare_warning!(DEPRECATED, 10, "this was marked as deprecated");
 declare_warning!(DEPRECATED_MSG, 11, "this was marked as deprecated: {}");
 
 // Safety
-declare_warning!(UNSAFE_UNWRAP, 7, "unsafe unwrapping, value might not be {}");
-declare_warning!(UNSAFE_FUNC, 7, "this function was marked as unsafe");
+declare_warning!(
+    UNSAFE_UNWRAP,
+    12,
+    "unsafe unwrapping, value might not be {}"
+);
+declare_warning!(UNSAFE_FUNC, 13, "this function was marked as unsafe");
 declare_warning!(
     SHADOWFUNC_INFINITEARGS,
-    8,
+    14,
     "this function doesn't have strict arguments, use with caution"
 );
+
+// Arithmetic
+declare_warning!(
+    LOSSY_PROMOTION,
+    15,
+    "{} cannot be fully represented once promoted to {}"
+);
+
+/// Every warning along with the name of its constant. Used to describe the codes within machine-readable outputs
+pub const WARNINGS: &[(&str, (usize, &str))] = &[
+    ("UNUSED_VAR", UNUSED_VAR),
+    ("UNUSED_FUNCTION", UNUSED_FUNCTION),
+    ("UNUSED_TYPE", UNUSED_TYPE),
+    ("UNUSED_ARGUMENT", UNUSED_ARGUMENT),
+    ("UNUSED_USE", UNUSED_USE),
+    ("CPU_SUPPORTED_TYPE", CPU_SUPPORTED_TYPE),
+    ("CPU_SUPPORTED_OP", CPU_SUPPORTED_OP),
+    ("EXPERIMENTAL_FEAT", EXPERIMENTAL_FEAT),
+    ("EXPERIMENTAL_FUNC", EXPERIMENTAL_FUNC),
+    ("EXPERIMENTAL_TYPE", EXPERIMENTAL_TYPE),
+    ("DEPRECATED", DEPRECATED),
+    ("DEPRECATED_MSG", DEPRECATED_MSG),
+    ("UNSAFE_UNWRAP", UNSAFE_UNWRAP),
+    ("UNSAFE_FUNC", UNSAFE_FUNC),
+    ("SHADOWFUNC_INFINITEARGS", SHADOWFUNC_INFINITEARGS),
+    ("LOSSY_PROMOTION", LOSSY_PROMOTION),
+];
+
+/// The names warnings are referred to by within `-W`, `-A`, `-D` and `@allow`. A name can cover multiple warnings
+pub const WARNING_NAMES: &[(&str, usize)] = &[
+    ("unused_variable", UNUSED_VAR.0),
+    ("unused_function", UNUSED_FUNCTION.0),
+    ("unused_type", UNUSED_TYPE.0),
+    ("unused_argument", UNUSED_ARGUMENT.0),
+    ("unused_use", UNUSED_USE.0),
+    ("cpu_unsupported_type", CPU_SUPPORTED_TYPE.0),
+    ("cpu_unsupported_op", CPU_SUPPORTED_OP.0),
+    ("experimental", EXPERIMENTAL_FEAT.0),
+    ("experimental", EXPERIMENTAL_FUNC.0),
+    ("experimental", EXPERIMENTAL_TYPE.0),
+    ("deprecated", DEPRECATED.0),
+    ("deprecated", DEPRECATED_MSG.0),
+    ("unsafe_unwrap", UNSAFE_UNWRAP.0),
+    ("unsafe_function", UNSAFE_FUNC.0),
+    ("infinite_arguments", SHADOWFUNC_INFINITEARGS.0),
+    ("lossy_promotion", LOSSY_PROMOTION.0),
+];
+
+/// Gets the codes of the warnings referred to by the given name
+pub fn get_warning_codes(name: &str) -> Vec<usize> {
+    return WARNING_NAMES
+        .iter()
+        .filter(|(n, _)| *n == name)
+        .map(|(_, code)| *code)
+        .collect();
+}
@@ -0,0 +1,104 @@
+//! JSON rendering of diagnostics, see `diagnostics::json`.
+
+use diagnostics::{
+    clear_diagnostics,
+    diagnostic::{Diagnostic, Level, Span, SpanPosition},
+    errors::FIND_TYPE,
+    json::{escape_json, make_json_summary},
+    lints::{LintLevel, set_lint_level},
+    warnings::UNUSED_VAR,
+};
+use serde_json::Value;
+
+fn make_pos(line: usize, col: usize, end_col: usize) -> SpanPosition {
+    SpanPosition {
+        line,
+        col,
+        file_path: "test.qf".to_string(),
+        end_col,
+    }
+}
+
+fn make_diagnostic(level: Level, decl: (usize, &str), message: &str, spans: Vec<Span>) -> Value {
+    clear_diagnostics();
+
+    let diagnostic = Diagnostic::new_base(
+        level,
+        decl.0,
+        message.to_string(),
+        Span::make_primary(make_pos(4, 1, 6), None),
+        spans,
+        vec!["a note".to_string()],
+        vec![],
+    );
+
+    serde_json::from_str(&diagnostic.to_json()).unwrap()
+}
+
+#[test]
+fn renders_diagnostics_as_json_objects() {
+    let json = make_diagnostic(
+        Level::Error,
+        FIND_TYPE,
+        "cannot find type pt in the current scope",
+        vec![
+            Span::make_secondary(make_pos(2, 0, 3), Some("declared here".to_string())),
+            Span::make_fix(
+                make_pos(4, 1, 6),
+                Some("use point".to_string()),
+                "point".to_string(),
+            ),
+        ],
+    );
+
+    assert_eq!(json["type"], "diagnostic");
+    assert_eq!(json["level"], "error");
+    assert_eq!(json["code"], format!("E{:04}", FIND_TYPE.0));
+    assert_eq!(json["message"], "cannot find type pt in the current scope");
+
+    assert_eq!(json["primary_span"]["file"], "test.qf");
+    assert_eq!(json["primary_span"]["line"], 4);
+    assert_eq!(json["primary_span"]["col"], 1);
+    assert_eq!(json["primary_span"]["end"], 6);
+    assert!(json["primary_span"]["label"].is_null());
+
+    assert_eq!(json["secondary_spans"].as_array().unwrap().len(), 1);
+    assert_eq!(json["secondary_spans"][0]["label"], "declared here");
+
+    assert_eq!(json["fixes"].as_array().unwrap().len(), 1);
+    assert_eq!(json["fixes"][0]["replacement"], "point");
+
+    assert_eq!(json["notes"], serde_json::json!(["a note"]));
+    assert_eq!(json["help"], serde_json::json!([]));
+}
+
+#[test]
+fn escapes_strings() {
+    let message = "quote \" backslash \\ newline \n tab \t bell \u{7}";
+
+    let escaped: Value = serde_json::from_str(&escape_json(message)).unwrap();
+    assert_eq!(escaped, message);
+
+    let json = make_diagnostic(Level::Warning, UNUSED_VAR, message, vec![]);
+    assert_eq!(json["message"], message);
+}
+
+#[test]
+fn keeps_the_warning_code_of_denied_warnings() {
+    set_lint_level(UNUSED_VAR.0, LintLevel::Deny);
+
+    let json = make_diagnostic(Level::Warning, UNUSED_VAR, "unused variable: a", vec![]);
+
+    assert_eq!(json["level"], "error");
+    assert_eq!(json["code"], format!("W{:04}", UNUSED_VAR.0));
+}
+
+#[test]
+fn renders_summaries() {
+    let json: Value = serde_json::from_str(&make_json_summary(2, 5)).unwrap();
+
+    assert_eq!(
+        json,
+        serde_json::json!({"type": "summary", "errors": 2, "warnings": 5})
+    );
+}
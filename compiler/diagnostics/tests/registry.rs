@@ -0,0 +1,106 @@
+//! The registry of error and warning codes, see `diagnostics::registry`.
+
+use diagnostics::{
+    diagnostic::Level,
+    errors::{ERRORS, FIND_TYPE},
+    registry::{
+        are_codes_explained, are_codes_unique, find_registered_code, get_registered_codes,
+        is_explanation_file,
+    },
+    warnings::{UNUSED_VAR, WARNING_NAMES, WARNINGS},
+};
+
+#[test]
+fn registers_unique_codes() {
+    assert!(are_codes_unique(ERRORS));
+    assert!(are_codes_unique(WARNINGS));
+
+    let codes = get_registered_codes();
+    assert_eq!(codes.len(), ERRORS.len() + WARNINGS.len());
+
+    for (i, a) in codes.iter().enumerate() {
+        assert!(
+            codes[i + 1..]
+                .iter()
+                .all(|b| a.get_display_code() != b.get_display_code())
+        );
+    }
+}
+
+#[test]
+fn detects_duplicate_codes() {
+    let declarations = [("FIRST", (3, "first")), ("SECOND", (3, "second"))];
+
+    assert!(!are_codes_unique(&declarations));
+}
+
+#[test]
+fn explains_every_code() {
+    for code in get_registered_codes() {
+        assert!(
+            !code.explanation.trim().is_empty(),
+            "{} has no explanation",
+            code.get_display_code()
+        );
+    }
+}
+
+#[test]
+fn detects_missing_and_stray_explanations() {
+    let declarations = [("FIRST", (0, "first")), ("SECOND", (1, "second"))];
+
+    assert!(are_codes_explained(&declarations, &[(1, ""), (0, "")]));
+    assert!(!are_codes_explained(&declarations, &[(0, "")]));
+    assert!(!are_codes_explained(&declarations, &[(0, ""), (2, "")]));
+}
+
+#[test]
+fn matches_explanation_files_with_codes() {
+    assert!(is_explanation_file("E0012", 12));
+    assert!(is_explanation_file("W0000", 0));
+    assert!(!is_explanation_file("E0013", 12));
+    assert!(!is_explanation_file("E012", 12));
+    assert!(!is_explanation_file("X0012", 12));
+}
+
+#[test]
+fn names_only_registered_warnings() {
+    for (name, code) in WARNING_NAMES {
+        assert!(
+            WARNINGS.iter().any(|(_, (c, _))| c == code),
+            "{} refers to the unknown warning {}",
+            name,
+            code
+        );
+    }
+
+    for code in get_registered_codes() {
+        assert_eq!(
+            code.get_warning_names().is_empty(),
+            code.level == Level::Error,
+            "{} must have a name if and only if it is a warning",
+            code.get_display_code()
+        );
+    }
+}
+
+#[test]
+fn finds_codes_written_by_the_user() {
+    for (str, level, code) in [
+        ("E0004", Level::Error, FIND_TYPE.0),
+        ("e4", Level::Error, FIND_TYPE.0),
+        (" W0000 ", Level::Warning, UNUSED_VAR.0),
+    ] {
+        let found = find_registered_code(str).unwrap();
+
+        assert!(found.level == level);
+        assert_eq!(found.code, code);
+    }
+}
+
+#[test]
+fn rejects_unknown_codes() {
+    for str in ["E9999", "W0999", "I0000", "X0001", "E", "Efour", ""] {
+        assert!(find_registered_code(str).is_none(), "{} was found", str);
+    }
+}
@@ -0,0 +1,148 @@
+//! SARIF rendering of diagnostics, see `diagnostics::sarif`.
+
+use diagnostics::{
+    clear_diagnostics,
+    diagnostic::{Diagnostic, Level, Span, SpanPosition},
+    errors::FIND_TYPE,
+    lints::{LintLevel, set_lint_level},
+    registry::get_registered_codes,
+    sarif::make_sarif_log,
+    warnings::{UNUSED_FUNCTION, UNUSED_VAR},
+};
+use serde_json::Value;
+
+fn make_pos(line: usize, col: usize, end_col: usize) -> SpanPosition {
+    SpanPosition {
+        line,
+        col,
+        file_path: "test.qf".to_string(),
+        end_col,
+    }
+}
+
+fn make_diagnostic(level: Level, decl: (usize, &str), spans: Vec<Span>) -> Diagnostic {
+    Diagnostic::new_base(
+        level,
+        decl.0,
+        decl.1.to_string(),
+        Span::make_primary(make_pos(4, 1, 6), None),
+        spans,
+        vec![],
+        vec!["a help".to_string()],
+    )
+}
+
+fn make_log(diagnostics: &[Diagnostic]) -> Value {
+    serde_json::from_str(&make_sarif_log(diagnostics)).unwrap()
+}
+
+#[test]
+fn describes_every_code_as_a_rule() {
+    clear_diagnostics();
+
+    let log = make_log(&[]);
+    let run = &log["runs"][0];
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(run["tool"]["driver"]["name"], "quickfall");
+    assert!(run["results"].as_array().unwrap().is_empty());
+
+    assert_eq!(rules.len(), get_registered_codes().len());
+
+    for (rule, code) in rules.iter().zip(get_registered_codes()) {
+        assert_eq!(rule["id"], code.get_display_code());
+        assert_eq!(rule["shortDescription"]["text"], code.template);
+        assert_eq!(rule["help"]["markdown"], code.explanation);
+        assert_eq!(rule["defaultConfiguration"]["level"], code.level.get_name());
+    }
+}
+
+#[test]
+fn renders_results_pointing_to_their_rule() {
+    clear_diagnostics();
+
+    let diagnostics = [
+        make_diagnostic(
+            Level::Error,
+            FIND_TYPE,
+            vec![
+                Span::make_secondary(make_pos(2, 0, 3), Some("declared here".to_string())),
+                Span::make_fix(
+                    make_pos(4, 1, 6),
+                    Some("use point".to_string()),
+                    "point".to_string(),
+                ),
+            ],
+        ),
+        make_diagnostic(Level::Warning, UNUSED_VAR, vec![]),
+    ];
+
+    let log = make_log(&diagnostics);
+    let run = &log["runs"][0];
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    let results = run["results"].as_array().unwrap();
+
+    assert_eq!(results.len(), 2);
+
+    for result in results {
+        let index = result["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(rules[index]["id"], result["ruleId"]);
+    }
+
+    let error = &results[0];
+
+    assert_eq!(error["ruleId"], format!("E{:04}", FIND_TYPE.0));
+    assert_eq!(error["level"], "error");
+    assert_eq!(error["message"]["text"], FIND_TYPE.1);
+    assert_eq!(error["properties"]["help"], serde_json::json!(["a help"]));
+
+    // SARIF columns start at 1
+    let location = &error["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "test.qf");
+    assert_eq!(
+        location["region"],
+        serde_json::json!({"startLine": 4, "startColumn": 2, "endColumn": 7})
+    );
+
+    assert_eq!(error["relatedLocations"].as_array().unwrap().len(), 1);
+    assert_eq!(error["relatedLocations"][0]["id"], 1);
+    assert_eq!(
+        error["relatedLocations"][0]["message"]["text"],
+        "declared here"
+    );
+
+    let fix = &error["fixes"][0];
+    assert_eq!(fix["description"]["text"], "use point");
+    assert_eq!(
+        fix["artifactChanges"][0]["replacements"][0]["insertedContent"]["text"],
+        "point"
+    );
+
+    assert_eq!(results[1]["level"], "warning");
+    assert!(
+        results[1]["relatedLocations"]
+            .as_array()
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn keeps_the_warning_rule_of_denied_warnings() {
+    clear_diagnostics();
+    set_lint_level(UNUSED_FUNCTION.0, LintLevel::Deny);
+
+    let log = make_log(&[make_diagnostic(Level::Warning, UNUSED_FUNCTION, vec![])]);
+    let run = &log["runs"][0];
+    let result = &run["results"][0];
+
+    assert_eq!(result["level"], "error");
+    assert_eq!(result["ruleId"], format!("W{:04}", UNUSED_FUNCTION.0));
+
+    let index = result["ruleIndex"].as_u64().unwrap() as usize;
+    assert_eq!(
+        run["tool"]["driver"]["rules"][index]["defaultConfiguration"]["level"],
+        "warning"
+    );
+}
@@ -4,7 +4,7 @@
 
 use std::fs;
 
-use compiler_utils::{Position, hash};
+use compiler_utils::{Position, hash, hash::HashedString};
 use diagnostics::DiagnosticResult;
 use diagnostics::builders::make_unexpected_simple_error_outside;
 use diagnostics::diagnostic::SpanPosition;
@@ -17,6 +17,7 @@ const RET_KEYWORD_HASH: u64 = hash!("ret");
 const VAR_KEYWORD_HASH: u64 = hash!("var");
 const STRUCT_KEYWORD_HASH: u64 = hash!("struct");
 const LAYOUT_KEYWORD_HASH: u64 = hash!("layout");
+const UNION_KEYWORD_HASH: u64 = hash!("union");
 const FALSE_KEYWORD_HASH: u64 = hash!("false");
 const TRUE_KEYWORD_HASH: u64 = hash!("true");
 const IF_KEYWORD_HASH: u64 = hash!("if");
@@ -28,8 +29,16 @@ const THIS_KEYWORD_HASH: u64 = hash!("this");
 const NEW_KEYWORD_HASH: u64 = hash!("new");
 const UNWRAP_KEYWORD_HASH: u64 = hash!("unwrap");
 const UNWRAP_UNSAFE_KEYWORD_HASH: u64 = hash!("unsafe_unwrap");
+const UNCHECKED_KEYWORD_HASH: u64 = hash!("unchecked");
+const FAST_KEYWORD_HASH: u64 = hash!("fast");
 const ENUM_KEYWORD_HASH: u64 = hash!("enum");
 const USE_KEYWORD_HASH: u64 = hash!("use");
+const TRAIT_KEYWORD_HASH: u64 = hash!("trait");
+const IMPL_KEYWORD_HASH: u64 = hash!("impl");
+const CAST_KEYWORD_HASH: u64 = hash!("cast");
+const SIZEOF_KEYWORD_HASH: u64 = hash!("sizeof");
+const ALIGNOF_KEYWORD_HASH: u64 = hash!("alignof");
+const OFFSETOF_KEYWORD_HASH: u64 = hash!("offsetof");
 
 /// Parses a file into a set of lexer tokens.
 ///
@@ -85,7 +94,8 @@ pub fn lexer_parse(contents: String, file_path: &String) -> DiagnosticResult<Vec
             continue;
         }
 
-        if c.is_alphabetic() {
+        // A leading underscore marks the name as intentionally unused
+        if c.is_alphabetic() || c == '_' {
             let col = i - last_line_break;
 
             tokens.push(parse_keyword(
@@ -186,6 +196,7 @@ pub fn lexer_parse(contents: String, file_path: &String) -> DiagnosticResult<Vec
                 pos,
                 LexerTokenType::PercentSign,
             )),
+            '@' => tokens.push(LexerToken::make_single_sized(pos, LexerTokenType::At)),
             _ => continue,
         }
     }
@@ -206,18 +217,15 @@ fn parse_comment(
     *ind += 2;
 
     let start = *ind;
-    let mut end = start;
 
-    for (i, c) in contents[start..].char_indices() {
-        if c == '\n' || c == '\0' {
-            end = start + i + c.len_utf8();
-            break;
-        }
-
-        end = start + i + c.len_utf16();
-    }
+    // The line break is left to the main loop in order to keep counting lines
+    let end = match contents[start..].find(['\n', '\0']) {
+        Some(i) => start + i,
+        None => contents.len(),
+    };
 
-    let slice = &contents[*ind + 1..end - 1];
+    let slice = &contents[start..end];
+    let slice = slice.strip_prefix(' ').unwrap_or(slice);
 
     *ind = end;
 
@@ -236,18 +244,15 @@ fn parse_global_comment(
     *ind += 2;
 
     let start = *ind;
-    let mut end = start;
 
-    for (i, c) in contents[start..].char_indices() {
-        if c == '\n' || c == '\0' {
-            end = start + i + c.len_utf8();
-            break;
-        }
-
-        end = start + i + c.len_utf16();
-    }
+    // The line break is left to the main loop in order to keep counting lines
+    let end = match contents[start..].find(['\n', '\0']) {
+        Some(i) => start + i,
+        None => contents.len(),
+    };
 
-    let slice = &contents[*ind + 1..end - 1];
+    let slice = &contents[start..end];
+    let slice = slice.strip_prefix(' ').unwrap_or(slice);
 
     *ind = end;
 
@@ -288,7 +293,7 @@ fn parse_number_token(
 
     *ind = end;
 
-    let mut hash = hash!("s64"); // s64
+    let mut int_type = HashedString::new("s64".to_string());
 
     let endpos = start_pos.increment_by(end - start);
 
@@ -301,13 +306,16 @@ fn parse_number_token(
             Err(e) => return Err(e),
         };
 
-        hash = k.1;
+        int_type = HashedString {
+            val: k.0,
+            hash: k.1,
+        };
     }
 
     return Ok(LexerToken::new(
         start_pos,
         end - start,
-        LexerTokenType::IntLit(num, hash),
+        LexerTokenType::IntLit(num, int_type),
     ));
 }
 
@@ -359,6 +367,7 @@ fn parse_keyword(str: &String, ind: &mut usize, start_pos: Position) -> LexerTok
         RET_KEYWORD_HASH => LexerTokenType::Return,
         STRUCT_KEYWORD_HASH => LexerTokenType::Struct,
         LAYOUT_KEYWORD_HASH => LexerTokenType::Layout,
+        UNION_KEYWORD_HASH => LexerTokenType::Union,
         TRUE_KEYWORD_HASH => LexerTokenType::True,
         FALSE_KEYWORD_HASH => LexerTokenType::False,
         VAR_KEYWORD_HASH => LexerTokenType::Var,
@@ -371,8 +380,16 @@ fn parse_keyword(str: &String, ind: &mut usize, start_pos: Position) -> LexerTok
         NEW_KEYWORD_HASH => LexerTokenType::New,
         UNWRAP_KEYWORD_HASH => LexerTokenType::Unwrap,
         UNWRAP_UNSAFE_KEYWORD_HASH => LexerTokenType::UnwrapUnsafe,
+        UNCHECKED_KEYWORD_HASH => LexerTokenType::Unchecked,
+        FAST_KEYWORD_HASH => LexerTokenType::Fast,
         ENUM_KEYWORD_HASH => LexerTokenType::Enum,
         USE_KEYWORD_HASH => LexerTokenType::Use,
+        TRAIT_KEYWORD_HASH => LexerTokenType::Trait,
+        IMPL_KEYWORD_HASH => LexerTokenType::Impl,
+        CAST_KEYWORD_HASH => LexerTokenType::Cast,
+        SIZEOF_KEYWORD_HASH => LexerTokenType::SizeOf,
+        ALIGNOF_KEYWORD_HASH => LexerTokenType::AlignOf,
+        OFFSETOF_KEYWORD_HASH => LexerTokenType::OffsetOf,
         _ => LexerTokenType::Keyword(slice.to_string(), hash),
     };
 
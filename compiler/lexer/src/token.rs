@@ -4,7 +4,7 @@
 
 use std::fmt::Display;
 
-use compiler_utils::Position;
+use compiler_utils::{Position, hash::HashedString};
 use diagnostics::{
     DiagnosticResult, DiagnosticSpanOrigin,
     builders::make_expected_simple_error,
@@ -12,7 +12,7 @@ use diagnostics::{
 };
 
 /// The token type for the lexer
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum LexerTokenType {
     /// Represent the func keyword
     Function,
@@ -24,15 +24,28 @@ pub enum LexerTokenType {
     Unwrap,
     UnwrapUnsafe,
 
+    Unchecked,
+    Fast,
+
+    Cast,
+
     Use,
 
     Var,
     Struct,
     Layout,
+    Union,
     Lay,
 
     Enum,
 
+    Trait,
+    Impl,
+
+    SizeOf,
+    AlignOf,
+    OffsetOf,
+
     This,
 
     Static,
@@ -66,6 +79,7 @@ pub enum LexerTokenType {
     Divide,
     Tidle,
     PercentSign,
+    At,
 
     BracketOpen,
     BracketClose,
@@ -76,7 +90,9 @@ pub enum LexerTokenType {
     ArrayOpen,
     ArrayClose,
 
-    IntLit(i128, u64),
+    /// 0: The value
+    /// 1: The integer type given by the suffix
+    IntLit(i128, HashedString),
     StringLit(String),
 
     AngelBracketOpen,
@@ -86,7 +102,7 @@ pub enum LexerTokenType {
     EndOfFile,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LexerToken {
     pub tok_type: LexerTokenType,
     pub pos: Position,
@@ -122,9 +138,9 @@ impl LexerToken {
         return Ok(());
     }
 
-    pub fn expects_int_lit(&self) -> DiagnosticResult<(i128, u64)> {
+    pub fn expects_int_lit(&self) -> DiagnosticResult<(i128, HashedString)> {
         match &self.tok_type {
-            LexerTokenType::IntLit(v, h) => return Ok((*v, *h)),
+            LexerTokenType::IntLit(v, t) => return Ok((*v, t.clone())),
             _ => {
                 return Err(make_expected_simple_error(
                     self,
@@ -188,6 +204,12 @@ impl Display for LexerTokenType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
             Self::Enum => "enum",
+            Self::Trait => "trait",
+            Self::Impl => "impl",
+            Self::Cast => "cast",
+            Self::SizeOf => "sizeof",
+            Self::AlignOf => "alignof",
+            Self::OffsetOf => "offsetof",
             Self::Ampersand => "&",
             Self::AngelBracketClose => ">",
             Self::AngelBracketOpen => "<",
@@ -214,6 +236,7 @@ impl Display for LexerTokenType {
             Self::Keyword(_, _) => "keyword",
             Self::Lay => "lay",
             Self::Layout => "layout",
+            Self::Union => "union",
             Self::New => "new",
             Self::ParenClose => ")",
             Self::ParenOpen => "(",
@@ -229,11 +252,14 @@ impl Display for LexerTokenType {
             Self::Unwrap => "unwrap",
             Self::Use => "use",
             Self::UnwrapUnsafe => "unsafe_unwrap",
+            Self::Unchecked => "unchecked",
+            Self::Fast => "fast",
             Self::Plus => "+",
             Self::Minus => "-",
             Self::Divide => "/",
             Self::Tidle => "~",
             Self::PercentSign => "%",
+            Self::At => "@",
         };
 
         write!(f, "{}", s)?;
@@ -247,6 +273,7 @@ impl DiagnosticSpanOrigin for LexerToken {
             kind,
             label: msg,
             start: SpanPosition::from_pos(self.pos.clone(), self.get_size()),
+            replacement: None,
         }
     }
 
@@ -268,6 +295,7 @@ impl DiagnosticSpanOrigin for LexerToken {
             kind: SpanKind::Primary,
             label: primary_span_msg,
             start: SpanPosition::from_pos(self.pos.clone(), self.get_size()),
+            replacement: None,
         };
 
         Diagnostic::new_base(level, code, message, primary, spans, notes, help)
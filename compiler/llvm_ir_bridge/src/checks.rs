@@ -0,0 +1,95 @@
+//! Runtime checks emitted by the MIR such as bounds checks.
+
+use std::mem::transmute;
+
+use compiler_typing::raw::RawType;
+use inkwell::{
+    basic_block::BasicBlock, intrinsics::Intrinsic, module::Linkage, values::FunctionValue,
+};
+
+use crate::{ctx::LLVMBridgeContext, llvm_to_base_returnless};
+
+/// The name of the function checking the conditions of `assert` instructions
+const ASSERT_FUNCTION: &str = "__quickfall_assert";
+
+/// The libc function used to print the message of failed checks
+const PUTS_FUNCTION: &str = "puts";
+
+/// Gets the external `puts` function, declaring it if needed.
+fn get_puts_function(bridge: &mut LLVMBridgeContext) -> FunctionValue<'static> {
+    if let Some(func) = bridge.module.get_function(PUTS_FUNCTION) {
+        return func;
+    }
+
+    let int_type = bridge.types.convert_raw(RawType::Integer(32, true)).inner;
+    let ptr_type = bridge.types.convert_raw(RawType::Pointer).inner;
+
+    return bridge.module.add_function(
+        PUTS_FUNCTION,
+        int_type.into_int_type().fn_type(&[ptr_type.into()], false),
+        Some(Linkage::External),
+    );
+}
+
+/// Gets the function printing the given message then trapping when the given condition is false. The function is generated on its first use.
+///
+/// Checks are calls rather than branches in order to keep the blocks of the MIR intact.
+pub fn get_assert_function(bridge: &mut LLVMBridgeContext) -> FunctionValue<'static> {
+    if let Some(func) = bridge.module.get_function(ASSERT_FUNCTION) {
+        return func;
+    }
+
+    let bool_type = bridge.types.convert_raw(RawType::Boolean).inner;
+    let ptr_type = bridge.types.convert_raw(RawType::Pointer).inner;
+
+    let puts = get_puts_function(bridge);
+
+    let func = bridge.module.add_function(
+        ASSERT_FUNCTION,
+        bridge
+            .void_type
+            .fn_type(&[bool_type.into(), ptr_type.into()], false),
+        Some(Linkage::Private),
+    );
+
+    let entry = unsafe {
+        transmute::<BasicBlock, BasicBlock<'static>>(bridge.ctx.append_basic_block(func, ""))
+    };
+    let ok = unsafe {
+        transmute::<BasicBlock, BasicBlock<'static>>(bridge.ctx.append_basic_block(func, ""))
+    };
+    let fail = unsafe {
+        transmute::<BasicBlock, BasicBlock<'static>>(bridge.ctx.append_basic_block(func, ""))
+    };
+
+    let previous = bridge.builder.get_insert_block();
+
+    bridge.builder.position_at_end(entry);
+
+    let cond = func.get_nth_param(0).unwrap().into_int_value();
+
+    llvm_to_base_returnless!(bridge.builder.build_conditional_branch(cond, ok, fail));
+
+    bridge.builder.position_at_end(ok);
+    llvm_to_base_returnless!(bridge.builder.build_return(None));
+
+    bridge.builder.position_at_end(fail);
+
+    let msg = func.get_nth_param(1).unwrap();
+
+    llvm_to_base_returnless!(bridge.builder.build_call(puts, &[msg.into()], ""));
+
+    let trap = Intrinsic::find("llvm.trap")
+        .unwrap()
+        .get_declaration(&bridge.module, &[])
+        .unwrap();
+
+    llvm_to_base_returnless!(bridge.builder.build_call(trap, &[], ""));
+    llvm_to_base_returnless!(bridge.builder.build_unreachable());
+
+    if let Some(previous) = previous {
+        bridge.builder.position_at_end(previous);
+    }
+
+    return func;
+}
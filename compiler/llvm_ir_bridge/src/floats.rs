@@ -0,0 +1,180 @@
+//! Floating point widths without native support.
+//!
+//! 8 bit floats are stored as `i8` and computed on as halves. `f8` uses the E5M2 format, which is the upper byte of a
+//! half. `uf8` doesn't have any sign bit and uses an extra mantissa bit instead (E5M3); negative values are clamped to
+//! zero when stored.
+
+use astoir_mir::vals::{base::BaseMIRValue, float::MIRFloatValue};
+use compiler_typing::raw::RawType;
+use inkwell::{
+    FloatPredicate,
+    intrinsics::Intrinsic,
+    types::FloatType,
+    values::{BasicValueEnum, FloatValue, IntValue},
+};
+
+use crate::{ctx::LLVMBridgeContext, llvm_to_base};
+
+/// The quiet NaN of the signed 8 bit float format
+const F8_NAN: u64 = 0x7E;
+
+/// The quiet NaN of the unsigned 8 bit float format
+const UF8_NAN: u64 = 0xFC;
+
+/// Whether floats of the given size are emulated in software
+pub fn is_emulated_float(size: usize) -> bool {
+    return size == 8;
+}
+
+/// The amount of bits an 8 bit float is shifted by in order to become the upper bits of a half
+fn get_emulated_shift(signed: bool) -> u64 {
+    if signed {
+        return 8;
+    }
+
+    return 7;
+}
+
+/// Gets the native float type floats of the given size are computed on
+pub fn get_float_compute_type(size: usize, bridge: &mut LLVMBridgeContext) -> FloatType<'static> {
+    if is_emulated_float(size) {
+        return bridge
+            .types
+            .convert_raw(RawType::Floating(16, true))
+            .into_float_type();
+    }
+
+    return bridge
+        .types
+        .convert_raw(RawType::Floating(size, true))
+        .into_float_type();
+}
+
+/// Gets the given float value as a native float of the type given by `get_float_compute_type`
+pub fn get_float_operand(
+    val: MIRFloatValue,
+    bridge: &mut LLVMBridgeContext,
+) -> FloatValue<'static> {
+    let size = val.size;
+    let signed = val.signed;
+
+    let val: BaseMIRValue = MIRFloatValue::into(val);
+    let v = bridge.values[&val.get_ssa_index()].clone();
+
+    if !is_emulated_float(size) {
+        return v.into_float_value();
+    }
+
+    let half_type = get_float_compute_type(size, bridge);
+    let bits_type = bridge
+        .types
+        .convert_raw(RawType::Integer(16, false))
+        .into_int_type();
+
+    let bits = llvm_to_base!(
+        bridge
+            .builder
+            .build_int_z_extend(v.into_int_value(), bits_type, "")
+    );
+
+    let shift = bits_type.const_int(get_emulated_shift(signed), false);
+    let bits = llvm_to_base!(bridge.builder.build_left_shift(bits, shift, ""));
+
+    return llvm_to_base!(bridge.builder.build_bit_cast(bits, half_type, "")).into_float_value();
+}
+
+/// Converts a native float computed on the type given by `get_float_compute_type` into a value of the given float type
+pub fn make_float_result(
+    res: FloatValue<'static>,
+    size: usize,
+    signed: bool,
+    bridge: &mut LLVMBridgeContext,
+) -> BasicValueEnum<'static> {
+    if !is_emulated_float(size) {
+        return res.into();
+    }
+
+    let mut res = res;
+
+    if !signed {
+        res = build_float_max_zero(res, bridge);
+    }
+
+    let bits_type = bridge
+        .types
+        .convert_raw(RawType::Integer(16, false))
+        .into_int_type();
+    let byte_type = bridge
+        .types
+        .convert_raw(RawType::Integer(8, false))
+        .into_int_type();
+
+    let bits = llvm_to_base!(bridge.builder.build_bit_cast(res, bits_type, "")).into_int_value();
+
+    // Rounds the discarded lower bits to the nearest value, ties to even
+    let shift = get_emulated_shift(signed);
+
+    let lowest_kept = llvm_to_base!(bridge.builder.build_right_shift(
+        bits,
+        bits_type.const_int(shift, false),
+        false,
+        ""
+    ));
+    let lowest_kept = llvm_to_base!(bridge.builder.build_and(
+        lowest_kept,
+        bits_type.const_int(1, false),
+        ""
+    ));
+
+    let bias = llvm_to_base!(bridge.builder.build_int_add(
+        lowest_kept,
+        bits_type.const_int((1 << (shift - 1)) - 1, false),
+        ""
+    ));
+
+    let rounded = llvm_to_base!(bridge.builder.build_int_add(bits, bias, ""));
+    let rounded = llvm_to_base!(bridge.builder.build_right_shift(
+        rounded,
+        bits_type.const_int(shift, false),
+        false,
+        ""
+    ));
+    let rounded: IntValue<'static> =
+        llvm_to_base!(bridge.builder.build_int_truncate(rounded, byte_type, ""));
+
+    // Rounding could otherwise turn NaNs into infinities
+    let nan = match signed {
+        true => byte_type.const_int(F8_NAN, false),
+        false => byte_type.const_int(UF8_NAN, false),
+    };
+
+    let is_nan = llvm_to_base!(bridge.builder.build_float_compare(
+        FloatPredicate::UNO,
+        res,
+        res,
+        ""
+    ));
+
+    return llvm_to_base!(bridge.builder.build_select(is_nan, nan, rounded, ""));
+}
+
+/// Clamps the given native float to zero if it is negative. NaNs are also clamped to zero
+pub fn build_float_max_zero(
+    val: FloatValue<'static>,
+    bridge: &mut LLVMBridgeContext,
+) -> FloatValue<'static> {
+    let float_type = val.get_type();
+
+    let intrinsic = Intrinsic::find("llvm.maxnum")
+        .unwrap()
+        .get_declaration(&bridge.module, &[float_type.into()])
+        .unwrap();
+
+    let res = llvm_to_base!(bridge.builder.build_call(
+        intrinsic,
+        &[val.into(), float_type.const_zero().into()],
+        ""
+    ));
+
+    return res.try_as_basic_value().basic().unwrap().into_float_value();
+}
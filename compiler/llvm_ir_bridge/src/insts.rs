@@ -1,18 +1,27 @@
 use astoir_mir::{
     blocks::MIRBlockHeldInstruction,
     ctx::MIRContext,
-    insts::MIRInstruction,
-    vals::{base::BaseMIRValue, float::MIRFloatValue, int::MIRIntValue, ptr::MIRPointerValue},
+    insts::{MIRInstruction, MIROverflowOperation},
+    vals::{base::BaseMIRValue, int::MIRIntValue, ptr::MIRPointerValue},
 };
-use compiler_typing::raw::RawType;
+use compiler_typing::{raw::RawType, tree::Type};
 use inkwell::{
     IntPredicate,
+    intrinsics::Intrinsic,
     module::Linkage,
     types::{BasicType, BasicTypeEnum, StringRadix},
-    values::{BasicValue, BasicValueEnum, FastMathFlags, FloatValue, IntValue},
+    values::{
+        BasicMetadataValueEnum, BasicValue, BasicValueEnum, FastMathFlags, FloatValue, IntValue,
+    },
 };
 
-use crate::{ctx::LLVMBridgeContext, llvm_to_base, llvm_to_base_returnless, utils::LLVMBasicValue};
+use crate::{
+    checks::get_assert_function,
+    ctx::LLVMBridgeContext,
+    floats::{build_float_max_zero, get_float_compute_type, get_float_operand, make_float_result},
+    llvm_to_base, llvm_to_base_returnless,
+    utils::LLVMBasicValue,
+};
 
 pub fn bridge_llvm_instruction(
     instruction: MIRBlockHeldInstruction,
@@ -78,7 +87,7 @@ pub fn bridge_llvm_instruction(
             }
 
             MIRInstruction::IntegerAdd {
-                signed: _,
+                signed,
                 fast,
                 left,
                 right,
@@ -96,17 +105,14 @@ pub fn bridge_llvm_instruction(
                 ));
 
                 if fast {
-                    let res2 = res.as_instruction_value().unwrap();
-
-                    llvm_to_base_returnless!(res2.set_no_signed_wrap_flag(true));
-                    llvm_to_base_returnless!(res2.set_no_unsigned_wrap_flag(true));
+                    set_fast_int_flags(res, signed);
                 }
 
                 Some(res.into())
             }
 
             MIRInstruction::IntegerSub {
-                signed: _,
+                signed,
                 fast,
                 left,
                 right,
@@ -124,17 +130,14 @@ pub fn bridge_llvm_instruction(
                 ));
 
                 if fast {
-                    let res2 = res.as_instruction_value().unwrap();
-
-                    llvm_to_base_returnless!(res2.set_no_signed_wrap_flag(true));
-                    llvm_to_base_returnless!(res2.set_no_unsigned_wrap_flag(true));
+                    set_fast_int_flags(res, signed);
                 }
 
                 Some(res.into())
             }
 
             MIRInstruction::IntegerMul {
-                signed: _,
+                signed,
                 fast,
                 left,
                 right,
@@ -152,10 +155,7 @@ pub fn bridge_llvm_instruction(
                 ));
 
                 if fast {
-                    let res2 = res.as_instruction_value().unwrap();
-
-                    llvm_to_base_returnless!(res2.set_no_signed_wrap_flag(true));
-                    llvm_to_base_returnless!(res2.set_no_unsigned_wrap_flag(true));
+                    set_fast_int_flags(res, signed);
                 }
 
                 Some(res.into())
@@ -189,19 +189,19 @@ pub fn bridge_llvm_instruction(
                     ))
                 }
 
+                // Divisions cannot wrap, fast divisions are exact instead
                 if fast {
                     let res2 = res.as_instruction_value().unwrap();
 
-                    llvm_to_base_returnless!(res2.set_no_signed_wrap_flag(true));
-                    llvm_to_base_returnless!(res2.set_no_unsigned_wrap_flag(true));
                     llvm_to_base_returnless!(res2.set_exact_flag(true));
                 }
+
                 Some(res.into())
             }
 
             MIRInstruction::IntegerMod {
                 signed,
-                fast,
+                fast: _,
                 left,
                 right,
             } => {
@@ -227,14 +227,6 @@ pub fn bridge_llvm_instruction(
                     ))
                 }
 
-                if fast {
-                    llvm_to_base_returnless!(
-                        res.as_instruction_value()
-                            .unwrap()
-                            .set_fast_math_flags(FastMathFlags::all())
-                    )
-                }
-
                 Some(res.into())
             }
 
@@ -244,27 +236,23 @@ pub fn bridge_llvm_instruction(
                 left,
                 right,
             } => {
-                let left: BaseMIRValue = MIRFloatValue::into(left);
-                let right: BaseMIRValue = MIRFloatValue::into(right);
+                let (size, signed) = (left.size, left.signed);
 
-                let l = bridge.values[&left.get_ssa_index()].clone();
-                let r = bridge.values[&right.get_ssa_index()].clone();
+                let l = get_float_operand(left, bridge);
+                let r = get_float_operand(right, bridge);
 
-                let res: FloatValue<'static> = llvm_to_base!(bridge.builder.build_float_add(
-                    l.into_float_value(),
-                    r.into_float_value(),
-                    ""
-                ));
+                let res: FloatValue<'static> =
+                    llvm_to_base!(bridge.builder.build_float_add(l, r, ""));
 
                 if fast {
                     llvm_to_base_returnless!(
                         res.as_instruction_value()
                             .unwrap()
-                            .set_fast_math_flags(FastMathFlags::all())
+                            .set_fast_math_flags(get_fast_float_flags())
                     )
                 }
 
-                Some(res.into())
+                Some(make_float_result(res, size, signed, bridge))
             }
 
             MIRInstruction::FloatSub {
@@ -273,27 +261,23 @@ pub fn bridge_llvm_instruction(
                 left,
                 right,
             } => {
-                let left: BaseMIRValue = MIRFloatValue::into(left);
-                let right: BaseMIRValue = MIRFloatValue::into(right);
+                let (size, signed) = (left.size, left.signed);
 
-                let l = bridge.values[&left.get_ssa_index()].clone();
-                let r = bridge.values[&right.get_ssa_index()].clone();
+                let l = get_float_operand(left, bridge);
+                let r = get_float_operand(right, bridge);
 
-                let res: FloatValue<'static> = llvm_to_base!(bridge.builder.build_float_sub(
-                    l.into_float_value(),
-                    r.into_float_value(),
-                    ""
-                ));
+                let res: FloatValue<'static> =
+                    llvm_to_base!(bridge.builder.build_float_sub(l, r, ""));
 
                 if fast {
                     llvm_to_base_returnless!(
                         res.as_instruction_value()
                             .unwrap()
-                            .set_fast_math_flags(FastMathFlags::all())
+                            .set_fast_math_flags(get_fast_float_flags())
                     )
                 }
 
-                Some(res.into())
+                Some(make_float_result(res, size, signed, bridge))
             }
 
             MIRInstruction::FloatMul {
@@ -302,27 +286,23 @@ pub fn bridge_llvm_instruction(
                 left,
                 right,
             } => {
-                let left: BaseMIRValue = MIRFloatValue::into(left);
-                let right: BaseMIRValue = MIRFloatValue::into(right);
+                let (size, signed) = (left.size, left.signed);
 
-                let l = bridge.values[&left.get_ssa_index()].clone();
-                let r = bridge.values[&right.get_ssa_index()].clone();
+                let l = get_float_operand(left, bridge);
+                let r = get_float_operand(right, bridge);
 
-                let res: FloatValue<'static> = llvm_to_base!(bridge.builder.build_float_mul(
-                    l.into_float_value(),
-                    r.into_float_value(),
-                    ""
-                ));
+                let res: FloatValue<'static> =
+                    llvm_to_base!(bridge.builder.build_float_mul(l, r, ""));
 
                 if fast {
                     llvm_to_base_returnless!(
                         res.as_instruction_value()
                             .unwrap()
-                            .set_fast_math_flags(FastMathFlags::all())
+                            .set_fast_math_flags(get_fast_float_flags())
                     )
                 }
 
-                Some(res.into())
+                Some(make_float_result(res, size, signed, bridge))
             }
 
             MIRInstruction::FloatDiv {
@@ -331,27 +311,23 @@ pub fn bridge_llvm_instruction(
                 left,
                 right,
             } => {
-                let left: BaseMIRValue = MIRFloatValue::into(left);
-                let right: BaseMIRValue = MIRFloatValue::into(right);
+                let (size, signed) = (left.size, left.signed);
 
-                let l = bridge.values[&left.get_ssa_index()].clone();
-                let r = bridge.values[&right.get_ssa_index()].clone();
+                let l = get_float_operand(left, bridge);
+                let r = get_float_operand(right, bridge);
 
-                let res: FloatValue<'static> = llvm_to_base!(bridge.builder.build_float_div(
-                    l.into_float_value(),
-                    r.into_float_value(),
-                    ""
-                ));
+                let res: FloatValue<'static> =
+                    llvm_to_base!(bridge.builder.build_float_div(l, r, ""));
 
                 if fast {
                     llvm_to_base_returnless!(
                         res.as_instruction_value()
                             .unwrap()
-                            .set_fast_math_flags(FastMathFlags::all())
+                            .set_fast_math_flags(get_fast_float_flags())
                     )
                 }
 
-                Some(res.into())
+                Some(make_float_result(res, size, signed, bridge))
             }
 
             MIRInstruction::FloatMod {
@@ -360,27 +336,23 @@ pub fn bridge_llvm_instruction(
                 left,
                 right,
             } => {
-                let left: BaseMIRValue = MIRFloatValue::into(left);
-                let right: BaseMIRValue = MIRFloatValue::into(right);
+                let (size, signed) = (left.size, left.signed);
 
-                let l = bridge.values[&left.get_ssa_index()].clone();
-                let r = bridge.values[&right.get_ssa_index()].clone();
+                let l = get_float_operand(left, bridge);
+                let r = get_float_operand(right, bridge);
 
-                let res: FloatValue<'static> = llvm_to_base!(bridge.builder.build_float_rem(
-                    l.into_float_value(),
-                    r.into_float_value(),
-                    ""
-                ));
+                let res: FloatValue<'static> =
+                    llvm_to_base!(bridge.builder.build_float_rem(l, r, ""));
 
                 if fast {
                     llvm_to_base_returnless!(
                         res.as_instruction_value()
                             .unwrap()
-                            .set_fast_math_flags(FastMathFlags::all())
+                            .set_fast_math_flags(get_fast_float_flags())
                     )
                 }
 
-                Some(res.into())
+                Some(make_float_result(res, size, signed, bridge))
             }
 
             MIRInstruction::BitwiseAnd { a, b } => {
@@ -442,6 +414,127 @@ pub fn bridge_llvm_instruction(
                 Some(res.into())
             }
 
+            MIRInstruction::UpcastInteger { val, size } => {
+                let signed = val.signed;
+                let val: BaseMIRValue = MIRIntValue::into(val);
+
+                let v = bridge.values[&val.get_ssa_index()].clone();
+                let int_type = bridge
+                    .types
+                    .convert_raw(RawType::Integer(size, signed))
+                    .into_int_type();
+
+                let res: IntValue<'static> = if signed {
+                    llvm_to_base!(bridge.builder.build_int_s_extend(
+                        v.into_int_value(),
+                        int_type,
+                        "e"
+                    ))
+                } else {
+                    llvm_to_base!(bridge.builder.build_int_z_extend(
+                        v.into_int_value(),
+                        int_type,
+                        "e"
+                    ))
+                };
+
+                Some(res.into())
+            }
+
+            MIRInstruction::DowncastInteger { val, size } => {
+                let signed = val.signed;
+                let val: BaseMIRValue = MIRIntValue::into(val);
+
+                let v = bridge.values[&val.get_ssa_index()].clone();
+                let int_type = bridge
+                    .types
+                    .convert_raw(RawType::Integer(size, signed))
+                    .into_int_type();
+
+                let res: IntValue<'static> = llvm_to_base!(bridge.builder.build_int_truncate(
+                    v.into_int_value(),
+                    int_type,
+                    "e"
+                ));
+
+                Some(res.into())
+            }
+
+            MIRInstruction::IntegerToFloat {
+                val,
+                size,
+                signed: float_signed,
+            } => {
+                let signed = val.signed;
+                let val: BaseMIRValue = MIRIntValue::into(val);
+
+                let v = bridge.values[&val.get_ssa_index()].clone();
+                let float_type = get_float_compute_type(size, bridge);
+
+                let mut res: FloatValue<'static> = if signed {
+                    llvm_to_base!(bridge.builder.build_signed_int_to_float(
+                        v.into_int_value(),
+                        float_type,
+                        "e"
+                    ))
+                } else {
+                    llvm_to_base!(bridge.builder.build_unsigned_int_to_float(
+                        v.into_int_value(),
+                        float_type,
+                        "e"
+                    ))
+                };
+
+                if signed && !float_signed {
+                    res = build_float_max_zero(res, bridge);
+                }
+
+                Some(make_float_result(res, size, float_signed, bridge))
+            }
+
+            MIRInstruction::FloatToInteger { val, size, signed } => {
+                let v = get_float_operand(val, bridge);
+
+                let int_type = bridge
+                    .types
+                    .convert_raw(RawType::Integer(size, signed))
+                    .into_int_type();
+
+                let res: IntValue<'static> = if signed {
+                    llvm_to_base!(bridge.builder.build_float_to_signed_int(v, int_type, "e"))
+                } else {
+                    llvm_to_base!(bridge.builder.build_float_to_unsigned_int(v, int_type, "e"))
+                };
+
+                Some(res.into())
+            }
+
+            MIRInstruction::FloatConvert { val, size, signed } => {
+                let val_size = val.size;
+
+                let mut v = get_float_operand(val, bridge);
+                let float_type = get_float_compute_type(size, bridge);
+
+                if get_float_compute_type(val_size, bridge) != float_type {
+                    v = llvm_to_base!(bridge.builder.build_float_cast(v, float_type, "e"));
+                }
+
+                if !signed {
+                    v = build_float_max_zero(v, bridge);
+                }
+
+                Some(make_float_result(v, size, signed, bridge))
+            }
+
+            MIRInstruction::FloatClampZero { val } => {
+                let (size, signed) = (val.size, val.signed);
+
+                let v = get_float_operand(val, bridge);
+                let res = build_float_max_zero(v, bridge);
+
+                Some(make_float_result(res, size, signed, bridge))
+            }
+
             MIRInstruction::ShiftLeft { a, shift } => {
                 let val: BaseMIRValue = MIRIntValue::into(a);
                 let shift: BaseMIRValue = MIRIntValue::into(shift);
@@ -539,28 +632,53 @@ pub fn bridge_llvm_instruction(
             }
 
             MIRInstruction::FloatSignedConstant { raw, size } => {
-                let t = RawType::Floating(size, true);
-
-                let float_type = bridge.types.convert_raw(t).into_float_type();
+                let float_type = get_float_compute_type(size, bridge);
 
                 let res = unsafe { float_type.const_float_from_string(&raw.to_string()) };
 
-                Some(res.into())
+                Some(make_float_result(res, size, true, bridge))
             }
 
             MIRInstruction::FloatUnsignedConstant { raw, size } => {
-                let t = RawType::Floating(size, false);
-
-                let float_type = bridge.types.convert_raw(t).into_float_type();
+                let float_type = get_float_compute_type(size, bridge);
 
                 let res = unsafe { float_type.const_float_from_string(&raw.to_string()) };
 
+                Some(make_float_result(res, size, false, bridge))
+            }
+
+            MIRInstruction::FixedSignedConstant {
+                raw,
+                number,
+                fraction,
+            } => {
+                let t = RawType::FixedPoint(number, fraction, true);
+
+                let int_type = bridge.types.convert_raw(t).into_int_type();
+                let scaled = (raw * 2f64.powi(fraction as i32)).round() as i128;
+
+                let res = int_type
+                    .const_int_from_string(&scaled.to_string(), StringRadix::Decimal)
+                    .unwrap();
+
                 Some(res.into())
             }
 
-            MIRInstruction::FixedSignedConstant { .. }
-            | MIRInstruction::FixedUnsignedConstant { .. } => {
-                panic!("fixed points numbers are not currently supported")
+            MIRInstruction::FixedUnsignedConstant {
+                raw,
+                number,
+                fraction,
+            } => {
+                let t = RawType::FixedPoint(number, fraction, false);
+
+                let int_type = bridge.types.convert_raw(t).into_int_type();
+                let scaled = (raw * 2f64.powi(fraction as i32)).round() as u128;
+
+                let res = int_type
+                    .const_int_from_string(&scaled.to_string(), StringRadix::Decimal)
+                    .unwrap();
+
+                Some(res.into())
             }
 
             MIRInstruction::StaticStringConstant { raw } => {
@@ -918,6 +1036,98 @@ pub fn bridge_llvm_instruction(
                 res.try_as_basic_value().basic()
             }
 
+            MIRInstruction::IndirectCall { callee, arguments } => {
+                let fn_type = bridge.types.convert_function(callee.vtype.clone());
+                let ptr = bridge.values[&callee.get_ssa_index()].into_pointer_value();
+
+                let mut args = vec![];
+
+                for arg in arguments {
+                    args.push(bridge.values[&arg.get_ssa_index()].inner.into());
+                }
+
+                let res =
+                    llvm_to_base!(bridge.builder.build_indirect_call(fn_type, ptr, &args, ""));
+
+                res.try_as_basic_value().basic()
+            }
+
+            MIRInstruction::FunctionAddress { function } => {
+                let func = bridge.functions[&function].clone().inner;
+
+                Some(func.as_global_value().as_pointer_value().into())
+            }
+
+            MIRInstruction::ClosureCreate { function, env } => {
+                let closure_type = bridge
+                    .types
+                    .convert(Type::Closure(vec![], None))
+                    .into_struct_type();
+                let func = bridge.functions[&function].clone().inner;
+
+                let env = match env {
+                    Some(env) => {
+                        let env: BaseMIRValue = env.into();
+
+                        bridge.values[&env.get_ssa_index()].inner.clone()
+                    }
+                    None => bridge
+                        .types
+                        .convert_raw(RawType::Pointer)
+                        .into_pointer_type()
+                        .const_null()
+                        .into(),
+                };
+
+                let closure = llvm_to_base!(bridge.builder.build_insert_value(
+                    closure_type.get_undef(),
+                    func.as_global_value().as_pointer_value(),
+                    0,
+                    ""
+                ));
+                let closure = llvm_to_base!(bridge.builder.build_insert_value(
+                    closure.into_struct_value(),
+                    env,
+                    1,
+                    ""
+                ));
+
+                Some(closure.into_struct_value().into())
+            }
+
+            MIRInstruction::ClosureCall { closure, arguments } => {
+                let (arg_types, ret) = match &closure.vtype {
+                    Type::Closure(args, ret) => (args.clone(), ret.clone()),
+                    _ => panic!("cannot call a non closure value!"),
+                };
+
+                // The closure function takes the environment as first argument
+                let mut fn_args = vec![Box::new(Type::GenericLowered(RawType::Pointer))];
+                fn_args.extend(arg_types);
+
+                let fn_type = bridge.types.convert_function(Type::Function(fn_args, ret));
+
+                let llvm_closure = bridge.values[&closure.get_ssa_index()].into_struct_value();
+
+                let ptr = llvm_to_base!(bridge.builder.build_extract_value(llvm_closure, 0, ""));
+                let env = llvm_to_base!(bridge.builder.build_extract_value(llvm_closure, 1, ""));
+
+                let mut args = vec![env.into()];
+
+                for arg in arguments {
+                    args.push(bridge.values[&arg.get_ssa_index()].inner.into());
+                }
+
+                let res = llvm_to_base!(bridge.builder.build_indirect_call(
+                    fn_type,
+                    ptr.into_pointer_value(),
+                    &args,
+                    ""
+                ));
+
+                res.try_as_basic_value().basic()
+            }
+
             MIRInstruction::FuncArgumentGrab { ind, argtype: _ } => {
                 let func = bridge.functions[&func].clone().inner;
 
@@ -935,7 +1145,7 @@ pub fn bridge_llvm_instruction(
                     .types
                     .convert_raw(RawType::Integer(32, false))
                     .into_int_type();
-                let sz = sz_type.const_int(sz as u64, false);
+                let sz = sz_type.const_int(sz.div_ceil(8) as u64, false); // Sizes are in bits
 
                 llvm_to_base_returnless!(bridge.builder.build_memcpy(
                     llvm_dest.inner.into_pointer_value(),
@@ -948,6 +1158,84 @@ pub fn bridge_llvm_instruction(
                 None
             }
 
+            MIRInstruction::IntegerOverflows {
+                operation,
+                signed,
+                left,
+                right,
+            } => {
+                let name = format!(
+                    "llvm.{}{}.with.overflow",
+                    if signed { "s" } else { "u" },
+                    operation
+                );
+
+                let res = bridge_llvm_int_intrinsic(&name, left, right, &[], bridge);
+
+                // The intrinsic returns both the wrapped result and the overflow bit
+                Some(llvm_to_base!(bridge.builder.build_extract_value(
+                    res.into_struct_value(),
+                    1,
+                    ""
+                )))
+            }
+
+            MIRInstruction::IntegerSaturating {
+                operation,
+                signed,
+                left,
+                right,
+            } => {
+                let sign = if signed { "s" } else { "u" };
+
+                // There is no saturating multiplication intrinsic, a fixed point one without any fraction bits is used instead
+                let res = if operation == MIROverflowOperation::Mul {
+                    let scale = bridge
+                        .types
+                        .convert_raw(RawType::Integer(32, true))
+                        .into_int_type()
+                        .const_zero();
+
+                    bridge_llvm_int_intrinsic(
+                        &format!("llvm.{}mul.fix.sat", sign),
+                        left,
+                        right,
+                        &[scale.into()],
+                        bridge,
+                    )
+                } else {
+                    bridge_llvm_int_intrinsic(
+                        &format!("llvm.{}{}.sat", sign, operation),
+                        left,
+                        right,
+                        &[],
+                        bridge,
+                    )
+                };
+
+                Some(res)
+            }
+
+            MIRInstruction::Assert { cond, msg } => {
+                let cond: BaseMIRValue = MIRIntValue::into(cond);
+                let cond = bridge.values[&cond.get_ssa_index()]
+                    .clone()
+                    .into_int_value();
+
+                let msg: BaseMIRValue = msg.into();
+                let msg = bridge.values[&msg.get_ssa_index()].inner.clone();
+
+                let func = get_assert_function(bridge);
+
+                llvm_to_base_returnless!(bridge.builder.build_call(
+                    func,
+                    &[cond.into(), msg.into()],
+                    ""
+                ));
+
+                None
+            }
+
             MIRInstruction::IRCast { val, to: _ } => {
                 Some(bridge.values[&val.get_ssa_index()].inner.clone())
             }
@@ -962,6 +1250,27 @@ pub fn bridge_llvm_instruction(
     return None;
 }
 
+/// Sets the no-wrap flag matching the signedness of a fast integer operation
+fn set_fast_int_flags(res: IntValue<'static>, signed: bool) {
+    let inst = res.as_instruction_value().unwrap();
+
+    if signed {
+        llvm_to_base_returnless!(inst.set_no_signed_wrap_flag(true));
+    } else {
+        llvm_to_base_returnless!(inst.set_no_unsigned_wrap_flag(true));
+    }
+}
+
+/// The fast-math flags of fast float operations: `nnan`, `ninf`, `reassoc` and `contract`. Values match `LLVMFastMathFlags`
+fn get_fast_float_flags() -> FastMathFlags {
+    const ALLOW_REASSOC: u32 = 1 << 0;
+    const NO_NANS: u32 = 1 << 1;
+    const NO_INFS: u32 = 1 << 2;
+    const ALLOW_CONTRACT: u32 = 1 << 5;
+
+    return FastMathFlags::from_bits_truncate(ALLOW_REASSOC | NO_NANS | NO_INFS | ALLOW_CONTRACT);
+}
+
 pub fn bridge_llvm_int_cmp(
     a: MIRIntValue,
     b: MIRIntValue,
@@ -981,3 +1290,30 @@ pub fn bridge_llvm_int_cmp(
         "e"
     ));
 }
+
+/// Calls the given integer intrinsic, overloaded on the type of the operands
+pub fn bridge_llvm_int_intrinsic(
+    name: &str,
+    a: MIRIntValue,
+    b: MIRIntValue,
+    extra: &[BasicMetadataValueEnum<'static>],
+    bridge: &mut LLVMBridgeContext,
+) -> BasicValueEnum<'static> {
+    let left: BaseMIRValue = MIRIntValue::into(a);
+    let right: BaseMIRValue = MIRIntValue::into(b);
+
+    let l = bridge.values[&left.get_ssa_index()].into_int_value();
+    let r = bridge.values[&right.get_ssa_index()].into_int_value();
+
+    let intrinsic = Intrinsic::find(name)
+        .unwrap()
+        .get_declaration(&bridge.module, &[l.get_type().into()])
+        .unwrap();
+
+    let mut args: Vec<BasicMetadataValueEnum<'static>> = vec![l.into(), r.into()];
+    args.extend_from_slice(extra);
+
+    let res = llvm_to_base!(bridge.builder.build_call(intrinsic, &args, ""));
+
+    return res.try_as_basic_value().basic().unwrap();
+}
@@ -8,7 +8,9 @@ use inkwell::context::Context;
 use crate::{blocks::bridge_llvm_blocks, ctx::LLVMBridgeContext, funcs::bridge_llvm_functions};
 
 pub mod blocks;
+pub mod checks;
 pub mod ctx;
+pub mod floats;
 pub mod funcs;
 pub mod insts;
 pub mod types;
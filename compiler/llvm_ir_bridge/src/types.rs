@@ -4,7 +4,7 @@ use compiler_typing::{raw::RawType, tree::Type};
 use inkwell::{
     AddressSpace,
     context::Context,
-    types::{BasicType, BasicTypeEnum},
+    types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum, FunctionType},
 };
 
 use crate::utils::LLVMTypeEnum;
@@ -27,9 +27,20 @@ impl LLVMTypeStorage {
         match base {
             Type::GenericLowered(raw) => return self.convert_raw(raw),
             Type::Generic(_, _, _) => panic!("cannot convert unlowered generics"),
+            Type::Tuple(_) => panic!("cannot convert unlowered tuples"),
+            Type::Slice(_) => panic!("cannot convert unlowered slices"),
 
             Type::Reference(_) => return self.convert_raw(RawType::Pointer),
             Type::Pointer(_, _) => return self.convert_raw(RawType::Pointer),
+            Type::Function(_, _) => return self.convert_raw(RawType::Pointer),
+
+            // The function pointer followed by the environment pointer
+            Type::Closure(_, _) => {
+                let ptr = self.convert_raw(RawType::Pointer).inner;
+                let t: BasicTypeEnum = self.ctxref.struct_type(&[ptr, ptr], false).into();
+
+                LLVMTypeEnum::new(unsafe { transmute::<BasicTypeEnum, BasicTypeEnum<'static>>(t) })
+            }
 
             Type::Array(size, inner) => {
                 let inner_type = self.convert(*inner);
@@ -39,6 +50,26 @@ impl LLVMTypeStorage {
         }
     }
 
+    /// Converts a function pointer type into the type of the pointed function
+    pub fn convert_function(&mut self, base: Type) -> FunctionType<'static> {
+        if let Type::Function(args, ret) = base {
+            let mut llvm_args: Vec<BasicMetadataTypeEnum> = vec![];
+
+            for arg in args {
+                llvm_args.push(self.convert(*arg).inner.into());
+            }
+
+            let t = match ret {
+                Some(ret) => self.convert(*ret).fn_type(&llvm_args, false),
+                None => self.ctxref.void_type().fn_type(&llvm_args, false),
+            };
+
+            return unsafe { transmute::<FunctionType, FunctionType<'static>>(t) };
+        }
+
+        panic!("cannot convert non function type to LLVM function type!")
+    }
+
     pub fn convert_raw(&mut self, base: RawType) -> LLVMTypeEnum {
         if self.map.contains_key(&base) {
             return LLVMTypeEnum::clone(&self.map[&base]);
@@ -52,6 +83,8 @@ impl LLVMTypeStorage {
                 .into(),
 
             RawType::Floating(a, _) => match a {
+                // Emulated in software, see `crate::floats`
+                8 => self.ctxref.i8_type().into(),
                 16 => self.ctxref.f16_type().into(),
                 32 => self.ctxref.f32_type().into(),
                 64 => self.ctxref.f64_type().into(),
@@ -61,14 +94,15 @@ impl LLVMTypeStorage {
                 _ => panic!("cannot convert float to LLVM type"),
             },
 
-            RawType::LoweredStruct(layout, b) => {
+            RawType::LoweredStruct(_, b) => {
                 let mut fields = vec![];
 
                 for field in &b.fields.vals {
                     fields.push(self.convert(field.clone()).inner);
                 }
 
-                self.ctxref.struct_type(&fields, !*layout).into()
+                // Fields are already ordered, LLVM handles the padding the same way as `compiler_typing::layout`
+                self.ctxref.struct_type(&fields, false).into()
             }
 
             RawType::FixedPoint(a, b, _) => {
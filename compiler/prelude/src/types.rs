@@ -66,7 +66,10 @@ pub fn register_prelude_type<K: DiagnosticSpanOrigin>(
     origin: &K,
 ) -> MaybeDiagnostic {
     hir.global_scope.append(
-        EntryKey { name_hash: hash },
+        EntryKey {
+            name_hash: hash,
+            name: t.to_string(),
+        },
         GlobalStorageEntryType::Type(t),
         origin,
     )?;
@@ -213,50 +216,50 @@ pub fn apply_prelude_types<K: DiagnosticSpanOrigin>(
     register_prelude_type(
         hir,
         SIGNED_FIXED_POINT_64,
-        RawType::FixedPoint(64, 64, true),
+        RawType::FixedPoint(32, 32, true),
         origin,
     )?;
     register_prelude_type(
         hir,
         SIGNED_FIXED_POINT_128,
-        RawType::FixedPoint(128, 128, true),
+        RawType::FixedPoint(64, 64, true),
         origin,
     )?;
 
     register_prelude_type(
         hir,
         UNSIGNED_FIXED_POINT,
-        RawType::SizedFixedPoint(true),
+        RawType::SizedFixedPoint(false),
         origin,
     )?;
     register_prelude_type(
         hir,
         UNSIGNED_FIXED_POINT_8,
-        RawType::FixedPoint(4, 4, true),
+        RawType::FixedPoint(4, 4, false),
         origin,
     )?;
     register_prelude_type(
         hir,
         UNSIGNED_FIXED_POINT_16,
-        RawType::FixedPoint(8, 8, true),
+        RawType::FixedPoint(8, 8, false),
         origin,
     )?;
     register_prelude_type(
         hir,
         UNSIGNED_FIXED_POINT_32,
-        RawType::FixedPoint(16, 16, true),
+        RawType::FixedPoint(16, 16, false),
         origin,
     )?;
     register_prelude_type(
         hir,
         UNSIGNED_FIXED_POINT_64,
-        RawType::FixedPoint(64, 64, true),
+        RawType::FixedPoint(32, 32, false),
         origin,
     )?;
     register_prelude_type(
         hir,
         UNSIGNED_FIXED_POINT_128,
-        RawType::FixedPoint(128, 128, true),
+        RawType::FixedPoint(64, 64, false),
         origin,
     )?;
 
@@ -271,17 +274,23 @@ pub fn apply_prelude_types<K: DiagnosticSpanOrigin>(
         type_params.insert(HashedString::new("V".to_string()), 0);
         type_params.insert(HashedString::new("E".to_string()), 1);
 
-        let mut result_enum =
-            RawEnumTypeContainer::new(hir.global_scope.scope.entries.len(), type_params);
+        let mut result_enum = RawEnumTypeContainer::new(
+            HashedString::new("result".to_string()),
+            hir.global_scope.scope.entries.len(),
+            type_params,
+            vec![],
+        );
 
         result_enum.append_entry(
             HashedString::new("value".to_string()),
             vec![(hash!("val"), TypeReference::make_unresolved(0))],
-        );
+            None,
+        )?;
         result_enum.append_entry(
             HashedString::new("error".to_string()),
             vec![(hash!("err"), TypeReference::make_unresolved(1))],
-        );
+            None,
+        )?;
 
         register_prelude_type(hir, RESULT_TYPE, RawType::Enum(result_enum), origin)?;
     }